@@ -15,12 +15,39 @@
 
 //! The core `BacktestEngine` for backtesting on historical data.
 
-use std::ops::{Deref, DerefMut};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    ops::{Deref, DerefMut},
+    rc::Rc,
+};
 
-use nautilus_common::{clock::TestClock, ffi::clock::TestClock_API, timer::TimeEventHandler};
+use log::{debug, error, info};
+use nautilus_common::{
+    cache::Cache,
+    clock::TestClock,
+    ffi::clock::TestClock_API,
+    msgbus::MessageBus,
+    timer::{TimeEvent, TimeEventHandler},
+};
 use nautilus_core::{
+    correctness::{check_key_in_map, check_key_not_in_map},
     ffi::{cvec::CVec, parsing::u8_as_bool},
     nanos::UnixNanos,
+    time::{get_atomic_clock_static, AtomicTime},
+};
+use nautilus_execution::engine::ExecutionEngine;
+use nautilus_model::{
+    data::{funding::FundingRateUpdate, status::InstrumentStatus, Data, GetTsInit},
+    identifiers::{InstrumentId, Venue},
+    instruments::any::InstrumentAny,
+};
+
+use crate::{
+    exchange::{SimulatedExchange, SimulatedExchangeConfig},
+    execution_client::BacktestExecutionClient,
+    models::{fee::FeeModel, latency::LatencyModel},
 };
 
 /// Provides a means of accumulating and draining time event handlers.
@@ -61,6 +88,476 @@ impl Default for TimeEventAccumulator {
     }
 }
 
+/// Venue data processed by the [`BacktestEngine`] which is not a built-in [`Data`] type.
+enum VenueData {
+    FundingRate(FundingRateUpdate),
+    Status(InstrumentStatus),
+}
+
+impl VenueData {
+    fn instrument_id(&self) -> InstrumentId {
+        match self {
+            Self::FundingRate(funding_rate) => funding_rate.instrument_id,
+            Self::Status(status) => status.instrument_id,
+        }
+    }
+}
+
+impl GetTsInit for VenueData {
+    fn ts_init(&self) -> UnixNanos {
+        match self {
+            Self::FundingRate(funding_rate) => funding_rate.ts_init,
+            Self::Status(status) => status.ts_init,
+        }
+    }
+}
+
+/// Provides a backtest engine to run a portfolio of strategies over historical
+/// data, driving the clock, the simulated venues and the message bus.
+///
+/// Data is processed in `ts_init` order. Before each data point is processed
+/// the clock is advanced to its `ts_init`, with any time events up to and
+/// including that time dispatched in order. Time events are published on the
+/// `events.time.{name}` topic (and passed to the timer callback if it is a native
+/// Rust callback), and market data is published on the same topics used by the
+/// `DataEngine`.
+///
+/// Venue data which is not a built-in [`Data`] type (funding rate updates and
+/// instrument status changes) is processed alongside the data in `ts_init` order,
+/// before any data with the same `ts_init`. It is published on the
+/// `data.funding_rates.{venue}.{symbol}` and `data.status.{venue}.{symbol}` topics.
+///
+/// The venues are processed after each data point (or time event) is published,
+/// so commands sent to a venue by its [`BacktestExecutionClient`] in reaction are
+/// handled at the same time (subject to the venues latency model).
+///
+/// The run is stopped if the balance of a venue account would go negative.
+pub struct BacktestEngine {
+    clock: Rc<RefCell<TestClock>>,
+    time: &'static AtomicTime,
+    msgbus: Rc<RefCell<MessageBus>>,
+    cache: Rc<RefCell<Cache>>,
+    exec_engine: Option<Rc<RefCell<ExecutionEngine>>>,
+    venues: HashMap<Venue, SimulatedExchange>,
+    data: VecDeque<Data>,
+    venue_data: VecDeque<VenueData>,
+    iteration: usize,
+}
+
+impl BacktestEngine {
+    /// Creates a new [`BacktestEngine`] instance.
+    #[must_use]
+    pub fn new(msgbus: Rc<RefCell<MessageBus>>, cache: Rc<RefCell<Cache>>) -> Self {
+        Self {
+            clock: Rc::new(RefCell::new(TestClock::new())),
+            time: get_atomic_clock_static(),
+            msgbus,
+            cache,
+            exec_engine: None,
+            venues: HashMap::new(),
+            data: VecDeque::new(),
+            venue_data: VecDeque::new(),
+            iteration: 0,
+        }
+    }
+
+    /// Returns the engines clock (for setting timers).
+    #[must_use]
+    pub fn clock(&self) -> Rc<RefCell<TestClock>> {
+        self.clock.clone()
+    }
+
+    /// Returns the count of data points processed since the engine was created or reset.
+    #[must_use]
+    pub const fn iteration(&self) -> usize {
+        self.iteration
+    }
+
+    /// Returns the count of data points remaining to be processed.
+    #[must_use]
+    pub fn remaining_data(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns the simulated exchange for the given `venue` (if found).
+    #[must_use]
+    pub fn get_exchange(&self, venue: &Venue) -> Option<&SimulatedExchange> {
+        self.venues.get(venue)
+    }
+
+    /// Returns a mutable reference to the simulated exchange for the given `venue` (if found).
+    pub fn get_exchange_mut(&mut self, venue: &Venue) -> Option<&mut SimulatedExchange> {
+        self.venues.get_mut(venue)
+    }
+
+    /// Adds a simulated exchange venue with the given `config` to the engine, along
+    /// with an optional `latency_model` for commands sent to the venue.
+    pub fn add_venue(
+        &mut self,
+        venue: Venue,
+        config: SimulatedExchangeConfig,
+        latency_model: Option<Box<dyn LatencyModel>>,
+    ) -> anyhow::Result<()> {
+        check_key_not_in_map(&venue, &self.venues, "venue", "venues")?;
+
+        let exchange = SimulatedExchange::new(
+            venue,
+            config,
+            latency_model,
+            self.clock.clone(),
+            self.time,
+            self.msgbus.clone(),
+            self.cache.clone(),
+        )?;
+        exchange.initialize_account();
+        if let Some(exec_engine) = &self.exec_engine {
+            let client = BacktestExecutionClient::new(&exchange, self.time, self.msgbus.clone());
+            exec_engine.borrow_mut().register_client(Box::new(client))?;
+        }
+        self.venues.insert(venue, exchange);
+        info!("Added venue {venue}");
+        Ok(())
+    }
+
+    /// Sets the `exec_engine` for the backtest, registering a [`BacktestExecutionClient`]
+    /// with it for each venue (including venues added afterwards).
+    pub fn set_exec_engine(
+        &mut self,
+        exec_engine: Rc<RefCell<ExecutionEngine>>,
+    ) -> anyhow::Result<()> {
+        for exchange in self.venues.values() {
+            let client = BacktestExecutionClient::new(exchange, self.time, self.msgbus.clone());
+            exec_engine.borrow_mut().register_client(Box::new(client))?;
+        }
+        self.exec_engine = Some(exec_engine);
+        Ok(())
+    }
+
+    /// Adds the given `instrument` to the cache and its venue, creating a matching
+    /// engine for it using the `fee_model`.
+    ///
+    /// The instruments venue must have already been added.
+    pub fn add_instrument(
+        &mut self,
+        instrument: InstrumentAny,
+        fee_model: Box<dyn FeeModel>,
+    ) -> anyhow::Result<()> {
+        let venue = instrument.id().venue;
+        check_key_in_map(&venue, &self.venues, "venue", "venues")?;
+
+        self.cache.borrow_mut().add_instrument(instrument.clone())?;
+        self.venues
+            .get_mut(&venue)
+            .expect("Venue was checked")
+            .add_instrument(instrument, fee_model)
+    }
+
+    /// Adds the given `data` to the engine, which will be sorted into `ts_init` order
+    /// with any data previously added.
+    ///
+    /// The instrument for each data point must have already been added.
+    pub fn add_data(&mut self, data: Vec<Data>) -> anyhow::Result<()> {
+        for item in &data {
+            self.check_instrument(item.instrument_id())?;
+        }
+
+        let count = data.len();
+        self.data.extend(data);
+        self.data.make_contiguous().sort_by_key(GetTsInit::ts_init);
+        info!("Added {count} data elements");
+        Ok(())
+    }
+
+    /// Adds the given `funding_rates` to the engine, which will be sorted into `ts_init`
+    /// order with any venue data previously added.
+    ///
+    /// The instrument for each funding rate must have already been added.
+    pub fn add_funding_rates(
+        &mut self,
+        funding_rates: Vec<FundingRateUpdate>,
+    ) -> anyhow::Result<()> {
+        let count = funding_rates.len();
+        self.add_venue_data(funding_rates.into_iter().map(VenueData::FundingRate))?;
+        info!("Added {count} funding rates");
+        Ok(())
+    }
+
+    /// Adds the given instrument `statuses` to the engine, which will be sorted into
+    /// `ts_init` order with any venue data previously added.
+    ///
+    /// The instrument for each status must have already been added.
+    pub fn add_instrument_statuses(
+        &mut self,
+        statuses: Vec<InstrumentStatus>,
+    ) -> anyhow::Result<()> {
+        let count = statuses.len();
+        self.add_venue_data(statuses.into_iter().map(VenueData::Status))?;
+        info!("Added {count} instrument statuses");
+        Ok(())
+    }
+
+    /// Runs the engine over the added data, up to and including the optional `end` time.
+    ///
+    /// Any data after `end` is retained so that a subsequent run may continue from
+    /// where this run finished. If `end` is given then the clock is advanced to
+    /// `end` once the data is exhausted, dispatching any remaining time events.
+    pub fn run(&mut self, end: Option<UnixNanos>) {
+        while let Some(data) = self.data.front() {
+            if end.is_some_and(|end| data.ts_init() > end) {
+                break;
+            }
+            let data = self.data.pop_front().unwrap();
+            self.process_venue_data(Some(data.ts_init()));
+            self.process_data(data);
+            if self.is_account_negative() {
+                return;
+            }
+        }
+
+        self.process_venue_data(end);
+        if let Some(end) = end {
+            self.advance_time(end);
+        }
+    }
+
+    /// Runs the engine over the given `stream` of data, up to and including the
+    /// optional `end` time.
+    ///
+    /// The stream must already be in `ts_init` order, such as the result of a
+    /// `DataBackendSession` query. Data after `end` is not consumed.
+    pub fn run_stream<I>(&mut self, stream: I, end: Option<UnixNanos>) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = Data>,
+    {
+        for data in stream {
+            if end.is_some_and(|end| data.ts_init() > end) {
+                break;
+            }
+            self.check_instrument(data.instrument_id())?;
+            self.process_venue_data(Some(data.ts_init()));
+            self.process_data(data);
+            if self.is_account_negative() {
+                return Ok(());
+            }
+        }
+
+        self.process_venue_data(end);
+        if let Some(end) = end {
+            self.advance_time(end);
+        }
+        Ok(())
+    }
+
+    /// Clears all data (including venue data) which has not yet been processed.
+    pub fn clear_data(&mut self) {
+        self.data.clear();
+        self.venue_data.clear();
+        info!("Cleared data");
+    }
+
+    /// Resets the engine, clearing all data and resetting the venues.
+    ///
+    /// Venues, their accounts and instruments are retained.
+    pub fn reset(&mut self) {
+        self.clock.replace(TestClock::new());
+        self.time.set_time(UnixNanos::default());
+        for exchange in self.venues.values_mut() {
+            exchange.reset();
+        }
+        self.data.clear();
+        self.venue_data.clear();
+        self.iteration = 0;
+        info!("Reset");
+    }
+
+    // -- INTERNAL --------------------------------------------------------------------------------
+
+    fn process_data(&mut self, data: Data) {
+        self.advance_time(data.ts_init());
+
+        let exchange = self
+            .venues
+            .get_mut(&data.instrument_id().venue)
+            .expect("Exchange should exist for venue");
+
+        match &data {
+            Data::Delta(delta) => exchange.process_order_book_delta(delta),
+            Data::Deltas(deltas) => exchange.process_order_book_deltas(deltas),
+            Data::Depth10(depth) => exchange.process_order_book_depth10(depth),
+            Data::Quote(quote) => exchange.process_quote_tick(quote),
+            Data::Trade(trade) => exchange.process_trade_tick(trade),
+            Data::Bar(bar) => exchange.process_bar(bar),
+        }
+
+        self.publish_data(&data);
+        self.process_venues(data.ts_init());
+        self.iteration += 1;
+    }
+
+    /// Returns true if the balance of any venue account has gone negative, in which
+    /// case the run is stopped.
+    fn is_account_negative(&self) -> bool {
+        let Some((venue, balance)) = self.venues.iter().find_map(|(venue, exchange)| {
+            exchange.negative_balance().map(|balance| (venue, balance))
+        }) else {
+            return false;
+        };
+        error!("Stopping backtest from negative balance {balance} at {venue}");
+        true
+    }
+
+    fn add_venue_data<I>(&mut self, venue_data: I) -> anyhow::Result<()>
+    where
+        I: IntoIterator<Item = VenueData>,
+    {
+        let venue_data: Vec<VenueData> = venue_data.into_iter().collect();
+        for item in &venue_data {
+            self.check_instrument(&item.instrument_id())?;
+        }
+
+        self.venue_data.extend(venue_data);
+        self.venue_data
+            .make_contiguous()
+            .sort_by_key(GetTsInit::ts_init);
+        Ok(())
+    }
+
+    /// Processes the venue data up to and including the optional `end` time.
+    fn process_venue_data(&mut self, end: Option<UnixNanos>) {
+        while let Some(item) = self.venue_data.front() {
+            if end.is_some_and(|end| item.ts_init() > end) {
+                break;
+            }
+            let item = self.venue_data.pop_front().unwrap();
+            self.advance_time(item.ts_init());
+
+            let instrument_id = item.instrument_id();
+            let Some(exchange) = self.venues.get_mut(&instrument_id.venue) else {
+                continue;
+            };
+            match &item {
+                VenueData::FundingRate(funding_rate) => {
+                    exchange.process_funding_rate(funding_rate);
+                    let topic = format!(
+                        "data.funding_rates.{}.{}",
+                        instrument_id.venue, instrument_id.symbol
+                    );
+                    self.msgbus
+                        .borrow()
+                        .publish(&topic, funding_rate as &dyn Any);
+                }
+                VenueData::Status(status) => {
+                    exchange.process_instrument_status(status);
+                    let topic = format!(
+                        "data.status.{}.{}",
+                        instrument_id.venue, instrument_id.symbol
+                    );
+                    self.msgbus.borrow().publish(&topic, status as &dyn Any);
+                }
+            }
+            self.process_venues(item.ts_init());
+        }
+    }
+
+    fn check_instrument(&self, instrument_id: &InstrumentId) -> anyhow::Result<()> {
+        let has_instrument = self
+            .venues
+            .get(&instrument_id.venue)
+            .map_or(false, |exchange| {
+                exchange.get_matching_engine(instrument_id).is_some()
+            });
+        anyhow::ensure!(
+            has_instrument,
+            "Instrument {instrument_id} has not been added"
+        );
+        Ok(())
+    }
+
+    /// Advances the clock to `ts_now`, processing the venues at the time of each time event
+    /// once it has been dispatched (so commands released by venue latency alerts are processed
+    /// when they arrive).
+    fn advance_time(&mut self, ts_now: UnixNanos) {
+        let events = self.clock.borrow_mut().advance_time(ts_now, false);
+        for event in events {
+            let ts_event = event.ts_event;
+            self.set_time(ts_event);
+            self.publish_time_event(&event);
+            let callback = self.clock.borrow().rust_callback(&event);
+            if let Some(callback) = callback {
+                callback.call(event);
+            }
+            self.process_venues(ts_event);
+        }
+        self.set_time(ts_now);
+        self.process_venues(ts_now);
+    }
+
+    fn set_time(&self, ts_now: UnixNanos) {
+        self.clock.borrow().set_time(ts_now);
+        self.time.set_time(ts_now);
+    }
+
+    fn process_venues(&mut self, ts_now: UnixNanos) {
+        for exchange in self.venues.values_mut() {
+            exchange.process(ts_now);
+        }
+    }
+
+    fn publish_time_event(&self, event: &TimeEvent) {
+        debug!("Dispatching {event}");
+
+        let topic = format!("events.time.{}", event.name);
+        self.msgbus.borrow().publish(&topic, event as &dyn Any);
+    }
+
+    fn publish_data(&self, data: &Data) {
+        let instrument_id = data.instrument_id();
+        match data {
+            Data::Delta(delta) => {
+                let topic = format!(
+                    "data.book.deltas.{}.{}",
+                    instrument_id.venue, instrument_id.symbol
+                );
+                self.msgbus.borrow().publish(&topic, delta as &dyn Any);
+            }
+            Data::Deltas(deltas) => {
+                let topic = format!(
+                    "data.book.snapshots.{}.{}",
+                    instrument_id.venue, instrument_id.symbol
+                );
+                self.msgbus
+                    .borrow()
+                    .publish(&topic, deltas.deref() as &dyn Any);
+            }
+            Data::Depth10(depth) => {
+                let topic = format!(
+                    "data.book.depth.{}.{}",
+                    instrument_id.venue, instrument_id.symbol
+                );
+                self.msgbus.borrow().publish(&topic, depth as &dyn Any);
+            }
+            Data::Quote(quote) => {
+                let topic = format!(
+                    "data.quotes.{}.{}",
+                    instrument_id.venue, instrument_id.symbol
+                );
+                self.msgbus.borrow().publish(&topic, quote as &dyn Any);
+            }
+            Data::Trade(trade) => {
+                let topic = format!(
+                    "data.trades.{}.{}",
+                    instrument_id.venue, instrument_id.symbol
+                );
+                self.msgbus.borrow().publish(&topic, trade as &dyn Any);
+            }
+            Data::Bar(bar) => {
+                let topic = format!("data.bars.{}", bar.bar_type);
+                self.msgbus.borrow().publish(&topic, bar as &dyn Any);
+            }
+        }
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // C API
 ////////////////////////////////////////////////////////////////////////////////
@@ -111,15 +608,686 @@ pub extern "C" fn time_event_accumulator_drain(accumulator: &mut TimeEventAccumu
 ////////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
-    use std::ffi::c_char;
+    use std::{cell::RefCell, ffi::c_char};
 
-    use nautilus_common::timer::TimeEvent;
+    use nautilus_common::{
+        clock::Clock,
+        handlers::{EventHandler, SafeTimeEventCallback},
+        msgbus::{stubs::get_message_saving_handler, MessageHandler, ShareableMessageHandler},
+    };
     use nautilus_core::uuid::UUID4;
+    use nautilus_execution::messages::{submit::SubmitOrder, TradingCommand};
+    use nautilus_model::{
+        data::quote::QuoteTick,
+        enums::{AccountType, BookType, MarketStatus, MarketStatusAction, OmsType, OrderSide},
+        events::order::OrderEventAny,
+        identifiers::{AccountId, ClientId, ClientOrderId, TraderId},
+        instruments::stubs::{audusd_sim, equity_aapl, futures_contract_es},
+        orders::stubs::{TestOrderEventStubs, TestOrderStubs},
+        types::{money::Money, price::Price, quantity::Quantity},
+    };
     use pyo3::{prelude::*, types::PyList, Py, Python};
     use rstest::*;
+    use rust_decimal_macros::dec;
     use ustr::Ustr;
 
     use super::*;
+    use crate::{
+        matching_engine::OrderMatchingEngineConfig,
+        models::{fee::MakerTakerFeeModel, latency::FixedLatencyModel},
+        stubs::register_exec_engine,
+    };
+
+    type Saved<T> = Rc<RefCell<Vec<T>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    #[fixture]
+    fn venue_config() -> SimulatedExchangeConfig {
+        SimulatedExchangeConfig::new(
+            OmsType::Netting,
+            AccountType::Margin,
+            BookType::L1_MBP,
+            vec![Money::from("1000000 USD")],
+            None,
+            OrderMatchingEngineConfig {
+                bar_execution: false,
+                reject_stop_orders: false,
+                support_gtd_orders: true,
+                support_contingent_orders: true,
+                use_position_ids: false,
+                use_random_ids: false,
+                use_reduce_only: true,
+            },
+        )
+    }
+
+    fn get_backtest_engine(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) -> (BacktestEngine, Saved<QuoteTick>, Saved<OrderEventAny>) {
+        get_backtest_engine_with_latency(instrument, venue_config, None)
+    }
+
+    fn get_backtest_engine_with_latency(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+        latency_model: Option<Box<dyn LatencyModel>>,
+    ) -> (BacktestEngine, Saved<QuoteTick>, Saved<OrderEventAny>) {
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (quote_handler, quotes) = get_message_saving_handler::<QuoteTick>(None);
+        msgbus.subscribe("data.quotes.SIM.AUD/USD", quote_handler, None);
+        let msgbus = Rc::new(RefCell::new(msgbus));
+        let cache = Rc::new(RefCell::new(Cache::default()));
+        let (_, events) = register_exec_engine(&msgbus, cache.clone(), get_atomic_clock_static());
+
+        let mut engine = BacktestEngine::new(msgbus, cache);
+        engine
+            .add_venue(instrument.id().venue, venue_config, latency_model)
+            .unwrap();
+        engine
+            .add_instrument(instrument, Box::new(MakerTakerFeeModel))
+            .unwrap();
+        (engine, quotes, events)
+    }
+
+    fn quote(instrument: &InstrumentAny, bid: &str, ask: &str, ts_init: u64) -> Data {
+        Data::Quote(
+            QuoteTick::new(
+                instrument.id(),
+                Price::from(bid),
+                Price::from(ask),
+                Quantity::from("1000000"),
+                Quantity::from("1000000"),
+                ts_init.into(),
+                ts_init.into(),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn status(
+        instrument: &InstrumentAny,
+        action: MarketStatusAction,
+        ts_init: u64,
+    ) -> InstrumentStatus {
+        InstrumentStatus::new(
+            instrument.id(),
+            action,
+            ts_init.into(),
+            ts_init.into(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[rstest]
+    fn test_add_instrument_without_venue_fails(instrument: InstrumentAny) {
+        let msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let mut engine = BacktestEngine::new(
+            Rc::new(RefCell::new(msgbus)),
+            Rc::new(RefCell::new(Cache::default())),
+        );
+
+        let result = engine.add_instrument(instrument, Box::new(MakerTakerFeeModel));
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_add_venue_twice_fails(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, _, _) = get_backtest_engine(instrument.clone(), venue_config.clone());
+
+        let result = engine.add_venue(instrument.id().venue, venue_config, None);
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_add_data_for_unknown_instrument_fails(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, _, _) = get_backtest_engine(instrument, venue_config);
+        let other = InstrumentAny::Equity(equity_aapl());
+        let data = quote(&other, "100.00", "100.01", 1);
+
+        let result = engine.add_data(vec![data]);
+
+        assert!(result.is_err());
+        assert_eq!(engine.remaining_data(), 0);
+    }
+
+    #[rstest]
+    fn test_run_processes_data_in_ts_init_order(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, quotes, _) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80002", "0.80012", 3),
+                quote(&instrument, "0.80000", "0.80010", 1),
+            ])
+            .unwrap();
+        engine
+            .add_data(vec![quote(&instrument, "0.80001", "0.80011", 2)])
+            .unwrap();
+
+        engine.run(None);
+
+        let ts_inits: Vec<u64> = quotes.borrow().iter().map(|q| q.ts_init.as_u64()).collect();
+        assert_eq!(ts_inits, vec![1, 2, 3]);
+        assert_eq!(engine.iteration(), 3);
+        assert_eq!(engine.remaining_data(), 0);
+        assert_eq!(engine.clock().borrow().timestamp_ns(), UnixNanos::from(3));
+        let matching_engine = engine
+            .get_exchange(&instrument.id().venue)
+            .unwrap()
+            .get_matching_engine(&instrument.id())
+            .unwrap();
+        assert_eq!(
+            matching_engine.best_bid_price(),
+            Some(Price::from("0.80002"))
+        );
+        assert_eq!(
+            matching_engine.best_ask_price(),
+            Some(Price::from("0.80012"))
+        );
+    }
+
+    #[rstest]
+    fn test_run_to_end_retains_remaining_data(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, quotes, _) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 1),
+                quote(&instrument, "0.80001", "0.80011", 2),
+                quote(&instrument, "0.80002", "0.80012", 3),
+            ])
+            .unwrap();
+
+        engine.run(Some(UnixNanos::from(2)));
+
+        assert_eq!(quotes.borrow().len(), 2);
+        assert_eq!(engine.remaining_data(), 1);
+        assert_eq!(engine.clock().borrow().timestamp_ns(), UnixNanos::from(2));
+
+        engine.run(None);
+
+        assert_eq!(quotes.borrow().len(), 3);
+        assert_eq!(engine.remaining_data(), 0);
+    }
+
+    #[rstest]
+    fn test_run_stream(instrument: InstrumentAny, venue_config: SimulatedExchangeConfig) {
+        let (mut engine, quotes, _) = get_backtest_engine(instrument.clone(), venue_config);
+        let stream = vec![
+            quote(&instrument, "0.80000", "0.80010", 1),
+            quote(&instrument, "0.80001", "0.80011", 2),
+            quote(&instrument, "0.80002", "0.80012", 3),
+        ];
+
+        engine
+            .run_stream(stream, Some(UnixNanos::from(10)))
+            .unwrap();
+
+        assert_eq!(quotes.borrow().len(), 3);
+        assert_eq!(engine.iteration(), 3);
+        assert_eq!(engine.clock().borrow().timestamp_ns(), UnixNanos::from(10));
+    }
+
+    #[rstest]
+    fn test_run_processes_funding_rates_with_data(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, funding_rates) = get_message_saving_handler::<FundingRateUpdate>(None);
+        msgbus.subscribe("data.funding_rates.SIM.AUD/USD", handler, None);
+        let mut engine = BacktestEngine::new(
+            Rc::new(RefCell::new(msgbus)),
+            Rc::new(RefCell::new(Cache::default())),
+        );
+        engine
+            .add_venue(instrument.id().venue, venue_config, None)
+            .unwrap();
+        engine
+            .add_instrument(instrument.clone(), Box::new(MakerTakerFeeModel))
+            .unwrap();
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 1),
+                quote(&instrument, "0.80001", "0.80011", 3),
+            ])
+            .unwrap();
+        engine
+            .add_funding_rates(vec![
+                FundingRateUpdate::new(instrument.id(), dec!(0.0001), None, 5.into(), 5.into()),
+                FundingRateUpdate::new(instrument.id(), dec!(0.0001), None, 2.into(), 2.into()),
+            ])
+            .unwrap();
+
+        engine.run(Some(UnixNanos::from(4)));
+        let funding_count = funding_rates.borrow().len();
+        engine.run(None);
+
+        assert_eq!(funding_count, 1);
+        assert_eq!(funding_rates.borrow().len(), 2);
+        assert_eq!(funding_rates.borrow()[0].ts_init, UnixNanos::from(2));
+        assert_eq!(engine.clock().borrow().timestamp_ns(), UnixNanos::from(5));
+    }
+
+    #[rstest]
+    fn test_run_processes_instrument_statuses(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, quotes, _) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 1),
+                quote(&instrument, "0.80001", "0.80011", 3),
+            ])
+            .unwrap();
+        engine
+            .add_instrument_statuses(vec![
+                status(&instrument, MarketStatusAction::Trading, 4),
+                status(&instrument, MarketStatusAction::Halt, 2),
+            ])
+            .unwrap();
+        let market_status = |engine: &BacktestEngine| {
+            engine
+                .get_exchange(&instrument.id().venue)
+                .unwrap()
+                .get_matching_engine(&instrument.id())
+                .unwrap()
+                .market_status
+        };
+
+        engine.run(Some(UnixNanos::from(3)));
+        let status_during_halt = market_status(&engine);
+        engine.run(None);
+
+        assert_eq!(quotes.borrow().len(), 2);
+        assert_eq!(status_during_halt, MarketStatus::Suspended);
+        assert_eq!(market_status(&engine), MarketStatus::Open);
+    }
+
+    #[rstest]
+    fn test_run_fills_resting_order(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, _, events) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 1),
+                quote(&instrument, "0.79995", "0.80005", 2),
+            ])
+            .unwrap();
+        engine.run(Some(UnixNanos::from(1)));
+
+        let account_id = AccountId::from("SIM-001");
+        let mut order = TestOrderStubs::limit_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("0.80005"),
+            Quantity::from("100000"),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        order
+            .apply(TestOrderEventStubs::order_submitted(&order, account_id))
+            .unwrap();
+        engine
+            .get_exchange_mut(&instrument.id().venue)
+            .unwrap()
+            .get_matching_engine_mut(&instrument.id())
+            .unwrap()
+            .process_order(&order, account_id);
+        engine.run(None);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80005"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    fn submit_market_order(
+        engine: &mut BacktestEngine,
+        instrument: &InstrumentAny,
+        account_id: AccountId,
+        quantity: &str,
+    ) {
+        let mut order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Quantity::from(quantity),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        order
+            .apply(TestOrderEventStubs::order_submitted(&order, account_id))
+            .unwrap();
+        engine
+            .cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        engine
+            .get_exchange_mut(&instrument.id().venue)
+            .unwrap()
+            .send(TradingCommand::SubmitOrder(SubmitOrder {
+                trader_id: order.trader_id(),
+                strategy_id: order.strategy_id(),
+                instrument_id: order.instrument_id(),
+                client_order_id: order.client_order_id(),
+                ..Default::default()
+            }))
+            .unwrap();
+    }
+
+    #[rstest]
+    fn test_run_processes_command_when_latency_elapsed(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, _, events) = get_backtest_engine_with_latency(
+            instrument.clone(),
+            venue_config,
+            Some(Box::new(FixedLatencyModel::new(0, 100, 0, 0))),
+        );
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 1),
+                quote(&instrument, "0.80020", "0.80030", 150),
+            ])
+            .unwrap();
+        engine.run(Some(UnixNanos::from(1)));
+
+        submit_market_order(
+            &mut engine,
+            &instrument,
+            AccountId::from("SIM-001"),
+            "100000",
+        );
+        engine.run(None);
+
+        // Order arrives at the venue (and fills) at 101, before the market moves at 150
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80010"));
+                assert_eq!(fill.ts_event, UnixNanos::from(101));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    struct SubmitOnQuoteHandler {
+        msgbus: Rc<RefCell<MessageBus>>,
+        command: RefCell<Option<TradingCommand>>,
+    }
+
+    impl MessageHandler for SubmitOnQuoteHandler {
+        fn id(&self) -> Ustr {
+            Ustr::from("SubmitOnQuoteHandler")
+        }
+
+        fn handle(&self, _message: &dyn Any) {
+            if let Some(command) = self.command.borrow_mut().take() {
+                self.msgbus
+                    .borrow()
+                    .send("ExecEngine.execute", &command as &dyn Any);
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_run_routes_command_through_execution_client(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let msgbus = Rc::new(RefCell::new(MessageBus::new(
+            TraderId::from("TRADER-001"),
+            UUID4::new(),
+            None,
+            None,
+        )));
+        let cache = Rc::new(RefCell::new(Cache::default()));
+        let (exec_engine, events) =
+            register_exec_engine(&msgbus, cache.clone(), get_atomic_clock_static());
+        let mut engine = BacktestEngine::new(msgbus.clone(), cache.clone());
+        engine.set_exec_engine(exec_engine).unwrap();
+        engine
+            .add_venue(instrument.id().venue, venue_config, None)
+            .unwrap();
+        engine
+            .add_instrument(instrument.clone(), Box::new(MakerTakerFeeModel))
+            .unwrap();
+
+        let order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Quantity::from("100000"),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        let handler = SubmitOnQuoteHandler {
+            msgbus: msgbus.clone(),
+            command: RefCell::new(Some(TradingCommand::SubmitOrder(SubmitOrder {
+                trader_id: order.trader_id(),
+                client_id: ClientId::from("SIM"),
+                strategy_id: order.strategy_id(),
+                instrument_id: order.instrument_id(),
+                client_order_id: order.client_order_id(),
+                ..Default::default()
+            }))),
+        };
+        msgbus.borrow_mut().subscribe(
+            "data.quotes.SIM.AUD/USD",
+            ShareableMessageHandler(Rc::new(handler)),
+            None,
+        );
+
+        engine
+            .add_data(vec![quote(&instrument, "0.80000", "0.80010", 1)])
+            .unwrap();
+        engine.run(None);
+
+        // Submitted by the execution client, then filled when the venue is processed
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Submitted(_)));
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80010"));
+                assert_eq!(fill.ts_event, UnixNanos::from(1));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        let order = cache
+            .borrow()
+            .order(&ClientOrderId::from("O-1"))
+            .cloned()
+            .unwrap();
+        assert!(order.is_closed());
+    }
+
+    #[rstest]
+    fn test_run_settles_expired_position_through_execution_engine(
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let instrument = InstrumentAny::FuturesContract(futures_contract_es());
+        let expiration_ns = instrument.expiration_ns().unwrap();
+        let (mut engine, _, events) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![quote(&instrument, "4000.00", "4000.25", 1)])
+            .unwrap();
+        engine.run(Some(UnixNanos::from(1)));
+        submit_market_order(&mut engine, &instrument, AccountId::from("GLBX-001"), "1");
+        engine
+            .get_exchange_mut(&instrument.id().venue)
+            .unwrap()
+            .set_settlement_price(instrument.id(), Price::from("4010.00"));
+
+        engine.run(Some(expiration_ns));
+
+        let cache = engine.cache.borrow();
+        let settlement_order = cache.order(&ClientOrderId::from("EXP-GLBX-1")).unwrap();
+        let positions = cache.positions(None, Some(&instrument.id()), None, None);
+        assert!(events.borrow().iter().any(|event| matches!(event,
+            OrderEventAny::Filled(fill) if fill.client_order_id == settlement_order.client_order_id())));
+        assert!(settlement_order.is_closed());
+        assert_eq!(settlement_order.avg_px(), Some(4010.0));
+        assert_eq!(positions.len(), 1);
+        assert!(positions[0].is_closed());
+        assert_eq!(
+            positions[0].closing_order_id,
+            Some(settlement_order.client_order_id())
+        );
+    }
+
+    #[rstest]
+    fn test_run_stops_when_account_balance_negative(
+        instrument: InstrumentAny,
+        mut venue_config: SimulatedExchangeConfig,
+    ) {
+        venue_config.starting_balances = vec![Money::from("10000 USD")];
+        venue_config.default_leverage = 50.0;
+        let (mut engine, _, _) = get_backtest_engine(instrument.clone(), venue_config);
+        engine
+            .add_data(vec![quote(&instrument, "0.80000", "0.80010", 1)])
+            .unwrap();
+        engine.run(Some(UnixNanos::from(1)));
+        submit_market_order(
+            &mut engine,
+            &instrument,
+            AccountId::from("SIM-001"),
+            "1000000",
+        );
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.78000", "0.78010", 2),
+                quote(&instrument, "0.78001", "0.78011", 3),
+            ])
+            .unwrap();
+
+        engine.run(None);
+
+        let exchange = engine.get_exchange(&instrument.id().venue).unwrap();
+        assert!(exchange.negative_balance().is_some());
+        assert_eq!(engine.remaining_data(), 1);
+    }
+
+    #[rstest]
+    fn test_run_dispatches_time_events_in_order(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        pyo3::prepare_freethreaded_python();
+
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, time_events) = get_message_saving_handler::<TimeEvent>(None);
+        msgbus.subscribe("events.time.TEST_TIMER", handler, None);
+        let mut engine = BacktestEngine::new(
+            Rc::new(RefCell::new(msgbus)),
+            Rc::new(RefCell::new(Cache::default())),
+        );
+        engine
+            .add_venue(instrument.id().venue, venue_config, None)
+            .unwrap();
+        engine
+            .add_instrument(instrument.clone(), Box::new(MakerTakerFeeModel))
+            .unwrap();
+        let callback = Python::with_gil(|py| EventHandler::new(py.None()));
+        engine
+            .clock()
+            .borrow_mut()
+            .set_timer_ns(
+                "TEST_TIMER",
+                100,
+                UnixNanos::default(),
+                None,
+                Some(callback),
+            )
+            .unwrap();
+        engine
+            .add_data(vec![
+                quote(&instrument, "0.80000", "0.80010", 150),
+                quote(&instrument, "0.80001", "0.80011", 250),
+            ])
+            .unwrap();
+
+        engine.run(Some(UnixNanos::from(300)));
+
+        let ts_events: Vec<u64> = time_events
+            .borrow()
+            .iter()
+            .map(|e| e.ts_event.as_u64())
+            .collect();
+        assert_eq!(ts_events, vec![100, 200, 300]);
+        assert_eq!(engine.iteration(), 2);
+    }
+
+    #[rstest]
+    fn test_run_calls_rust_timer_callbacks(
+        instrument: InstrumentAny,
+        venue_config: SimulatedExchangeConfig,
+    ) {
+        let (mut engine, _, _) = get_backtest_engine(instrument.clone(), venue_config);
+        let received: Saved<TimeEvent> = Rc::new(RefCell::new(Vec::new()));
+        let received_clone = received.clone();
+        let callback = SafeTimeEventCallback::new(Rc::new(move |event: TimeEvent| {
+            received_clone.borrow_mut().push(event);
+        }));
+        engine
+            .clock()
+            .borrow_mut()
+            .set_timer_ns(
+                "TEST_TIMER",
+                100,
+                UnixNanos::default(),
+                None,
+                Some(EventHandler::from_rust(callback)),
+            )
+            .unwrap();
+        engine
+            .add_data(vec![quote(&instrument, "0.80000", "0.80010", 250)])
+            .unwrap();
+
+        engine.run(Some(UnixNanos::from(300)));
+
+        let ts_events: Vec<u64> = received
+            .borrow()
+            .iter()
+            .map(|e| e.ts_event.as_u64())
+            .collect();
+        assert_eq!(ts_events, vec![100, 200, 300]);
+    }
 
     #[rstest]
     fn test_accumulator_drain_sorted() {
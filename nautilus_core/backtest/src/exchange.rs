@@ -0,0 +1,1927 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A simulated exchange venue for backtesting, holding the matching engines for
+//! each instrument along with the simulated account for the venue.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet, VecDeque},
+    rc::Rc,
+};
+
+use log::{debug, error, info, warn};
+use nautilus_common::{cache::Cache, clock::TestClock, msgbus::MessageBus};
+use nautilus_core::{
+    correctness::{check_key_in_map, check_key_not_in_map, check_slice_not_empty},
+    nanos::UnixNanos,
+    time::AtomicTime,
+    uuid::UUID4,
+};
+use nautilus_execution::messages::TradingCommand;
+use nautilus_model::{
+    accounts::{any::AccountAny, cash::CashAccount, margin::MarginAccount},
+    data::{
+        bar::Bar, delta::OrderBookDelta, deltas::OrderBookDeltas, depth::OrderBookDepth10,
+        funding::FundingRateUpdate, quote::QuoteTick, status::InstrumentStatus, trade::TradeTick,
+    },
+    enums::{AccountType, BookType, OmsType, OptionKind, OrderSide, TimeInForce},
+    events::{
+        account::state::AccountState,
+        order::{OrderEventAny, OrderFilled, OrderSubmitted},
+    },
+    identifiers::{AccountId, ClientOrderId, InstrumentId, PositionId, Symbol, Venue},
+    instruments::{any::InstrumentAny, options_contract::OptionsContract},
+    orders::{any::OrderAny, market::MarketOrder},
+    position::Position,
+    types::{
+        balance::{AccountBalance, MarginBalance},
+        currency::Currency,
+        money::Money,
+        price::Price,
+        quantity::Quantity,
+    },
+};
+use rust_decimal::prelude::ToPrimitive;
+use ustr::Ustr;
+
+use crate::{
+    matching_engine::{OrderMatchingEngine, OrderMatchingEngineConfig},
+    models::{
+        fee::FeeModel,
+        fill::FillModel,
+        latency::{InflightQueue, LatencyModel},
+    },
+};
+
+/// A shared queue of trading commands for a [`SimulatedExchange`], which are sent to
+/// the exchange when it is next processed.
+pub type CommandQueue = Rc<RefCell<VecDeque<TradingCommand>>>;
+
+/// The configuration for a [`SimulatedExchange`].
+#[derive(Clone, Debug)]
+pub struct SimulatedExchangeConfig {
+    /// The order management system type for the venue.
+    pub oms_type: OmsType,
+    /// The account type for the venue.
+    pub account_type: AccountType,
+    /// The order book type for the venue matching engines.
+    pub book_type: BookType,
+    /// The starting account balances (one per currency).
+    pub starting_balances: Vec<Money>,
+    /// The account base currency (single-currency account if set).
+    pub base_currency: Option<Currency>,
+    /// The default leverage for margin accounts.
+    pub default_leverage: f64,
+    /// The leverages for specific instruments on margin accounts.
+    pub leverages: HashMap<InstrumentId, f64>,
+    /// If in-the-money options are physically settled at expiration by assignment of
+    /// the underlying (when listed on the venue), otherwise options are cash settled.
+    pub physical_option_settlement: bool,
+    /// The fill model for the venue matching engines.
+    pub fill_model: FillModel,
+    /// The config for the venue matching engines.
+    pub matching_engine_config: OrderMatchingEngineConfig,
+}
+
+impl SimulatedExchangeConfig {
+    /// Creates a new [`SimulatedExchangeConfig`] instance with a default leverage of 1.0,
+    /// cash settlement of options and the default fill model.
+    #[must_use]
+    pub fn new(
+        oms_type: OmsType,
+        account_type: AccountType,
+        book_type: BookType,
+        starting_balances: Vec<Money>,
+        base_currency: Option<Currency>,
+        matching_engine_config: OrderMatchingEngineConfig,
+    ) -> Self {
+        Self {
+            oms_type,
+            account_type,
+            book_type,
+            starting_balances,
+            base_currency,
+            default_leverage: 1.0,
+            leverages: HashMap::new(),
+            physical_option_settlement: false,
+            fill_model: FillModel::default(),
+            matching_engine_config,
+        }
+    }
+}
+
+/// Provides a simulated exchange venue for backtesting.
+///
+/// The exchange routes trading commands to the matching engine for each
+/// instrument (delayed by the optional latency model), and settles every fill
+/// against the simulated account for the venue, updating balances, margins and
+/// realized PnL. Account state changes are sent to the `Portfolio.update_account`
+/// endpoint.
+///
+/// Funding rate updates for perpetual instruments charge or credit the open
+/// position with the funding payment, which is included in its realized PnL.
+///
+/// When an instrument reaches its expiration all working orders are canceled and
+/// any open position is closed by a venue settlement order: futures at the
+/// settlement price, and options at their intrinsic value versus the underlying
+/// (or by assignment of the underlying at the strike price if physically settled).
+///
+/// Positions are read from the cache, where they are maintained by the execution
+/// engine from the fill events. Orders generated by the venue (for liquidation and
+/// settlement) are added to the cache before their events are sent.
+///
+/// For margin accounts the open positions are marked to market after each data
+/// update. When the account equity (balance plus unrealized PnL) falls below the
+/// total maintenance margin for a currency, all positions settled in that currency
+/// are force liquidated with market orders. Liquidation of a position is held
+/// pending while the market for its instrument is not open.
+///
+/// If a loss would take an account balance below zero the account state is not
+/// applied, and the negative balance is reported by [`SimulatedExchange::negative_balance`].
+pub struct SimulatedExchange {
+    /// The venue ID for the exchange.
+    pub id: Venue,
+    /// The order management system (OMS) type for the exchange.
+    pub oms_type: OmsType,
+    /// The account type for the exchange.
+    pub account_type: AccountType,
+    /// The order book type for the exchange.
+    pub book_type: BookType,
+    /// The account base currency (if single-currency).
+    pub base_currency: Option<Currency>,
+    physical_option_settlement: bool,
+    fill_model: FillModel,
+    config: OrderMatchingEngineConfig,
+    clock: &'static AtomicTime,
+    msgbus: Rc<RefCell<MessageBus>>,
+    cache: Rc<RefCell<Cache>>,
+    account: AccountAny,
+    matching_engines: HashMap<InstrumentId, OrderMatchingEngine>,
+    settlement_prices: HashMap<InstrumentId, Price>,
+    queued: CommandQueue,
+    inflight: Option<InflightQueue>,
+    pending_liquidations: HashSet<PositionId>,
+    negative_balance: Option<Money>,
+    liquidation_count: usize,
+    settlement_count: usize,
+}
+
+impl SimulatedExchange {
+    /// Creates a new [`SimulatedExchange`] instance.
+    ///
+    /// Commands delayed by the optional `latency_model` arrive at the exchange as time
+    /// alerts set on the `test_clock` fire.
+    pub fn new(
+        venue: Venue,
+        config: SimulatedExchangeConfig,
+        latency_model: Option<Box<dyn LatencyModel>>,
+        test_clock: Rc<RefCell<TestClock>>,
+        clock: &'static AtomicTime,
+        msgbus: Rc<RefCell<MessageBus>>,
+        cache: Rc<RefCell<Cache>>,
+    ) -> anyhow::Result<Self> {
+        check_slice_not_empty(&config.starting_balances, "starting_balances")?;
+        if let Some(base_currency) = config.base_currency {
+            anyhow::ensure!(
+                config.starting_balances.len() == 1
+                    && config.starting_balances[0].currency == base_currency,
+                "Single-currency account requires a single starting balance in {base_currency}"
+            );
+        }
+
+        let account_id =
+            AccountId::new(&format!("{venue}-{}", msgbus.borrow().trader_id.get_tag()))?;
+        let ts_now = clock.get_time_ns();
+        let balances = config
+            .starting_balances
+            .iter()
+            .map(|balance| {
+                AccountBalance::new(*balance, Money::new(0.0, balance.currency)?, *balance)
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let event = AccountState::new(
+            account_id,
+            config.account_type,
+            balances,
+            vec![],
+            true,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            config.base_currency,
+        )?;
+
+        let account = match config.account_type {
+            AccountType::Cash => AccountAny::Cash(CashAccount::new(event, false)?),
+            AccountType::Margin => {
+                let mut account = MarginAccount::new(event, false)?;
+                account.set_default_leverage(config.default_leverage);
+                for (instrument_id, leverage) in &config.leverages {
+                    account.set_leverage(*instrument_id, *leverage);
+                }
+                AccountAny::Margin(account)
+            }
+            AccountType::Betting => anyhow::bail!("Betting accounts are not supported"),
+        };
+
+        Ok(Self {
+            id: venue,
+            oms_type: config.oms_type,
+            account_type: config.account_type,
+            book_type: config.book_type,
+            base_currency: config.base_currency,
+            physical_option_settlement: config.physical_option_settlement,
+            fill_model: config.fill_model,
+            config: config.matching_engine_config,
+            clock,
+            msgbus,
+            cache,
+            account,
+            matching_engines: HashMap::new(),
+            settlement_prices: HashMap::new(),
+            queued: CommandQueue::default(),
+            inflight: latency_model.map(|latency_model| {
+                InflightQueue::new(&format!("{venue}-INFLIGHT"), test_clock, latency_model)
+            }),
+            pending_liquidations: HashSet::new(),
+            negative_balance: None,
+            liquidation_count: 0,
+            settlement_count: 0,
+        })
+    }
+
+    /// Returns the account ID for the exchange.
+    #[must_use]
+    pub fn account_id(&self) -> AccountId {
+        self.account.id()
+    }
+
+    /// Returns the simulated account for the exchange.
+    #[must_use]
+    pub const fn get_account(&self) -> &AccountAny {
+        &self.account
+    }
+
+    /// Returns the balance which would have gone negative when settling the account
+    /// (if any), after which the account state is no longer updated.
+    #[must_use]
+    pub const fn negative_balance(&self) -> Option<Money> {
+        self.negative_balance
+    }
+
+    /// Returns the matching engine for the given `instrument_id` (if found).
+    #[must_use]
+    pub fn get_matching_engine(
+        &self,
+        instrument_id: &InstrumentId,
+    ) -> Option<&OrderMatchingEngine> {
+        self.matching_engines.get(instrument_id)
+    }
+
+    /// Returns a mutable reference to the matching engine for the given `instrument_id` (if found).
+    pub fn get_matching_engine_mut(
+        &mut self,
+        instrument_id: &InstrumentId,
+    ) -> Option<&mut OrderMatchingEngine> {
+        self.matching_engines.get_mut(instrument_id)
+    }
+
+    /// Returns the queue of trading commands sent to the exchange when it is next processed
+    /// (as used by the venues execution client).
+    #[must_use]
+    pub fn command_queue(&self) -> CommandQueue {
+        self.queued.clone()
+    }
+
+    /// Sets the final settlement price for the given `instrument_id`.
+    ///
+    /// The settlement price closes futures positions at expiration, and determines
+    /// the intrinsic value at expiration of options on the instrument as the underlying.
+    /// If not set then the mid price of the instruments market is used.
+    pub fn set_settlement_price(&mut self, instrument_id: InstrumentId, price: Price) {
+        self.settlement_prices.insert(instrument_id, price);
+    }
+
+    /// Sends the initial account state for the exchange to the portfolio.
+    pub fn initialize_account(&self) {
+        if let Some(event) = self.account.last_event() {
+            self.send_account_state(&event);
+        }
+    }
+
+    /// Adds the given `instrument` to the exchange, creating a matching engine for
+    /// it using the `fee_model`.
+    pub fn add_instrument(
+        &mut self,
+        instrument: InstrumentAny,
+        fee_model: Box<dyn FeeModel>,
+    ) -> anyhow::Result<()> {
+        let instrument_id = instrument.id();
+        anyhow::ensure!(
+            instrument_id.venue == self.id,
+            "Venue of instrument {instrument_id} does not equal exchange venue {}",
+            self.id
+        );
+        check_key_not_in_map(
+            &instrument_id,
+            &self.matching_engines,
+            "instrument_id",
+            "matching_engines",
+        )?;
+
+        let raw_id = self.matching_engines.len() as u32 + 1;
+        let matching_engine = OrderMatchingEngine::new(
+            instrument,
+            raw_id,
+            self.fill_model.clone(),
+            fee_model,
+            self.book_type,
+            self.oms_type,
+            self.account_type,
+            self.clock,
+            self.msgbus.clone(),
+            self.cache.clone(),
+            self.config.clone(),
+        );
+        self.matching_engines.insert(instrument_id, matching_engine);
+        info!("Added instrument {instrument_id} to {}", self.id);
+        Ok(())
+    }
+
+    /// Sends the given trading `command` to the exchange.
+    ///
+    /// If the exchange has a latency model then the command is held in flight
+    /// until its latency has elapsed, otherwise it is processed immediately.
+    pub fn send(&mut self, command: TradingCommand) -> anyhow::Result<()> {
+        check_key_in_map(
+            &command.instrument_id(),
+            &self.matching_engines,
+            "instrument_id",
+            "matching_engines",
+        )?;
+
+        match self.inflight.as_mut() {
+            Some(inflight) => {
+                inflight.send(command)?;
+                self.process_arrived_commands();
+            }
+            None => self.process_trading_command(command),
+        }
+        Ok(())
+    }
+
+    /// Processes the exchange at the given `ts_now`, sending any queued commands and
+    /// processing in-flight commands which have arrived, iterating the matching engines
+    /// and then settling any instruments which have reached expiration.
+    pub fn process(&mut self, ts_now: UnixNanos) {
+        let queued: Vec<TradingCommand> = self.queued.borrow_mut().drain(..).collect();
+        for command in queued {
+            if let Err(e) = self.send(command) {
+                error!("Error sending command: {e}");
+            }
+        }
+        self.process_arrived_commands();
+
+        let instrument_ids: Vec<InstrumentId> = self.matching_engines.keys().copied().collect();
+        for instrument_id in instrument_ids {
+            if let Some(matching_engine) = self.matching_engines.get_mut(&instrument_id) {
+                matching_engine.iterate(ts_now);
+            }
+            self.settle_fills(&instrument_id);
+        }
+        self.check_expirations(ts_now);
+        self.check_liquidation();
+    }
+
+    /// Resets the exchange, clearing queued and in-flight commands, pending liquidations and
+    /// the matching engines. The simulated account is retained.
+    pub fn reset(&mut self) {
+        for matching_engine in self.matching_engines.values_mut() {
+            matching_engine.reset();
+        }
+        self.queued.borrow_mut().clear();
+        if let Some(inflight) = self.inflight.as_mut() {
+            inflight.clear();
+        }
+        self.pending_liquidations.clear();
+        self.negative_balance = None;
+        self.liquidation_count = 0;
+        self.settlement_count = 0;
+
+        info!("Reset {}", self.id);
+    }
+
+    // -- DATA PROCESSING -------------------------------------------------------------------------
+
+    /// Process the exchange market for the given order book delta.
+    pub fn process_order_book_delta(&mut self, delta: &OrderBookDelta) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&delta.instrument_id) {
+            matching_engine.process_order_book_delta(delta);
+        }
+        self.settle_market_update(&delta.instrument_id);
+    }
+
+    /// Process the exchange market for the given order book deltas.
+    pub fn process_order_book_deltas(&mut self, deltas: &OrderBookDeltas) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&deltas.instrument_id) {
+            matching_engine.process_order_book_deltas(deltas);
+        }
+        self.settle_market_update(&deltas.instrument_id);
+    }
+
+    /// Process the exchange market for the given order book depth.
+    pub fn process_order_book_depth10(&mut self, depth: &OrderBookDepth10) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&depth.instrument_id) {
+            matching_engine.process_order_book_depth10(depth);
+        }
+        self.settle_market_update(&depth.instrument_id);
+    }
+
+    /// Process the exchange market for the given quote tick.
+    pub fn process_quote_tick(&mut self, quote: &QuoteTick) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&quote.instrument_id) {
+            matching_engine.process_quote_tick(quote);
+        }
+        self.settle_market_update(&quote.instrument_id);
+    }
+
+    /// Process the exchange market for the given trade tick.
+    pub fn process_trade_tick(&mut self, trade: &TradeTick) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&trade.instrument_id) {
+            matching_engine.process_trade_tick(trade);
+        }
+        self.settle_market_update(&trade.instrument_id);
+    }
+
+    /// Process the exchange market for the given bar.
+    pub fn process_bar(&mut self, bar: &Bar) {
+        let instrument_id = bar.bar_type.instrument_id;
+        if let Some(matching_engine) = self.matching_engines.get_mut(&instrument_id) {
+            matching_engine.process_bar(bar);
+        }
+        self.settle_market_update(&instrument_id);
+    }
+
+    /// Process the exchange market for the given instrument status, which changes the
+    /// trading phase of the instruments matching engine (held orders may fill on reopen).
+    pub fn process_instrument_status(&mut self, status: &InstrumentStatus) {
+        if let Some(matching_engine) = self.matching_engines.get_mut(&status.instrument_id) {
+            matching_engine.process_status(status);
+        }
+        self.settle_market_update(&status.instrument_id);
+    }
+
+    /// Process the exchange account for the given funding rate, charging or crediting
+    /// the funding payment for each open position in the perpetual instrument.
+    ///
+    /// The payment is calculated on the position notional value at the current mark
+    /// price (the price the position could be closed at), and is applied to the
+    /// position in the cache.
+    pub fn process_funding_rate(&mut self, funding_rate: &FundingRateUpdate) {
+        let instrument_id = funding_rate.instrument_id;
+        let Some(matching_engine) = self.matching_engines.get(&instrument_id) else {
+            return;
+        };
+        if !matches!(
+            matching_engine.instrument,
+            InstrumentAny::CryptoPerpetual(_)
+        ) {
+            warn!("Funding rate for non-perpetual instrument {instrument_id} ignored");
+            return;
+        }
+
+        let rate = funding_rate.rate.to_f64().unwrap_or(0.0);
+        let mut payments = Vec::new();
+        for mut position in self.positions_open(Some(&instrument_id)) {
+            let notional = position.notional_value(get_mark_price(matching_engine, &position));
+            // Long positions pay a positive funding rate, short positions receive it
+            let sign = if position.is_long() { -1.0 } else { 1.0 };
+            let payment = match Money::new(sign * notional.as_f64() * rate, notional.currency) {
+                Ok(payment) => payment,
+                Err(e) => {
+                    error!("Error calculating funding payment for {}: {e}", position.id);
+                    continue;
+                }
+            };
+            if payment.currency != position.settlement_currency {
+                error!(
+                    "Cannot apply funding payment {payment} to {position} settled in {}",
+                    position.settlement_currency
+                );
+                continue;
+            }
+
+            position.apply_funding(payment);
+            if let Err(e) = self.cache.borrow_mut().update_position(&position) {
+                error!("Error updating position {}: {e}", position.id);
+                continue;
+            }
+            info!(
+                "Applied funding payment {payment} to {position} at rate {}",
+                funding_rate.rate
+            );
+            payments.push(payment);
+        }
+        if payments.is_empty() {
+            return;
+        }
+
+        let mut totals = self.account.balances_total();
+        for payment in payments {
+            let total = totals
+                .entry(payment.currency)
+                .or_insert_with(|| Money::from_raw(0, payment.currency));
+            *total += payment;
+        }
+        self.update_account_state(totals);
+        self.check_liquidation();
+    }
+
+    // -- COMMAND HANDLERS ------------------------------------------------------------------------
+
+    fn process_arrived_commands(&mut self) {
+        let Some(inflight) = self.inflight.as_mut() else {
+            return;
+        };
+        for command in inflight.drain_arrived() {
+            self.process_trading_command(command);
+        }
+    }
+
+    fn process_trading_command(&mut self, command: TradingCommand) {
+        let instrument_id = command.instrument_id();
+        let account_id = self.account_id();
+        let Some(matching_engine) = self.matching_engines.get_mut(&instrument_id) else {
+            error!("No matching engine for {instrument_id}");
+            return;
+        };
+
+        match command {
+            TradingCommand::SubmitOrder(command) => {
+                let order = self.cache.borrow().order(&command.client_order_id).cloned();
+                match order {
+                    Some(order) => matching_engine.process_order(&order, account_id),
+                    None => error!("Cannot submit order: {} not found", command.client_order_id),
+                }
+            }
+            TradingCommand::SubmitOrderList(command) => {
+                for order in &command.order_list.orders {
+                    matching_engine.process_order(order, account_id);
+                }
+            }
+            TradingCommand::ModifyOrder(command) => {
+                matching_engine.process_modify(&command, account_id);
+            }
+            TradingCommand::CancelOrder(command) => {
+                matching_engine.process_cancel(&command, account_id);
+            }
+            TradingCommand::CancelAllOrders(command) => {
+                matching_engine.process_cancel_all(&command, account_id);
+            }
+            TradingCommand::BatchCancelOrders(command) => {
+                for cancel in &command.cancels {
+                    matching_engine.process_cancel(cancel, account_id);
+                }
+            }
+            TradingCommand::QueryOrder(command) => {
+                debug!("Query not supported by simulated venue: {command}");
+            }
+        }
+
+        self.settle_fills(&instrument_id);
+        self.check_liquidation();
+    }
+
+    // -- ACCOUNT SETTLEMENT ----------------------------------------------------------------------
+
+    fn settle_market_update(&mut self, instrument_id: &InstrumentId) {
+        self.settle_fills(instrument_id);
+        self.check_liquidation();
+    }
+
+    /// Settles any fills generated by the matching engine for the `instrument_id`
+    /// against the account, sending the updated account state.
+    fn settle_fills(&mut self, instrument_id: &InstrumentId) {
+        let Some(matching_engine) = self.matching_engines.get_mut(instrument_id) else {
+            return;
+        };
+        let fills = matching_engine.drain_fills();
+        if fills.is_empty() {
+            return;
+        }
+        let instrument = matching_engine.instrument.clone();
+
+        let mut totals = self.account.balances_total();
+        for (fill, position) in fills {
+            for pnl in self.apply_fill(&instrument, fill, position) {
+                let total = totals
+                    .entry(pnl.currency)
+                    .or_insert_with(|| Money::from_raw(0, pnl.currency));
+                *total += pnl;
+            }
+        }
+
+        self.update_account_state(totals);
+    }
+
+    /// Applies the `fill` to a copy of the open `position` it filled against (as it
+    /// was before the fill), returning the resulting balance changes for the account.
+    fn apply_fill(
+        &self,
+        instrument: &InstrumentAny,
+        fill: OrderFilled,
+        position: Option<Position>,
+    ) -> Vec<Money> {
+        let (realized_pnl_before, position) = match position {
+            Some(mut position) => {
+                let realized_pnl_before = position.realized_pnl.map_or(0.0, |pnl| pnl.as_f64());
+                position.apply(&fill);
+                (realized_pnl_before, position)
+            }
+            None => {
+                let mut fill = fill;
+                if fill.position_id.is_none() {
+                    // Only identifies the copy of the position used to calculate the PnL
+                    let position_id = format!("{}-{}", fill.instrument_id, fill.strategy_id);
+                    fill.position_id = Some(PositionId::from(position_id.as_str()));
+                }
+                match Position::new(instrument, fill) {
+                    Ok(position) => (0.0, position),
+                    Err(e) => {
+                        error!("Error opening position from {fill}: {e}");
+                        return vec![];
+                    }
+                }
+            }
+        };
+
+        let mut pnls = Vec::new();
+        match self.account {
+            AccountAny::Margin(_) => {
+                // Realized PnL includes any commission in the settlement currency
+                let realized_pnl_after = position.realized_pnl.map_or(0.0, |pnl| pnl.as_f64());
+                pnls.push(Money::new(
+                    realized_pnl_after - realized_pnl_before,
+                    position.settlement_currency,
+                ));
+                if let Some(commission) = fill.commission {
+                    if commission.currency != position.settlement_currency {
+                        pnls.push(Ok(-commission));
+                    }
+                }
+            }
+            AccountAny::Cash(_) => {
+                let notional =
+                    instrument.calculate_notional_value(fill.last_qty, fill.last_px, None);
+                let base_currency = instrument
+                    .base_currency()
+                    .filter(|_| self.base_currency.is_none());
+                match fill.order_side {
+                    OrderSide::Buy => {
+                        pnls.push(Ok(-notional));
+                        if let Some(base_currency) = base_currency {
+                            pnls.push(Money::new(fill.last_qty.as_f64(), base_currency));
+                        }
+                    }
+                    _ => {
+                        pnls.push(Ok(notional));
+                        if let Some(base_currency) = base_currency {
+                            pnls.push(Money::new(-fill.last_qty.as_f64(), base_currency));
+                        }
+                    }
+                }
+                if let Some(commission) = fill.commission {
+                    pnls.push(Ok(-commission));
+                }
+            }
+        }
+
+        pnls.into_iter()
+            .filter_map(|pnl| pnl.map_err(|e| error!("Error calculating PnL: {e}")).ok())
+            .collect()
+    }
+
+    /// Updates the account with the given balance `totals`, locking the
+    /// maintenance margin for open positions on margin accounts.
+    ///
+    /// If any balance total is negative then the account is not updated, and the
+    /// negative balance is retained to be reported.
+    fn update_account_state(&mut self, totals: HashMap<Currency, Money>) {
+        if self.negative_balance.is_some() {
+            return;
+        }
+        if let Some(total) = totals.values().find(|total| total.raw < 0) {
+            error!(
+                "Account {} balance negative ({total}), account state no longer updated",
+                self.account_id()
+            );
+            self.negative_balance = Some(*total);
+            return;
+        }
+
+        let margins = self.calculate_margins();
+
+        let mut balances = Vec::with_capacity(totals.len());
+        for (currency, total) in totals {
+            let margin_raw: i64 = margins
+                .iter()
+                .filter(|margin| margin.currency == currency)
+                .map(|margin| margin.initial.raw + margin.maintenance.raw)
+                .sum();
+            let locked = Money::from_raw(margin_raw.min(total.raw), currency);
+            match AccountBalance::new(total, locked, total - locked) {
+                Ok(balance) => balances.push(balance),
+                Err(e) => error!("Error calculating balance for {currency}: {e}"),
+            }
+        }
+
+        let ts_now = self.clock.get_time_ns();
+        let event = match AccountState::new(
+            self.account_id(),
+            self.account_type,
+            balances,
+            margins.clone(),
+            true,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            self.base_currency,
+        ) {
+            Ok(event) => event,
+            Err(e) => {
+                error!("Error creating account state: {e}");
+                return;
+            }
+        };
+
+        if let AccountAny::Margin(account) = &mut self.account {
+            account.margins = margins
+                .into_iter()
+                .map(|margin| (margin.instrument_id, margin))
+                .collect();
+        }
+        self.account.apply(event.clone());
+        self.send_account_state(&event);
+    }
+
+    /// Returns the open positions at the venue from the cache, optionally filtered
+    /// by `instrument_id` (sorted by position ID).
+    fn positions_open(&self, instrument_id: Option<&InstrumentId>) -> Vec<Position> {
+        let mut positions: Vec<Position> = self
+            .cache
+            .borrow()
+            .positions_open(Some(&self.id), instrument_id, None, None)
+            .into_iter()
+            .cloned()
+            .collect();
+        positions.sort_by_key(|position| position.id);
+        positions
+    }
+
+    /// Returns the maintenance margins for each instrument with open positions
+    /// (margin accounts only).
+    fn calculate_margins(&mut self) -> Vec<MarginBalance> {
+        if !matches!(self.account, AccountAny::Margin(_)) {
+            return vec![];
+        }
+        let positions = self.positions_open(None);
+        let AccountAny::Margin(account) = &mut self.account else {
+            return vec![];
+        };
+
+        let mut maintenances: HashMap<InstrumentId, Money> = HashMap::new();
+        for position in &positions {
+            let Some(matching_engine) = self.matching_engines.get(&position.instrument_id) else {
+                continue;
+            };
+            let mark_px = get_mark_price(matching_engine, position);
+            let maintenance = calculate_maintenance_margin(
+                account,
+                &matching_engine.instrument,
+                position.quantity,
+                mark_px,
+            );
+            *maintenances
+                .entry(position.instrument_id)
+                .or_insert_with(|| Money::from_raw(0, maintenance.currency)) += maintenance;
+        }
+
+        let mut margins = Vec::with_capacity(maintenances.len());
+        for (instrument_id, maintenance) in maintenances {
+            match MarginBalance::new(
+                Money::from_raw(0, maintenance.currency),
+                maintenance,
+                instrument_id,
+            ) {
+                Ok(margin) => margins.push(margin),
+                Err(e) => error!("Error calculating margin for {instrument_id}: {e}"),
+            }
+        }
+        margins
+    }
+
+    /// Checks the margin account equity against the maintenance margin for each
+    /// currency, force liquidating positions when the margin is breached.
+    fn check_liquidation(&mut self) {
+        let positions = self.positions_open(None);
+        self.pending_liquidations
+            .retain(|position_id| positions.iter().any(|position| position.id == *position_id));
+        if positions.is_empty() || self.negative_balance.is_some() {
+            return;
+        }
+
+        let margins = self.calculate_margins();
+        let mut maintenance: HashMap<Currency, f64> = HashMap::new();
+        for margin in &margins {
+            *maintenance.entry(margin.currency).or_default() += margin.maintenance.as_f64();
+        }
+
+        for (currency, maintenance) in maintenance {
+            let balance = self
+                .account
+                .balance_total(Some(currency))
+                .map_or(0.0, |balance| balance.as_f64());
+            let unrealized_pnl: f64 = positions
+                .iter()
+                .filter(|position| position.settlement_currency == currency)
+                .filter_map(|position| {
+                    self.matching_engines
+                        .get(&position.instrument_id)
+                        .map(|engine| position.unrealized_pnl(get_mark_price(engine, position)))
+                })
+                .map(|pnl| pnl.as_f64())
+                .sum();
+            let equity = balance + unrealized_pnl;
+
+            if equity < maintenance {
+                let positions = positions
+                    .iter()
+                    .filter(|position| position.settlement_currency == currency)
+                    .cloned()
+                    .collect();
+                self.liquidate(positions, equity, maintenance);
+            }
+        }
+    }
+
+    /// Force liquidates the given `positions` with market orders, after the account
+    /// `equity` fell below the `maintenance` margin for their settlement currency.
+    ///
+    /// Positions whose market is not open are held pending until it reopens, rather
+    /// than having liquidation orders rejected on every update.
+    fn liquidate(&mut self, positions: Vec<Position>, equity: f64, maintenance: f64) {
+        for position in positions {
+            let currency = position.settlement_currency;
+            let is_market_open = self
+                .matching_engines
+                .get(&position.instrument_id)
+                .is_some_and(OrderMatchingEngine::is_market_open);
+            if !is_market_open {
+                if self.pending_liquidations.insert(position.id) {
+                    warn!(
+                        "Account {} margin call: equity {equity:.2} {currency} below maintenance margin {maintenance:.2} {currency}, liquidation of {position} pending until the market reopens",
+                        self.account_id(),
+                    );
+                }
+                continue;
+            }
+            self.pending_liquidations.remove(&position.id);
+
+            self.liquidation_count += 1;
+            let account_id = self.account_id();
+            let client_order_id = format!("LIQ-{}-{}", self.id, self.liquidation_count);
+            let client_order_id = ClientOrderId::from(client_order_id.as_str());
+            let order_side = closing_side(&position);
+            let Some(order) = self.create_venue_order(
+                &position,
+                position.instrument_id,
+                client_order_id,
+                order_side,
+                position.quantity,
+                "LIQUIDATION",
+            ) else {
+                continue;
+            };
+
+            warn!(
+                "Account {} margin call: equity {equity:.2} {currency} below maintenance margin {maintenance:.2} {currency}, liquidating {position} with {client_order_id}",
+                self.account_id(),
+            );
+            if let Some(matching_engine) = self.matching_engines.get_mut(&position.instrument_id) {
+                matching_engine.process_order(&order, account_id);
+            }
+            self.settle_fills(&position.instrument_id);
+        }
+    }
+
+    // -- EXPIRATION ------------------------------------------------------------------------------
+
+    /// Processes the expiration of any instruments which have expired at `ts_now`.
+    fn check_expirations(&mut self, ts_now: UnixNanos) {
+        let mut instrument_ids: Vec<InstrumentId> = self
+            .matching_engines
+            .values()
+            .filter(|matching_engine| {
+                !matching_engine.is_expired()
+                    && matching_engine
+                        .instrument
+                        .expiration_ns()
+                        .is_some_and(|expiration_ns| {
+                            expiration_ns.as_u64() > 0 && expiration_ns <= ts_now
+                        })
+            })
+            .map(|matching_engine| matching_engine.instrument.id())
+            .collect();
+        instrument_ids.sort();
+
+        for instrument_id in instrument_ids {
+            self.process_expiration(&instrument_id);
+        }
+    }
+
+    /// Cancels all working orders for the expired instrument, then settles each open position.
+    fn process_expiration(&mut self, instrument_id: &InstrumentId) {
+        let Some(matching_engine) = self.matching_engines.get_mut(instrument_id) else {
+            return;
+        };
+        matching_engine.process_expiration();
+        let instrument = matching_engine.instrument.clone();
+
+        for position in self.positions_open(Some(instrument_id)) {
+            match &instrument {
+                InstrumentAny::OptionsContract(option) => self.settle_option(option, &position),
+                _ => {
+                    let settlement_px =
+                        self.get_settlement_price(instrument_id).unwrap_or_else(|| {
+                            warn!("No settlement price for {instrument_id}, using last fill price");
+                            position.last_event().last_px
+                        });
+                    self.settle(
+                        &position,
+                        *instrument_id,
+                        closing_side(&position),
+                        position.quantity,
+                        settlement_px,
+                        "EXPIRATION",
+                    );
+                }
+            }
+        }
+    }
+
+    /// Settles the expired `option` position at its intrinsic value versus the underlying,
+    /// or by assignment of the underlying at the strike price if physically settled.
+    fn settle_option(&mut self, option: &OptionsContract, position: &Position) {
+        let underlying_id = InstrumentId::new(Symbol::from(option.underlying.as_str()), self.id);
+        let Some(underlying_px) = self.get_settlement_price(&underlying_id) else {
+            warn!(
+                "No price for underlying {underlying_id} of {}, settling at market",
+                option.id
+            );
+            let settlement_px = self
+                .get_settlement_price(&option.id)
+                .unwrap_or_else(|| position.last_event().last_px);
+            self.settle(
+                position,
+                option.id,
+                closing_side(position),
+                position.quantity,
+                settlement_px,
+                "EXPIRATION",
+            );
+            return;
+        };
+
+        let strike = option.strike_price.as_f64();
+        let intrinsic_value = match option.option_kind {
+            OptionKind::Call => (underlying_px.as_f64() - strike).max(0.0),
+            OptionKind::Put => (strike - underlying_px.as_f64()).max(0.0),
+        };
+        let is_assigned = self.physical_option_settlement
+            && intrinsic_value > 0.0
+            && self.matching_engines.contains_key(&underlying_id);
+
+        let settlement_value = if is_assigned { 0.0 } else { intrinsic_value };
+        let settlement_px = match Price::new(settlement_value, option.price_precision) {
+            Ok(price) => price,
+            Err(e) => {
+                error!("Error calculating settlement price for {}: {e}", option.id);
+                return;
+            }
+        };
+        self.settle(
+            position,
+            option.id,
+            closing_side(position),
+            position.quantity,
+            settlement_px,
+            "EXPIRATION",
+        );
+
+        if !is_assigned {
+            return;
+        }
+
+        // Deliver the underlying at the strike price (long calls and short puts buy)
+        let underlying = &self.matching_engines[&underlying_id].instrument;
+        let order_side = match (option.option_kind, position.is_long()) {
+            (OptionKind::Call, true) | (OptionKind::Put, false) => OrderSide::Buy,
+            _ => OrderSide::Sell,
+        };
+        let quantity = underlying.make_qty(position.quantity.as_f64() * option.multiplier.as_f64());
+        let strike_px = underlying.make_price(strike);
+        match (quantity, strike_px) {
+            (Ok(quantity), Ok(strike_px)) => {
+                self.settle(
+                    position,
+                    underlying_id,
+                    order_side,
+                    quantity,
+                    strike_px,
+                    "ASSIGNMENT",
+                );
+            }
+            (Err(e), _) | (_, Err(e)) => {
+                error!("Error assigning {underlying_id} for {}: {e}", option.id);
+            }
+        }
+    }
+
+    /// Fills a venue settlement order for the `position` owner in the `instrument_id`
+    /// at the `settlement_px`, settling the fill against the account.
+    fn settle(
+        &mut self,
+        position: &Position,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        settlement_px: Price,
+        tag: &str,
+    ) {
+        self.settlement_count += 1;
+        let client_order_id = format!("EXP-{}-{}", self.id, self.settlement_count);
+        let client_order_id = ClientOrderId::from(client_order_id.as_str());
+        let Some(order) = self.create_venue_order(
+            position,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            tag,
+        ) else {
+            return;
+        };
+
+        info!("Settling {quantity} {instrument_id} at {settlement_px} with {client_order_id}");
+        let account_id = self.account_id();
+        if let Some(matching_engine) = self.matching_engines.get_mut(&instrument_id) {
+            matching_engine.process_settlement(&order, settlement_px, account_id);
+        }
+        self.settle_fills(&instrument_id);
+    }
+
+    /// Returns the settlement price for the `instrument_id`, falling back to the mid
+    /// price of its market on the venue.
+    fn get_settlement_price(&self, instrument_id: &InstrumentId) -> Option<Price> {
+        if let Some(price) = self.settlement_prices.get(instrument_id) {
+            return Some(*price);
+        }
+
+        let matching_engine = self.matching_engines.get(instrument_id)?;
+        match (
+            matching_engine.best_bid_price(),
+            matching_engine.best_ask_price(),
+        ) {
+            (Some(bid), Some(ask)) => matching_engine
+                .instrument
+                .make_price((bid.as_f64() + ask.as_f64()) / 2.0)
+                .ok(),
+            (bid, ask) => bid.or(ask),
+        }
+    }
+
+    /// Creates a market order generated by the venue (for liquidation or settlement)
+    /// on behalf of the owner of the `position`, adding it to the cache and sending
+    /// the submitted event to the execution engine.
+    ///
+    /// Orders for the instrument of the `position` are indexed against it, so that
+    /// their fills apply to the position (as required for hedging).
+    fn create_venue_order(
+        &self,
+        position: &Position,
+        instrument_id: InstrumentId,
+        client_order_id: ClientOrderId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        tag: &str,
+    ) -> Option<OrderAny> {
+        let ts_now = self.clock.get_time_ns();
+        let order = MarketOrder::new(
+            position.trader_id,
+            position.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            TimeInForce::Ioc,
+            UUID4::new(),
+            ts_now,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(vec![Ustr::from(tag)]),
+        );
+        let mut order = match order {
+            Ok(order) => OrderAny::Market(order),
+            Err(e) => {
+                error!("Error creating {tag} order: {e}");
+                return None;
+            }
+        };
+
+        let position_id = (instrument_id == position.instrument_id).then_some(position.id);
+        if let Err(e) = self
+            .cache
+            .borrow_mut()
+            .add_order(order.clone(), position_id, None, false)
+        {
+            error!("Error adding {tag} order {client_order_id} to the cache: {e}");
+            return None;
+        }
+
+        let submitted = OrderSubmitted::new(
+            position.trader_id,
+            position.strategy_id,
+            instrument_id,
+            client_order_id,
+            self.account_id(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+        )
+        .map(OrderEventAny::Submitted)
+        .and_then(|event| {
+            order.apply(event.clone())?;
+            Ok(event)
+        });
+        match submitted {
+            Ok(event) => {
+                self.msgbus
+                    .borrow()
+                    .send("ExecEngine.process", &event as &dyn Any);
+                Some(order)
+            }
+            Err(e) => {
+                error!("Error submitting {tag} order: {e}");
+                None
+            }
+        }
+    }
+
+    fn send_account_state(&self, event: &AccountState) {
+        self.msgbus
+            .borrow()
+            .send("Portfolio.update_account", event as &dyn Any);
+    }
+}
+
+/// Returns the order side which closes the `position`.
+fn closing_side(position: &Position) -> OrderSide {
+    if position.is_long() {
+        OrderSide::Sell
+    } else {
+        OrderSide::Buy
+    }
+}
+
+/// Returns the price the `position` could currently be closed at on the venue,
+/// falling back to the last fill price when there is no market.
+fn get_mark_price(matching_engine: &OrderMatchingEngine, position: &Position) -> Price {
+    let price = if position.is_long() {
+        matching_engine.best_bid_price()
+    } else {
+        matching_engine.best_ask_price()
+    };
+    price.unwrap_or_else(|| position.last_event().last_px)
+}
+
+fn calculate_maintenance_margin(
+    account: &mut MarginAccount,
+    instrument: &InstrumentAny,
+    quantity: Quantity,
+    price: Price,
+) -> Money {
+    match instrument.clone() {
+        InstrumentAny::CryptoFuture(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::CryptoPerpetual(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::CurrencyPair(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::Equity(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::FuturesContract(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::FuturesSpread(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::OptionsContract(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+        InstrumentAny::OptionsSpread(inst) => {
+            account.calculate_maintenance_margin(inst, quantity, price, None)
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        clock::TestClock,
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, time::AtomicTime, uuid::UUID4};
+    use nautilus_execution::messages::{submit::SubmitOrder, TradingCommand};
+    use nautilus_model::{
+        accounts::any::AccountAny,
+        data::{funding::FundingRateUpdate, quote::QuoteTick, status::InstrumentStatus},
+        enums::{AccountType, BookType, MarketStatusAction, OmsType, OrderSide},
+        events::{account::state::AccountState, order::OrderEventAny},
+        identifiers::{AccountId, ClientOrderId, InstrumentId, TraderId, Venue},
+        instruments::{
+            any::InstrumentAny,
+            stubs::{
+                audusd_sim, crypto_perpetual_ethusdt, equity_aapl, futures_contract_es,
+                options_contract_appl,
+            },
+        },
+        orders::{
+            any::OrderAny,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        position::Position,
+        types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+    use rust_decimal_macros::dec;
+
+    use super::{SimulatedExchange, SimulatedExchangeConfig};
+    use crate::{
+        matching_engine::OrderMatchingEngineConfig,
+        models::{
+            fee::MakerTakerFeeModel,
+            latency::{FixedLatencyModel, LatencyModel},
+        },
+        stubs::register_exec_engine,
+    };
+
+    type SavedStates = Rc<RefCell<Vec<AccountState>>>;
+    type SavedEvents = Rc<RefCell<Vec<OrderEventAny>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn config(account_type: AccountType, starting_balance: &str) -> SimulatedExchangeConfig {
+        let starting_balance = Money::from(starting_balance);
+        let base_currency = match account_type {
+            AccountType::Margin => Some(starting_balance.currency),
+            _ => None,
+        };
+        let mut config = SimulatedExchangeConfig::new(
+            OmsType::Netting,
+            account_type,
+            BookType::L1_MBP,
+            vec![starting_balance],
+            base_currency,
+            OrderMatchingEngineConfig {
+                bar_execution: false,
+                reject_stop_orders: false,
+                support_gtd_orders: true,
+                support_contingent_orders: true,
+                use_position_ids: false,
+                use_random_ids: false,
+                use_reduce_only: true,
+            },
+        );
+        config.default_leverage = 50.0;
+        config
+    }
+
+    fn get_exchange(
+        instrument: InstrumentAny,
+        config: SimulatedExchangeConfig,
+        latency_model: Option<Box<dyn LatencyModel>>,
+        orders: Vec<OrderAny>,
+    ) -> (SimulatedExchange, SavedStates, SavedEvents) {
+        let test_clock = Rc::new(RefCell::new(TestClock::new()));
+        get_exchange_with_clock(instrument, config, latency_model, orders, test_clock)
+    }
+
+    fn get_exchange_with_clock(
+        instrument: InstrumentAny,
+        config: SimulatedExchangeConfig,
+        latency_model: Option<Box<dyn LatencyModel>>,
+        orders: Vec<OrderAny>,
+        test_clock: Rc<RefCell<TestClock>>,
+    ) -> (SimulatedExchange, SavedStates, SavedEvents) {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, states) = get_message_saving_handler::<AccountState>(None);
+        msgbus.register("Portfolio.update_account", handler);
+
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        for order in orders {
+            cache.add_order(order, None, None, false).unwrap();
+        }
+        let msgbus = Rc::new(RefCell::new(msgbus));
+        let cache = Rc::new(RefCell::new(cache));
+        let (_, events) = register_exec_engine(&msgbus, cache.clone(), clock);
+
+        let mut exchange = SimulatedExchange::new(
+            instrument.id().venue,
+            config,
+            latency_model,
+            test_clock,
+            clock,
+            msgbus,
+            cache,
+        )
+        .unwrap();
+        exchange
+            .add_instrument(instrument, Box::new(MakerTakerFeeModel))
+            .unwrap();
+        (exchange, states, events)
+    }
+
+    fn submitted_market_order(
+        instrument: &InstrumentAny,
+        order_side: OrderSide,
+        quantity: &str,
+    ) -> OrderAny {
+        let mut order = TestOrderStubs::market_order(
+            instrument.id(),
+            order_side,
+            Quantity::from(quantity),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        let event = TestOrderEventStubs::order_submitted(&order, "SIM-001".into());
+        order.apply(event).unwrap();
+        order
+    }
+
+    fn submit_command(order: &OrderAny) -> TradingCommand {
+        TradingCommand::SubmitOrder(SubmitOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        })
+    }
+
+    fn quote(instrument: &InstrumentAny, bid: &str, ask: &str) -> QuoteTick {
+        QuoteTick::new(
+            instrument.id(),
+            Price::from(bid),
+            Price::from(ask),
+            Quantity::new(1_000_000.0, instrument.size_precision()).unwrap(),
+            Quantity::new(1_000_000.0, instrument.size_precision()).unwrap(),
+            UnixNanos::default(),
+            UnixNanos::default(),
+        )
+        .unwrap()
+    }
+
+    fn get_position(
+        exchange: &SimulatedExchange,
+        instrument_id: &InstrumentId,
+    ) -> Option<Position> {
+        exchange
+            .cache
+            .borrow()
+            .positions(None, Some(instrument_id), None, None)
+            .first()
+            .map(|position| (*position).clone())
+    }
+
+    fn status(instrument: &InstrumentAny, action: MarketStatusAction) -> InstrumentStatus {
+        InstrumentStatus::new(
+            instrument.id(),
+            action,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    fn count_filled(events: &SavedEvents, client_order_id: &str) -> usize {
+        events
+            .borrow()
+            .iter()
+            .filter(|event| {
+                matches!(event, OrderEventAny::Filled(fill)
+                    if fill.client_order_id == ClientOrderId::from(client_order_id))
+            })
+            .count()
+    }
+
+    #[rstest]
+    fn test_new_with_base_currency_and_multiple_balances_fails() {
+        let mut config = config(AccountType::Margin, "1000000 USD");
+        config.starting_balances.push(Money::from("1000000 AUD"));
+        let msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+
+        let result = SimulatedExchange::new(
+            Venue::from("SIM"),
+            config,
+            None,
+            Rc::new(RefCell::new(TestClock::new())),
+            Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default()))),
+            Rc::new(RefCell::new(msgbus)),
+            Rc::new(RefCell::new(Cache::default())),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_add_instrument_for_other_venue_fails(instrument: InstrumentAny) {
+        let (mut exchange, _, _) = get_exchange(
+            instrument,
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![],
+        );
+        let other = InstrumentAny::Equity(equity_aapl());
+
+        let result = exchange.add_instrument(other, Box::new(MakerTakerFeeModel));
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_initialize_account_sends_starting_state(instrument: InstrumentAny) {
+        let (exchange, states, _) = get_exchange(
+            instrument,
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![],
+        );
+
+        exchange.initialize_account();
+
+        let states = states.borrow();
+        assert_eq!(states.len(), 1);
+        assert_eq!(states[0].account_id, exchange.account_id());
+        assert_eq!(states[0].balances[0].total, Money::from("1000000 USD"));
+        assert_eq!(exchange.account_id().as_str(), "SIM-001");
+    }
+
+    #[rstest]
+    fn test_cash_account_fill_settles_balances(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "100000");
+        let (mut exchange, states, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Cash, "1000000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+
+        exchange.send(submit_command(&order)).unwrap();
+
+        let account = exchange.get_account();
+        assert_eq!(count_filled(&events, "O-1"), 1);
+        assert_eq!(states.borrow().len(), 1);
+        assert_eq!(
+            account.balance_total(Some(Currency::USD())),
+            Some(Money::from("919988.40 USD"))
+        );
+        assert_eq!(
+            account.balance_total(Some(Currency::AUD())),
+            Some(Money::from("100000 AUD"))
+        );
+    }
+
+    #[rstest]
+    fn test_margin_account_fill_locks_maintenance_margin(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1000000");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+
+        exchange.send(submit_command(&order)).unwrap();
+
+        assert_eq!(count_filled(&events, "O-1"), 1);
+        let position = get_position(&exchange, &instrument.id()).unwrap();
+        assert!(position.is_long());
+        let AccountAny::Margin(account) = exchange.get_account() else {
+            panic!("Expected margin account");
+        };
+        assert_eq!(
+            account.maintenance_margin(instrument.id()),
+            Money::from("480.32 USD")
+        );
+        let balance = account.balances[&Currency::USD()];
+        assert_eq!(balance.total, Money::from("999984.00 USD"));
+        assert_eq!(balance.locked, Money::from("480.32 USD"));
+    }
+
+    #[rstest]
+    fn test_margin_breach_liquidates_position(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1000000");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "10000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        exchange.send(submit_command(&order)).unwrap();
+
+        exchange.process_quote_tick(&quote(&instrument, "0.79050", "0.79060"));
+
+        let liquidation_order = exchange
+            .cache
+            .borrow()
+            .order(&ClientOrderId::from("LIQ-SIM-1"))
+            .cloned()
+            .unwrap();
+        assert_eq!(count_filled(&events, "LIQ-SIM-1"), 1);
+        assert!(liquidation_order.is_closed());
+        assert_eq!(liquidation_order.filled_qty(), Quantity::from("1000000"));
+        assert!(get_position(&exchange, &instrument.id())
+            .unwrap()
+            .is_closed());
+        assert_eq!(
+            exchange.get_account().balance_total(Some(Currency::USD())),
+            Some(Money::from("368.19 USD"))
+        );
+    }
+
+    #[rstest]
+    fn test_margin_breach_while_market_halted_liquidates_on_reopen(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1000000");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "10000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        exchange.send(submit_command(&order)).unwrap();
+        exchange.process_instrument_status(&status(&instrument, MarketStatusAction::Halt));
+
+        exchange.process_quote_tick(&quote(&instrument, "0.79050", "0.79060"));
+        exchange.process_quote_tick(&quote(&instrument, "0.79040", "0.79050"));
+        let orders_during_halt = events
+            .borrow()
+            .iter()
+            .filter(|event| event.client_order_id().as_str().starts_with("LIQ-"))
+            .count();
+        exchange.process_instrument_status(&status(&instrument, MarketStatusAction::Trading));
+
+        assert_eq!(orders_during_halt, 0);
+        assert_eq!(count_filled(&events, "LIQ-SIM-1"), 1);
+        assert!(get_position(&exchange, &instrument.id())
+            .unwrap()
+            .is_closed());
+        assert!(exchange
+            .cache
+            .borrow()
+            .order(&ClientOrderId::from("LIQ-SIM-2"))
+            .is_none());
+    }
+
+    #[rstest]
+    fn test_loss_beyond_balance_reports_negative_balance(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1000000");
+        let (mut exchange, states, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "10000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        exchange.send(submit_command(&order)).unwrap();
+        let state_count = states.borrow().len();
+
+        exchange.process_quote_tick(&quote(&instrument, "0.78000", "0.78010"));
+
+        assert_eq!(count_filled(&events, "LIQ-SIM-1"), 1);
+        assert_eq!(
+            exchange.negative_balance(),
+            Some(Money::from("-10131.60 USD"))
+        );
+        assert_eq!(states.borrow().len(), state_count);
+        assert_eq!(
+            exchange.get_account().balance_total(Some(Currency::USD())),
+            Some(Money::from("9984.00 USD"))
+        );
+    }
+
+    #[rstest]
+    fn test_margin_above_maintenance_does_not_liquidate(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1000000");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "10000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        exchange.send(submit_command(&order)).unwrap();
+
+        exchange.process_quote_tick(&quote(&instrument, "0.79900", "0.79910"));
+
+        assert_eq!(count_filled(&events, "LIQ-SIM-1"), 0);
+        assert!(get_position(&exchange, &instrument.id()).unwrap().is_open());
+    }
+
+    fn advance_clock(
+        test_clock: &Rc<RefCell<TestClock>>,
+        exchange: &mut SimulatedExchange,
+        to_time_ns: u64,
+    ) {
+        let events = test_clock
+            .borrow_mut()
+            .advance_time(to_time_ns.into(), true);
+        for event in events {
+            let callback = test_clock.borrow().rust_callback(&event);
+            if let Some(callback) = callback {
+                callback.call(event);
+            }
+        }
+        exchange.process(to_time_ns.into());
+    }
+
+    #[rstest]
+    fn test_latency_delays_command_processing(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "100000");
+        let test_clock = Rc::new(RefCell::new(TestClock::new()));
+        let (mut exchange, _, events) = get_exchange_with_clock(
+            instrument.clone(),
+            config(AccountType::Margin, "1000000 USD"),
+            Some(Box::new(FixedLatencyModel::new(0, 100, 0, 0))),
+            vec![order.clone()],
+            test_clock.clone(),
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+
+        exchange.send(submit_command(&order)).unwrap();
+        advance_clock(&test_clock, &mut exchange, 99);
+        let filled_before_arrival = count_filled(&events, "O-1");
+        advance_clock(&test_clock, &mut exchange, 100);
+
+        assert_eq!(filled_before_arrival, 0);
+        assert_eq!(count_filled(&events, "O-1"), 1);
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, "99987.49960000 USDT", "-12.50040000 USDT")]
+    #[case(OrderSide::Sell, "99992.50010000 USDT", "-7.49990000 USDT")]
+    fn test_funding_rate_settles_open_position(
+        #[case] order_side: OrderSide,
+        #[case] expected_balance: &str,
+        #[case] expected_realized_pnl: &str,
+    ) {
+        let instrument = InstrumentAny::CryptoPerpetual(crypto_perpetual_ethusdt());
+        let order = submitted_market_order(&instrument, order_side, "10.000");
+        let (mut exchange, states, _) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "100000 USDT"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "2500.00", "2500.10"));
+        exchange.send(submit_command(&order)).unwrap();
+        let state_count = states.borrow().len();
+
+        // Funding is paid on the notional at the closing side of the market, and the
+        // realized PnL includes the taker commission on the opening fill
+        exchange.process_funding_rate(&FundingRateUpdate::new(
+            instrument.id(),
+            dec!(0.0001),
+            None,
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        ));
+
+        let position = get_position(&exchange, &instrument.id()).unwrap();
+        assert_eq!(states.borrow().len(), state_count + 1);
+        assert_eq!(
+            position.realized_pnl,
+            Some(Money::from(expected_realized_pnl))
+        );
+        assert_eq!(
+            exchange.get_account().balance_total(None),
+            Some(Money::from(expected_balance))
+        );
+    }
+
+    #[rstest]
+    fn test_funding_rate_for_non_perpetual_ignored(instrument: InstrumentAny) {
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "100000");
+        let (mut exchange, states, _) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        exchange.send(submit_command(&order)).unwrap();
+        let state_count = states.borrow().len();
+
+        exchange.process_funding_rate(&FundingRateUpdate::new(
+            instrument.id(),
+            dec!(0.0001),
+            None,
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        ));
+
+        assert_eq!(states.borrow().len(), state_count);
+    }
+
+    fn count_events(events: &SavedEvents, client_order_id: &str, filled: bool) -> usize {
+        events
+            .borrow()
+            .iter()
+            .filter(|event| match event {
+                OrderEventAny::Filled(fill) => {
+                    filled && fill.client_order_id == ClientOrderId::from(client_order_id)
+                }
+                OrderEventAny::Canceled(cancel) => {
+                    !filled && cancel.client_order_id == ClientOrderId::from(client_order_id)
+                }
+                _ => false,
+            })
+            .count()
+    }
+
+    #[rstest]
+    fn test_futures_expiration_cancels_orders_and_settles_position() {
+        let instrument = InstrumentAny::FuturesContract(futures_contract_es());
+        let expiration_ns = instrument.expiration_ns().unwrap();
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "4000.00", "4000.25"));
+        exchange.send(submit_command(&order)).unwrap();
+        let mut limit_order = TestOrderStubs::limit_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("3990.00"),
+            Quantity::from("1"),
+            Some(ClientOrderId::from("O-2")),
+            None,
+        );
+        let account_id = AccountId::from("GLBX-001");
+        limit_order
+            .apply(TestOrderEventStubs::order_submitted(
+                &limit_order,
+                account_id,
+            ))
+            .unwrap();
+        exchange
+            .get_matching_engine_mut(&instrument.id())
+            .unwrap()
+            .process_order(&limit_order, account_id);
+        exchange.set_settlement_price(instrument.id(), Price::from("4010.00"));
+
+        exchange.process(expiration_ns - 1);
+        let filled_before_expiration = count_events(&events, "EXP-GLBX-1", true);
+        exchange.process(expiration_ns);
+
+        assert_eq!(filled_before_expiration, 0);
+        assert_eq!(count_events(&events, "O-2", false), 1);
+        assert_eq!(count_events(&events, "EXP-GLBX-1", true), 1);
+        assert!(get_position(&exchange, &instrument.id())
+            .unwrap()
+            .is_closed());
+        assert_eq!(
+            exchange.get_account().balance_total(None),
+            Some(Money::from("1000009.75 USD"))
+        );
+    }
+
+    #[rstest]
+    #[case("155.00", "1000003.50 USD")]
+    #[case("140.00", "999997.50 USD")]
+    fn test_option_expiration_cash_settles_at_intrinsic_value(
+        #[case] underlying_px: &str,
+        #[case] expected_balance: &str,
+    ) {
+        let instrument = InstrumentAny::OptionsContract(options_contract_appl());
+        let expiration_ns = instrument.expiration_ns().unwrap();
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1");
+        let (mut exchange, _, events) = get_exchange(
+            instrument.clone(),
+            config(AccountType::Margin, "1000000 USD"),
+            None,
+            vec![order.clone()],
+        );
+        exchange.process_quote_tick(&quote(&instrument, "2.40", "2.50"));
+        exchange.send(submit_command(&order)).unwrap();
+        exchange.set_settlement_price(InstrumentId::from("AAPL.OPRA"), Price::from(underlying_px));
+
+        exchange.process(expiration_ns);
+
+        assert_eq!(count_events(&events, "EXP-OPRA-1", true), 1);
+        assert!(get_position(&exchange, &instrument.id())
+            .unwrap()
+            .is_closed());
+        assert_eq!(
+            exchange.get_account().balance_total(None),
+            Some(Money::from(expected_balance))
+        );
+    }
+
+    #[rstest]
+    fn test_option_expiration_physically_settles_by_assignment() {
+        let instrument = InstrumentAny::OptionsContract(options_contract_appl());
+        let mut underlying = equity_aapl();
+        underlying.id = InstrumentId::from("AAPL.OPRA");
+        let underlying = InstrumentAny::Equity(underlying);
+        let expiration_ns = instrument.expiration_ns().unwrap();
+        let order = submitted_market_order(&instrument, OrderSide::Buy, "1");
+        let mut config = config(AccountType::Margin, "1000000 USD");
+        config.physical_option_settlement = true;
+        let (mut exchange, _, events) =
+            get_exchange(instrument.clone(), config, None, vec![order.clone()]);
+        exchange
+            .cache
+            .borrow_mut()
+            .add_instrument(underlying.clone())
+            .unwrap();
+        exchange
+            .add_instrument(underlying.clone(), Box::new(MakerTakerFeeModel))
+            .unwrap();
+        exchange.process_quote_tick(&quote(&instrument, "2.40", "2.50"));
+        exchange.process_quote_tick(&quote(&underlying, "155.00", "155.02"));
+        exchange.send(submit_command(&order)).unwrap();
+
+        exchange.process(expiration_ns);
+
+        let underlying_position = get_position(&exchange, &underlying.id()).unwrap();
+        assert_eq!(count_events(&events, "EXP-OPRA-1", true), 1);
+        assert_eq!(count_events(&events, "EXP-OPRA-2", true), 1);
+        assert!(get_position(&exchange, &instrument.id())
+            .unwrap()
+            .is_closed());
+        assert!(underlying_position.is_long());
+        assert_eq!(underlying_position.quantity, Quantity::from("1"));
+        assert_eq!(underlying_position.avg_px_open, 149.0);
+        assert_eq!(
+            exchange.get_account().balance_total(None),
+            Some(Money::from("999997.50 USD"))
+        );
+    }
+}
@@ -0,0 +1,172 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! An execution client for a simulated exchange venue.
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use nautilus_common::msgbus::MessageBus;
+use nautilus_core::{time::AtomicTime, uuid::UUID4};
+use nautilus_execution::{
+    client::ExecutionClient,
+    messages::{
+        cancel::CancelOrder, cancel_all::CancelAllOrders, cancel_batch::BatchCancelOrders,
+        modify::ModifyOrder, query::QueryOrder, submit::SubmitOrder, submit_list::SubmitOrderList,
+        TradingCommand,
+    },
+    reports::mass_status::ExecutionMassStatus,
+};
+use nautilus_model::{
+    enums::OmsType,
+    events::order::{OrderEventAny, OrderSubmitted},
+    identifiers::{AccountId, ClientId, ClientOrderId, InstrumentId, StrategyId, TraderId, Venue},
+};
+
+use crate::exchange::{CommandQueue, SimulatedExchange};
+
+/// Provides an execution client for a [`SimulatedExchange`].
+///
+/// Orders are submitted immediately (an `OrderSubmitted` event is sent to the
+/// `ExecEngine.process` endpoint), and each command is queued for the exchange,
+/// which handles it when next processed.
+pub struct BacktestExecutionClient {
+    client_id: ClientId,
+    venue: Venue,
+    account_id: AccountId,
+    oms_type: OmsType,
+    clock: &'static AtomicTime,
+    msgbus: Rc<RefCell<MessageBus>>,
+    queue: CommandQueue,
+}
+
+impl BacktestExecutionClient {
+    /// Creates a new [`BacktestExecutionClient`] instance for the given `exchange`.
+    #[must_use]
+    pub fn new(
+        exchange: &SimulatedExchange,
+        clock: &'static AtomicTime,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) -> Self {
+        Self {
+            client_id: ClientId::from(exchange.id.as_str()),
+            venue: exchange.id,
+            account_id: exchange.account_id(),
+            oms_type: exchange.oms_type,
+            clock,
+            msgbus,
+            queue: exchange.command_queue(),
+        }
+    }
+
+    fn generate_order_submitted(
+        &self,
+        trader_id: TraderId,
+        strategy_id: StrategyId,
+        instrument_id: InstrumentId,
+        client_order_id: ClientOrderId,
+    ) -> anyhow::Result<()> {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Submitted(OrderSubmitted::new(
+            trader_id,
+            strategy_id,
+            instrument_id,
+            client_order_id,
+            self.account_id,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+        )?);
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+        Ok(())
+    }
+
+    fn queue_command(&self, command: TradingCommand) -> anyhow::Result<()> {
+        self.queue.borrow_mut().push_back(command);
+        Ok(())
+    }
+}
+
+impl ExecutionClient for BacktestExecutionClient {
+    fn client_id(&self) -> ClientId {
+        self.client_id
+    }
+
+    fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    fn venue(&self) -> Venue {
+        self.venue
+    }
+
+    fn oms_type(&self) -> OmsType {
+        self.oms_type
+    }
+
+    fn is_connected(&self) -> bool {
+        true
+    }
+
+    fn submit_order(&self, command: SubmitOrder) -> anyhow::Result<()> {
+        self.generate_order_submitted(
+            command.trader_id,
+            command.strategy_id,
+            command.instrument_id,
+            command.client_order_id,
+        )?;
+        self.queue_command(TradingCommand::SubmitOrder(command))
+    }
+
+    fn submit_order_list(&self, command: SubmitOrderList) -> anyhow::Result<()> {
+        for order in &command.order_list.orders {
+            self.generate_order_submitted(
+                command.trader_id,
+                command.strategy_id,
+                order.instrument_id(),
+                order.client_order_id(),
+            )?;
+        }
+        self.queue_command(TradingCommand::SubmitOrderList(command))
+    }
+
+    fn modify_order(&self, command: ModifyOrder) -> anyhow::Result<()> {
+        self.queue_command(TradingCommand::ModifyOrder(command))
+    }
+
+    fn cancel_order(&self, command: CancelOrder) -> anyhow::Result<()> {
+        self.queue_command(TradingCommand::CancelOrder(command))
+    }
+
+    fn cancel_all_orders(&self, command: CancelAllOrders) -> anyhow::Result<()> {
+        self.queue_command(TradingCommand::CancelAllOrders(command))
+    }
+
+    fn batch_cancel_orders(&self, command: BatchCancelOrders) -> anyhow::Result<()> {
+        self.queue_command(TradingCommand::BatchCancelOrders(command))
+    }
+
+    fn query_order(&self, command: QueryOrder) -> anyhow::Result<()> {
+        self.queue_command(TradingCommand::QueryOrder(command))
+    }
+
+    fn generate_mass_status(
+        &self,
+        _lookback_mins: Option<u64>,
+    ) -> anyhow::Result<Option<ExecutionMassStatus>> {
+        Ok(None) // The simulated exchange holds no state before the backtest
+    }
+}
@@ -28,5 +28,10 @@
 //! - `python`: Enables Python bindings from `pyo3`
 
 pub mod engine;
+pub mod exchange;
+pub mod execution_client;
 pub mod matching_engine;
 pub mod models;
+
+#[cfg(test)]
+mod stubs;
@@ -19,21 +19,35 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use std::{any::Any, collections::HashMap, rc::Rc};
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
 
-use log::{debug, info};
+use log::{debug, error, info, warn};
 use nautilus_common::{cache::Cache, msgbus::MessageBus};
 use nautilus_core::{nanos::UnixNanos, time::AtomicTime, uuid::UUID4};
-use nautilus_execution::matching_core::OrderMatchingCore;
+use nautilus_execution::{
+    matching_core::OrderMatchingCore,
+    messages::{cancel::CancelOrder, cancel_all::CancelAllOrders, modify::ModifyOrder},
+    trailing::TrailingStopCalculator,
+};
 use nautilus_model::{
     data::{
-        bar::{Bar, BarType},
+        bar::{get_bar_interval_ns, Bar, BarType},
         delta::OrderBookDelta,
+        deltas::OrderBookDeltas,
+        depth::OrderBookDepth10,
+        order::BookOrder,
+        quote::QuoteTick,
+        status::InstrumentStatus,
+        trade::TradeTick,
+    },
+    enums::{
+        AccountType, AggregationSource, AggressorSide, BarAggregation, BookAction, BookType,
+        ContingencyType, LiquiditySide, MarketStatus, MarketStatusAction, OmsType, OrderSide,
+        OrderSideSpecified, OrderStatus, OrderType, PriceType, TimeInForce,
     },
-    enums::{AccountType, BookType, LiquiditySide, MarketStatus, OmsType},
     events::order::{
-        OrderAccepted, OrderCancelRejected, OrderCanceled, OrderExpired, OrderFilled,
-        OrderModifyRejected, OrderRejected, OrderTriggered, OrderUpdated,
+        OrderAccepted, OrderCancelRejected, OrderCanceled, OrderEventAny, OrderExpired,
+        OrderFilled, OrderModifyRejected, OrderRejected, OrderTriggered, OrderUpdated,
     },
     identifiers::{
         AccountId, ClientOrderId, InstrumentId, PositionId, StrategyId, TradeId, TraderId, Venue,
@@ -41,15 +55,15 @@ use nautilus_model::{
     },
     instruments::any::InstrumentAny,
     orderbook::book::OrderBook,
-    orders::{
-        any::{OrderAny, PassiveOrderAny, StopOrderAny},
-        trailing_stop_limit::TrailingStopLimitOrder,
-        trailing_stop_market::TrailingStopMarketOrder,
-    },
+    orders::any::{OrderAny, PassiveOrderAny},
+    position::Position,
     types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
 };
 use ustr::Ustr;
 
+use crate::models::{fee::FeeModel, fill::FillModel, queue::QueuePosition};
+
+#[derive(Clone, Debug)]
 pub struct OrderMatchingEngineConfig {
     pub bar_execution: bool,
     pub reject_stop_orders: bool,
@@ -79,36 +93,45 @@ pub struct OrderMatchingEngine {
     /// The config for the matching engine.
     pub config: OrderMatchingEngineConfig,
     clock: &'static AtomicTime,
-    msgbus: Rc<MessageBus>,
-    cache: Rc<Cache>,
+    msgbus: Rc<RefCell<MessageBus>>,
+    cache: Rc<RefCell<Cache>>,
     book: OrderBook,
     core: OrderMatchingCore,
+    fill_model: FillModel,
+    fee_model: Box<dyn FeeModel>,
+    orders: HashMap<ClientOrderId, OrderAny>,
+    contingent_orders: HashMap<ClientOrderId, OrderAny>,
+    pending_child_orders: HashMap<ClientOrderId, Vec<OrderAny>>,
+    queue_positions: HashMap<ClientOrderId, QueuePosition>,
+    fills: Vec<(OrderFilled, Option<Position>)>,
     target_bid: Option<Price>,
     target_ask: Option<Price>,
     target_last: Option<Price>,
     last_bar_bid: Option<Bar>,
     last_bar_ask: Option<Bar>,
     execution_bar_types: HashMap<InstrumentId, BarType>,
-    execution_bar_deltas: HashMap<InstrumentId, u64>,
+    execution_bar_deltas: HashMap<BarType, UnixNanos>,
     account_ids: HashMap<TraderId, AccountId>,
     position_count: usize,
     order_count: usize,
     execution_count: usize,
+    expired: bool,
 }
 
-// TODO: we'll probably be changing the `FillModel` (don't add for now)
 impl OrderMatchingEngine {
     /// Creates a new [`OrderMatchingEngine`] instance.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         instrument: InstrumentAny,
         raw_id: u32,
+        fill_model: FillModel,
+        fee_model: Box<dyn FeeModel>,
         book_type: BookType,
         oms_type: OmsType,
         account_type: AccountType,
         clock: &'static AtomicTime,
-        msgbus: Rc<MessageBus>,
-        cache: Rc<Cache>,
+        msgbus: Rc<RefCell<MessageBus>>,
+        cache: Rc<RefCell<Cache>>,
         config: OrderMatchingEngineConfig,
     ) -> Self {
         let book = OrderBook::new(book_type, instrument.id());
@@ -131,6 +154,13 @@ impl OrderMatchingEngine {
             cache,
             book,
             core,
+            fill_model,
+            fee_model,
+            orders: HashMap::new(),
+            contingent_orders: HashMap::new(),
+            pending_child_orders: HashMap::new(),
+            queue_positions: HashMap::new(),
+            fills: Vec::new(),
             market_status: MarketStatus::Open,
             config,
             target_bid: None,
@@ -144,6 +174,7 @@ impl OrderMatchingEngine {
             position_count: 0,
             order_count: 0,
             execution_count: 0,
+            expired: false,
         }
     }
 
@@ -152,6 +183,11 @@ impl OrderMatchingEngine {
         self.execution_bar_types.clear();
         self.execution_bar_deltas.clear();
         self.account_ids.clear();
+        self.orders.clear();
+        self.contingent_orders.clear();
+        self.pending_child_orders.clear();
+        self.queue_positions.clear();
+        self.fills.clear();
         self.core.reset();
         self.target_bid = None;
         self.target_ask = None;
@@ -159,6 +195,7 @@ impl OrderMatchingEngine {
         self.position_count = 0;
         self.order_count = 0;
         self.execution_count = 0;
+        self.expired = false;
 
         info!("Reset {}", self.instrument.id());
     }
@@ -193,6 +230,33 @@ impl OrderMatchingEngine {
         self.core.order_exists(client_order_id)
     }
 
+    /// Returns whether the market is open for matching.
+    #[must_use]
+    pub fn is_market_open(&self) -> bool {
+        self.market_status == MarketStatus::Open
+    }
+
+    /// Returns whether the instrument has expired on the venue.
+    #[must_use]
+    pub const fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Returns the matching engines working copy of the order with the given `client_order_id`.
+    #[must_use]
+    pub fn get_order(&self, client_order_id: &ClientOrderId) -> Option<&OrderAny> {
+        self.orders.get(client_order_id)
+    }
+
+    /// Drains the fills generated since the last call, for the owning venue to
+    /// settle against its account.
+    ///
+    /// Each fill is paired with the open position it applies to (if any), as it
+    /// was in the cache immediately before the fill.
+    pub fn drain_fills(&mut self) -> Vec<(OrderFilled, Option<Position>)> {
+        self.fills.drain(..).collect()
+    }
+
     // -- DATA PROCESSING -----------------------------------------------------
 
     /// Process the venues market for the given order book delta.
@@ -200,309 +264,3618 @@ impl OrderMatchingEngine {
         debug!("Processing {delta}");
 
         self.book.apply_delta(delta);
+        self.update_queue_positions(delta);
+
+        self.iterate(delta.ts_init);
     }
 
-    // -- ORDER PROCESSING ----------------------------------------------------
+    /// Process the venues market for the given order book deltas.
+    pub fn process_order_book_deltas(&mut self, deltas: &OrderBookDeltas) {
+        debug!("Processing {deltas}");
 
-    /// Iterate the matching engine by processing the bid and ask order sides
-    /// and advancing time up to the given UNIX `timestamp_ns`.
-    pub fn iterate(&mut self, timestamp_ns: UnixNanos) {
-        self.clock.set_time(timestamp_ns);
+        self.book.apply_deltas(deltas);
+        for delta in &deltas.deltas {
+            self.update_queue_positions(delta);
+        }
 
-        self.core.bid = self.book.best_bid_price();
-        self.core.ask = self.book.best_ask_price();
+        self.iterate(deltas.ts_init);
+    }
 
-        let orders_bid = self.core.get_orders_bid().to_vec();
-        let orders_ask = self.core.get_orders_ask().to_vec();
+    /// Process the venues market for the given order book depth.
+    pub fn process_order_book_depth10(&mut self, depth: &OrderBookDepth10) {
+        debug!("Processing {depth}");
+
+        self.book.apply_depth(depth);
 
-        self.iterate_orders(timestamp_ns, &orders_bid);
-        self.iterate_orders(timestamp_ns, &orders_ask);
+        self.iterate(depth.ts_init);
     }
 
-    fn iterate_orders(&mut self, timestamp_ns: UnixNanos, orders: &[PassiveOrderAny]) {
-        for order in orders {
-            if order.is_closed() {
-                continue;
-            };
+    /// Process the venues market for the given quote tick.
+    pub fn process_quote_tick(&mut self, quote: &QuoteTick) {
+        debug!("Processing {quote}");
 
-            // Check expiration
-            if self.config.support_gtd_orders {
-                if let Some(expire_time) = order.expire_time() {
-                    if timestamp_ns >= expire_time {
-                        // SAFTEY: We know this order is in the core
-                        self.core.delete_order(order).unwrap();
-                        self.expire_order(order);
-                    }
-                }
+        if self.book_type == BookType::L1_MBP {
+            if let Err(e) = self.book.update_quote_tick(quote) {
+                error!("Error updating book with {quote}: {e}");
             }
+        }
 
-            // Manage trailing stop
-            if let PassiveOrderAny::Stop(o) = order {
-                match o {
-                    StopOrderAny::TrailingStopMarket(o) => self.update_trailing_stop_market(o),
-                    StopOrderAny::TrailingStopLimit(o) => self.update_trailing_stop_limit(o),
-                    _ => {}
-                }
+        self.iterate(quote.ts_init);
+    }
+
+    /// Process the venues market for the given instrument status, simulating the
+    /// trading phases of the venue.
+    ///
+    /// While the market is not open (pre-open, auction, paused, halted or closed)
+    /// no matching occurs: market orders are rejected and other orders are held
+    /// until the market reopens, when all working orders are re-evaluated against
+    /// the book (GTD orders still expire while held). When the market becomes not
+    /// available all working orders are canceled, and new orders are rejected.
+    pub fn process_status(&mut self, status: &InstrumentStatus) {
+        let market_status = match status.action {
+            MarketStatusAction::Trading | MarketStatusAction::PreClose => MarketStatus::Open,
+            MarketStatusAction::PreOpen
+            | MarketStatusAction::PreCross
+            | MarketStatusAction::Quoting
+            | MarketStatusAction::Cross
+            | MarketStatusAction::Rotation
+            | MarketStatusAction::NewPriceIndication
+            | MarketStatusAction::Close
+            | MarketStatusAction::PostClose => MarketStatus::Closed,
+            MarketStatusAction::Pause => MarketStatus::Paused,
+            MarketStatusAction::Halt | MarketStatusAction::Suspend => MarketStatus::Suspended,
+            MarketStatusAction::NotAvailableForTrading => MarketStatus::NotAvailable,
+            MarketStatusAction::None | MarketStatusAction::ShortSellRestrictionChange => return,
+        };
+
+        if market_status == self.market_status {
+            return;
+        }
+
+        info!(
+            "{} market status {} -> {market_status} ({})",
+            self.instrument.id(),
+            self.market_status,
+            status.action,
+        );
+        self.market_status = market_status;
+
+        match market_status {
+            // Re-evaluate held orders against the book on reopen
+            MarketStatus::Open => self.iterate(status.ts_init),
+            MarketStatus::NotAvailable => self.cancel_working_orders(),
+            _ => {}
+        }
+    }
+
+    /// Process the venues market for the given trade tick.
+    pub fn process_trade_tick(&mut self, trade: &TradeTick) {
+        debug!("Processing {trade}");
+
+        if self.book_type == BookType::L1_MBP {
+            if let Err(e) = self.book.update_trade_tick(trade) {
+                error!("Error updating book with {trade}: {e}");
             }
+        }
 
-            // Move market back to targets
-            self.core.bid = self.target_bid;
-            self.core.ask = self.target_ask;
-            self.core.last = self.target_last;
+        self.core.last = Some(trade.price);
+
+        if self.book_type == BookType::L3_MBO && self.is_market_open() {
+            self.fill_queue_positions(trade);
         }
 
-        // Reset any targets after iteration
-        self.target_bid = None;
-        self.target_ask = None;
-        self.target_last = None;
+        self.iterate(trade.ts_init);
     }
 
-    fn expire_order(&mut self, order: &PassiveOrderAny) {
-        todo!();
+    /// Process the venues market for the given bar.
+    ///
+    /// Each bar is converted into a simulated intrabar price path of synthetic
+    /// top-of-book updates, visiting the high or the low first depending on
+    /// which is closer to the open.
+    pub fn process_bar(&mut self, bar: &Bar) {
+        debug!("Processing {bar}");
+
+        // Check if configured for bar execution (can only process an L1 book with bars)
+        if !self.config.bar_execution || self.book_type != BookType::L1_MBP {
+            return;
+        }
+
+        let bar_type = bar.bar_type;
+
+        // Do not process internally aggregated bars
+        if bar_type.aggregation_source == AggregationSource::Internal {
+            return;
+        }
+
+        if !self.is_execution_bar_type(bar_type) {
+            return;
+        }
+
+        match bar_type.spec.price_type {
+            PriceType::Last | PriceType::Mid => self.process_trade_ticks_from_bar(bar),
+            PriceType::Bid => {
+                self.last_bar_bid = Some(*bar);
+                self.process_quote_ticks_from_bar();
+            }
+            PriceType::Ask => {
+                self.last_bar_ask = Some(*bar);
+                self.process_quote_ticks_from_bar();
+            }
+        }
     }
 
-    fn update_trailing_stop_market(&mut self, order: &TrailingStopMarketOrder) {
-        todo!()
+    /// Returns whether the given `bar_type` should be used for execution, which
+    /// will be the time bar type with the shortest interval seen for the instrument.
+    fn is_execution_bar_type(&mut self, bar_type: BarType) -> bool {
+        let instrument_id = bar_type.instrument_id;
+        let execution_bar_type = *self
+            .execution_bar_types
+            .entry(instrument_id)
+            .or_insert(bar_type);
+
+        if let Some(interval) = get_execution_bar_interval(&bar_type) {
+            self.execution_bar_deltas
+                .entry(bar_type)
+                .or_insert(interval);
+        }
+
+        if execution_bar_type == bar_type {
+            return true;
+        }
+
+        match (
+            self.execution_bar_deltas.get(&execution_bar_type),
+            self.execution_bar_deltas.get(&bar_type),
+        ) {
+            (Some(execution_interval), Some(interval)) if execution_interval >= interval => {
+                self.execution_bar_types.insert(instrument_id, bar_type);
+                true
+            }
+            _ => false,
+        }
     }
 
-    fn update_trailing_stop_limit(&mut self, order: &TrailingStopLimitOrder) {
-        todo!()
+    fn process_trade_ticks_from_bar(&mut self, bar: &Bar) {
+        let size = self.get_bar_tick_size(bar.volume);
+
+        for price in get_bar_price_path(bar) {
+            let aggressor_side = match self.core.last {
+                Some(last) if price == last => continue, // No price movement
+                Some(last) if price < last => AggressorSide::Seller,
+                _ => AggressorSide::Buyer,
+            };
+            let trade = TradeTick::new(
+                bar.bar_type.instrument_id,
+                price,
+                size,
+                aggressor_side,
+                self.generate_trade_id(),
+                bar.ts_init,
+                bar.ts_init,
+            );
+            self.process_trade_tick(&trade);
+        }
     }
 
-    // -- IDENTIFIER GENERATORS -----------------------------------------------------
+    fn process_quote_ticks_from_bar(&mut self) {
+        let (Some(bid_bar), Some(ask_bar)) = (self.last_bar_bid, self.last_bar_ask) else {
+            return; // Wait for next bar
+        };
 
-    fn generate_trade_id(&mut self) -> TradeId {
-        self.execution_count += 1;
-        TradeId::new(self.generate_trade_id_str().as_str()).unwrap()
+        if bid_bar.ts_event != ask_bar.ts_event {
+            return; // Wait for next bar
+        }
+
+        let bid_size = self.get_bar_tick_size(bid_bar.volume);
+        let ask_size = self.get_bar_tick_size(ask_bar.volume);
+
+        for (bid_price, ask_price) in get_quote_price_path(&bid_bar, &ask_bar) {
+            match QuoteTick::new(
+                self.instrument.id(),
+                bid_price,
+                ask_price,
+                bid_size,
+                ask_size,
+                bid_bar.ts_init,
+                bid_bar.ts_init,
+            ) {
+                Ok(quote) => self.process_quote_tick(&quote),
+                Err(e) => error!("Error creating quote from bars: {e}"),
+            }
+        }
+
+        self.last_bar_bid = None;
+        self.last_bar_ask = None;
     }
 
-    fn generate_trade_id_str(&self) -> Ustr {
-        if self.config.use_random_ids {
-            UUID4::new().to_string().into()
+    /// Returns the size for each synthetic tick of a bar, spreading the bar
+    /// volume evenly over the four price points.
+    fn get_bar_tick_size(&self, volume: Quantity) -> Quantity {
+        let size = Quantity::from_raw(volume.raw / 4, volume.precision).unwrap();
+        if size.is_zero() {
+            self.instrument.size_increment()
         } else {
-            format!("{}-{}-{}", self.venue, self.raw_id, self.execution_count).into()
+            size
         }
     }
 
-    // -- EVENT GENERATORS -----------------------------------------------------
+    // -- TRADING COMMANDS ----------------------------------------------------
 
-    fn generate_order_rejected(&self, order: &OrderAny, reason: Ustr) {
-        let ts_now = self.clock.get_time_ns();
-        let account_id = order
-            .account_id()
-            .unwrap_or(self.account_ids.get(&order.trader_id()).unwrap().to_owned());
-        let event = OrderRejected::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
-            account_id,
-            reason,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-        )
-        .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+    /// Process the given `order` submitted to the venue for the given `account_id`.
+    pub fn process_order(&mut self, order: &OrderAny, account_id: AccountId) {
+        if self.core.order_exists(order.client_order_id()) {
+            error!("Order already exists: {}", order.client_order_id());
+            return;
+        }
+
+        // Index identifiers
+        self.account_ids.insert(order.trader_id(), account_id);
+
+        let mut order = order.clone();
+
+        if self.expired {
+            let reason = format!("Instrument {} has expired", self.instrument.id());
+            self.generate_order_rejected(&mut order, reason.into());
+            return;
+        }
+
+        if self.config.support_contingent_orders {
+            // Check parent of OTO child order
+            if let Some(parent_order_id) = order.parent_order_id() {
+                let parent_order = self
+                    .contingent_orders
+                    .get(&parent_order_id)
+                    .cloned()
+                    .or_else(|| self.cache.borrow().order(&parent_order_id).cloned());
+                match parent_order {
+                    Some(parent_order) if !parent_order.filled_qty().is_zero() => {}
+                    Some(parent_order) if parent_order.is_closed() => {
+                        let reason = format!("REJECT OTO from {parent_order_id}");
+                        self.generate_order_rejected(&mut order, reason.into());
+                        return;
+                    }
+                    Some(_) => {
+                        info!(
+                            "Pending OTO {} triggers from {parent_order_id}",
+                            order.client_order_id()
+                        );
+                        self.pending_child_orders
+                            .entry(parent_order_id)
+                            .or_default()
+                            .push(order);
+                        return;
+                    }
+                    None => {
+                        let reason = format!("Parent order {parent_order_id} not found");
+                        self.generate_order_rejected(&mut order, reason.into());
+                        return;
+                    }
+                }
+            }
+
+            // Check linked orders of OCO and OUO orders
+            if matches!(
+                order.contingency_type(),
+                Some(ContingencyType::Oco | ContingencyType::Ouo)
+            ) {
+                let closed_order_id = order
+                    .linked_order_ids()
+                    .unwrap_or_default()
+                    .iter()
+                    .find(|client_order_id| {
+                        self.contingent_orders
+                            .get(*client_order_id)
+                            .is_some_and(OrderAny::is_closed)
+                    })
+                    .copied();
+                if let Some(closed_order_id) = closed_order_id {
+                    let reason = format!("Contingent order {closed_order_id} already closed");
+                    self.generate_order_rejected(&mut order, reason.into());
+                    return;
+                }
+            }
+        }
+
+        // Check order quantity precision
+        if order.quantity().precision != self.instrument.size_precision() {
+            let reason = format!(
+                "Invalid order quantity precision for order {}, was {} when {} size precision is {}",
+                order.client_order_id(),
+                order.quantity().precision,
+                self.instrument.id(),
+                self.instrument.size_precision(),
+            );
+            self.generate_order_rejected(&mut order, reason.into());
+            return;
+        }
+
+        // Check order price precision
+        if let Some(price) = order.price() {
+            if price.precision != self.instrument.price_precision() {
+                let reason = format!(
+                    "Invalid order price precision for order {}, was {} when {} price precision is {}",
+                    order.client_order_id(),
+                    price.precision,
+                    self.instrument.id(),
+                    self.instrument.price_precision(),
+                );
+                self.generate_order_rejected(&mut order, reason.into());
+                return;
+            }
+        }
+
+        // Check order trigger price precision
+        if let Some(trigger_price) = order.trigger_price() {
+            if trigger_price.precision != self.instrument.price_precision() {
+                let reason = format!(
+                    "Invalid order trigger price precision for order {}, was {} when {} price precision is {}",
+                    order.client_order_id(),
+                    trigger_price.precision,
+                    self.instrument.id(),
+                    self.instrument.price_precision(),
+                );
+                self.generate_order_rejected(&mut order, reason.into());
+                return;
+            }
+        }
+
+        // Check not shorting an equity without a margin account
+        if let InstrumentAny::Equity(_) = self.instrument {
+            if order.is_sell() && self.account_type != AccountType::Margin {
+                let position = self.get_position(&order);
+                let would_reduce = position
+                    .as_ref()
+                    .map_or(false, |p| order.would_reduce_only(p.side, p.quantity));
+                if !would_reduce {
+                    let reason = format!(
+                        "SHORT SELLING not permitted on a CASH account with order {}",
+                        order.client_order_id(),
+                    );
+                    self.generate_order_rejected(&mut order, reason.into());
+                    return;
+                }
+            }
+        }
+
+        // Check reduce-only instruction
+        if self.config.use_reduce_only && order.is_reduce_only() && !order.is_closed() {
+            let position = self.get_position(&order);
+            let would_increase = position.as_ref().map_or(true, |p| {
+                p.is_closed()
+                    || (order.is_buy() && p.is_long())
+                    || (order.is_sell() && p.is_short())
+            });
+            if would_increase {
+                let reason = format!(
+                    "REDUCE_ONLY {} {} order would have increased position",
+                    order.order_type(),
+                    order.order_side(),
+                );
+                self.generate_order_rejected(&mut order, reason.into());
+                return;
+            }
+        }
+
+        // Check market status
+        if !self.is_market_open() {
+            let is_held = self.market_status != MarketStatus::NotAvailable
+                && !matches!(
+                    order.order_type(),
+                    OrderType::Market | OrderType::MarketToLimit
+                )
+                && !matches!(order.time_in_force(), TimeInForce::Fok | TimeInForce::Ioc);
+            if is_held {
+                // Order is held (without matching) until the market reopens
+                self.accept_order(&mut order);
+            } else {
+                let reason = format!(
+                    "Market for {} is {}",
+                    self.instrument.id(),
+                    self.market_status
+                );
+                self.generate_order_rejected(&mut order, reason.into());
+            }
+            self.update_working_order(order);
+            return;
+        }
+
+        match order.order_type() {
+            OrderType::Market => self.process_market_order(&mut order),
+            OrderType::MarketToLimit => self.process_market_to_limit_order(&mut order),
+            OrderType::Limit => self.process_limit_order(&mut order),
+            OrderType::StopMarket | OrderType::MarketIfTouched | OrderType::TrailingStopMarket => {
+                self.process_stop_market_order(&mut order);
+            }
+            OrderType::StopLimit | OrderType::LimitIfTouched | OrderType::TrailingStopLimit => {
+                self.process_stop_limit_order(&mut order);
+            }
+        }
+
+        self.update_working_order(order);
     }
 
-    fn generate_order_accepted(&self, order: &OrderAny, venue_order_id: VenueOrderId) {
-        let ts_now = self.clock.get_time_ns();
-        let account_id = order
-            .account_id()
-            .unwrap_or(self.account_ids.get(&order.trader_id()).unwrap().to_owned());
-        let event = OrderAccepted::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
-            venue_order_id,
-            account_id,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-        )
-        .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+    /// Process the given modify `command` for the given `account_id`.
+    pub fn process_modify(&mut self, command: &ModifyOrder, account_id: AccountId) {
+        match self.orders.remove(&command.client_order_id) {
+            Some(mut order) => {
+                let price = order.price();
+                let quantity = order.quantity();
+                self.update_order(
+                    &mut order,
+                    command.quantity,
+                    command.price,
+                    command.trigger_price,
+                );
+
+                // Order loses queue priority when repriced or increased in size
+                if self.queue_positions.contains_key(&order.client_order_id())
+                    && (order.price() != price || order.quantity() > quantity)
+                {
+                    self.init_queue_position(&order);
+                }
+                self.update_working_order(order);
+            }
+            None => self.generate_order_modify_rejected(
+                command.trader_id,
+                command.strategy_id,
+                account_id,
+                command.instrument_id,
+                command.client_order_id,
+                Some(command.venue_order_id),
+                format!("Order {} not found", command.client_order_id).into(),
+            ),
+        }
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn generate_order_modify_rejected(
-        &self,
-        trader_id: TraderId,
-        strategy_id: StrategyId,
-        account_id: AccountId,
-        instrument_id: InstrumentId,
-        client_order_id: ClientOrderId,
-        venue_order_id: VenueOrderId,
-        reason: Ustr,
-    ) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderModifyRejected::new(
-            trader_id,
-            strategy_id,
-            instrument_id,
-            client_order_id,
-            reason,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-            Some(venue_order_id),
-            Some(account_id),
+    /// Process the given cancel `command` for the given `account_id`.
+    ///
+    /// Child orders pending the fill of their OTO parent are also canceled.
+    pub fn process_cancel(&mut self, command: &CancelOrder, account_id: AccountId) {
+        if let Some(mut order) = self.orders.remove(&command.client_order_id) {
+            self.cancel_order(&mut order);
+            self.update_working_order(order);
+            return;
+        }
+
+        match self.take_pending_child_order(&command.client_order_id) {
+            Some(mut order) => self.cancel_order(&mut order),
+            None => self.generate_order_cancel_rejected(
+                command.trader_id,
+                command.strategy_id,
+                account_id,
+                command.instrument_id,
+                command.client_order_id,
+                Some(command.venue_order_id),
+                format!("Order {} not found", command.client_order_id).into(),
+            ),
+        }
+    }
+
+    /// Process the given cancel all `command` for the given `account_id`.
+    ///
+    /// Cancels all working orders on the given side, or on both sides if
+    /// `command.order_side` is `NoOrderSide`.
+    pub fn process_cancel_all(&mut self, command: &CancelAllOrders, account_id: AccountId) {
+        let mut client_order_ids: Vec<ClientOrderId> = self
+            .orders
+            .values()
+            .filter(|order| {
+                order.trader_id() == command.trader_id
+                    && order.strategy_id() == command.strategy_id
+                    && (command.order_side == OrderSide::NoOrderSide
+                        || order.order_side() == command.order_side)
+            })
+            .map(OrderAny::client_order_id)
+            .collect();
+        client_order_ids.sort();
+
+        debug!(
+            "Canceling {} orders for {account_id}",
+            client_order_ids.len()
+        );
+        for client_order_id in client_order_ids {
+            if let Some(mut order) = self.orders.remove(&client_order_id) {
+                self.cancel_order(&mut order);
+                self.update_working_order(order);
+            }
+        }
+    }
+
+    /// Processes the expiration of the instrument, canceling all working orders.
+    ///
+    /// Any orders subsequently processed for the instrument are rejected.
+    pub fn process_expiration(&mut self) {
+        self.expired = true;
+
+        let mut client_order_ids: Vec<ClientOrderId> = self.orders.keys().copied().collect();
+        client_order_ids.sort();
+
+        info!(
+            "{} expired, canceling {} orders",
+            self.instrument.id(),
+            client_order_ids.len()
         );
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+        for client_order_id in client_order_ids {
+            if let Some(mut order) = self.orders.remove(&client_order_id) {
+                self.cancel_order(&mut order);
+                self.update_working_order(order);
+            }
+        }
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn generate_order_cancel_rejected(
-        &self,
-        trader_id: TraderId,
-        strategy_id: StrategyId,
-        account_id: AccountId,
-        instrument_id: InstrumentId,
-        client_order_id: ClientOrderId,
-        venue_order_id: VenueOrderId,
-        reason: Ustr,
-    ) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderCancelRejected::new(
-            trader_id,
-            strategy_id,
-            instrument_id,
-            client_order_id,
-            reason,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-            Some(venue_order_id),
-            Some(account_id),
-        )
-        .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+    /// Cancels all working orders (such as when the market becomes not available).
+    fn cancel_working_orders(&mut self) {
+        let mut client_order_ids: Vec<ClientOrderId> = self.orders.keys().copied().collect();
+        client_order_ids.sort();
+
+        info!(
+            "{} not available, canceling {} orders",
+            self.instrument.id(),
+            client_order_ids.len()
+        );
+        for client_order_id in client_order_ids {
+            if let Some(mut order) = self.orders.remove(&client_order_id) {
+                self.cancel_order(&mut order);
+                self.update_working_order(order);
+            }
+        }
     }
 
-    fn generate_order_updated(
-        &self,
+    /// Processes the given venue generated settlement `order` for the `account_id`,
+    /// filling it in full at the `settlement_px` regardless of the market (such as
+    /// when closing positions at instrument expiration).
+    pub fn process_settlement(
+        &mut self,
         order: &OrderAny,
-        quantity: Quantity,
-        price: Price,
-        trigger_price: Price,
+        settlement_px: Price,
+        account_id: AccountId,
     ) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderUpdated::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
+        self.account_ids.insert(order.trader_id(), account_id);
+
+        let mut order = order.clone();
+        order.set_liquidity_side(LiquiditySide::Taker);
+        let quantity = order.quantity();
+        self.fill_order(
+            &mut order,
+            settlement_px,
             quantity,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-            order.venue_order_id(),
-            order.account_id(),
-            Some(price),
-            Some(trigger_price),
-        )
-        .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+            LiquiditySide::Taker,
+            None,
+        );
     }
 
-    fn generate_order_canceled(&self, order: &OrderAny, venue_order_id: VenueOrderId) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderCanceled::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
+    fn process_market_order(&mut self, order: &mut OrderAny) {
+        if matches!(
+            order.time_in_force(),
+            TimeInForce::AtTheOpen | TimeInForce::AtTheClose
+        ) {
+            let reason = format!(
+                "Market auction for time in force {} is currently not supported",
+                order.time_in_force(),
+            );
+            self.generate_order_rejected(order, reason.into());
+            return;
+        }
+
+        // Check market exists
+        if !self.has_market_for_side(order.order_side_specified()) {
+            let reason = format!("No market for {}", order.instrument_id());
+            self.generate_order_rejected(order, reason.into());
+            return;
+        }
+
+        self.fill_market_order(order);
+    }
+
+    fn process_market_to_limit_order(&mut self, order: &mut OrderAny) {
+        // Check market exists
+        if !self.has_market_for_side(order.order_side_specified()) {
+            let reason = format!("No market for {}", order.instrument_id());
+            self.generate_order_rejected(order, reason.into());
+            return;
+        }
+
+        // Order is valid and accepted
+        self.accept_order(order);
+
+        // Immediately fill marketable order (remainder will rest at the initial fill price)
+        self.fill_market_order(order);
+    }
+
+    fn process_limit_order(&mut self, order: &mut OrderAny) {
+        let price = order.price().expect("Limit order must have a price");
+        let is_matched = self
+            .core
+            .is_limit_price_matched(order.order_side_specified(), price);
+
+        if order.is_post_only() && is_matched {
+            let reason = format!(
+                "POST_ONLY {} {} order limit px of {} would have been a TAKER: {}",
+                order.order_type(),
+                order.order_side(),
+                price,
+                self.market_str(),
+            );
+            self.generate_order_rejected(order, reason.into());
+            return;
+        }
+
+        // Order is valid and accepted
+        self.accept_order(order);
+
+        // Check for immediate fill
+        if is_matched {
+            // Filling as liquidity taker
+            self.fill_limit_order(order, LiquiditySide::Taker);
+        } else if matches!(order.time_in_force(), TimeInForce::Fok | TimeInForce::Ioc) {
+            self.cancel_order(order);
+        }
+    }
+
+    /// Processes stop-market, market-if-touched and trailing-stop-market orders.
+    fn process_stop_market_order(&mut self, order: &mut OrderAny) {
+        if self.is_trigger_matched(order) {
+            if self.config.reject_stop_orders {
+                let reason = format!(
+                    "{} {} order trigger px of {} was in the market: {}, but rejected because of configuration",
+                    order.order_type(),
+                    order.order_side(),
+                    order.trigger_price().expect("Order must have a trigger price"),
+                    self.market_str(),
+                );
+                self.generate_order_rejected(order, reason.into());
+            } else {
+                self.fill_market_order(order);
+            }
+            return;
+        }
+
+        // Order is valid and accepted
+        self.accept_order(order);
+    }
+
+    /// Processes stop-limit, limit-if-touched and trailing-stop-limit orders.
+    fn process_stop_limit_order(&mut self, order: &mut OrderAny) {
+        if self.is_trigger_matched(order) {
+            if self.config.reject_stop_orders {
+                let reason = format!(
+                    "{} {} order trigger px of {} was in the market: {}, but rejected because of configuration",
+                    order.order_type(),
+                    order.order_side(),
+                    order.trigger_price().expect("Order must have a trigger price"),
+                    self.market_str(),
+                );
+                self.generate_order_rejected(order, reason.into());
+            } else {
+                self.accept_order(order);
+                self.trigger_stop_order(order);
+            }
+            return;
+        }
+
+        // Order is valid and accepted
+        self.accept_order(order);
+    }
+
+    fn update_order(
+        &mut self,
+        order: &mut OrderAny,
+        quantity: Option<Quantity>,
+        price: Option<Price>,
+        trigger_price: Option<Price>,
+    ) {
+        let quantity = quantity.unwrap_or(order.quantity());
+        if quantity < order.filled_qty() {
+            let reason = format!(
+                "Modified quantity {} was less than filled quantity {}",
+                quantity,
+                order.filled_qty(),
+            );
+            self.generate_order_modify_rejected_for(order, reason.into());
+            return;
+        }
+
+        let side = order.order_side_specified();
+        match order.order_type() {
+            OrderType::Limit | OrderType::MarketToLimit => {
+                let Some(price) = price.or(order.price()) else {
+                    let reason = format!("{} order has no price to modify", order.order_type());
+                    self.generate_order_modify_rejected_for(order, reason.into());
+                    return;
+                };
+                self.update_limit_order(order, quantity, price);
+            }
+            OrderType::StopMarket | OrderType::MarketIfTouched | OrderType::TrailingStopMarket => {
+                let trigger_price = trigger_price
+                    .or(order.trigger_price())
+                    .expect("Order must have a trigger price");
+                if self.is_trigger_price_matched(order, trigger_price) {
+                    let reason = format!(
+                        "{} {} order new trigger px of {} was in the market: {}",
+                        order.order_type(),
+                        order.order_side(),
+                        trigger_price,
+                        self.market_str(),
+                    );
+                    self.generate_order_modify_rejected_for(order, reason.into());
+                    return;
+                }
+                self.generate_order_updated(order, quantity, None, Some(trigger_price));
+            }
+            OrderType::StopLimit | OrderType::LimitIfTouched | OrderType::TrailingStopLimit => {
+                let price = price.or(order.price()).expect("Order must have a price");
+                let trigger_price = trigger_price
+                    .or(order.trigger_price())
+                    .expect("Order must have a trigger price");
+
+                if order.is_triggered() == Some(true) {
+                    // Updating limit price
+                    if self.is_market_open() && self.core.is_limit_price_matched(side, price) {
+                        if order.is_post_only() {
+                            let reason = format!(
+                                "POST_ONLY {} {} order new limit px of {} would have been a TAKER: {}",
+                                order.order_type(),
+                                order.order_side(),
+                                price,
+                                self.market_str(),
+                            );
+                            self.generate_order_modify_rejected_for(order, reason.into());
+                            return;
+                        }
+                        self.generate_order_updated(order, quantity, Some(price), None);
+                        self.fill_limit_order(order, LiquiditySide::Taker);
+                        return; // Filled
+                    }
+                } else if self.is_trigger_price_matched(order, trigger_price) {
+                    // Updating trigger price
+                    let reason = format!(
+                        "{} {} order new trigger px of {} was in the market: {}",
+                        order.order_type(),
+                        order.order_side(),
+                        trigger_price,
+                        self.market_str(),
+                    );
+                    self.generate_order_modify_rejected_for(order, reason.into());
+                    return;
+                }
+                self.generate_order_updated(order, quantity, Some(price), Some(trigger_price));
+            }
+            OrderType::Market => {
+                let reason = format!("Cannot modify {} order", order.order_type());
+                self.generate_order_modify_rejected_for(order, reason.into());
+            }
+        }
+    }
+
+    fn update_limit_order(&mut self, order: &mut OrderAny, quantity: Quantity, price: Price) {
+        if self.is_market_open()
+            && self
+                .core
+                .is_limit_price_matched(order.order_side_specified(), price)
+        {
+            if order.is_post_only() {
+                let reason = format!(
+                    "POST_ONLY {} {} order new limit px of {} would have been a TAKER: {}",
+                    order.order_type(),
+                    order.order_side(),
+                    price,
+                    self.market_str(),
+                );
+                self.generate_order_modify_rejected_for(order, reason.into());
+                return;
+            }
+
+            self.generate_order_updated(order, quantity, Some(price), None);
+            self.fill_limit_order(order, LiquiditySide::Taker); // Immediate fill as taker
+            return;
+        }
+
+        self.generate_order_updated(order, quantity, Some(price), None);
+    }
+
+    // -- ORDER PROCESSING ----------------------------------------------------
+
+    /// Iterate the matching engine by processing the bid and ask order sides
+    /// and advancing time up to the given UNIX `timestamp_ns`.
+    pub fn iterate(&mut self, timestamp_ns: UnixNanos) {
+        self.clock.set_time(timestamp_ns);
+
+        self.core.bid = self.book.best_bid_price();
+        self.core.ask = self.book.best_ask_price();
+
+        if self.config.support_gtd_orders {
+            self.expire_orders(timestamp_ns);
+        }
+
+        if !self.is_market_open() {
+            return; // Working orders are held until the market reopens
+        }
+
+        let orders_bid = self.core.get_orders_bid().to_vec();
+        let orders_ask = self.core.get_orders_ask().to_vec();
+
+        self.iterate_orders(&orders_bid);
+        self.iterate_orders(&orders_ask);
+    }
+
+    /// Expires all working orders with an expire time at or before `timestamp_ns`.
+    fn expire_orders(&mut self, timestamp_ns: UnixNanos) {
+        let mut client_order_ids: Vec<ClientOrderId> = self
+            .orders
+            .values()
+            .filter(|order| {
+                // Orders without an expiry carry a zero expire time
+                order.is_open()
+                    && order
+                        .expire_time()
+                        .is_some_and(|expire_time| expire_time != 0 && timestamp_ns >= expire_time)
+            })
+            .map(OrderAny::client_order_id)
+            .collect();
+        client_order_ids.sort();
+
+        for client_order_id in client_order_ids {
+            if let Some(mut order) = self.orders.remove(&client_order_id) {
+                self.expire_order(&mut order);
+                self.update_working_order(order);
+            }
+        }
+    }
+
+    fn iterate_orders(&mut self, orders: &[PassiveOrderAny]) {
+        for order in orders {
+            let Some(mut order) = self.orders.remove(&order.client_order_id()) else {
+                continue; // Orders state has changed since the loop started
+            };
+
+            if order.is_closed() {
+                self.update_working_order(order);
+                continue;
+            };
+
+            // Manage trailing stop
+            if matches!(
+                order.order_type(),
+                OrderType::TrailingStopMarket | OrderType::TrailingStopLimit
+            ) {
+                self.update_trailing_stop_order(&mut order);
+            }
+
+            self.match_order(&mut order);
+            self.update_working_order(order);
+
+            // Move market back to targets
+            if let Some(target_bid) = self.target_bid {
+                self.core.bid = Some(target_bid);
+            }
+            if let Some(target_ask) = self.target_ask {
+                self.core.ask = Some(target_ask);
+            }
+            if let Some(target_last) = self.target_last {
+                self.core.last = Some(target_last);
+            }
+        }
+
+        // Reset any targets after iteration
+        self.target_bid = None;
+        self.target_ask = None;
+        self.target_last = None;
+    }
+
+    fn match_order(&mut self, order: &mut OrderAny) {
+        match order.order_type() {
+            OrderType::Limit | OrderType::MarketToLimit => self.match_limit_order(order),
+            OrderType::StopMarket | OrderType::MarketIfTouched | OrderType::TrailingStopMarket => {
+                self.match_stop_market_order(order);
+            }
+            OrderType::StopLimit | OrderType::LimitIfTouched | OrderType::TrailingStopLimit => {
+                if order.is_triggered() == Some(true) {
+                    self.match_limit_order(order);
+                } else if self.is_trigger_matched(order) {
+                    self.trigger_stop_order(order);
+                }
+            }
+            OrderType::Market => {} // Market orders never rest on the venue
+        }
+    }
+
+    fn match_limit_order(&mut self, order: &mut OrderAny) {
+        let Some(price) = order.price() else {
+            return; // Market-to-limit order not yet repriced
+        };
+        let side = order.order_side_specified();
+        if !self.core.is_limit_price_matched(side, price) {
+            return;
+        }
+
+        // When the market only rests on the limit price the fill model decides whether the
+        // order is filled, unless its queue position is being tracked through trades
+        if self.is_touching(side, price)
+            && !self.queue_positions.contains_key(&order.client_order_id())
+            && !self.fill_model.is_limit_filled()
+        {
+            return;
+        }
+
+        self.fill_limit_order(order, LiquiditySide::Maker);
+    }
+
+    fn match_stop_market_order(&mut self, order: &mut OrderAny) {
+        if !self.is_trigger_matched(order) {
+            return;
+        }
+
+        // When the market only rests on the stop price the fill model decides whether the
+        // order is filled
+        let trigger_price = order
+            .trigger_price()
+            .expect("Order must have a trigger price");
+        if order.order_type() == OrderType::StopMarket
+            && self.is_touching(order.order_side_specified(), trigger_price)
+            && !self.fill_model.is_stop_filled()
+        {
+            return;
+        }
+
+        self.fill_market_order(order);
+    }
+
+    /// Returns whether the opposite side of the market is exactly at the given `price`.
+    fn is_touching(&self, side: OrderSideSpecified, price: Price) -> bool {
+        match side {
+            OrderSideSpecified::Buy => self.core.ask == Some(price),
+            OrderSideSpecified::Sell => self.core.bid == Some(price),
+        }
+    }
+
+    fn is_trigger_matched(&self, order: &OrderAny) -> bool {
+        let trigger_price = order
+            .trigger_price()
+            .expect("Order must have a trigger price");
+        self.is_trigger_price_matched(order, trigger_price)
+    }
+
+    fn is_trigger_price_matched(&self, order: &OrderAny, trigger_price: Price) -> bool {
+        let side = order.order_side_specified();
+        match order.order_type() {
+            OrderType::MarketIfTouched | OrderType::LimitIfTouched => {
+                self.core.is_touch_triggered(side, trigger_price)
+            }
+            _ => self.core.is_stop_triggered(side, trigger_price),
+        }
+    }
+
+    fn has_market_for_side(&self, side: OrderSideSpecified) -> bool {
+        match side {
+            OrderSideSpecified::Buy => self.core.ask.is_some(),
+            OrderSideSpecified::Sell => self.core.bid.is_some(),
+        }
+    }
+
+    fn market_str(&self) -> String {
+        format!(
+            "bid={}, ask={}",
+            self.core.bid.map_or("None".to_string(), |p| p.to_string()),
+            self.core.ask.map_or("None".to_string(), |p| p.to_string()),
+        )
+    }
+
+    /// Synchronizes the working order with the matching core, removing the
+    /// order if it is now closed.
+    fn update_working_order(&mut self, order: OrderAny) {
+        let client_order_id = order.client_order_id();
+        if !self.core.order_exists(client_order_id) {
+            // A market-to-limit order starts working once its remainder is repriced
+            if order.order_type() == OrderType::MarketToLimit
+                && order.is_open()
+                && order.price().is_some()
+            {
+                if let Err(e) = self.core.add_order(order.clone().into()) {
+                    error!("Error adding order: {e}");
+                }
+                self.orders.insert(client_order_id, order);
+            }
+            return; // Order was never working on the venue
+        }
+
+        if order.is_closed() {
+            self.orders.remove(&client_order_id);
+            self.queue_positions.remove(&client_order_id);
+            if let Err(e) = self.core.delete_order(&order.into()) {
+                error!("Error deleting order: {e}");
+            }
+        } else {
+            if let Err(e) = self.core.update_order(order.clone().into()) {
+                error!("Error updating order: {e}");
+            }
+            self.orders.insert(client_order_id, order);
+        }
+    }
+
+    fn accept_order(&mut self, order: &mut OrderAny) {
+        if order.is_closed() {
+            return; // Temporary guard to prevent invalid processing
+        }
+
+        let venue_order_id = self.get_venue_order_id(order);
+        self.generate_order_accepted(order, venue_order_id);
+
+        if order.order_type() == OrderType::MarketToLimit && order.price().is_none() {
+            return; // Added to the core once repriced at its initial fill
+        }
+
+        if let Err(e) = self.core.add_order(order.clone().into()) {
+            error!("Error adding order: {e}");
+        }
+
+        if order.order_type() == OrderType::Limit {
+            self.init_queue_position(order);
+        }
+    }
+
+    /// Places the passive `order` at the back of the queue for its price level
+    /// (only tracked for `L3_MBO` books).
+    fn init_queue_position(&mut self, order: &OrderAny) {
+        if self.book_type != BookType::L3_MBO {
+            return;
+        }
+
+        if let Some(price) = order.price() {
+            let queue_position = QueuePosition::new(order.order_side(), price, &self.book);
+            self.queue_positions
+                .insert(order.client_order_id(), queue_position);
+        }
+    }
+
+    /// Resets the size traded at the queue positions for the level of the `delta`,
+    /// once the book has reduced or removed orders at the level.
+    fn update_queue_positions(&mut self, delta: &OrderBookDelta) {
+        if !matches!(
+            delta.action,
+            BookAction::Update | BookAction::Delete | BookAction::Clear
+        ) {
+            return;
+        }
+
+        for queue_position in self.queue_positions.values_mut() {
+            if delta.action == BookAction::Clear
+                || (queue_position.side == delta.order.side
+                    && queue_position.price == delta.order.price)
+            {
+                queue_position.reset_traded();
+            }
+        }
+    }
+
+    /// Fills passive orders resting at the traded price level, once the book
+    /// orders ahead of them in the queue have been traded through.
+    fn fill_queue_positions(&mut self, trade: &TradeTick) {
+        let mut orders = self.core.get_orders_bid().to_vec();
+        orders.extend_from_slice(self.core.get_orders_ask());
+
+        let mut consumed_raw = 0;
+        for order in orders {
+            let client_order_id = order.client_order_id();
+            let Some(queue_position) = self.queue_positions.get_mut(&client_order_id) else {
+                continue; // Order not resting in a queue
+            };
+            let available_raw = queue_position
+                .trade_fill_raw(&self.book, trade)
+                .saturating_sub(consumed_raw);
+            if available_raw == 0 {
+                continue;
+            }
+
+            let Some(mut order) = self.orders.remove(&client_order_id) else {
+                continue;
+            };
+            let fill_raw = available_raw.min(order.leaves_qty().raw);
+            consumed_raw += fill_raw;
+
+            let last_qty = Quantity::from_raw(fill_raw, self.instrument.size_precision()).unwrap();
+            self.fill_queue_order(&mut order, last_qty);
+            self.update_working_order(order);
+        }
+    }
+
+    fn cancel_order(&mut self, order: &mut OrderAny) {
+        let venue_order_id = self.get_venue_order_id(order);
+        self.generate_order_canceled(order, venue_order_id);
+    }
+
+    fn expire_order(&mut self, order: &mut OrderAny) {
+        self.generate_order_expired(order);
+    }
+
+    // -- CONTINGENCIES -------------------------------------------------------
+
+    /// Handles a fill of the contingent `order`.
+    ///
+    /// OTO child orders pending the parent fill are released, OCO linked orders
+    /// are canceled, and OUO linked orders are reduced to the leaves quantity of
+    /// the order (or canceled once it is closed).
+    fn handle_contingent_fill(&mut self, order: &OrderAny) {
+        match order.contingency_type() {
+            Some(ContingencyType::Oto) => {
+                let child_orders = self
+                    .pending_child_orders
+                    .remove(&order.client_order_id())
+                    .unwrap_or_default();
+                for child_order in child_orders {
+                    debug!(
+                        "Releasing OTO {} from {}",
+                        child_order.client_order_id(),
+                        order.client_order_id()
+                    );
+                    let account_id = self.account_id_for_order(&child_order);
+                    self.process_order(&child_order, account_id);
+                }
+            }
+            Some(ContingencyType::Oco) => {
+                for client_order_id in order.linked_order_ids().unwrap_or_default() {
+                    self.cancel_contingent_order(client_order_id);
+                }
+            }
+            Some(ContingencyType::Ouo) => {
+                for client_order_id in order.linked_order_ids().unwrap_or_default() {
+                    if order.is_closed() {
+                        self.cancel_contingent_order(client_order_id);
+                    } else {
+                        self.reduce_contingent_order(client_order_id, order.leaves_qty());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Handles the contingent `order` closing without being completely filled.
+    ///
+    /// OTO child orders pending a parent which never filled are rejected, and
+    /// OCO and OUO linked orders are canceled.
+    fn handle_contingent_close(&mut self, order: &OrderAny) {
+        match order.contingency_type() {
+            Some(ContingencyType::Oto) => {
+                if !order.filled_qty().is_zero() {
+                    return; // Child orders were already released
+                }
+
+                let child_orders = self
+                    .pending_child_orders
+                    .remove(&order.client_order_id())
+                    .unwrap_or_default();
+                for mut child_order in child_orders {
+                    let reason = format!("REJECT OTO from {}", order.client_order_id());
+                    self.generate_order_rejected(&mut child_order, reason.into());
+                }
+            }
+            Some(ContingencyType::Oco | ContingencyType::Ouo) => {
+                for client_order_id in order.linked_order_ids().unwrap_or_default() {
+                    self.cancel_contingent_order(client_order_id);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Cancels the linked order with the given `client_order_id`, if it is
+    /// working or pending on the venue.
+    fn cancel_contingent_order(&mut self, client_order_id: &ClientOrderId) {
+        if let Some(mut order) = self.orders.remove(client_order_id) {
+            if !order.is_closed() {
+                self.cancel_order(&mut order);
+            }
+            self.update_working_order(order);
+        } else if let Some(mut order) = self.take_pending_child_order(client_order_id) {
+            self.cancel_order(&mut order);
+        }
+    }
+
+    /// Reduces the quantity of the working linked order with the given
+    /// `client_order_id` so that its leaves quantity matches `leaves_qty`.
+    fn reduce_contingent_order(&mut self, client_order_id: &ClientOrderId, leaves_qty: Quantity) {
+        let Some(mut order) = self.orders.remove(client_order_id) else {
+            return; // Order not working on the venue
+        };
+
+        let quantity = order.filled_qty() + leaves_qty;
+        if !order.is_closed() && order.quantity() != quantity {
+            self.generate_order_updated(&mut order, quantity, None, None);
+        }
+        self.update_working_order(order);
+    }
+
+    /// Removes and returns the OTO child order with the given `client_order_id`
+    /// pending the fill of its parent.
+    fn take_pending_child_order(&mut self, client_order_id: &ClientOrderId) -> Option<OrderAny> {
+        self.pending_child_orders
+            .values_mut()
+            .find_map(|child_orders| {
+                let index = child_orders
+                    .iter()
+                    .position(|order| order.client_order_id() == *client_order_id)?;
+                Some(child_orders.remove(index))
+            })
+    }
+
+    fn trigger_stop_order(&mut self, order: &mut OrderAny) {
+        self.generate_order_triggered(order);
+
+        // Check for immediate fill
+        let price = order.price().expect("Order must have a price");
+        if !self
+            .core
+            .is_limit_price_matched(order.order_side_specified(), price)
+        {
+            self.init_queue_position(order);
+            return;
+        }
+
+        if order.is_post_only() {
+            // Would be liquidity taker
+            let reason = format!(
+                "POST_ONLY {} {} order limit px of {} would have been a TAKER: {}",
+                order.order_type(),
+                order.order_side(),
+                price,
+                self.market_str(),
+            );
+            self.generate_order_rejected(order, reason.into());
+            return;
+        }
+
+        self.fill_limit_order(order, LiquiditySide::Taker);
+    }
+
+    fn update_trailing_stop_order(&mut self, order: &mut OrderAny) {
+        if order.is_triggered() == Some(true) {
+            return; // Triggered trailing stop limit orders no longer trail the market
+        }
+
+        let calculator = TrailingStopCalculator::new(self.instrument.price_increment(), None);
+        let (new_trigger_price, new_price) =
+            match calculator.calculate(order, self.core.bid, self.core.ask, self.core.last) {
+                Ok(prices) => prices,
+                Err(e) => {
+                    error!("Cannot update trailing stop order: {e}");
+                    return;
+                }
+            };
+
+        if new_trigger_price.is_none() && new_price.is_none() {
+            return; // No updates
+        }
+
+        let price = match order.order_type() {
+            OrderType::TrailingStopLimit => new_price.or(order.price()),
+            _ => None,
+        };
+        let trigger_price = new_trigger_price.or(order.trigger_price());
+        let quantity = order.quantity();
+        self.generate_order_updated(order, quantity, price, trigger_price);
+    }
+
+    // -- ORDER FILLING -------------------------------------------------------
+
+    fn fill_market_order(&mut self, order: &mut OrderAny) {
+        let venue_position_id = self.get_position_id(order, true);
+        let position = venue_position_id.and_then(|id| self.cache.borrow().position(&id).cloned());
+
+        if self.config.use_reduce_only && order.is_reduce_only() && position.is_none() {
+            warn!(
+                "Canceling REDUCE_ONLY {} as would increase position",
+                order.order_type()
+            );
+            self.cancel_order(order);
+            return;
+        }
+
+        order.set_liquidity_side(LiquiditySide::Taker);
+        let fills = self.determine_market_price_and_volume(order);
+        self.apply_fills(
+            order,
+            fills,
+            LiquiditySide::Taker,
+            venue_position_id,
+            position,
+        );
+    }
+
+    fn fill_limit_order(&mut self, order: &mut OrderAny, liquidity_side: LiquiditySide) {
+        let venue_position_id = self.get_position_id(order, true);
+        let position = venue_position_id.and_then(|id| self.cache.borrow().position(&id).cloned());
+
+        if self.config.use_reduce_only && order.is_reduce_only() && position.is_none() {
+            warn!(
+                "Canceling REDUCE_ONLY {} as would increase position",
+                order.order_type()
+            );
+            self.cancel_order(order);
+            return;
+        }
+
+        order.set_liquidity_side(liquidity_side);
+        let fills = self.determine_limit_price_and_volume(order);
+        self.apply_fills(order, fills, liquidity_side, venue_position_id, position);
+    }
+
+    /// Fills the passive `order` for the `last_qty` which reached it through the queue.
+    fn fill_queue_order(&mut self, order: &mut OrderAny, last_qty: Quantity) {
+        let venue_position_id = self.get_position_id(order, true);
+        let position = venue_position_id.and_then(|id| self.cache.borrow().position(&id).cloned());
+
+        if self.config.use_reduce_only && order.is_reduce_only() && position.is_none() {
+            warn!(
+                "Canceling REDUCE_ONLY {} as would increase position",
+                order.order_type()
+            );
+            self.cancel_order(order);
+            return;
+        }
+
+        order.set_liquidity_side(LiquiditySide::Maker);
+        let price = order.price().expect("Order must have a price");
+        self.apply_fills(
+            order,
+            vec![(price, last_qty)],
+            LiquiditySide::Maker,
+            venue_position_id,
+            position,
+        );
+    }
+
+    fn determine_market_price_and_volume(&mut self, order: &OrderAny) -> Vec<(Price, Quantity)> {
+        let side = order.order_side_specified();
+        let price = match side {
+            OrderSideSpecified::Buy => Price::max(self.instrument.price_precision()),
+            OrderSideSpecified::Sell => Price::min(self.instrument.price_precision()),
+        };
+        let book_order = BookOrder::new(order.order_side(), price, order.leaves_qty(), 0);
+        let fills = self.book.simulate_fills(&book_order);
+
+        if self.book_type == BookType::L1_MBP && self.fill_model.is_slipped() {
+            // Top-of-book fills slip by one tick against the order
+            let price_increment = self.instrument.price_increment();
+            return fills
+                .into_iter()
+                .map(|(fill_px, fill_qty)| match side {
+                    OrderSideSpecified::Buy => (fill_px + price_increment, fill_qty),
+                    OrderSideSpecified::Sell => (fill_px - price_increment, fill_qty),
+                })
+                .collect();
+        }
+
+        fills
+    }
+
+    fn determine_limit_price_and_volume(&self, order: &OrderAny) -> Vec<(Price, Quantity)> {
+        let price = order.price().expect("Order must have a price");
+        let book_order = BookOrder::new(order.order_side(), price, order.leaves_qty(), 0);
+        let fills = self.book.simulate_fills(&book_order);
+
+        match order.liquidity_side() {
+            // Passive orders are filled at their limit price as the market trades through
+            Some(LiquiditySide::Maker) => fills.into_iter().map(|(_, qty)| (price, qty)).collect(),
+            _ => fills,
+        }
+    }
+
+    fn apply_fills(
+        &mut self,
+        order: &mut OrderAny,
+        fills: Vec<(Price, Quantity)>,
+        liquidity_side: LiquiditySide,
+        venue_position_id: Option<PositionId>,
+        position: Option<Position>,
+    ) {
+        let venue_position_id = match self.oms_type {
+            OmsType::Netting => None, // No position IDs generated by the venue
+            _ => venue_position_id,
+        };
+
+        if order.time_in_force() == TimeInForce::Fok {
+            let total_size_raw: u64 = fills.iter().map(|(_, qty)| qty.raw).sum();
+            if order.leaves_qty().raw > total_size_raw {
+                self.cancel_order(order);
+                return; // Cannot fill full size - so kill/cancel
+            }
+        }
+
+        if fills.is_empty() {
+            if order.status() == OrderStatus::Submitted {
+                let reason = format!("No market for {}", order.instrument_id());
+                self.generate_order_rejected(order, reason.into());
+            } else {
+                error!(
+                    "Cannot fill order {}: no fills from book when fills were expected (check sizes in data)",
+                    order.client_order_id(),
+                );
+            }
+            return;
+        }
+
+        let mut initial_market_to_limit_fill = false;
+        let mut last_fill_px = None;
+
+        for (fill_px, mut fill_qty) in fills {
+            assert_eq!(
+                fill_px.precision,
+                self.instrument.price_precision(),
+                "Invalid price precision for fill {} for instrument {}",
+                fill_px.precision,
+                self.instrument.id(),
+            );
+            assert_eq!(
+                fill_qty.precision,
+                self.instrument.size_precision(),
+                "Invalid quantity precision for fill {} for instrument {}",
+                fill_qty.precision,
+                self.instrument.id(),
+            );
+
+            if order.filled_qty().is_zero() && order.order_type() == OrderType::MarketToLimit {
+                // Remainder of order will rest at the initial fill price
+                let quantity = order.quantity();
+                self.generate_order_updated(order, quantity, Some(fill_px), None);
+                initial_market_to_limit_fill = true;
+            }
+
+            // Check reduce only order
+            if self.config.use_reduce_only && order.is_reduce_only() {
+                if let Some(position) = &position {
+                    if fill_qty > position.quantity {
+                        if position.quantity.is_zero() {
+                            return; // Done
+                        }
+
+                        // Adjust fill to honor reduce only execution (fill remaining position size only)
+                        let updated_qty = order.quantity() - (fill_qty - position.quantity);
+                        fill_qty = position.quantity;
+                        self.generate_order_updated(order, updated_qty, None, None);
+                    }
+                }
+            }
+
+            if fill_qty.is_zero() {
+                return; // Done
+            }
+
+            self.fill_order(order, fill_px, fill_qty, liquidity_side, venue_position_id);
+            last_fill_px = Some(fill_px);
+
+            if initial_market_to_limit_fill {
+                return; // Filled initial level
+            }
+        }
+
+        if order.is_open()
+            && self.book_type == BookType::L1_MBP
+            && matches!(
+                order.order_type(),
+                OrderType::Market
+                    | OrderType::MarketIfTouched
+                    | OrderType::StopMarket
+                    | OrderType::TrailingStopMarket
+            )
+        {
+            // Exhausted simulated book volume (continue aggressive filling into next level).
+            // This is a very basic implementation of slipping by a single tick, in the future
+            // we will implement more detailed fill modeling.
+            if let Some(last_fill_px) = last_fill_px {
+                let price_increment = self.instrument.price_increment();
+                let fill_px = match order.order_side_specified() {
+                    OrderSideSpecified::Buy => last_fill_px + price_increment,
+                    OrderSideSpecified::Sell => last_fill_px - price_increment,
+                };
+                let leaves_qty = order.leaves_qty();
+                self.fill_order(
+                    order,
+                    fill_px,
+                    leaves_qty,
+                    liquidity_side,
+                    venue_position_id,
+                );
+            }
+        }
+
+        if order.time_in_force() == TimeInForce::Ioc && order.is_open() {
+            // IOC order has filled all available size
+            self.cancel_order(order);
+        }
+    }
+
+    fn fill_order(
+        &mut self,
+        order: &mut OrderAny,
+        last_px: Price,
+        last_qty: Quantity,
+        liquidity_side: LiquiditySide,
+        venue_position_id: Option<PositionId>,
+    ) {
+        let commission = self
+            .fee_model
+            .get_commission(order, last_qty, last_px, &self.instrument)
+            .unwrap_or_else(|e| {
+                error!("Error calculating commission: {e}");
+                Money::from_raw(0, self.instrument.quote_currency())
+            });
+
+        self.fee_model
+            .record_fill(order, last_qty, last_px, &self.instrument);
+
+        let venue_order_id = self.get_venue_order_id(order);
+        self.generate_order_filled(
+            order,
+            venue_order_id,
+            venue_position_id,
+            last_qty,
+            last_px,
+            self.instrument.quote_currency(),
+            commission,
+            liquidity_side,
+        );
+    }
+
+    // -- IDENTIFIER GENERATORS -----------------------------------------------------
+
+    fn get_position(&self, order: &OrderAny) -> Option<Position> {
+        let cache = self.cache.borrow();
+        let position_id = match self.oms_type {
+            OmsType::Hedging => order
+                .position_id()
+                .or_else(|| cache.position_id(&order.client_order_id()).copied()),
+            _ => cache
+                .positions_open(None, Some(&order.instrument_id()), None, None)
+                .first()
+                .map(|p| p.id),
+        };
+        position_id.and_then(|id| cache.position(&id).cloned())
+    }
+
+    /// Returns the open position in the cache which a fill of the `order` applies to,
+    /// netting positions being held per strategy by the execution engine.
+    fn get_fill_position(
+        &self,
+        order: &OrderAny,
+        venue_position_id: Option<PositionId>,
+    ) -> Option<Position> {
+        let cache = self.cache.borrow();
+        let position = match self.oms_type {
+            OmsType::Hedging => venue_position_id
+                .or_else(|| order.position_id())
+                .or_else(|| cache.position_id(&order.client_order_id()).copied())
+                .and_then(|id| cache.position(&id)),
+            _ => cache
+                .positions_open(
+                    None,
+                    Some(&order.instrument_id()),
+                    Some(&order.strategy_id()),
+                    None,
+                )
+                .first()
+                .copied(),
+        };
+        position.filter(|position| position.is_open()).cloned()
+    }
+
+    fn get_position_id(&mut self, order: &OrderAny, generate: bool) -> Option<PositionId> {
+        match self.oms_type {
+            OmsType::Hedging => {
+                let position_id = order.position_id().or_else(|| {
+                    self.cache
+                        .borrow()
+                        .position_id(&order.client_order_id())
+                        .copied()
+                });
+                if let Some(position_id) = position_id {
+                    return Some(position_id);
+                }
+                if generate {
+                    // Generate a venue position ID
+                    self.generate_venue_position_id()
+                } else {
+                    None
+                }
+            }
+            _ => self
+                .cache
+                .borrow()
+                .positions_open(None, Some(&order.instrument_id()), None, None)
+                .first()
+                .map(|p| p.id),
+        }
+    }
+
+    fn get_venue_order_id(&mut self, order: &OrderAny) -> VenueOrderId {
+        match order.venue_order_id() {
+            Some(venue_order_id) => venue_order_id,
+            None => self.generate_venue_order_id(),
+        }
+    }
+
+    fn generate_venue_position_id(&mut self) -> Option<PositionId> {
+        if !self.config.use_position_ids {
+            return None;
+        }
+
+        self.position_count += 1;
+        let value = if self.config.use_random_ids {
+            UUID4::new().to_string()
+        } else {
+            format!("{}-{}-{}", self.venue, self.raw_id, self.position_count)
+        };
+        Some(PositionId::new(&value).unwrap())
+    }
+
+    fn generate_venue_order_id(&mut self) -> VenueOrderId {
+        self.order_count += 1;
+        let value = if self.config.use_random_ids {
+            UUID4::new().to_string()
+        } else {
+            format!("{}-{}-{}", self.venue, self.raw_id, self.order_count)
+        };
+        VenueOrderId::new(&value).unwrap()
+    }
+
+    fn generate_trade_id(&mut self) -> TradeId {
+        self.execution_count += 1;
+        TradeId::new(self.generate_trade_id_str().as_str()).unwrap()
+    }
+
+    fn generate_trade_id_str(&self) -> Ustr {
+        if self.config.use_random_ids {
+            UUID4::new().to_string().into()
+        } else {
+            format!("{}-{}-{}", self.venue, self.raw_id, self.execution_count).into()
+        }
+    }
+
+    // -- EVENT GENERATORS -----------------------------------------------------
+
+    fn account_id_for_order(&self, order: &OrderAny) -> AccountId {
+        order.account_id().unwrap_or_else(|| {
+            self.account_ids
+                .get(&order.trader_id())
+                .copied()
+                .expect("No account ID for trader")
+        })
+    }
+
+    /// Applies the `event` to the working `order` and sends it to the execution engine,
+    /// then handles any contingencies of the order.
+    fn send_order_event(&mut self, order: &mut OrderAny, event: OrderEventAny) {
+        if let Err(e) = order.apply(event.clone()) {
+            error!(
+                "Error applying event {event} to {}: {e}",
+                order.client_order_id()
+            );
+            return;
+        }
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+
+        if !self.config.support_contingent_orders
+            || matches!(
+                order.contingency_type(),
+                None | Some(ContingencyType::NoContingency)
+            )
+        {
+            return;
+        }
+
+        self.contingent_orders
+            .insert(order.client_order_id(), order.clone());
+        match event {
+            OrderEventAny::PartiallyFilled(_) | OrderEventAny::Filled(_) => {
+                self.handle_contingent_fill(order);
+            }
+            OrderEventAny::Canceled(_) | OrderEventAny::Expired(_) | OrderEventAny::Rejected(_) => {
+                self.handle_contingent_close(order);
+            }
+            _ => {}
+        }
+    }
+
+    fn generate_order_rejected(&mut self, order: &mut OrderAny, reason: Ustr) {
+        let ts_now = self.clock.get_time_ns();
+        let account_id = self.account_id_for_order(order);
+        let event = OrderRejected::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            account_id,
+            reason,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Rejected(event));
+    }
+
+    fn generate_order_accepted(&mut self, order: &mut OrderAny, venue_order_id: VenueOrderId) {
+        let ts_now = self.clock.get_time_ns();
+        let account_id = self.account_id_for_order(order);
+        let event = OrderAccepted::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            venue_order_id,
+            account_id,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Accepted(event));
+    }
+
+    fn generate_order_modify_rejected_for(&self, order: &OrderAny, reason: Ustr) {
+        self.generate_order_modify_rejected(
+            order.trader_id(),
+            order.strategy_id(),
+            self.account_id_for_order(order),
+            order.instrument_id(),
+            order.client_order_id(),
+            order.venue_order_id(),
+            reason,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn generate_order_modify_rejected(
+        &self,
+        trader_id: TraderId,
+        strategy_id: StrategyId,
+        account_id: AccountId,
+        instrument_id: InstrumentId,
+        client_order_id: ClientOrderId,
+        venue_order_id: Option<VenueOrderId>,
+        reason: Ustr,
+    ) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderModifyRejected::new(
+            trader_id,
+            strategy_id,
+            instrument_id,
+            client_order_id,
+            reason,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            venue_order_id,
+            Some(account_id),
+        )
+        .unwrap();
+        let event = OrderEventAny::ModifyRejected(event);
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn generate_order_cancel_rejected(
+        &self,
+        trader_id: TraderId,
+        strategy_id: StrategyId,
+        account_id: AccountId,
+        instrument_id: InstrumentId,
+        client_order_id: ClientOrderId,
+        venue_order_id: Option<VenueOrderId>,
+        reason: Ustr,
+    ) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderCancelRejected::new(
+            trader_id,
+            strategy_id,
+            instrument_id,
+            client_order_id,
+            reason,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            venue_order_id,
+            Some(account_id),
+        )
+        .unwrap();
+        let event = OrderEventAny::CancelRejected(event);
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+    }
+
+    fn generate_order_updated(
+        &mut self,
+        order: &mut OrderAny,
+        quantity: Quantity,
+        price: Option<Price>,
+        trigger_price: Option<Price>,
+    ) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderUpdated::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            quantity,
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            order.venue_order_id(),
+            order.account_id(),
+            price,
+            trigger_price,
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Updated(event));
+    }
+
+    fn generate_order_canceled(&mut self, order: &mut OrderAny, venue_order_id: VenueOrderId) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderCanceled::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            Some(venue_order_id),
+            order.account_id(),
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Canceled(event));
+    }
+
+    fn generate_order_triggered(&mut self, order: &mut OrderAny) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderTriggered::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            order.venue_order_id(),
+            order.account_id(),
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Triggered(event));
+    }
+
+    fn generate_order_expired(&mut self, order: &mut OrderAny) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderExpired::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            order.venue_order_id(),
+            order.account_id(),
+        )
+        .unwrap();
+        self.send_order_event(order, OrderEventAny::Expired(event));
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn generate_order_filled(
+        &mut self,
+        order: &mut OrderAny,
+        venue_order_id: VenueOrderId,
+        venue_position_id: Option<PositionId>,
+        last_qty: Quantity,
+        last_px: Price,
+        quote_currency: Currency,
+        commission: Money,
+        liquidity_side: LiquiditySide,
+    ) {
+        let ts_now = self.clock.get_time_ns();
+        let account_id = self.account_id_for_order(order);
+        let event = OrderFilled::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
             order.client_order_id(),
+            venue_order_id,
+            account_id,
+            self.generate_trade_id(),
+            order.order_side(),
+            order.order_type(),
+            last_qty,
+            last_px,
+            quote_currency,
+            liquidity_side,
             UUID4::new(),
             ts_now,
             ts_now,
             false,
-            Some(venue_order_id),
-            order.account_id(),
+            venue_position_id,
+            Some(commission),
+        )
+        .unwrap();
+        let position = self.get_fill_position(order, venue_position_id);
+        self.fills.push((event, position));
+        let event = if last_qty < order.leaves_qty() {
+            OrderEventAny::PartiallyFilled(event)
+        } else {
+            OrderEventAny::Filled(event)
+        };
+        self.send_order_event(order, event);
+    }
+}
+
+/// Returns the simulated price path for the given `bar`.
+///
+/// The path is open -> high -> low -> close when the open is closer to the
+/// high (ties included), otherwise open -> low -> high -> close.
+fn get_bar_price_path(bar: &Bar) -> [Price; 4] {
+    get_ordered_bar_price_path(bar, is_high_first(bar))
+}
+
+/// Returns the bid and ask prices to quote for the given bid and ask bars.
+///
+/// Both paths follow the order of the bid bar, so the bid and ask move to the
+/// same extreme together and the quotes never cross.
+fn get_quote_price_path(bid_bar: &Bar, ask_bar: &Bar) -> [(Price, Price); 4] {
+    let high_first = is_high_first(bid_bar);
+    let bid_path = get_ordered_bar_price_path(bid_bar, high_first);
+    let ask_path = get_ordered_bar_price_path(ask_bar, high_first);
+    [0, 1, 2, 3].map(|i| (bid_path[i], ask_path[i]))
+}
+
+/// Returns whether the high of the `bar` is assumed to trade before its low,
+/// which is the case when the high is no further from the open.
+fn is_high_first(bar: &Bar) -> bool {
+    let distance_to_high = bar.high.raw - bar.open.raw;
+    let distance_to_low = bar.open.raw - bar.low.raw;
+    distance_to_high <= distance_to_low
+}
+
+fn get_ordered_bar_price_path(bar: &Bar, high_first: bool) -> [Price; 4] {
+    if high_first {
+        [bar.open, bar.high, bar.low, bar.close]
+    } else {
+        [bar.open, bar.low, bar.high, bar.close]
+    }
+}
+
+/// Returns the interval for the given `bar_type` if time based.
+fn get_execution_bar_interval(bar_type: &BarType) -> Option<UnixNanos> {
+    match bar_type.spec.aggregation {
+        BarAggregation::Millisecond
+        | BarAggregation::Second
+        | BarAggregation::Minute
+        | BarAggregation::Hour
+        | BarAggregation::Day => Some(get_bar_interval_ns(bar_type)),
+        _ => None,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, time::get_atomic_clock_static, uuid::UUID4};
+    use nautilus_execution::messages::{
+        cancel::CancelOrder, cancel_all::CancelAllOrders, modify::ModifyOrder,
+    };
+    use nautilus_model::{
+        data::{
+            bar::{Bar, BarType},
+            delta::OrderBookDelta,
+            order::BookOrder,
+            quote::QuoteTick,
+            status::InstrumentStatus,
+            trade::TradeTick,
+        },
+        enums::{
+            AccountType, AggressorSide, BookAction, BookType, ContingencyType, LiquiditySide,
+            MarketStatus, MarketStatusAction, OmsType, OrderSide, OrderStatus, TimeInForce,
+        },
+        events::order::OrderEventAny,
+        identifiers::{AccountId, ClientOrderId, OrderListId, StrategyId, TradeId, TraderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{
+            any::{OrderAny, PassiveOrderAny},
+            limit::LimitOrder,
+            market_to_limit::MarketToLimitOrder,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+
+    use super::{
+        get_bar_price_path, get_quote_price_path, OrderMatchingEngine, OrderMatchingEngineConfig,
+    };
+    use crate::models::{fee::MakerTakerFeeModel, fill::FillModel};
+
+    type SavedEvents = Rc<RefCell<Vec<OrderEventAny>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    #[fixture]
+    fn account_id() -> AccountId {
+        AccountId::from("SIM-001")
+    }
+
+    #[fixture]
+    fn config() -> OrderMatchingEngineConfig {
+        OrderMatchingEngineConfig {
+            bar_execution: false,
+            reject_stop_orders: false,
+            support_gtd_orders: true,
+            support_contingent_orders: true,
+            use_position_ids: false,
+            use_random_ids: false,
+            use_reduce_only: true,
+        }
+    }
+
+    fn get_matching_engine(
+        instrument: InstrumentAny,
+        book_type: BookType,
+        config: OrderMatchingEngineConfig,
+    ) -> (OrderMatchingEngine, SavedEvents) {
+        get_matching_engine_with_fill_model(instrument, book_type, config, FillModel::default())
+    }
+
+    fn get_matching_engine_with_fill_model(
+        instrument: InstrumentAny,
+        book_type: BookType,
+        config: OrderMatchingEngineConfig,
+        fill_model: FillModel,
+    ) -> (OrderMatchingEngine, SavedEvents) {
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, events) = get_message_saving_handler::<OrderEventAny>(None);
+        msgbus.register("ExecEngine.process", handler);
+
+        let engine = OrderMatchingEngine::new(
+            instrument,
+            1,
+            fill_model,
+            Box::new(MakerTakerFeeModel),
+            book_type,
+            OmsType::Netting,
+            AccountType::Margin,
+            get_atomic_clock_static(),
+            Rc::new(RefCell::new(msgbus)),
+            Rc::new(RefCell::new(Cache::default())),
+            config,
+        );
+        (engine, events)
+    }
+
+    fn submitted(order: OrderAny, account_id: AccountId) -> OrderAny {
+        let mut order = order;
+        let event = TestOrderEventStubs::order_submitted(&order, account_id);
+        order.apply(event).unwrap();
+        order
+    }
+
+    fn quote(instrument: &InstrumentAny, bid: &str, ask: &str) -> QuoteTick {
+        QuoteTick::new(
+            instrument.id(),
+            Price::from(bid),
+            Price::from(ask),
+            Quantity::from("1000000"),
+            Quantity::from("1000000"),
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_process_market_order_with_no_market_rejected(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Rejected(event) => {
+                assert_eq!(event.reason.as_str(), "No market for AUD/USD.SIM");
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_order_with_invalid_price_precision_rejected(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.800"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], OrderEventAny::Rejected(_)));
+        assert!(!engine.order_exists(order.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_process_market_order_filled_at_ask(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80010"));
+                assert_eq!(fill.last_qty, Quantity::from("100000"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_limit_order_accepted_then_filled_as_maker(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.80005"),
+                Quantity::from("100000"),
+                Some(client_order_id),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        assert!(engine.order_exists(client_order_id));
+        assert_eq!(
+            engine.get_order(&client_order_id).unwrap().status(),
+            OrderStatus::Accepted
+        );
+
+        engine.process_quote_tick(&quote(&instrument, "0.79995", "0.80005"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80005"));
+                assert_eq!(fill.liquidity_side, LiquiditySide::Maker);
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(!engine.order_exists(client_order_id));
+        assert!(engine.get_order(&client_order_id).is_none());
+    }
+
+    #[rstest]
+    fn test_limit_order_touched_by_market_filled_per_fill_model(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let fill_model = FillModel::new(0.0, 1.0, 0.0, None).unwrap();
+        let (mut engine, events) = get_matching_engine_with_fill_model(
+            instrument.clone(),
+            BookType::L1_MBP,
+            config,
+            fill_model,
+        );
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.80005"),
+                Quantity::from("100000"),
+                Some(client_order_id),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        // Market only rests on the limit price
+        engine.process_quote_tick(&quote(&instrument, "0.79995", "0.80005"));
+
+        assert_eq!(events.borrow().len(), 1);
+        assert!(engine.order_exists(client_order_id));
+
+        // Market trades through the limit price
+        engine.process_quote_tick(&quote(&instrument, "0.79990", "0.80000"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80005"));
+                assert_eq!(fill.liquidity_side, LiquiditySide::Maker);
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_market_order_slipped_per_fill_model(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let fill_model = FillModel::new(1.0, 1.0, 1.0, None).unwrap();
+        let (mut engine, events) = get_matching_engine_with_fill_model(
+            instrument.clone(),
+            BookType::L1_MBP,
+            config,
+            fill_model,
+        );
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(
+                    fill.last_px,
+                    Price::from("0.80010") + instrument.price_increment()
+                );
+                assert_eq!(fill.last_qty, Quantity::from("100000"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_stop_market_order_triggered(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::stop_market_order(
+                instrument.id(),
+                OrderSide::Sell,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                None,
+                Some(client_order_id),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+        engine.process_quote_tick(&quote(&instrument, "0.79980", "0.79990"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.79980"));
+                assert_eq!(fill.liquidity_side, LiquiditySide::Taker);
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(!engine.order_exists(client_order_id));
+    }
+
+    #[rstest]
+    fn test_process_market_to_limit_order_rests_remainder_at_initial_fill_price(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = MarketToLimitOrder::new(
+            TraderId::from("TRADER-001"),
+            StrategyId::from("S-001"),
+            instrument.id(),
+            client_order_id,
+            OrderSide::Buy,
+            Quantity::from("1500000"),
+            TimeInForce::Gtc,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
+        )
+        .unwrap();
+        let order = submitted(OrderAny::MarketToLimit(order), account_id);
+
+        engine.process_order(&order, account_id);
+
+        assert!(engine.order_exists(client_order_id));
+        assert_eq!(
+            engine.get_order(&client_order_id).unwrap().price(),
+            Some(Price::from("0.80010"))
+        );
+
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80005"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        assert!(matches!(events[1], OrderEventAny::Updated(_)));
+        match (&events[2], &events[3]) {
+            (OrderEventAny::PartiallyFilled(initial), OrderEventAny::Filled(remainder)) => {
+                assert_eq!(initial.last_px, Price::from("0.80010"));
+                assert_eq!(initial.last_qty, Quantity::from("1000000"));
+                assert_eq!(remainder.last_px, Price::from("0.80010"));
+                assert_eq!(remainder.last_qty, Quantity::from("500000"));
+                assert_eq!(remainder.liquidity_side, LiquiditySide::Maker);
+            }
+            (initial, remainder) => panic!("Unexpected events {initial}, {remainder}"),
+        }
+        assert!(!engine.order_exists(client_order_id));
+    }
+
+    #[rstest]
+    fn test_process_modify_limit_order(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79000"),
+                Quantity::from("100000"),
+                Some(client_order_id),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        let command = ModifyOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id,
+            quantity: Some(Quantity::from("200000")),
+            price: Some(Price::from("0.79500")),
+            ..Default::default()
+        };
+        engine.process_modify(&command, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], OrderEventAny::Updated(_)));
+        let working_order = engine.get_order(&client_order_id).unwrap();
+        assert_eq!(working_order.quantity(), Quantity::from("200000"));
+        assert_eq!(working_order.price(), Some(Price::from("0.79500")));
+        match &engine.get_open_bid_orders()[0] {
+            PassiveOrderAny::Limit(order) => assert_eq!(order.limit_px(), Price::from("0.79500")),
+            order => panic!("Unexpected order {order:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_cancel(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79000"),
+                Quantity::from("100000"),
+                Some(client_order_id),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        let command = CancelOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id,
+            ..Default::default()
+        };
+        engine.process_cancel(&command, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[1], OrderEventAny::Canceled(_)));
+        assert!(!engine.order_exists(client_order_id));
+    }
+
+    #[rstest]
+    fn test_process_cancel_when_order_not_found(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let command = CancelOrder {
+            instrument_id: instrument.id(),
+            client_order_id: ClientOrderId::from("O-1"),
+            ..Default::default()
+        };
+
+        engine.process_cancel(&command, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::CancelRejected(event) => {
+                assert_eq!(event.reason.as_str(), "Order O-1 not found");
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_cancel_all_for_side(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        for (client_order_id, side, price) in [
+            ("O-1", OrderSide::Buy, "0.79000"),
+            ("O-2", OrderSide::Buy, "0.79500"),
+            ("O-3", OrderSide::Sell, "0.81000"),
+        ] {
+            let order = submitted(
+                TestOrderStubs::limit_order(
+                    instrument.id(),
+                    side,
+                    Price::from(price),
+                    Quantity::from("100000"),
+                    Some(ClientOrderId::from(client_order_id)),
+                    None,
+                ),
+                account_id,
+            );
+            engine.process_order(&order, account_id);
+        }
+        let order = engine.get_order(&ClientOrderId::from("O-1")).unwrap();
+        let command = CancelAllOrders {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: instrument.id(),
+            order_side: OrderSide::Buy,
+            ..Default::default()
+        };
+
+        engine.process_cancel_all(&command, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(events[3], OrderEventAny::Canceled(_)));
+        assert!(matches!(events[4], OrderEventAny::Canceled(_)));
+        assert!(!engine.order_exists(ClientOrderId::from("O-1")));
+        assert!(!engine.order_exists(ClientOrderId::from("O-2")));
+        assert!(engine.order_exists(ClientOrderId::from("O-3")));
+    }
+
+    #[rstest]
+    fn test_drain_fills(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, _) = get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        let fills = engine.drain_fills();
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].0.last_px, Price::from("0.80010"));
+        assert!(fills[0].1.is_none());
+        assert!(engine.drain_fills().is_empty());
+    }
+
+    #[rstest]
+    fn test_process_expiration_cancels_orders_then_rejects(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        engine.process_expiration();
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-2")),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert!(engine.is_expired());
+        assert!(!engine.order_exists(ClientOrderId::from("O-1")));
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        assert!(matches!(events[1], OrderEventAny::Canceled(_)));
+        match &events[2] {
+            OrderEventAny::Rejected(event) => {
+                assert_eq!(event.reason.as_str(), "Instrument AUD/USD.SIM has expired");
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_process_settlement_fills_at_settlement_price(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Sell,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_settlement(&order, Price::from("0.81234"), account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.81234"));
+                assert_eq!(fill.last_qty, Quantity::from("100000"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    fn status(instrument: &InstrumentAny, action: MarketStatusAction) -> InstrumentStatus {
+        InstrumentStatus::new(
+            instrument.id(),
+            action,
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+    }
+
+    #[rstest]
+    #[case(MarketStatusAction::PreOpen, MarketStatus::Closed)]
+    #[case(MarketStatusAction::Cross, MarketStatus::Closed)]
+    #[case(MarketStatusAction::Trading, MarketStatus::Open)]
+    #[case(MarketStatusAction::Pause, MarketStatus::Paused)]
+    #[case(MarketStatusAction::Halt, MarketStatus::Suspended)]
+    #[case(MarketStatusAction::NotAvailableForTrading, MarketStatus::NotAvailable)]
+    #[case(MarketStatusAction::ShortSellRestrictionChange, MarketStatus::Open)]
+    fn test_process_status(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        #[case] action: MarketStatusAction,
+        #[case] expected: MarketStatus,
+    ) {
+        let (mut engine, _) = get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+
+        engine.process_status(&status(&instrument, action));
+
+        assert_eq!(engine.market_status, expected);
+    }
+
+    #[rstest]
+    #[case(MarketStatusAction::Halt, "Market for AUD/USD.SIM is SUSPENDED")]
+    #[case(MarketStatusAction::PreOpen, "Market for AUD/USD.SIM is CLOSED")]
+    fn test_market_order_rejected_when_market_not_open(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+        #[case] action: MarketStatusAction,
+        #[case] expected_reason: &str,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        engine.process_status(&status(&instrument, action));
+        let order = submitted(
+            TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            OrderEventAny::Rejected(event) => {
+                assert_eq!(event.reason.as_str(), expected_reason);
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+    }
+
+    #[rstest]
+    fn test_limit_order_rejected_when_market_not_available(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_status(&status(
+            &instrument,
+            MarketStatusAction::NotAvailableForTrading,
+        ));
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        assert!(matches!(events[0], OrderEventAny::Rejected(_)));
+    }
+
+    #[rstest]
+    fn test_limit_order_held_during_halt_then_filled_on_reopen(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        engine.process_status(&status(&instrument, MarketStatusAction::Halt));
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.80010"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+
+        engine.process_order(&order, account_id);
+        engine.process_quote_tick(&quote(&instrument, "0.79995", "0.80005"));
+        let event_count_during_halt = events.borrow().len();
+        engine.process_status(&status(&instrument, MarketStatusAction::Trading));
+
+        let events = events.borrow();
+        assert_eq!(event_count_during_halt, 1);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            OrderEventAny::Filled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80010"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(!engine.order_exists(ClientOrderId::from("O-1")));
+    }
+
+    #[rstest]
+    fn test_gtd_order_expired_while_market_halted(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = LimitOrder::new(
+            TraderId::default(),
+            StrategyId::default(),
+            instrument.id(),
+            ClientOrderId::from("O-1"),
+            OrderSide::Buy,
+            Quantity::from("100000"),
+            Price::from("0.79990"),
+            TimeInForce::Gtd,
+            Some(UnixNanos::from(10)),
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
         )
         .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+        let order = submitted(OrderAny::Limit(order), account_id);
+        engine.process_order(&order, account_id);
+        engine.process_status(&status(&instrument, MarketStatusAction::Halt));
+
+        engine.iterate(UnixNanos::from(10));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        assert!(matches!(events[1], OrderEventAny::Expired(_)));
+        assert!(!engine.order_exists(ClientOrderId::from("O-1")));
     }
 
-    fn generate_order_triggered(&self, order: &OrderAny) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderTriggered::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
-            UUID4::new(),
-            ts_now,
-            ts_now,
+    #[rstest]
+    fn test_working_orders_canceled_when_market_not_available(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&order, account_id);
+
+        engine.process_status(&status(
+            &instrument,
+            MarketStatusAction::NotAvailableForTrading,
+        ));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], OrderEventAny::Accepted(_)));
+        assert!(matches!(events[1], OrderEventAny::Canceled(_)));
+        assert!(!engine.order_exists(ClientOrderId::from("O-1")));
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn contingent_limit_order(
+        instrument: &InstrumentAny,
+        client_order_id: &str,
+        order_side: OrderSide,
+        price: &str,
+        quantity: &str,
+        contingency_type: ContingencyType,
+        linked_order_ids: &[&str],
+        parent_order_id: Option<&str>,
+    ) -> OrderAny {
+        let order = LimitOrder::new(
+            TraderId::default(),
+            StrategyId::default(),
+            instrument.id(),
+            ClientOrderId::from(client_order_id),
+            order_side,
+            Quantity::from(quantity),
+            Price::from(price),
+            TimeInForce::Gtc,
+            None,
             false,
-            order.venue_order_id(),
-            order.account_id(),
+            false,
+            false,
+            None,
+            None,
+            None,
+            Some(contingency_type),
+            Some(OrderListId::from("OL-1")),
+            Some(
+                linked_order_ids
+                    .iter()
+                    .map(|id| ClientOrderId::from(*id))
+                    .collect(),
+            ),
+            parent_order_id.map(ClientOrderId::from),
+            None,
+            None,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
         )
         .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+        submitted(OrderAny::Limit(order), AccountId::from("SIM-001"))
     }
 
-    fn generate_order_expired(&self, order: &OrderAny) {
-        let ts_now = self.clock.get_time_ns();
-        let event = OrderExpired::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-            order.venue_order_id(),
-            order.account_id(),
+    #[rstest]
+    fn test_oto_child_order_released_when_parent_filled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let parent = contingent_limit_order(
+            &instrument,
+            "O-1",
+            OrderSide::Buy,
+            "0.80005",
+            "100000",
+            ContingencyType::Oto,
+            &["O-2"],
+            None,
+        );
+        let child = contingent_limit_order(
+            &instrument,
+            "O-2",
+            OrderSide::Sell,
+            "0.81000",
+            "100000",
+            ContingencyType::NoContingency,
+            &[],
+            Some("O-1"),
+        );
+
+        engine.process_order(&parent, account_id);
+        engine.process_order(&child, account_id);
+        let event_count_before_fill = events.borrow().len();
+        let child_exists_before_fill = engine.order_exists(child.client_order_id());
+        engine.process_quote_tick(&quote(&instrument, "0.79995", "0.80005"));
+
+        let events = events.borrow();
+        assert_eq!(event_count_before_fill, 1);
+        assert!(!child_exists_before_fill);
+        assert_eq!(events.len(), 3);
+        assert!(matches!(events[1], OrderEventAny::Filled(_)));
+        match &events[2] {
+            OrderEventAny::Accepted(accepted) => {
+                assert_eq!(accepted.client_order_id, child.client_order_id());
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(engine.order_exists(child.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_oto_child_order_rejected_when_parent_canceled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let parent = contingent_limit_order(
+            &instrument,
+            "O-1",
+            OrderSide::Buy,
+            "0.79000",
+            "100000",
+            ContingencyType::Oto,
+            &["O-2"],
+            None,
+        );
+        let child = contingent_limit_order(
+            &instrument,
+            "O-2",
+            OrderSide::Sell,
+            "0.81000",
+            "100000",
+            ContingencyType::NoContingency,
+            &[],
+            Some("O-1"),
+        );
+        engine.process_order(&parent, account_id);
+        engine.process_order(&child, account_id);
+
+        let command = CancelOrder {
+            trader_id: parent.trader_id(),
+            strategy_id: parent.strategy_id(),
+            instrument_id: parent.instrument_id(),
+            client_order_id: parent.client_order_id(),
+            ..Default::default()
+        };
+        engine.process_cancel(&command, account_id);
+        engine.process_order(&child, account_id);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[1], OrderEventAny::Canceled(_)));
+        for event in &events[2..] {
+            match event {
+                OrderEventAny::Rejected(rejected) => {
+                    assert_eq!(rejected.client_order_id, child.client_order_id());
+                    assert_eq!(rejected.reason.as_str(), "REJECT OTO from O-1");
+                }
+                event => panic!("Unexpected event {event}"),
+            }
+        }
+        assert!(!engine.order_exists(child.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_oco_linked_order_canceled_when_order_filled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order1 = contingent_limit_order(
+            &instrument,
+            "O-1",
+            OrderSide::Buy,
+            "0.80005",
+            "100000",
+            ContingencyType::Oco,
+            &["O-2"],
+            None,
+        );
+        let order2 = contingent_limit_order(
+            &instrument,
+            "O-2",
+            OrderSide::Sell,
+            "0.81000",
+            "100000",
+            ContingencyType::Oco,
+            &["O-1"],
+            None,
+        );
+        engine.process_order(&order1, account_id);
+        engine.process_order(&order2, account_id);
+
+        engine.process_quote_tick(&quote(&instrument, "0.79995", "0.80005"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 4);
+        assert!(matches!(events[2], OrderEventAny::Filled(_)));
+        match &events[3] {
+            OrderEventAny::Canceled(canceled) => {
+                assert_eq!(canceled.client_order_id, order2.client_order_id());
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(!engine.order_exists(order2.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_oco_linked_order_canceled_when_order_canceled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        engine.process_quote_tick(&quote(&instrument, "0.80000", "0.80010"));
+        let order1 = contingent_limit_order(
+            &instrument,
+            "O-1",
+            OrderSide::Buy,
+            "0.79000",
+            "100000",
+            ContingencyType::Oco,
+            &["O-2"],
+            None,
+        );
+        let order2 = contingent_limit_order(
+            &instrument,
+            "O-2",
+            OrderSide::Sell,
+            "0.81000",
+            "100000",
+            ContingencyType::Oco,
+            &["O-1"],
+            None,
+        );
+        engine.process_order(&order1, account_id);
+        engine.process_order(&order2, account_id);
+
+        let command = CancelOrder {
+            trader_id: order1.trader_id(),
+            strategy_id: order1.strategy_id(),
+            instrument_id: order1.instrument_id(),
+            client_order_id: order1.client_order_id(),
+            ..Default::default()
+        };
+        engine.process_cancel(&command, account_id);
+        engine.process_order(
+            &contingent_limit_order(
+                &instrument,
+                "O-3",
+                OrderSide::Sell,
+                "0.81000",
+                "100000",
+                ContingencyType::Oco,
+                &["O-1"],
+                None,
+            ),
+            account_id,
+        );
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 5);
+        assert!(matches!(events[2], OrderEventAny::Canceled(_)));
+        assert!(matches!(events[3], OrderEventAny::Canceled(_)));
+        match &events[4] {
+            OrderEventAny::Rejected(rejected) => {
+                assert_eq!(
+                    rejected.reason.as_str(),
+                    "Contingent order O-1 already closed"
+                );
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(!engine.order_exists(order1.client_order_id()));
+        assert!(!engine.order_exists(order2.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_ouo_linked_order_reduced_when_order_partially_filled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let quote = QuoteTick::new(
+            instrument.id(),
+            Price::from("0.80000"),
+            Price::from("0.80010"),
+            Quantity::from("1000000"),
+            Quantity::from("40000"),
+            UnixNanos::from(1),
+            UnixNanos::from(1),
         )
         .unwrap();
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+        engine.process_quote_tick(&quote);
+        let order1 = contingent_limit_order(
+            &instrument,
+            "O-1",
+            OrderSide::Sell,
+            "0.81000",
+            "100000",
+            ContingencyType::Ouo,
+            &["O-2"],
+            None,
+        );
+        let order2 = contingent_limit_order(
+            &instrument,
+            "O-2",
+            OrderSide::Buy,
+            "0.80010",
+            "100000",
+            ContingencyType::Ouo,
+            &["O-1"],
+            None,
+        );
+        engine.process_order(&order1, account_id);
+
+        engine.process_order(&order2, account_id);
+
+        let events = events.borrow();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, OrderEventAny::PartiallyFilled(_))));
+        match events.last().unwrap() {
+            OrderEventAny::Updated(updated) => {
+                assert_eq!(updated.client_order_id, order1.client_order_id());
+                assert_eq!(updated.quantity, Quantity::from("60000"));
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert_eq!(
+            engine
+                .get_order(&order1.client_order_id())
+                .unwrap()
+                .quantity(),
+            Quantity::from("60000")
+        );
     }
 
-    #[allow(clippy::too_many_arguments)]
-    fn generate_order_filled(
-        &mut self,
-        order: &OrderAny,
-        venue_order_id: VenueOrderId,
-        venue_position_id: PositionId,
-        last_qty: Quantity,
-        last_px: Price,
-        quote_currency: Currency,
-        commission: Money,
-        liquidity_side: LiquiditySide,
+    fn bar(bar_type: &str, open: &str, high: &str, low: &str, close: &str) -> Bar {
+        Bar::new(
+            BarType::from(bar_type),
+            Price::from(open),
+            Price::from(high),
+            Price::from(low),
+            Price::from(close),
+            Quantity::from("400000"),
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        )
+    }
+
+    #[rstest]
+    #[case("0.80000", "0.80020", "0.79980", ["0.80000", "0.80020", "0.79980", "0.80010"])]
+    #[case("0.80000", "0.80010", "0.79980", ["0.80000", "0.80010", "0.79980", "0.80010"])]
+    #[case("0.80000", "0.80030", "0.79990", ["0.80000", "0.79990", "0.80030", "0.80010"])]
+    fn test_get_bar_price_path(
+        #[case] open: &str,
+        #[case] high: &str,
+        #[case] low: &str,
+        #[case] expected: [&str; 4],
     ) {
-        let ts_now = self.clock.get_time_ns();
-        let account_id = order
-            .account_id()
-            .unwrap_or(self.account_ids.get(&order.trader_id()).unwrap().to_owned());
-        let event = OrderFilled::new(
-            order.trader_id(),
-            order.strategy_id(),
-            order.instrument_id(),
-            order.client_order_id(),
-            venue_order_id,
+        let bar = bar(
+            "AUD/USD.SIM-1-MINUTE-LAST-EXTERNAL",
+            open,
+            high,
+            low,
+            "0.80010",
+        );
+
+        let path = get_bar_price_path(&bar);
+
+        assert_eq!(path, expected.map(Price::from));
+    }
+
+    #[rstest]
+    fn test_get_quote_price_path_follows_bid_bar_order() {
+        // The bid bar trades its high first, the ask bar alone would trade its low first
+        let bid_bar = bar(
+            "AUD/USD.SIM-1-MINUTE-BID-EXTERNAL",
+            "0.80000",
+            "0.80005",
+            "0.79980",
+            "0.80001",
+        );
+        let ask_bar = bar(
+            "AUD/USD.SIM-1-MINUTE-ASK-EXTERNAL",
+            "0.80010",
+            "0.80030",
+            "0.80008",
+            "0.80011",
+        );
+
+        let path = get_quote_price_path(&bid_bar, &ask_bar);
+
+        let expected = [
+            ("0.80000", "0.80010"),
+            ("0.80005", "0.80030"),
+            ("0.79980", "0.80008"),
+            ("0.80001", "0.80011"),
+        ]
+        .map(|(bid, ask)| (Price::from(bid), Price::from(ask)));
+        assert_eq!(path, expected);
+        assert!(path.iter().all(|(bid, ask)| bid < ask));
+    }
+
+    #[rstest]
+    fn test_process_bar_when_bar_execution_disabled(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+    ) {
+        let (mut engine, _) = get_matching_engine(instrument, BookType::L1_MBP, config);
+
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-MINUTE-LAST-EXTERNAL",
+            "0.80000",
+            "0.80020",
+            "0.79980",
+            "0.80010",
+        ));
+
+        assert_eq!(engine.best_bid_price(), None);
+        assert_eq!(engine.best_ask_price(), None);
+    }
+
+    #[rstest]
+    fn test_process_bar_fills_resting_orders_along_price_path(
+        mut config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        config.bar_execution = true;
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L1_MBP, config);
+        let buy_order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.79990"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            account_id,
+        );
+        let sell_order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Sell,
+                Price::from("0.80015"),
+                Quantity::from("100000"),
+                Some(ClientOrderId::from("O-2")),
+                None,
+            ),
+            account_id,
+        );
+        engine.process_order(&buy_order, account_id);
+        engine.process_order(&sell_order, account_id);
+
+        // Open is closer to the low, so the low is visited before the high
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-MINUTE-LAST-EXTERNAL",
+            "0.80000",
+            "0.80020",
+            "0.79990",
+            "0.80000",
+        ));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 4);
+        match (&events[2], &events[3]) {
+            (OrderEventAny::Filled(first), OrderEventAny::Filled(second)) => {
+                assert_eq!(first.client_order_id, ClientOrderId::from("O-1"));
+                assert_eq!(first.last_px, Price::from("0.79990"));
+                assert_eq!(second.client_order_id, ClientOrderId::from("O-2"));
+                assert_eq!(second.last_px, Price::from("0.80015"));
+            }
+            events => panic!("Unexpected events {events:?}"),
+        }
+        assert_eq!(engine.best_bid_price(), Some(Price::from("0.80000")));
+    }
+
+    #[rstest]
+    fn test_process_bid_ask_bars_updates_book(
+        mut config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+    ) {
+        config.bar_execution = true;
+        let (mut engine, _) = get_matching_engine(instrument, BookType::L1_MBP, config);
+
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-MINUTE-BID-EXTERNAL",
+            "0.80000",
+            "0.80020",
+            "0.79980",
+            "0.80010",
+        ));
+
+        // Waits for the matching ask bar
+        assert_eq!(engine.best_bid_price(), None);
+
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-MINUTE-ASK-EXTERNAL",
+            "0.80002",
+            "0.80022",
+            "0.79982",
+            "0.80012",
+        ));
+
+        assert_eq!(engine.best_bid_price(), Some(Price::from("0.80010")));
+        assert_eq!(engine.best_ask_price(), Some(Price::from("0.80012")));
+    }
+
+    #[rstest]
+    fn test_process_bar_uses_shortest_time_bar_type_for_execution(
+        mut config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+    ) {
+        config.bar_execution = true;
+        let (mut engine, _) = get_matching_engine(instrument, BookType::L1_MBP, config);
+
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-MINUTE-LAST-EXTERNAL",
+            "0.80000",
+            "0.80000",
+            "0.80000",
+            "0.80000",
+        ));
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-5-MINUTE-LAST-EXTERNAL",
+            "0.81000",
+            "0.81000",
+            "0.81000",
+            "0.81000",
+        ));
+
+        assert_eq!(engine.best_bid_price(), Some(Price::from("0.80000")));
+
+        engine.process_bar(&bar(
+            "AUD/USD.SIM-1-SECOND-LAST-EXTERNAL",
+            "0.82000",
+            "0.82000",
+            "0.82000",
+            "0.82000",
+        ));
+
+        assert_eq!(engine.best_bid_price(), Some(Price::from("0.82000")));
+    }
+
+    fn book_delta(
+        instrument: &InstrumentAny,
+        action: BookAction,
+        side: OrderSide,
+        price: &str,
+        size: &str,
+        order_id: u64,
+    ) -> OrderBookDelta {
+        let order = BookOrder::new(side, Price::from(price), Quantity::from(size), order_id);
+        OrderBookDelta::new(
+            instrument.id(),
+            action,
+            order,
+            0,
+            order_id,
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        )
+    }
+
+    fn trade(instrument: &InstrumentAny, price: &str, size: &str) -> TradeTick {
+        TradeTick::new(
+            instrument.id(),
+            Price::from(price),
+            Quantity::from(size),
+            AggressorSide::Seller,
+            TradeId::from("T-1"),
+            UnixNanos::from(1),
+            UnixNanos::from(1),
+        )
+    }
+
+    #[rstest]
+    fn test_l3_passive_order_fills_after_queue_ahead_traded(
+        config: OrderMatchingEngineConfig,
+        instrument: InstrumentAny,
+        account_id: AccountId,
+    ) {
+        let (mut engine, events) =
+            get_matching_engine(instrument.clone(), BookType::L3_MBO, config);
+        for delta in [
+            book_delta(
+                &instrument,
+                BookAction::Add,
+                OrderSide::Buy,
+                "0.80000",
+                "100000",
+                1,
+            ),
+            book_delta(
+                &instrument,
+                BookAction::Add,
+                OrderSide::Buy,
+                "0.80000",
+                "200000",
+                2,
+            ),
+            book_delta(
+                &instrument,
+                BookAction::Add,
+                OrderSide::Sell,
+                "0.80010",
+                "100000",
+                3,
+            ),
+        ] {
+            engine.process_order_book_delta(&delta);
+        }
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = submitted(
+            TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("0.80000"),
+                Quantity::from("100000"),
+                Some(client_order_id),
+                None,
+            ),
             account_id,
-            self.generate_trade_id(),
-            order.order_side(),
-            order.order_type(),
-            last_qty,
-            last_px,
-            quote_currency,
-            liquidity_side,
-            UUID4::new(),
-            ts_now,
-            ts_now,
-            false,
-            Some(venue_position_id),
-            Some(commission),
         );
-        self.msgbus.send("ExecEngine.process", &event as &dyn Any);
+        engine.process_order(&order, account_id);
+
+        // Trade only consumes part of the queue ahead
+        engine.process_trade_tick(&trade(&instrument, "0.80000", "200000"));
+        engine.process_order_book_delta(&book_delta(
+            &instrument,
+            BookAction::Delete,
+            OrderSide::Buy,
+            "0.80000",
+            "100000",
+            1,
+        ));
+        engine.process_order_book_delta(&book_delta(
+            &instrument,
+            BookAction::Update,
+            OrderSide::Buy,
+            "0.80000",
+            "100000",
+            2,
+        ));
+
+        assert_eq!(events.borrow().len(), 1);
+        assert!(engine.order_exists(client_order_id));
+
+        // Trade exhausts the remaining queue ahead and reaches the order
+        engine.process_trade_tick(&trade(&instrument, "0.80000", "150000"));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 2);
+        match &events[1] {
+            OrderEventAny::PartiallyFilled(fill) => {
+                assert_eq!(fill.last_px, Price::from("0.80000"));
+                assert_eq!(fill.last_qty, Quantity::from("50000"));
+                assert_eq!(fill.liquidity_side, LiquiditySide::Maker);
+            }
+            event => panic!("Unexpected event {event}"),
+        }
+        assert!(engine.order_exists(client_order_id));
     }
 }
@@ -13,13 +13,28 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+use std::{
+    cell::RefCell,
+    collections::{HashMap, VecDeque},
+    rc::Rc,
+};
+
+use log::warn;
+use nautilus_common::cache::Cache;
+use nautilus_core::{
+    correctness::{check_equal, check_predicate_true},
+    datetime::NANOSECONDS_IN_SECOND,
+    nanos::UnixNanos,
+    time::AtomicTime,
+};
 use nautilus_model::{
-    enums::LiquiditySide,
+    enums::{LiquiditySide, PriceType},
+    identifiers::AccountId,
     instruments::any::InstrumentAny,
     orders::any::OrderAny,
-    types::{money::Money, price::Price, quantity::Quantity},
+    types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
 };
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{prelude::ToPrimitive, Decimal};
 
 pub trait FeeModel {
     fn get_commission(
@@ -29,6 +44,16 @@ pub trait FeeModel {
         fill_px: Price,
         instrument: &InstrumentAny,
     ) -> anyhow::Result<Money>;
+
+    /// Records a fill for fee models which depend on traded volume.
+    fn record_fill(
+        &self,
+        _order: &OrderAny,
+        _fill_quantity: Quantity,
+        _fill_px: Price,
+        _instrument: &InstrumentAny,
+    ) {
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -92,18 +117,256 @@ impl FeeModel for MakerTakerFeeModel {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct PerContractFeeModel {
+    commission: Money,
+    min_commission: Option<Money>,
+    max_commission: Option<Money>,
+}
+
+impl PerContractFeeModel {
+    /// Creates a new [`PerContractFeeModel`] instance charging `commission` per contract,
+    /// with the total commission for each order bounded by the optional `min_commission`
+    /// and `max_commission`.
+    pub fn new(
+        commission: Money,
+        min_commission: Option<Money>,
+        max_commission: Option<Money>,
+    ) -> anyhow::Result<Self> {
+        if commission.as_f64() < 0.0 {
+            anyhow::bail!("Commission must be greater than or equal to zero.")
+        }
+        for bound in [min_commission, max_commission].into_iter().flatten() {
+            check_equal(
+                bound.currency,
+                commission.currency,
+                "commission bound currency",
+                "commission currency",
+            )?;
+        }
+        if let (Some(min), Some(max)) = (min_commission, max_commission) {
+            check_predicate_true(
+                min <= max,
+                "`min_commission` was greater than `max_commission`",
+            )?;
+        }
+        Ok(Self {
+            commission,
+            min_commission,
+            max_commission,
+        })
+    }
+
+    /// Returns the total commission for an order with the given filled `quantity`.
+    fn total_commission(&self, quantity: Quantity) -> f64 {
+        if quantity.is_zero() {
+            return 0.0;
+        }
+        let mut total = self.commission.as_f64() * quantity.as_f64();
+        if let Some(min_commission) = self.min_commission {
+            total = total.max(min_commission.as_f64());
+        }
+        if let Some(max_commission) = self.max_commission {
+            total = total.min(max_commission.as_f64());
+        }
+        total
+    }
+}
+
+impl FeeModel for PerContractFeeModel {
+    fn get_commission(
+        &self,
+        order: &OrderAny,
+        fill_quantity: Quantity,
+        _fill_px: Price,
+        _instrument: &InstrumentAny,
+    ) -> anyhow::Result<Money> {
+        // Commission bounds apply per order, so charge the change in total commission
+        let filled_qty = order.filled_qty();
+        let commission =
+            self.total_commission(filled_qty + fill_quantity) - self.total_commission(filled_qty);
+        Money::new(commission, self.commission.currency)
+    }
+}
+
+/// Represents a fee tier which applies once the rolling traded notional reaches `min_notional`.
+#[derive(Debug, Clone)]
+pub struct FeeTier {
+    pub min_notional: f64,
+    pub maker_fee: Decimal,
+    pub taker_fee: Decimal,
+}
+
+impl FeeTier {
+    /// Creates a new [`FeeTier`] instance.
+    #[must_use]
+    pub const fn new(min_notional: f64, maker_fee: Decimal, taker_fee: Decimal) -> Self {
+        Self {
+            min_notional,
+            maker_fee,
+            taker_fee,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TieredVolumeFeeModel {
+    tiers: Vec<FeeTier>,
+    currency: Currency,
+    window_ns: u64,
+    clock: &'static AtomicTime,
+    cache: Rc<RefCell<Cache>>,
+    volumes: RefCell<HashMap<AccountId, VecDeque<(UnixNanos, f64)>>>,
+}
+
+impl TieredVolumeFeeModel {
+    /// Creates a new [`TieredVolumeFeeModel`] instance.
+    ///
+    /// The maker/taker fees are taken from the highest tier reached by the traded
+    /// notional of the account over the rolling window (default 30 days), as of the
+    /// time from the `clock`. Tier notionals are in `currency`, with fills in other
+    /// currencies converted at the mid exchange rate from the `cache`.
+    pub fn new(
+        mut tiers: Vec<FeeTier>,
+        currency: Currency,
+        window_ns: Option<u64>,
+        clock: &'static AtomicTime,
+        cache: Rc<RefCell<Cache>>,
+    ) -> anyhow::Result<Self> {
+        tiers.sort_by(|a, b| a.min_notional.total_cmp(&b.min_notional));
+        check_predicate_true(
+            tiers.first().map_or(false, |tier| tier.min_notional == 0.0),
+            "`tiers` did not contain a tier with a `min_notional` of zero",
+        )?;
+        Ok(Self {
+            tiers,
+            currency,
+            window_ns: window_ns.unwrap_or(30 * 86_400 * NANOSECONDS_IN_SECOND),
+            clock,
+            cache,
+            volumes: RefCell::new(HashMap::new()),
+        })
+    }
+
+    /// Returns the rolling traded notional (in the tier currency) for the given `account_id`.
+    #[must_use]
+    pub fn rolling_notional(&self, account_id: &AccountId) -> f64 {
+        let mut volumes = self.volumes.borrow_mut();
+        match volumes.get_mut(account_id) {
+            Some(fills) => {
+                self.expire_fills(fills);
+                fills.iter().map(|(_, notional)| notional).sum()
+            }
+            None => 0.0,
+        }
+    }
+
+    fn get_tier(&self, rolling_notional: f64) -> &FeeTier {
+        self.tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_notional <= rolling_notional)
+            .unwrap_or(&self.tiers[0])
+    }
+
+    fn expire_fills(&self, fills: &mut VecDeque<(UnixNanos, f64)>) {
+        let ts_now = self.clock.get_time_ns().as_u64();
+        while let Some((ts_fill, _)) = fills.front() {
+            if ts_fill.as_u64() + self.window_ns > ts_now {
+                break;
+            }
+            fills.pop_front();
+        }
+    }
+}
+
+impl FeeModel for TieredVolumeFeeModel {
+    fn get_commission(
+        &self,
+        order: &OrderAny,
+        fill_quantity: Quantity,
+        fill_px: Price,
+        instrument: &InstrumentAny,
+    ) -> anyhow::Result<Money> {
+        let Some(account_id) = order.account_id() else {
+            anyhow::bail!("Account ID not set.")
+        };
+
+        let notional = instrument.calculate_notional_value(fill_quantity, fill_px, Some(false));
+        let tier = self.get_tier(self.rolling_notional(&account_id));
+        let commission = match order.liquidity_side() {
+            Some(LiquiditySide::Maker) => notional * tier.maker_fee.to_f64().unwrap(),
+            Some(LiquiditySide::Taker) => notional * tier.taker_fee.to_f64().unwrap(),
+            Some(LiquiditySide::NoLiquiditySide) | None => anyhow::bail!("Liquidity side not set."),
+        };
+
+        let currency: Currency = match instrument.is_inverse() {
+            true => instrument.base_currency().unwrap(),
+            false => instrument.quote_currency(),
+        };
+        Money::new(commission, currency)
+    }
+
+    fn record_fill(
+        &self,
+        order: &OrderAny,
+        fill_quantity: Quantity,
+        fill_px: Price,
+        instrument: &InstrumentAny,
+    ) {
+        let Some(account_id) = order.account_id() else {
+            return;
+        };
+
+        let notional = instrument.calculate_notional_value(fill_quantity, fill_px, Some(false));
+        let Some(xrate) = self.cache.borrow().get_xrate(
+            &instrument.id().venue,
+            notional.currency,
+            self.currency,
+            PriceType::Mid,
+        ) else {
+            warn!(
+                "Cannot record fill notional {notional}: no exchange rate to {}",
+                self.currency
+            );
+            return;
+        };
+
+        self.volumes
+            .borrow_mut()
+            .entry(account_id)
+            .or_default()
+            .push_back((
+                self.clock.get_time_ns(),
+                notional.as_f64() * xrate.to_f64().unwrap(),
+            ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::cache::Cache;
+    use nautilus_core::{datetime::NANOSECONDS_IN_SECOND, nanos::UnixNanos, time::AtomicTime};
     use nautilus_model::{
+        data::quote::QuoteTick,
         enums::{LiquiditySide, OrderSide},
-        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        events::order::OrderEventAny,
+        instruments::{
+            any::InstrumentAny,
+            stubs::{audusd_sim, usdjpy_idealpro},
+        },
         orders::stubs::{TestOrderEventStubs, TestOrderStubs},
         types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
     };
     use rstest::rstest;
-    use rust_decimal::prelude::ToPrimitive;
+    use rust_decimal::{prelude::ToPrimitive, Decimal};
 
-    use crate::models::fee::{FeeModel, FixedFeeModel, MakerTakerFeeModel};
+    use crate::models::fee::{
+        FeeModel, FeeTier, FixedFeeModel, MakerTakerFeeModel, PerContractFeeModel,
+        TieredVolumeFeeModel,
+    };
 
     #[rstest]
     fn test_fixed_model_single_fill() {
@@ -241,4 +504,245 @@ mod tests {
             .unwrap();
         assert_eq!(commission.as_f64(), expected_commission_amount);
     }
+
+    #[rstest]
+    #[case(Money::from("2.5 USD"), None, None, 10, Money::from("25 USD"))]
+    #[case(
+        Money::from("0.5 USD"),
+        Some(Money::from("1 USD")),
+        None,
+        1,
+        Money::from("1 USD")
+    )]
+    #[case(
+        Money::from("0.5 USD"),
+        None,
+        Some(Money::from("3 USD")),
+        10,
+        Money::from("3 USD")
+    )]
+    fn test_per_contract_fee_model_single_fill(
+        #[case] commission: Money,
+        #[case] min_commission: Option<Money>,
+        #[case] max_commission: Option<Money>,
+        #[case] fill_quantity: i64,
+        #[case] expected: Money,
+    ) {
+        let aud_usd = InstrumentAny::CurrencyPair(audusd_sim());
+        let fee_model =
+            PerContractFeeModel::new(commission, min_commission, max_commission).unwrap();
+        let market_order = TestOrderStubs::market_order(
+            aud_usd.id(),
+            OrderSide::Buy,
+            Quantity::from(10),
+            None,
+            None,
+        );
+        let accepted_order = TestOrderStubs::make_accepted_order(&market_order);
+
+        let commission = fee_model
+            .get_commission(
+                &accepted_order,
+                Quantity::from(fill_quantity),
+                Price::from("1.0"),
+                &aud_usd,
+            )
+            .unwrap();
+
+        assert_eq!(commission, expected);
+    }
+
+    #[rstest]
+    fn test_per_contract_fee_model_max_commission_per_order() {
+        let aud_usd = InstrumentAny::CurrencyPair(audusd_sim());
+        let fee_model =
+            PerContractFeeModel::new(Money::from("1 USD"), None, Some(Money::from("10 USD")))
+                .unwrap();
+        let market_order = TestOrderStubs::market_order(
+            aud_usd.id(),
+            OrderSide::Buy,
+            Quantity::from(20),
+            None,
+            None,
+        );
+        let mut accepted_order = TestOrderStubs::make_accepted_order(&market_order);
+
+        let mut commissions = Vec::new();
+        for fill_quantity in [8, 8, 4] {
+            let fill_quantity = Quantity::from(fill_quantity);
+            let commission = fee_model
+                .get_commission(&accepted_order, fill_quantity, Price::from("1.0"), &aud_usd)
+                .unwrap();
+            commissions.push(commission);
+            let OrderEventAny::Filled(fill) = TestOrderEventStubs::order_filled(
+                &accepted_order,
+                &aud_usd,
+                None,
+                None,
+                None,
+                Some(fill_quantity),
+                None,
+                None,
+                None,
+                None,
+            ) else {
+                panic!("Expected fill event");
+            };
+            let event = if accepted_order.leaves_qty() > fill_quantity {
+                OrderEventAny::PartiallyFilled(fill)
+            } else {
+                OrderEventAny::Filled(fill)
+            };
+            accepted_order.apply(event).unwrap();
+        }
+
+        assert_eq!(
+            commissions,
+            vec![
+                Money::from("8 USD"),
+                Money::from("2 USD"),
+                Money::from("0 USD")
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_per_contract_fee_model_min_greater_than_max_error() {
+        let result = PerContractFeeModel::new(
+            Money::from("1 USD"),
+            Some(Money::from("10 USD")),
+            Some(Money::from("5 USD")),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_tiered_volume_fee_model_without_zero_tier_error() {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let tiers = vec![FeeTier::new(
+            1_000.0,
+            Decimal::new(1, 3),
+            Decimal::new(2, 3),
+        )];
+
+        let result = TieredVolumeFeeModel::new(
+            tiers,
+            Currency::USD(),
+            None,
+            clock,
+            Rc::new(RefCell::new(Cache::default())),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_tiered_volume_fee_model_uses_rolling_notional() {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let tiers = vec![
+            FeeTier::new(1_000_000.0, Decimal::new(5, 4), Decimal::new(1, 3)),
+            FeeTier::new(0.0, Decimal::new(1, 3), Decimal::new(2, 3)),
+        ];
+        let fee_model = TieredVolumeFeeModel::new(
+            tiers,
+            Currency::USD(),
+            None,
+            clock,
+            Rc::new(RefCell::new(Cache::default())),
+        )
+        .unwrap();
+        let aud_usd = InstrumentAny::CurrencyPair(audusd_sim());
+        let limit_order = TestOrderStubs::limit_order(
+            aud_usd.id(),
+            OrderSide::Buy,
+            Price::from("1.0"),
+            Quantity::from(1_000_000),
+            None,
+            None,
+        );
+        let order = TestOrderStubs::make_filled_order(&limit_order, &aud_usd, LiquiditySide::Taker);
+        let account_id = order.account_id().unwrap();
+        let fill_quantity = Quantity::from(1_000_000);
+        let fill_px = Price::from("1.0");
+
+        let commission1 = fee_model
+            .get_commission(&order, fill_quantity, fill_px, &aud_usd)
+            .unwrap();
+        let commission2 = fee_model
+            .get_commission(&order, fill_quantity, fill_px, &aud_usd)
+            .unwrap();
+
+        // Querying the commission does not count towards the rolling notional
+        assert_eq!(commission1, Money::from("2000 USD"));
+        assert_eq!(commission2, Money::from("2000 USD"));
+        assert_eq!(fee_model.rolling_notional(&account_id), 0.0);
+
+        fee_model.record_fill(&order, fill_quantity, fill_px, &aud_usd);
+        let commission3 = fee_model
+            .get_commission(&order, fill_quantity, fill_px, &aud_usd)
+            .unwrap();
+
+        assert_eq!(commission3, Money::from("1000 USD"));
+        assert_eq!(fee_model.rolling_notional(&account_id), 1_000_000.0);
+
+        // Advance past the rolling window
+        clock.set_time(UnixNanos::from(31 * 86_400 * NANOSECONDS_IN_SECOND));
+        let commission4 = fee_model
+            .get_commission(&order, fill_quantity, fill_px, &aud_usd)
+            .unwrap();
+
+        assert_eq!(commission4, Money::from("2000 USD"));
+        assert_eq!(fee_model.rolling_notional(&account_id), 0.0);
+    }
+
+    #[rstest]
+    fn test_tiered_volume_fee_model_converts_notional_to_tier_currency() {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let tiers = vec![FeeTier::new(0.0, Decimal::new(1, 3), Decimal::new(2, 3))];
+        let usd_jpy = InstrumentAny::CurrencyPair(usdjpy_idealpro());
+        let mut cache = Cache::default();
+        cache.add_instrument(usd_jpy.clone()).unwrap();
+        cache
+            .add_quote(
+                QuoteTick::new(
+                    usd_jpy.id(),
+                    Price::from("150.000"),
+                    Price::from("150.000"),
+                    Quantity::from(1_000_000),
+                    Quantity::from(1_000_000),
+                    UnixNanos::default(),
+                    UnixNanos::default(),
+                )
+                .unwrap(),
+            )
+            .unwrap();
+        let fee_model = TieredVolumeFeeModel::new(
+            tiers,
+            Currency::USD(),
+            None,
+            clock,
+            Rc::new(RefCell::new(cache)),
+        )
+        .unwrap();
+        let limit_order = TestOrderStubs::limit_order(
+            usd_jpy.id(),
+            OrderSide::Buy,
+            Price::from("150.000"),
+            Quantity::from(1_000_000),
+            None,
+            None,
+        );
+        let order = TestOrderStubs::make_filled_order(&limit_order, &usd_jpy, LiquiditySide::Taker);
+        let account_id = order.account_id().unwrap();
+
+        fee_model.record_fill(
+            &order,
+            Quantity::from(1_000_000),
+            Price::from("150.000"),
+            &usd_jpy,
+        );
+
+        assert!((fee_model.rolling_notional(&account_id) - 1_000_000.0).abs() < 1e-6);
+    }
 }
@@ -72,6 +72,14 @@ impl FillModel {
     }
 }
 
+impl Default for FillModel {
+    /// Creates a new default [`FillModel`] instance which always fills limit and stop orders
+    /// resting at the market, without slippage.
+    fn default() -> Self {
+        Self::new(1.0, 1.0, 0.0, None).unwrap()
+    }
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
@@ -0,0 +1,516 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{
+    cell::RefCell,
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, VecDeque},
+    rc::Rc,
+};
+
+use nautilus_common::{
+    clock::{Clock, TestClock},
+    handlers::{EventHandler, SafeTimeEventCallback},
+    timer::TimeEvent,
+};
+use nautilus_core::{
+    correctness::{check_in_range_inclusive_u64, check_predicate_true},
+    datetime::{NANOSECONDS_IN_MILLISECOND, NANOSECONDS_IN_SECOND},
+    nanos::UnixNanos,
+};
+use nautilus_execution::messages::TradingCommand;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaChaRng;
+use ustr::Ustr;
+
+const NANOSECONDS_IN_DAY: u64 = 86_400 * NANOSECONDS_IN_SECOND;
+
+/// Provides the simulated latency (in nanoseconds) for order commands sent to a venue.
+pub trait LatencyModel {
+    fn get_insert_latency(&mut self, ts_now: UnixNanos) -> u64;
+    fn get_update_latency(&mut self, ts_now: UnixNanos) -> u64;
+    fn get_cancel_latency(&mut self, ts_now: UnixNanos) -> u64;
+}
+
+/// A latency model with constant latencies, where each command latency is
+/// the base latency plus the latency specific to that command.
+#[derive(Debug, Clone)]
+pub struct FixedLatencyModel {
+    insert_latency_nanos: u64,
+    update_latency_nanos: u64,
+    cancel_latency_nanos: u64,
+}
+
+impl FixedLatencyModel {
+    /// Creates a new [`FixedLatencyModel`] instance.
+    #[must_use]
+    pub const fn new(
+        base_latency_nanos: u64,
+        insert_latency_nanos: u64,
+        update_latency_nanos: u64,
+        cancel_latency_nanos: u64,
+    ) -> Self {
+        Self {
+            insert_latency_nanos: base_latency_nanos + insert_latency_nanos,
+            update_latency_nanos: base_latency_nanos + update_latency_nanos,
+            cancel_latency_nanos: base_latency_nanos + cancel_latency_nanos,
+        }
+    }
+}
+
+impl Default for FixedLatencyModel {
+    /// Creates a new default [`FixedLatencyModel`] instance with a 1 millisecond base latency.
+    fn default() -> Self {
+        Self::new(NANOSECONDS_IN_MILLISECOND, 0, 0, 0)
+    }
+}
+
+impl LatencyModel for FixedLatencyModel {
+    fn get_insert_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.insert_latency_nanos
+    }
+
+    fn get_update_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.update_latency_nanos
+    }
+
+    fn get_cancel_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.cancel_latency_nanos
+    }
+}
+
+/// A latency model drawing every command latency uniformly from an inclusive range.
+#[derive(Debug, Clone)]
+pub struct UniformLatencyModel {
+    min_latency_nanos: u64,
+    max_latency_nanos: u64,
+    rng: ChaChaRng,
+}
+
+impl UniformLatencyModel {
+    /// Creates a new [`UniformLatencyModel`] instance.
+    pub fn new(
+        min_latency_nanos: u64,
+        max_latency_nanos: u64,
+        random_seed: Option<u64>,
+    ) -> anyhow::Result<Self> {
+        check_in_range_inclusive_u64(min_latency_nanos, 0, max_latency_nanos, "min_latency_nanos")?;
+        let rng = match random_seed {
+            Some(seed) => ChaChaRng::seed_from_u64(seed),
+            None => ChaChaRng::from_entropy(),
+        };
+        Ok(Self {
+            min_latency_nanos,
+            max_latency_nanos,
+            rng,
+        })
+    }
+
+    fn sample(&mut self) -> u64 {
+        self.rng
+            .gen_range(self.min_latency_nanos..=self.max_latency_nanos)
+    }
+}
+
+impl LatencyModel for UniformLatencyModel {
+    fn get_insert_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.sample()
+    }
+
+    fn get_update_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.sample()
+    }
+
+    fn get_cancel_latency(&mut self, _ts_now: UnixNanos) -> u64 {
+        self.sample()
+    }
+}
+
+/// A latency model which delegates to a different model depending on the
+/// UTC time of day.
+///
+/// The schedule holds the start of each period (as nanoseconds since midnight UTC)
+/// with the model to use from then on, the last period wraps around midnight.
+pub struct TimeOfDayLatencyModel {
+    schedule: Vec<(u64, Box<dyn LatencyModel>)>,
+}
+
+impl TimeOfDayLatencyModel {
+    /// Creates a new [`TimeOfDayLatencyModel`] instance.
+    pub fn new(mut schedule: Vec<(u64, Box<dyn LatencyModel>)>) -> anyhow::Result<Self> {
+        check_predicate_true(!schedule.is_empty(), "`schedule` was empty")?;
+        for (start_nanos, _) in &schedule {
+            check_in_range_inclusive_u64(*start_nanos, 0, NANOSECONDS_IN_DAY - 1, "start_nanos")?;
+        }
+        schedule.sort_by_key(|(start_nanos, _)| *start_nanos);
+        Ok(Self { schedule })
+    }
+
+    fn get_model(&mut self, ts_now: UnixNanos) -> &mut dyn LatencyModel {
+        let time_of_day = ts_now.as_u64() % NANOSECONDS_IN_DAY;
+        let index = self
+            .schedule
+            .iter()
+            .rposition(|(start_nanos, _)| *start_nanos <= time_of_day)
+            .unwrap_or(self.schedule.len() - 1); // Still in the last period from the prior day
+        self.schedule[index].1.as_mut()
+    }
+}
+
+impl LatencyModel for TimeOfDayLatencyModel {
+    fn get_insert_latency(&mut self, ts_now: UnixNanos) -> u64 {
+        self.get_model(ts_now).get_insert_latency(ts_now)
+    }
+
+    fn get_update_latency(&mut self, ts_now: UnixNanos) -> u64 {
+        self.get_model(ts_now).get_update_latency(ts_now)
+    }
+
+    fn get_cancel_latency(&mut self, ts_now: UnixNanos) -> u64 {
+        self.get_model(ts_now).get_cancel_latency(ts_now)
+    }
+}
+
+struct InflightCommand {
+    ts_arrived: UnixNanos,
+    sequence: u64,
+    timer_name: Ustr,
+    command: TradingCommand,
+}
+
+impl PartialEq for InflightCommand {
+    fn eq(&self, other: &Self) -> bool {
+        self.ts_arrived == other.ts_arrived && self.sequence == other.sequence
+    }
+}
+
+impl Eq for InflightCommand {}
+
+impl PartialOrd for InflightCommand {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InflightCommand {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.ts_arrived, self.sequence).cmp(&(other.ts_arrived, other.sequence))
+    }
+}
+
+/// Provides a queue of trading commands in flight to a simulated venue.
+///
+/// Sending a command sets a time alert on the clock for when its latency has elapsed.
+/// As the clock is advanced each alert releases the commands which have arrived by then,
+/// in arrival order with commands arriving at the same time released in the order they
+/// were sent, ready to be taken by the venue with [`InflightQueue::drain_arrived`].
+pub struct InflightQueue {
+    name: Ustr,
+    clock: Rc<RefCell<TestClock>>,
+    latency_model: Box<dyn LatencyModel>,
+    inflight: Rc<RefCell<BinaryHeap<Reverse<InflightCommand>>>>,
+    arrived: Rc<RefCell<VecDeque<TradingCommand>>>,
+    sequence: u64,
+}
+
+impl InflightQueue {
+    /// Creates a new [`InflightQueue`] instance, with the `name` prefixing the name of each
+    /// time alert set on the `clock`.
+    #[must_use]
+    pub fn new(
+        name: &str,
+        clock: Rc<RefCell<TestClock>>,
+        latency_model: Box<dyn LatencyModel>,
+    ) -> Self {
+        Self {
+            name: Ustr::from(name),
+            clock,
+            latency_model,
+            inflight: Rc::new(RefCell::new(BinaryHeap::new())),
+            arrived: Rc::new(RefCell::new(VecDeque::new())),
+            sequence: 0,
+        }
+    }
+
+    /// Returns the count of commands still in flight.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.inflight.borrow().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inflight.borrow().is_empty()
+    }
+
+    /// Returns the time the next in-flight command arrives at the venue (if any).
+    #[must_use]
+    pub fn next_arrival_time(&self) -> Option<UnixNanos> {
+        self.inflight
+            .borrow()
+            .peek()
+            .map(|Reverse(inflight)| inflight.ts_arrived)
+    }
+
+    /// Sends the `command` at the current time of the clock, delayed by the latency from
+    /// the latency model.
+    ///
+    /// A command without latency is released immediately.
+    pub fn send(&mut self, command: TradingCommand) -> anyhow::Result<()> {
+        let ts_now = self.clock.borrow().get_time_ns();
+        let latency_nanos = match command {
+            TradingCommand::SubmitOrder(_) | TradingCommand::SubmitOrderList(_) => {
+                self.latency_model.get_insert_latency(ts_now)
+            }
+            TradingCommand::ModifyOrder(_) => self.latency_model.get_update_latency(ts_now),
+            TradingCommand::CancelOrder(_)
+            | TradingCommand::CancelAllOrders(_)
+            | TradingCommand::BatchCancelOrders(_) => self.latency_model.get_cancel_latency(ts_now),
+            TradingCommand::QueryOrder(_) => 0,
+        };
+
+        if latency_nanos == 0 {
+            self.arrived.borrow_mut().push_back(command);
+            return Ok(());
+        }
+
+        self.sequence += 1;
+        let ts_arrived = ts_now + latency_nanos;
+        let timer_name = Ustr::from(&format!("{}-{}", self.name, self.sequence));
+        self.clock.borrow_mut().set_time_alert_ns(
+            &timer_name,
+            ts_arrived,
+            Some(EventHandler::from_rust(self.arrival_callback())),
+        )?;
+        self.inflight.borrow_mut().push(Reverse(InflightCommand {
+            ts_arrived,
+            sequence: self.sequence,
+            timer_name,
+            command,
+        }));
+        Ok(())
+    }
+
+    /// Removes and returns all commands which have arrived at the venue, in arrival order.
+    pub fn drain_arrived(&mut self) -> Vec<TradingCommand> {
+        self.arrived.borrow_mut().drain(..).collect()
+    }
+
+    /// Clears all commands, canceling the time alerts for those still in flight.
+    pub fn clear(&mut self) {
+        let mut clock = self.clock.borrow_mut();
+        for Reverse(inflight) in self.inflight.borrow_mut().drain() {
+            clock.cancel_timer(&inflight.timer_name);
+        }
+        self.arrived.borrow_mut().clear();
+    }
+
+    fn arrival_callback(&self) -> SafeTimeEventCallback {
+        let inflight = self.inflight.clone();
+        let arrived = self.arrived.clone();
+        let clock = Rc::downgrade(&self.clock);
+        SafeTimeEventCallback::new(Rc::new(move |event: TimeEvent| {
+            // Release every command which has arrived (not only the command for this alert),
+            // so that commands arriving at the same time keep their send order
+            let mut inflight = inflight.borrow_mut();
+            while inflight.peek().map_or(false, |Reverse(inflight)| {
+                inflight.ts_arrived <= event.ts_event
+            }) {
+                if let Some(Reverse(inflight)) = inflight.pop() {
+                    arrived.borrow_mut().push_back(inflight.command);
+                }
+            }
+            if let Some(clock) = clock.upgrade() {
+                clock.borrow_mut().cancel_timer(&event.name);
+            }
+        }))
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_execution::messages::{cancel::CancelOrder, modify::ModifyOrder};
+    use nautilus_model::identifiers::ClientOrderId;
+    use rstest::rstest;
+
+    use super::*;
+
+    fn cancel_order(client_order_id: &str) -> TradingCommand {
+        TradingCommand::CancelOrder(CancelOrder {
+            client_order_id: ClientOrderId::from(client_order_id),
+            ..Default::default()
+        })
+    }
+
+    fn modify_order(client_order_id: &str) -> TradingCommand {
+        TradingCommand::ModifyOrder(ModifyOrder {
+            client_order_id: ClientOrderId::from(client_order_id),
+            ..Default::default()
+        })
+    }
+
+    fn client_order_id(command: &TradingCommand) -> ClientOrderId {
+        match command {
+            TradingCommand::CancelOrder(command) => command.client_order_id,
+            TradingCommand::ModifyOrder(command) => command.client_order_id,
+            _ => panic!("Unexpected command {command}"),
+        }
+    }
+
+    #[rstest]
+    fn test_fixed_latency_model() {
+        let mut model = FixedLatencyModel::new(1_000, 100, 200, 300);
+
+        assert_eq!(model.get_insert_latency(0.into()), 1_100);
+        assert_eq!(model.get_update_latency(0.into()), 1_200);
+        assert_eq!(model.get_cancel_latency(0.into()), 1_300);
+    }
+
+    #[rstest]
+    fn test_fixed_latency_model_default() {
+        let mut model = FixedLatencyModel::default();
+
+        assert_eq!(
+            model.get_insert_latency(0.into()),
+            NANOSECONDS_IN_MILLISECOND
+        );
+    }
+
+    #[rstest]
+    #[should_panic(expected = "Condition failed")]
+    fn test_uniform_latency_model_invalid_range() {
+        let _ = UniformLatencyModel::new(200, 100, None).unwrap();
+    }
+
+    #[rstest]
+    fn test_uniform_latency_model_samples_within_range() {
+        let mut model = UniformLatencyModel::new(100, 200, Some(42)).unwrap();
+
+        for _ in 0..100 {
+            let latency = model.get_insert_latency(0.into());
+            assert!((100..=200).contains(&latency));
+        }
+    }
+
+    #[rstest]
+    #[case(0, 5_000)]
+    #[case(8 * 3_600 * NANOSECONDS_IN_SECOND, 1_000)]
+    #[case(20 * 3_600 * NANOSECONDS_IN_SECOND, 5_000)]
+    #[case(NANOSECONDS_IN_DAY + 9 * 3_600 * NANOSECONDS_IN_SECOND, 1_000)]
+    fn test_time_of_day_latency_model(#[case] ts_now: u64, #[case] expected: u64) {
+        let mut model = TimeOfDayLatencyModel::new(vec![
+            (
+                16 * 3_600 * NANOSECONDS_IN_SECOND,
+                Box::new(FixedLatencyModel::new(5_000, 0, 0, 0)) as Box<dyn LatencyModel>,
+            ),
+            (
+                8 * 3_600 * NANOSECONDS_IN_SECOND,
+                Box::new(FixedLatencyModel::new(1_000, 0, 0, 0)) as Box<dyn LatencyModel>,
+            ),
+        ])
+        .unwrap();
+
+        assert_eq!(model.get_insert_latency(ts_now.into()), expected);
+    }
+
+    fn get_inflight_queue(latency_model: FixedLatencyModel) -> InflightQueue {
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        InflightQueue::new("SIM-INFLIGHT", clock, Box::new(latency_model))
+    }
+
+    fn advance_clock(queue: &InflightQueue, to_time_ns: u64) {
+        let events = queue
+            .clock
+            .borrow_mut()
+            .advance_time(to_time_ns.into(), true);
+        for event in events {
+            let callback = queue.clock.borrow().rust_callback(&event);
+            if let Some(callback) = callback {
+                callback.call(event);
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_inflight_queue_releases_commands_once_latency_elapsed() {
+        let mut queue = get_inflight_queue(FixedLatencyModel::new(0, 100, 50, 10));
+
+        queue.send(modify_order("O-1")).unwrap();
+        queue.send(cancel_order("O-2")).unwrap();
+
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.next_arrival_time(), Some(10.into()));
+        assert_eq!(queue.clock.borrow().timer_count(), 2);
+
+        advance_clock(&queue, 9);
+
+        assert!(queue.drain_arrived().is_empty());
+
+        advance_clock(&queue, 50);
+        let arrived = queue.drain_arrived();
+
+        assert_eq!(arrived.len(), 2);
+        assert_eq!(client_order_id(&arrived[0]), ClientOrderId::from("O-2"));
+        assert_eq!(client_order_id(&arrived[1]), ClientOrderId::from("O-1"));
+        assert!(queue.is_empty());
+        assert_eq!(queue.clock.borrow().timer_count(), 0);
+    }
+
+    #[rstest]
+    fn test_inflight_queue_same_arrival_time_keeps_send_order() {
+        let mut queue = get_inflight_queue(FixedLatencyModel::new(100, 0, 0, 0));
+
+        queue.send(cancel_order("O-1")).unwrap();
+        queue.send(cancel_order("O-2")).unwrap();
+        queue.send(cancel_order("O-3")).unwrap();
+        advance_clock(&queue, 100);
+
+        let ids: Vec<ClientOrderId> = queue.drain_arrived().iter().map(client_order_id).collect();
+        assert_eq!(
+            ids,
+            vec![
+                ClientOrderId::from("O-1"),
+                ClientOrderId::from("O-2"),
+                ClientOrderId::from("O-3"),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_inflight_queue_without_latency_releases_immediately() {
+        let mut queue = get_inflight_queue(FixedLatencyModel::new(0, 0, 0, 0));
+
+        queue.send(cancel_order("O-1")).unwrap();
+
+        assert!(queue.is_empty());
+        assert_eq!(queue.drain_arrived().len(), 1);
+    }
+
+    #[rstest]
+    fn test_inflight_queue_clear_cancels_alerts() {
+        let mut queue = get_inflight_queue(FixedLatencyModel::new(100, 0, 0, 0));
+        queue.send(cancel_order("O-1")).unwrap();
+
+        queue.clear();
+        advance_clock(&queue, 100);
+
+        assert!(queue.is_empty());
+        assert!(queue.drain_arrived().is_empty());
+        assert_eq!(queue.clock.borrow().timer_count(), 0);
+    }
+}
@@ -15,3 +15,5 @@
 
 pub mod fee;
 pub mod fill;
+pub mod latency;
+pub mod queue;
@@ -0,0 +1,241 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::HashSet;
+
+use nautilus_model::{
+    data::{order::OrderId, trade::TradeTick},
+    enums::{AggressorSide, OrderSide},
+    orderbook::book::OrderBook,
+    types::price::Price,
+};
+
+/// Tracks the position of a simulated passive order within the FIFO queue of
+/// an L3 (MBO) order book price level.
+///
+/// The book orders resting at the level when the simulated order joined the
+/// queue are considered ahead of it. The queue advances as those orders are
+/// traded away, reduced or canceled through book updates.
+///
+/// Trades at the level are accumulated until the book next reduces or removes
+/// orders at the level, so that consecutive trades arriving before the book
+/// updates for the orders they traded are measured against the queue together.
+#[derive(Debug, Clone)]
+pub struct QueuePosition {
+    /// The book side the simulated order rests on.
+    pub side: OrderSide,
+    /// The price level the simulated order rests at.
+    pub price: Price,
+    order_ids_ahead: HashSet<OrderId>,
+    traded_raw: u64,
+}
+
+impl QueuePosition {
+    /// Creates a new [`QueuePosition`] instance joining the back of the queue
+    /// at the given `price` level of the `book`.
+    #[must_use]
+    pub fn new(side: OrderSide, price: Price, book: &OrderBook) -> Self {
+        let order_ids_ahead = book
+            .get_level(side, price)
+            .map(|level| level.orders.keys().copied().collect())
+            .unwrap_or_default();
+        Self {
+            side,
+            price,
+            order_ids_ahead,
+            traded_raw: 0,
+        }
+    }
+
+    /// Returns the raw size of the book orders still ahead in the queue.
+    #[must_use]
+    pub fn size_ahead_raw(&self, book: &OrderBook) -> u64 {
+        book.get_level(self.side, self.price).map_or(0, |level| {
+            level
+                .orders
+                .iter()
+                .filter(|(order_id, _)| self.order_ids_ahead.contains(order_id))
+                .map(|(_, order)| order.size.raw)
+                .sum()
+        })
+    }
+
+    /// Returns the raw size of the given `trade` which reached the simulated
+    /// order, after first consuming the size ahead in the queue (along with the
+    /// size of any prior trades at the level since the last book update).
+    ///
+    /// Trades are expected to arrive before the book updates which remove the
+    /// traded orders (as with typical MBO feeds). Trades with no aggressor side
+    /// never advance the queue, as the side of the book they traded is unknown.
+    pub fn trade_fill_raw(&mut self, book: &OrderBook, trade: &TradeTick) -> u64 {
+        if trade.price != self.price {
+            return 0;
+        }
+
+        let is_aggressor_opposite = match trade.aggressor_side {
+            AggressorSide::Buyer => self.side == OrderSide::Sell,
+            AggressorSide::Seller => self.side == OrderSide::Buy,
+            AggressorSide::NoAggressor => false,
+        };
+        if !is_aggressor_opposite {
+            return 0;
+        }
+
+        let size_ahead_raw = self.size_ahead_raw(book);
+        let reached_raw = self.traded_raw.saturating_sub(size_ahead_raw);
+        self.traded_raw += trade.size.raw;
+        self.traded_raw.saturating_sub(size_ahead_raw) - reached_raw
+    }
+
+    /// Resets the size traded at the level, once the book has been updated for
+    /// the orders which traded.
+    pub fn reset_traded(&mut self) {
+        self.traded_raw = 0;
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_model::{
+        data::order::BookOrder,
+        enums::BookType,
+        identifiers::{InstrumentId, TradeId},
+        types::quantity::Quantity,
+    };
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn book() -> OrderBook {
+        let mut book = OrderBook::new(BookType::L3_MBO, InstrumentId::from("AAPL.XNAS"));
+        for (order_id, size) in [(1, "100"), (2, "200")] {
+            let order = BookOrder::new(
+                OrderSide::Buy,
+                Price::from("100.00"),
+                Quantity::from(size),
+                order_id,
+            );
+            book.add(order, 0, order_id, order_id.into());
+        }
+        book
+    }
+
+    fn trade(price: &str, size: &str, aggressor_side: AggressorSide) -> TradeTick {
+        TradeTick::new(
+            InstrumentId::from("AAPL.XNAS"),
+            Price::from(price),
+            Quantity::from(size),
+            aggressor_side,
+            TradeId::from("1"),
+            0.into(),
+            0.into(),
+        )
+    }
+
+    #[rstest]
+    fn test_size_ahead_for_empty_level(book: OrderBook) {
+        let queue = QueuePosition::new(OrderSide::Buy, Price::from("99.00"), &book);
+
+        assert_eq!(queue.size_ahead_raw(&book), 0);
+    }
+
+    #[rstest]
+    fn test_size_ahead_excludes_orders_joining_later(mut book: OrderBook) {
+        let queue = QueuePosition::new(OrderSide::Buy, Price::from("100.00"), &book);
+        let order = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("100.00"),
+            Quantity::from("500"),
+            3,
+        );
+        book.add(order, 0, 3, 3.into());
+
+        assert_eq!(queue.size_ahead_raw(&book), Quantity::from("300").raw);
+    }
+
+    #[rstest]
+    fn test_size_ahead_reduced_by_deletes_and_updates(mut book: OrderBook) {
+        let queue = QueuePosition::new(OrderSide::Buy, Price::from("100.00"), &book);
+        let order1 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("100.00"),
+            Quantity::from("100"),
+            1,
+        );
+        let order2 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("100.00"),
+            Quantity::from("50"),
+            2,
+        );
+        book.delete(order1, 0, 3, 3.into());
+        book.update(order2, 0, 4, 4.into());
+
+        assert_eq!(queue.size_ahead_raw(&book), Quantity::from("50").raw);
+    }
+
+    #[rstest]
+    #[case("100.00", "400", AggressorSide::Seller, "100")]
+    #[case("100.00", "300", AggressorSide::Seller, "0")]
+    #[case("100.00", "400", AggressorSide::NoAggressor, "0")]
+    #[case("100.00", "400", AggressorSide::Buyer, "0")]
+    #[case("99.00", "400", AggressorSide::Seller, "0")]
+    fn test_trade_fill_raw(
+        book: OrderBook,
+        #[case] price: &str,
+        #[case] size: &str,
+        #[case] aggressor_side: AggressorSide,
+        #[case] expected: &str,
+    ) {
+        let mut queue = QueuePosition::new(OrderSide::Buy, Price::from("100.00"), &book);
+
+        let fill_raw = queue.trade_fill_raw(&book, &trade(price, size, aggressor_side));
+
+        assert_eq!(fill_raw, Quantity::from(expected).raw);
+    }
+
+    #[rstest]
+    fn test_trade_fill_raw_accumulates_trades_until_book_update(mut book: OrderBook) {
+        let mut queue = QueuePosition::new(OrderSide::Buy, Price::from("100.00"), &book);
+
+        let fill1 = queue.trade_fill_raw(&book, &trade("100.00", "200", AggressorSide::Seller));
+        let fill2 = queue.trade_fill_raw(&book, &trade("100.00", "150", AggressorSide::Seller));
+        let fill3 = queue.trade_fill_raw(&book, &trade("100.00", "20", AggressorSide::Seller));
+
+        assert_eq!(fill1, 0);
+        assert_eq!(fill2, Quantity::from("50").raw);
+        assert_eq!(fill3, Quantity::from("20").raw);
+
+        // The book removes the orders which traded
+        for (order_id, size) in [(1, "100"), (2, "200")] {
+            let order = BookOrder::new(
+                OrderSide::Buy,
+                Price::from("100.00"),
+                Quantity::from(size),
+                order_id,
+            );
+            book.delete(order, 0, order_id + 2, (order_id + 2).into());
+        }
+        queue.reset_traded();
+
+        let fill4 = queue.trade_fill_raw(&book, &trade("100.00", "30", AggressorSide::Seller));
+
+        assert_eq!(fill4, Quantity::from("30").raw);
+    }
+}
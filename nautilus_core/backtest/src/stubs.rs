@@ -0,0 +1,85 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Stubs for testing simulated venues along with an `ExecutionEngine`.
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use nautilus_common::{
+    cache::Cache,
+    msgbus::{MessageBus, MessageHandler, ShareableMessageHandler},
+};
+use nautilus_core::time::AtomicTime;
+use nautilus_execution::engine::{ExecutionEngine, ExecutionEngineConfig};
+use nautilus_model::events::order::OrderEventAny;
+use ustr::Ustr;
+
+/// A message handler which saves each order event received before passing it to the
+/// `ExecEngine.process` handler registered by an [`ExecutionEngine`].
+pub struct ExecEngineHandler {
+    _engine: Rc<RefCell<ExecutionEngine>>,
+    handler: ShareableMessageHandler,
+    events: Rc<RefCell<Vec<OrderEventAny>>>,
+}
+
+impl MessageHandler for ExecEngineHandler {
+    fn id(&self) -> Ustr {
+        self.handler.0.id()
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        if let Some(event) = message.downcast_ref::<OrderEventAny>() {
+            self.events.borrow_mut().push(event.clone());
+        }
+        self.handler.0.handle(message);
+    }
+}
+
+/// Registers an [`ExecutionEngine`] maintaining orders and positions in the `cache`
+/// on the `msgbus`, returning the engine and a shared reference to the order events
+/// received.
+pub fn register_exec_engine(
+    msgbus: &Rc<RefCell<MessageBus>>,
+    cache: Rc<RefCell<Cache>>,
+    clock: &'static AtomicTime,
+) -> (
+    Rc<RefCell<ExecutionEngine>>,
+    Rc<RefCell<Vec<OrderEventAny>>>,
+) {
+    let engine = Rc::new(RefCell::new(ExecutionEngine::new(
+        clock,
+        cache,
+        msgbus.clone(),
+        ExecutionEngineConfig::default(),
+    )));
+    ExecutionEngine::register(&engine);
+
+    let endpoint = Ustr::from("ExecEngine.process");
+    let handler = msgbus
+        .borrow()
+        .get_endpoint(&endpoint)
+        .cloned()
+        .expect("Endpoint should be registered");
+    let events = Rc::new(RefCell::new(Vec::new()));
+    let handler = ExecEngineHandler {
+        _engine: engine.clone(),
+        handler,
+        events: events.clone(),
+    };
+    msgbus
+        .borrow_mut()
+        .register(&endpoint, ShareableMessageHandler(Rc::new(handler)));
+    (engine, events)
+}
@@ -45,15 +45,16 @@ use nautilus_model::{
         OrderListId, PositionId, StrategyId, Venue, VenueOrderId,
     },
     instruments::{any::InstrumentAny, synthetic::SyntheticInstrument},
-    orderbook::book::OrderBook,
+    orderbook::{book::OrderBook, own::OwnOrderBook},
     orders::{any::OrderAny, list::OrderList},
     position::Position,
     types::{currency::Currency, price::Price, quantity::Quantity},
 };
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use ustr::Ustr;
 
-use crate::{enums::SerializationEncoding, msgbus::database::DatabaseConfig};
+use crate::{enums::SerializationEncoding, msgbus::database::DatabaseConfig, xrate};
 
 /// Configuration for `Cache` instances.
 ///
@@ -211,6 +212,7 @@ pub struct Cache {
     quotes: HashMap<InstrumentId, VecDeque<QuoteTick>>,
     trades: HashMap<InstrumentId, VecDeque<TradeTick>>,
     books: HashMap<InstrumentId, OrderBook>,
+    own_books: HashMap<InstrumentId, OwnOrderBook>,
     bars: HashMap<BarType, VecDeque<Bar>>,
     currencies: HashMap<Ustr, Currency>,
     instruments: HashMap<InstrumentId, InstrumentAny>,
@@ -272,6 +274,7 @@ impl Cache {
             quotes: HashMap::new(),
             trades: HashMap::new(),
             books: HashMap::new(),
+            own_books: HashMap::new(),
             bars: HashMap::new(),
             currencies: HashMap::new(),
             instruments: HashMap::new(),
@@ -545,7 +548,7 @@ impl Cache {
     /// All data should be loaded from the database prior to this call.
     /// If an error is found then a log error message will also be produced.
     #[must_use]
-    fn check_integrity(&mut self) -> bool {
+    pub fn check_integrity(&mut self) -> bool {
         let mut error_count = 0;
         let failure = "Integrity failure";
 
@@ -952,6 +955,7 @@ impl Cache {
         self.quotes.clear();
         self.trades.clear();
         self.books.clear();
+        self.own_books.clear();
         self.bars.clear();
         self.currencies.clear();
         self.instruments.clear();
@@ -1014,6 +1018,13 @@ impl Cache {
         Ok(())
     }
 
+    /// Adds the given own order `book` to the cache.
+    pub fn add_own_order_book(&mut self, book: OwnOrderBook) {
+        debug!("Adding `OwnOrderBook` {}", book.instrument_id);
+
+        self.own_books.insert(book.instrument_id, book);
+    }
+
     /// Adds the given `quote` tick to the cache.
     pub fn add_quote(&mut self, quote: QuoteTick) -> anyhow::Result<()> {
         debug!("Adding `QuoteTick` {}", quote.instrument_id);
@@ -1329,12 +1340,12 @@ impl Cache {
 
         // Update emulation index
         match order.emulation_trigger() {
-            Some(_) => {
-                self.index.orders_emulated.remove(&client_order_id);
-            }
-            None => {
+            Some(trigger) if trigger != TriggerType::NoTrigger && !order.is_closed() => {
                 self.index.orders_emulated.insert(client_order_id);
             }
+            _ => {
+                self.index.orders_emulated.remove(&client_order_id);
+            }
         }
 
         // Index position ID if provided
@@ -1410,6 +1421,7 @@ impl Cache {
         self.positions.insert(position.id, position.clone());
         self.index.positions.insert(position.id);
         self.index.positions_open.insert(position.id);
+        self.index.positions_closed.remove(&position.id);
 
         log::debug!("Adding {position}");
 
@@ -1488,11 +1500,13 @@ impl Cache {
         }
 
         // Update emulation
-        if let Some(emulation_trigger) = order.emulation_trigger() {
-            match emulation_trigger {
-                TriggerType::NoTrigger => self.index.orders_emulated.remove(&client_order_id),
-                _ => self.index.orders_emulated.insert(client_order_id),
-            };
+        match order.emulation_trigger() {
+            Some(trigger) if trigger != TriggerType::NoTrigger && !order.is_closed() => {
+                self.index.orders_emulated.insert(client_order_id);
+            }
+            _ => {
+                self.index.orders_emulated.remove(&client_order_id);
+            }
         }
 
         if let Some(database) = &mut self.database {
@@ -1503,9 +1517,19 @@ impl Cache {
             // }
         }
 
+        self.orders.insert(client_order_id, order.clone());
+
         Ok(())
     }
 
+    /// Updates the own order book for the given `order`, creating the book if required.
+    pub fn update_own_order_book(&mut self, order: &OrderAny) -> anyhow::Result<()> {
+        self.own_books
+            .entry(order.instrument_id())
+            .or_insert_with(|| OwnOrderBook::new(order.instrument_id()))
+            .update(order)
+    }
+
     /// Updates the given `order` as pending cancel locally.
     pub fn update_order_pending_cancel_local(&mut self, order: &OrderAny) {
         self.index
@@ -1531,6 +1555,9 @@ impl Cache {
             //     database.snapshot_order_state(order)?;
             // }
         }
+
+        self.positions.insert(position.id, position.clone());
+
         Ok(())
     }
 
@@ -2151,13 +2178,13 @@ impl Cache {
         let mut total_quantity: Option<Quantity> = None;
 
         for spawn_order in exec_spawn_orders {
-            if !active_only || !spawn_order.is_closed() {
-                if let Some(mut total_quantity) = total_quantity {
-                    total_quantity += spawn_order.quantity();
-                }
-            } else {
-                total_quantity = Some(spawn_order.quantity());
+            if active_only && spawn_order.is_closed() {
+                continue;
             }
+            total_quantity = Some(match total_quantity {
+                Some(total_quantity) => total_quantity + spawn_order.quantity(),
+                None => spawn_order.quantity(),
+            });
         }
 
         total_quantity
@@ -2175,13 +2202,13 @@ impl Cache {
         let mut total_quantity: Option<Quantity> = None;
 
         for spawn_order in exec_spawn_orders {
-            if !active_only || !spawn_order.is_closed() {
-                if let Some(mut total_quantity) = total_quantity {
-                    total_quantity += spawn_order.filled_qty();
-                }
-            } else {
-                total_quantity = Some(spawn_order.filled_qty());
+            if active_only && spawn_order.is_closed() {
+                continue;
             }
+            total_quantity = Some(match total_quantity {
+                Some(total_quantity) => total_quantity + spawn_order.filled_qty(),
+                None => spawn_order.filled_qty(),
+            });
         }
 
         total_quantity
@@ -2199,13 +2226,13 @@ impl Cache {
         let mut total_quantity: Option<Quantity> = None;
 
         for spawn_order in exec_spawn_orders {
-            if !active_only || !spawn_order.is_closed() {
-                if let Some(mut total_quantity) = total_quantity {
-                    total_quantity += spawn_order.leaves_qty();
-                }
-            } else {
-                total_quantity = Some(spawn_order.leaves_qty());
+            if active_only && spawn_order.is_closed() {
+                continue;
             }
+            total_quantity = Some(match total_quantity {
+                Some(total_quantity) => total_quantity + spawn_order.leaves_qty(),
+                None => spawn_order.leaves_qty(),
+            });
         }
 
         total_quantity
@@ -2383,6 +2410,50 @@ impl Cache {
         }
     }
 
+    /// Returns the exchange rate between the given currencies for the given `venue` (if found).
+    ///
+    /// The rate is calculated from the latest quotes of the currency pairs for the venue,
+    /// falling back to the inverse rate where only the reverse pair is quoted.
+    #[must_use]
+    pub fn get_xrate(
+        &self,
+        venue: &Venue,
+        from_currency: Currency,
+        to_currency: Currency,
+        price_type: PriceType,
+    ) -> Option<Decimal> {
+        if from_currency == to_currency {
+            return Some(Decimal::ONE);
+        }
+
+        let mut quotes_bid = HashMap::new();
+        let mut quotes_ask = HashMap::new();
+        for instrument in self.instruments(venue) {
+            if !matches!(instrument, InstrumentAny::CurrencyPair(_)) {
+                continue;
+            }
+            let instrument_id = instrument.id();
+            if let Some(quote) = self.quote_tick(&instrument_id) {
+                quotes_bid.insert(instrument_id.symbol, quote.bid_price.as_decimal());
+                quotes_ask.insert(instrument_id.symbol, quote.ask_price.as_decimal());
+            }
+        }
+
+        if quotes_bid.is_empty() {
+            return None;
+        }
+
+        let rate = |from, to| {
+            xrate::get_exchange_rate(from, to, price_type, quotes_bid.clone(), quotes_ask.clone())
+                .map_err(|e| error!("Error calculating exchange rate: {e}"))
+                .ok()
+                .filter(|rate| !rate.is_zero())
+        };
+
+        rate(from_currency, to_currency)
+            .or_else(|| rate(to_currency, from_currency).map(|rate| Decimal::ONE / rate))
+    }
+
     /// Gets all quote ticks for the given `instrument_id`.
     #[must_use]
     pub fn quote_ticks(&self, instrument_id: &InstrumentId) -> Option<Vec<QuoteTick>> {
@@ -2413,6 +2484,21 @@ impl Cache {
         self.books.get_mut(instrument_id)
     }
 
+    /// Gets a reference to the own order book for the given `instrument_id`.
+    #[must_use]
+    pub fn own_order_book(&self, instrument_id: &InstrumentId) -> Option<&OwnOrderBook> {
+        self.own_books.get(instrument_id)
+    }
+
+    /// Gets a mutable reference to the own order book for the given `instrument_id`.
+    #[must_use]
+    pub fn own_order_book_mut(
+        &mut self,
+        instrument_id: &InstrumentId,
+    ) -> Option<&mut OwnOrderBook> {
+        self.own_books.get_mut(instrument_id)
+    }
+
     /// Gets a reference to the latest quote tick for the given `instrument_id`.
     #[must_use]
     pub fn quote_tick(&self, instrument_id: &InstrumentId) -> Option<&QuoteTick> {
@@ -2577,6 +2663,15 @@ impl Cache {
             .and_then(|account_id| self.accounts.get(account_id))
     }
 
+    /// Returns a mutable reference to the account for the given `venue` (if found).
+    #[must_use]
+    pub fn account_for_venue_mut(&mut self, venue: &Venue) -> Option<&mut AccountAny> {
+        self.index
+            .venue_account
+            .get(venue)
+            .and_then(|account_id| self.accounts.get_mut(account_id))
+    }
+
     /// Returns a reference to the account ID for the given `venue` (if found).
     #[must_use]
     pub fn account_id(&self, venue: &Venue) -> Option<&AccountId> {
@@ -2602,7 +2697,7 @@ mod tests {
     use nautilus_model::{
         accounts::any::AccountAny,
         data::{bar::Bar, quote::QuoteTick, trade::TradeTick},
-        enums::{BookType, OmsType, OrderSide, OrderStatus},
+        enums::{BookType, OmsType, OrderSide, OrderStatus, PriceType},
         events::order::{OrderAccepted, OrderEventAny, OrderRejected, OrderSubmitted},
         identifiers::{AccountId, ClientOrderId, PositionId, Venue},
         instruments::{
@@ -2612,9 +2707,10 @@ mod tests {
         orderbook::book::OrderBook,
         orders::stubs::{TestOrderEventStubs, TestOrderStubs},
         position::Position,
-        types::{price::Price, quantity::Quantity},
+        types::{currency::Currency, price::Price, quantity::Quantity},
     };
     use rstest::{fixture, rstest};
+    use rust_decimal_macros::dec;
 
     use super::Cache;
 
@@ -3066,6 +3162,28 @@ mod tests {
         assert_eq!(result, Some(&mut book));
     }
 
+    #[rstest]
+    fn test_update_own_order_book(mut cache: Cache, audusd_sim: CurrencyPair) {
+        let order = TestOrderStubs::limit_order(
+            audusd_sim.id,
+            OrderSide::Buy,
+            Price::from("1.00000"),
+            Quantity::from(100_000),
+            None,
+            None,
+        );
+        let order = TestOrderStubs::make_accepted_order(&order);
+
+        cache.update_own_order_book(&order).unwrap();
+
+        let own_book = cache.own_order_book(&audusd_sim.id).unwrap();
+        assert!(own_book.contains(&order.client_order_id()));
+        assert_eq!(own_book.best_bid_price(), Some(Price::from("1.00000")));
+
+        cache.reset();
+        assert!(cache.own_order_book(&audusd_sim.id).is_none());
+    }
+
     #[rstest]
     fn test_quote_tick_when_empty(cache: Cache, audusd_sim: CurrencyPair) {
         let result = cache.quote_tick(&audusd_sim.id);
@@ -3098,6 +3216,44 @@ mod tests {
         assert_eq!(result, Some(quotes));
     }
 
+    #[rstest]
+    fn test_get_xrate(mut cache: Cache, audusd_sim: CurrencyPair) {
+        let venue = audusd_sim.id.venue;
+        cache
+            .add_instrument(InstrumentAny::CurrencyPair(audusd_sim))
+            .unwrap();
+        let quote = QuoteTick::new(
+            audusd_sim.id,
+            Price::from("0.80000"),
+            Price::from("0.80010"),
+            Quantity::from(100_000),
+            Quantity::from(100_000),
+            0.into(),
+            0.into(),
+        )
+        .unwrap();
+        cache.add_quote(quote).unwrap();
+
+        let aud = Currency::AUD();
+        let usd = Currency::USD();
+        assert_eq!(
+            cache.get_xrate(&venue, aud, usd, PriceType::Bid),
+            Some(dec!(0.80000))
+        );
+        assert_eq!(
+            cache.get_xrate(&venue, usd, aud, PriceType::Bid),
+            Some(dec!(1.25))
+        );
+        assert_eq!(
+            cache.get_xrate(&venue, usd, usd, PriceType::Mid),
+            Some(dec!(1))
+        );
+        assert_eq!(
+            cache.get_xrate(&venue, usd, Currency::JPY(), PriceType::Mid),
+            None
+        );
+    }
+
     #[rstest]
     fn test_trade_tick_when_empty(cache: Cache, audusd_sim: CurrencyPair) {
         let result = cache.trade_tick(&audusd_sim.id);
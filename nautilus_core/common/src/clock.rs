@@ -27,7 +27,7 @@ use tracing::error;
 use ustr::Ustr;
 
 use crate::{
-    handlers::EventHandler,
+    handlers::{EventHandler, SafeTimeEventCallback},
     timer::{LiveTimer, TestTimer, TimeEvent, TimeEventHandler},
 };
 
@@ -139,10 +139,13 @@ impl TestClock {
     }
 
     /// Assumes time events are sorted by their `ts_event`.
+    ///
+    /// Events handled by a native Rust callback are skipped, see [`TestClock::rust_callback`].
     #[must_use]
     pub fn match_handlers(&self, events: Vec<TimeEvent>) -> Vec<TimeEventHandler> {
         events
             .into_iter()
+            .filter(|event| self.rust_callback(event).is_none())
             .map(|event| {
                 let handler = self.callbacks.get(&event.name).cloned().unwrap_or_else(|| {
                     // If callback_py is None, use the default_callback_py
@@ -153,6 +156,19 @@ impl TestClock {
             })
             .collect()
     }
+
+    /// Returns the native Rust callback which handles the given `event` (if any).
+    ///
+    /// The callback should be called once any borrow of the clock has been released,
+    /// as callbacks may access the clock (e.g. to get the next time of their timer).
+    #[must_use]
+    pub fn rust_callback(&self, event: &TimeEvent) -> Option<SafeTimeEventCallback> {
+        self.callbacks
+            .get(&event.name)
+            .or(self.default_callback.as_ref())
+            .and_then(EventHandler::rust_callback)
+            .cloned()
+    }
 }
 
 #[cfg(not(feature = "python"))]
@@ -166,7 +182,7 @@ fn create_time_event_handler(event: TimeEvent, handler: &EventHandler) -> TimeEv
 
     TimeEventHandler {
         event,
-        callback_ptr: handler.callback.as_ptr().cast::<c_char>(),
+        callback_ptr: handler.clone().as_ptr().cast::<c_char>(),
     }
 }
 
@@ -285,7 +301,9 @@ impl Clock for TestClock {
     }
 
     fn cancel_timer(&mut self, name: &str) {
-        let timer = self.timers.remove(&Ustr::from(name));
+        let name_ustr = Ustr::from(name);
+        self.callbacks.remove(&name_ustr);
+        let timer = self.timers.remove(&name_ustr);
         match timer {
             None => {}
             Some(mut timer) => timer.cancel(),
@@ -297,12 +315,16 @@ impl Clock for TestClock {
             timer.cancel();
         }
         self.timers = HashMap::new();
+        self.callbacks = HashMap::new();
     }
 }
 
 /// A real-time clock which uses system time.
 ///
 /// Timestamps are guaranteed to be unique and monotonically increasing.
+///
+/// Timer events are handled on the runtime, so only Python callbacks are supported (setting a
+/// timer with a native Rust callback is an error, as these are `!Send`).
 pub struct LiveClock {
     time: &'static AtomicTime,
     timers: HashMap<Ustr, LiveTimer>,
@@ -17,14 +17,25 @@
 
 use std::collections::HashMap;
 
-use nautilus_core::{time::AtomicTime, uuid::UUID4};
+use nautilus_core::{
+    correctness::check_predicate_true, nanos::UnixNanos, time::AtomicTime, uuid::UUID4,
+};
 use nautilus_model::{
-    enums::{ContingencyType, OrderSide, TimeInForce},
+    enums::{
+        ContingencyType, OrderSide, OrderSideSpecified, OrderType, TimeInForce, TrailingOffsetType,
+        TriggerType,
+    },
     identifiers::{
         ClientOrderId, ExecAlgorithmId, InstrumentId, OrderListId, StrategyId, TraderId,
     },
-    orders::{any::OrderAny, market::MarketOrder},
-    types::quantity::Quantity,
+    orders::{
+        any::OrderAny, limit::LimitOrder, limit_if_touched::LimitIfTouchedOrder, list::OrderList,
+        market::MarketOrder, market_if_touched::MarketIfTouchedOrder,
+        market_to_limit::MarketToLimitOrder, stop_limit::StopLimitOrder,
+        stop_market::StopMarketOrder, trailing_stop_limit::TrailingStopLimitOrder,
+        trailing_stop_market::TrailingStopMarketOrder,
+    },
+    types::{price::Price, quantity::Quantity},
 };
 use ustr::Ustr;
 
@@ -135,6 +146,851 @@ impl OrderFactory {
         .unwrap();
         OrderAny::Market(order)
     }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn limit(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        price: Price,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        post_only: Option<bool>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        display_qty: Option<Quantity>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = LimitOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            price,
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            post_only.unwrap_or(false),
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            display_qty,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::Limit(order))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn stop_market(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        trigger_price: Price,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = StopMarketOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            None,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::StopMarket(order))
+    }
+
+    /// Creates a new stop-limit order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the limit `price` is on the wrong side of the
+    /// `trigger_price` for the `order_side` (below it for a BUY, above it for a SELL).
+    #[allow(clippy::too_many_arguments)]
+    pub fn stop_limit(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        price: Price,
+        trigger_price: Price,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        post_only: Option<bool>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        display_qty: Option<Quantity>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        check_limit_price_side(order_side, price, trigger_price)?;
+
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = StopLimitOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            price,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            post_only.unwrap_or(false),
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            display_qty,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::StopLimit(order))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn market_to_limit(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        display_qty: Option<Quantity>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = MarketToLimitOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            false,
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            display_qty,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::MarketToLimit(order))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn market_if_touched(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        trigger_price: Price,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = MarketIfTouchedOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            None,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::MarketIfTouched(order))
+    }
+
+    /// Creates a new limit-if-touched order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the limit `price` is on the wrong side of the
+    /// `trigger_price` for the `order_side` (below it for a BUY, above it for a SELL).
+    #[allow(clippy::too_many_arguments)]
+    pub fn limit_if_touched(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        price: Price,
+        trigger_price: Price,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        post_only: Option<bool>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        display_qty: Option<Quantity>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        check_limit_price_side(order_side, price, trigger_price)?;
+
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = LimitIfTouchedOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            price,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            post_only.unwrap_or(false),
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            display_qty,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::LimitIfTouched(order))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn trailing_stop_market(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        trigger_price: Price,
+        trailing_offset: Price,
+        trailing_offset_type: Option<TrailingOffsetType>,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = TrailingStopMarketOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            trailing_offset,
+            trailing_offset_type.unwrap_or(TrailingOffsetType::Price),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            None,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::TrailingStopMarket(order))
+    }
+
+    /// Creates a new trailing-stop-limit order.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the limit `price` is on the wrong side of the
+    /// `trigger_price` for the `order_side` (below it for a BUY, above it for a SELL).
+    #[allow(clippy::too_many_arguments)]
+    pub fn trailing_stop_limit(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        price: Price,
+        trigger_price: Price,
+        limit_offset: Price,
+        trailing_offset: Price,
+        trailing_offset_type: Option<TrailingOffsetType>,
+        trigger_type: Option<TriggerType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        post_only: Option<bool>,
+        reduce_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        display_qty: Option<Quantity>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        exec_algorithm_id: Option<ExecAlgorithmId>,
+        exec_algorithm_params: Option<HashMap<Ustr, Ustr>>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderAny> {
+        check_limit_price_side(order_side, price, trigger_price)?;
+
+        let client_order_id = self.generate_client_order_id();
+        let exec_spawn_id = exec_algorithm_id.map(|_| client_order_id);
+        let order = TrailingStopLimitOrder::new(
+            self.trader_id,
+            self.strategy_id,
+            instrument_id,
+            client_order_id,
+            order_side,
+            quantity,
+            price,
+            trigger_price,
+            trigger_type.unwrap_or(TriggerType::Default),
+            limit_offset,
+            trailing_offset,
+            trailing_offset_type.unwrap_or(TrailingOffsetType::Price),
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            post_only.unwrap_or(false),
+            reduce_only.unwrap_or(false),
+            quote_quantity.unwrap_or(false),
+            display_qty,
+            emulation_trigger,
+            trigger_instrument_id,
+            Some(ContingencyType::NoContingency),
+            None,
+            None,
+            None,
+            exec_algorithm_id,
+            exec_algorithm_params,
+            exec_spawn_id,
+            tags,
+            UUID4::new(),
+            self.clock.get_time_ns(),
+        )?;
+        Ok(OrderAny::TrailingStopLimit(order))
+    }
+
+    /// Creates a bracket order list of an entry order with a stop-loss (SL) and a
+    /// take-profit (TP) exit order.
+    ///
+    /// The entry order is of the given `entry_order_type` (MARKET by default), requiring an
+    /// `entry_price` for limit types and an `entry_trigger_price` for if-touched types.
+    /// The TP is a LIMIT order at `tp_price`, or a LIMIT_IF_TOUCHED order when a
+    /// `tp_trigger_price` is given. The SL is a STOP_MARKET order at `sl_trigger_price`,
+    /// or a STOP_LIMIT order when an `sl_price` is given.
+    ///
+    /// The entry is an OTO parent of both exits, which are reduce-only and linked to each
+    /// other with the given `contingency_type` (OUO by default, or OCO).
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// - The `entry_order_type` is not supported or its prices are missing.
+    /// - The `contingency_type` is not OUO or OCO.
+    /// - The SL trigger, entry and TP prices are not in order for the `order_side`.
+    /// - A limit price is on the wrong side of its trigger price.
+    #[allow(clippy::too_many_arguments)]
+    pub fn bracket(
+        &mut self,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        entry_order_type: Option<OrderType>,
+        entry_price: Option<Price>,
+        entry_trigger_price: Option<Price>,
+        tp_price: Price,
+        tp_trigger_price: Option<Price>,
+        sl_trigger_price: Price,
+        sl_price: Option<Price>,
+        contingency_type: Option<ContingencyType>,
+        time_in_force: Option<TimeInForce>,
+        expire_time: Option<UnixNanos>,
+        entry_post_only: Option<bool>,
+        tp_post_only: Option<bool>,
+        quote_quantity: Option<bool>,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        tags: Option<Vec<Ustr>>,
+    ) -> anyhow::Result<OrderList> {
+        let entry_order_type = entry_order_type.unwrap_or(OrderType::Market);
+        let contingency_type = contingency_type.unwrap_or(ContingencyType::Ouo);
+        if !matches!(
+            contingency_type,
+            ContingencyType::Oco | ContingencyType::Ouo
+        ) {
+            anyhow::bail!(
+                "Invalid `contingency_type` for bracket: {contingency_type}, expected OUO or OCO"
+            );
+        }
+
+        let exit_side = match order_side.as_specified() {
+            OrderSideSpecified::Buy => OrderSide::Sell,
+            OrderSideSpecified::Sell => OrderSide::Buy,
+        };
+        let entry_px = match entry_order_type {
+            OrderType::Market => None,
+            OrderType::Limit => Some(required(entry_price, "entry_price", entry_order_type)?),
+            OrderType::MarketIfTouched => Some(required(
+                entry_trigger_price,
+                "entry_trigger_price",
+                entry_order_type,
+            )?),
+            OrderType::LimitIfTouched => {
+                let price = required(entry_price, "entry_price", entry_order_type)?;
+                let trigger_price =
+                    required(entry_trigger_price, "entry_trigger_price", entry_order_type)?;
+                check_limit_price_side(order_side, price, trigger_price)?;
+                Some(trigger_price)
+            }
+            _ => anyhow::bail!("Invalid `entry_order_type` for bracket: {entry_order_type}"),
+        };
+
+        // The SL must be below (BUY) or above (SELL) both the entry and the TP
+        let (lower, upper) = match order_side.as_specified() {
+            OrderSideSpecified::Buy => (sl_trigger_price, tp_price),
+            OrderSideSpecified::Sell => (tp_price, sl_trigger_price),
+        };
+        if lower >= upper || entry_px.is_some_and(|px| px <= lower || px >= upper) {
+            anyhow::bail!(
+                "Invalid bracket prices for {order_side} entry: SL trigger {sl_trigger_price}, entry {}, TP {tp_price}",
+                entry_px.map_or_else(|| "MARKET".to_string(), |px| px.to_string()),
+            );
+        }
+        if let Some(sl_price) = sl_price {
+            check_limit_price_side(exit_side, sl_price, sl_trigger_price)?;
+        }
+        if let Some(tp_trigger_price) = tp_trigger_price {
+            check_limit_price_side(exit_side, tp_price, tp_trigger_price)?;
+        }
+
+        let order_list_id = self.generate_order_list_id();
+        let entry_client_order_id = self.generate_client_order_id();
+        let sl_client_order_id = self.generate_client_order_id();
+        let tp_client_order_id = self.generate_client_order_id();
+        let quote_quantity = quote_quantity.unwrap_or(false);
+        let ts_init = self.clock.get_time_ns();
+
+        let entry_order = self.bracket_order(
+            entry_client_order_id,
+            entry_order_type,
+            instrument_id,
+            order_side,
+            quantity,
+            entry_price,
+            entry_trigger_price,
+            time_in_force.unwrap_or(TimeInForce::Gtc),
+            expire_time,
+            entry_post_only.unwrap_or(false),
+            false,
+            quote_quantity,
+            emulation_trigger,
+            trigger_instrument_id,
+            ContingencyType::Oto,
+            order_list_id,
+            vec![sl_client_order_id, tp_client_order_id],
+            None,
+            tags.clone(),
+            ts_init,
+        )?;
+        let sl_order = self.bracket_order(
+            sl_client_order_id,
+            if sl_price.is_some() {
+                OrderType::StopLimit
+            } else {
+                OrderType::StopMarket
+            },
+            instrument_id,
+            exit_side,
+            quantity,
+            sl_price,
+            Some(sl_trigger_price),
+            TimeInForce::Gtc,
+            None,
+            false,
+            true,
+            quote_quantity,
+            emulation_trigger,
+            trigger_instrument_id,
+            contingency_type,
+            order_list_id,
+            vec![tp_client_order_id],
+            Some(entry_client_order_id),
+            tags.clone(),
+            ts_init,
+        )?;
+        let tp_order = self.bracket_order(
+            tp_client_order_id,
+            if tp_trigger_price.is_some() {
+                OrderType::LimitIfTouched
+            } else {
+                OrderType::Limit
+            },
+            instrument_id,
+            exit_side,
+            quantity,
+            Some(tp_price),
+            tp_trigger_price,
+            TimeInForce::Gtc,
+            None,
+            tp_post_only.unwrap_or(true),
+            true,
+            quote_quantity,
+            emulation_trigger,
+            trigger_instrument_id,
+            contingency_type,
+            order_list_id,
+            vec![sl_client_order_id],
+            Some(entry_client_order_id),
+            tags,
+            ts_init,
+        )?;
+
+        OrderList::new(
+            order_list_id,
+            instrument_id,
+            self.strategy_id,
+            vec![entry_order, sl_order, tp_order],
+            ts_init,
+        )
+    }
+
+    /// Creates a contingent order of the given `order_type` for a bracket.
+    #[allow(clippy::too_many_arguments)]
+    fn bracket_order(
+        &self,
+        client_order_id: ClientOrderId,
+        order_type: OrderType,
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        quantity: Quantity,
+        price: Option<Price>,
+        trigger_price: Option<Price>,
+        time_in_force: TimeInForce,
+        expire_time: Option<UnixNanos>,
+        post_only: bool,
+        reduce_only: bool,
+        quote_quantity: bool,
+        emulation_trigger: Option<TriggerType>,
+        trigger_instrument_id: Option<InstrumentId>,
+        contingency_type: ContingencyType,
+        order_list_id: OrderListId,
+        linked_order_ids: Vec<ClientOrderId>,
+        parent_order_id: Option<ClientOrderId>,
+        tags: Option<Vec<Ustr>>,
+        ts_init: UnixNanos,
+    ) -> anyhow::Result<OrderAny> {
+        let price = || required(price, "price", order_type);
+        let trigger_price = || required(trigger_price, "trigger_price", order_type);
+
+        let order = match order_type {
+            OrderType::Market => OrderAny::Market(MarketOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                time_in_force,
+                UUID4::new(),
+                ts_init,
+                reduce_only,
+                quote_quantity,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+            )?),
+            OrderType::Limit => OrderAny::Limit(LimitOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                price()?,
+                time_in_force,
+                expire_time,
+                post_only,
+                reduce_only,
+                quote_quantity,
+                None,
+                emulation_trigger,
+                trigger_instrument_id,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+                UUID4::new(),
+                ts_init,
+            )?),
+            OrderType::StopMarket => OrderAny::StopMarket(StopMarketOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                trigger_price()?,
+                TriggerType::Default,
+                time_in_force,
+                expire_time,
+                reduce_only,
+                quote_quantity,
+                None,
+                emulation_trigger,
+                trigger_instrument_id,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+                UUID4::new(),
+                ts_init,
+            )?),
+            OrderType::StopLimit => OrderAny::StopLimit(StopLimitOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                price()?,
+                trigger_price()?,
+                TriggerType::Default,
+                time_in_force,
+                expire_time,
+                post_only,
+                reduce_only,
+                quote_quantity,
+                None,
+                emulation_trigger,
+                trigger_instrument_id,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+                UUID4::new(),
+                ts_init,
+            )?),
+            OrderType::MarketIfTouched => OrderAny::MarketIfTouched(MarketIfTouchedOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                trigger_price()?,
+                TriggerType::Default,
+                time_in_force,
+                expire_time,
+                reduce_only,
+                quote_quantity,
+                None,
+                emulation_trigger,
+                trigger_instrument_id,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+                UUID4::new(),
+                ts_init,
+            )?),
+            OrderType::LimitIfTouched => OrderAny::LimitIfTouched(LimitIfTouchedOrder::new(
+                self.trader_id,
+                self.strategy_id,
+                instrument_id,
+                client_order_id,
+                order_side,
+                quantity,
+                price()?,
+                trigger_price()?,
+                TriggerType::Default,
+                time_in_force,
+                expire_time,
+                post_only,
+                reduce_only,
+                quote_quantity,
+                None,
+                emulation_trigger,
+                trigger_instrument_id,
+                Some(contingency_type),
+                Some(order_list_id),
+                Some(linked_order_ids),
+                parent_order_id,
+                None,
+                None,
+                None,
+                tags,
+                UUID4::new(),
+                ts_init,
+            )?),
+            _ => anyhow::bail!("Invalid `order_type` for bracket: {order_type}"),
+        };
+        Ok(order)
+    }
+}
+
+fn required(price: Option<Price>, param: &str, order_type: OrderType) -> anyhow::Result<Price> {
+    price.ok_or_else(|| anyhow::anyhow!("`{param}` required for {order_type} order"))
+}
+
+/// Checks the limit `price` is not through the `trigger_price` for the `order_side`,
+/// i.e. at or above it for a BUY and at or below it for a SELL.
+fn check_limit_price_side(
+    order_side: OrderSide,
+    price: Price,
+    trigger_price: Price,
+) -> anyhow::Result<()> {
+    let valid = match order_side.as_specified() {
+        OrderSideSpecified::Buy => price >= trigger_price,
+        OrderSideSpecified::Sell => price <= trigger_price,
+    };
+    check_predicate_true(
+        valid,
+        &format!(
+            "invalid `price` {price} for {order_side} order with `trigger_price` {trigger_price}"
+        ),
+    )
 }
 
 ////////////////////////////////////////////////////////////////////////////////
@@ -144,11 +1000,12 @@ impl OrderFactory {
 pub mod tests {
     use nautilus_core::time::get_atomic_clock_static;
     use nautilus_model::{
-        enums::{OrderSide, TimeInForce},
+        enums::{ContingencyType, OrderSide, OrderType, TimeInForce},
         identifiers::{
             stubs::{strategy_id_ema_cross, trader_id},
             ClientOrderId, InstrumentId, OrderListId,
         },
+        types::price::Price,
     };
     use rstest::{fixture, rstest};
 
@@ -252,4 +1109,394 @@ pub mod tests {
         );
         // assert_eq!(market_order.order_list_id(), None);
     }
+
+    #[rstest]
+    fn test_limit_order(mut order_factory: OrderFactory) {
+        let order = order_factory
+            .limit(
+                InstrumentId::from("BTCUSDT.BINANCE"),
+                OrderSide::Sell,
+                100.into(),
+                Price::from("50000.00"),
+                None,
+                None,
+                Some(true),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(order.order_type(), OrderType::Limit);
+        assert_eq!(order.order_side(), OrderSide::Sell);
+        assert_eq!(order.price(), Some(Price::from("50000.00")));
+        assert_eq!(order.time_in_force(), TimeInForce::Gtc);
+        assert_eq!(
+            order.contingency_type(),
+            Some(ContingencyType::NoContingency)
+        );
+        assert_eq!(
+            order.client_order_id(),
+            ClientOrderId::new("O-19700101-000000-001-001-1").unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_stop_market_order(mut order_factory: OrderFactory) {
+        let order = order_factory
+            .stop_market(
+                InstrumentId::from("BTCUSDT.BINANCE"),
+                OrderSide::Buy,
+                100.into(),
+                Price::from("51000.00"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(order.order_type(), OrderType::StopMarket);
+        assert_eq!(order.trigger_price(), Some(Price::from("51000.00")));
+        assert_eq!(order.price(), None);
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, "51010.00", "51000.00", true)]
+    #[case(OrderSide::Buy, "50990.00", "51000.00", false)]
+    #[case(OrderSide::Sell, "49990.00", "50000.00", true)]
+    #[case(OrderSide::Sell, "50010.00", "50000.00", false)]
+    fn test_stop_limit_order_validates_price_side(
+        mut order_factory: OrderFactory,
+        #[case] order_side: OrderSide,
+        #[case] price: &str,
+        #[case] trigger_price: &str,
+        #[case] expected_ok: bool,
+    ) {
+        let result = order_factory.stop_limit(
+            InstrumentId::from("BTCUSDT.BINANCE"),
+            order_side,
+            100.into(),
+            Price::from(price),
+            Price::from(trigger_price),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert_eq!(result.is_ok(), expected_ok);
+        if let Ok(order) = result {
+            assert_eq!(order.order_type(), OrderType::StopLimit);
+            assert_eq!(order.trigger_price(), Some(Price::from(trigger_price)));
+        }
+    }
+
+    #[rstest]
+    fn test_market_to_limit_order(mut order_factory: OrderFactory) {
+        let order = order_factory
+            .market_to_limit(
+                InstrumentId::from("BTCUSDT.BINANCE"),
+                OrderSide::Buy,
+                100.into(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(order.order_type(), OrderType::MarketToLimit);
+        assert_eq!(order.price(), None);
+    }
+
+    #[rstest]
+    fn test_if_touched_orders(mut order_factory: OrderFactory) {
+        let instrument_id = InstrumentId::from("BTCUSDT.BINANCE");
+        let mit = order_factory
+            .market_if_touched(
+                instrument_id,
+                OrderSide::Buy,
+                100.into(),
+                Price::from("49000.00"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let lit = order_factory
+            .limit_if_touched(
+                instrument_id,
+                OrderSide::Buy,
+                100.into(),
+                Price::from("49010.00"),
+                Price::from("49000.00"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(mit.order_type(), OrderType::MarketIfTouched);
+        assert_eq!(lit.order_type(), OrderType::LimitIfTouched);
+        assert_eq!(lit.price(), Some(Price::from("49010.00")));
+        assert_eq!(
+            lit.client_order_id(),
+            ClientOrderId::new("O-19700101-000000-001-001-2").unwrap()
+        );
+    }
+
+    #[rstest]
+    fn test_trailing_stop_orders(mut order_factory: OrderFactory) {
+        let instrument_id = InstrumentId::from("BTCUSDT.BINANCE");
+        let tsm = order_factory
+            .trailing_stop_market(
+                instrument_id,
+                OrderSide::Sell,
+                100.into(),
+                Price::from("49000.00"),
+                Price::from("100.00"),
+                None,
+                None,
+                None,
+                None,
+                Some(true),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+        let tsl = order_factory
+            .trailing_stop_limit(
+                instrument_id,
+                OrderSide::Sell,
+                100.into(),
+                Price::from("48990.00"),
+                Price::from("49000.00"),
+                Price::from("10.00"),
+                Price::from("100.00"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(tsm.order_type(), OrderType::TrailingStopMarket);
+        assert!(tsm.is_reduce_only());
+        assert_eq!(tsl.order_type(), OrderType::TrailingStopLimit);
+        assert_eq!(tsl.price(), Some(Price::from("48990.00")));
+    }
+
+    #[rstest]
+    fn test_bracket_with_market_entry(mut order_factory: OrderFactory) {
+        let order_list = order_factory
+            .bracket(
+                InstrumentId::from("BTCUSDT.BINANCE"),
+                OrderSide::Buy,
+                100.into(),
+                None,
+                None,
+                None,
+                Price::from("51000.00"),
+                None,
+                Price::from("49000.00"),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let [entry, sl, tp] = order_list.orders.as_slice() else {
+            panic!("Expected three orders, was {}", order_list.orders.len());
+        };
+        assert_eq!(
+            order_list.id,
+            OrderListId::new("OL-19700101-000000-001-001-1").unwrap()
+        );
+        assert_eq!(entry.order_type(), OrderType::Market);
+        assert_eq!(sl.order_type(), OrderType::StopMarket);
+        assert_eq!(tp.order_type(), OrderType::Limit);
+        assert_eq!(sl.order_side(), OrderSide::Sell);
+        assert_eq!(tp.order_side(), OrderSide::Sell);
+        assert!(sl.is_reduce_only() && tp.is_reduce_only());
+        assert_eq!(entry.contingency_type(), Some(ContingencyType::Oto));
+        assert_eq!(sl.contingency_type(), Some(ContingencyType::Ouo));
+        assert_eq!(tp.contingency_type(), Some(ContingencyType::Ouo));
+        assert_eq!(
+            entry.linked_order_ids(),
+            Some([sl.client_order_id(), tp.client_order_id()].as_slice())
+        );
+        assert_eq!(
+            sl.linked_order_ids(),
+            Some([tp.client_order_id()].as_slice())
+        );
+        assert_eq!(
+            tp.linked_order_ids(),
+            Some([sl.client_order_id()].as_slice())
+        );
+        assert_eq!(entry.parent_order_id(), None);
+        assert_eq!(sl.parent_order_id(), Some(entry.client_order_id()));
+        assert_eq!(tp.parent_order_id(), Some(entry.client_order_id()));
+        for order in &order_list.orders {
+            assert_eq!(order.order_list_id(), Some(order_list.id));
+        }
+    }
+
+    #[rstest]
+    fn test_bracket_with_limit_entry_and_stop_limit_sl(mut order_factory: OrderFactory) {
+        let order_list = order_factory
+            .bracket(
+                InstrumentId::from("BTCUSDT.BINANCE"),
+                OrderSide::Sell,
+                100.into(),
+                Some(OrderType::Limit),
+                Some(Price::from("50000.00")),
+                None,
+                Price::from("49000.00"),
+                None,
+                Price::from("51000.00"),
+                Some(Price::from("51010.00")),
+                Some(ContingencyType::Oco),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+
+        let [entry, sl, tp] = order_list.orders.as_slice() else {
+            panic!("Expected three orders, was {}", order_list.orders.len());
+        };
+        assert_eq!(entry.order_type(), OrderType::Limit);
+        assert_eq!(entry.price(), Some(Price::from("50000.00")));
+        assert_eq!(sl.order_type(), OrderType::StopLimit);
+        assert_eq!(sl.order_side(), OrderSide::Buy);
+        assert_eq!(tp.contingency_type(), Some(ContingencyType::Oco));
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, Some("52000.00"), "51000.00", "49000.00")] // Entry above TP
+    #[case(OrderSide::Buy, None, "49000.00", "51000.00")] // SL above TP
+    #[case(OrderSide::Sell, Some("50000.00"), "51000.00", "49000.00")] // TP above entry
+    fn test_bracket_with_invalid_prices(
+        mut order_factory: OrderFactory,
+        #[case] order_side: OrderSide,
+        #[case] entry_price: Option<&str>,
+        #[case] tp_price: &str,
+        #[case] sl_trigger_price: &str,
+    ) {
+        let result = order_factory.bracket(
+            InstrumentId::from("BTCUSDT.BINANCE"),
+            order_side,
+            100.into(),
+            entry_price.map(|_| OrderType::Limit),
+            entry_price.map(Price::from),
+            None,
+            Price::from(tp_price),
+            None,
+            Price::from(sl_trigger_price),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_bracket_with_invalid_contingency_type(mut order_factory: OrderFactory) {
+        let result = order_factory.bracket(
+            InstrumentId::from("BTCUSDT.BINANCE"),
+            OrderSide::Buy,
+            100.into(),
+            None,
+            None,
+            None,
+            Price::from("51000.00"),
+            None,
+            Price::from("49000.00"),
+            None,
+            Some(ContingencyType::Oto),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+
+        assert!(result.is_err());
+    }
 }
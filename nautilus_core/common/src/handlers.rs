@@ -17,7 +17,9 @@
 
 #[cfg(not(feature = "python"))]
 use std::ffi::c_char;
-use std::{fmt::Debug, sync::Arc};
+#[cfg(not(feature = "python"))]
+use std::sync::Arc;
+use std::{fmt::Debug, rc::Rc};
 
 #[cfg(not(feature = "python"))]
 use nautilus_core::message::Message;
@@ -39,13 +41,35 @@ pub struct SafeMessageCallback {
 unsafe impl Send for SafeMessageCallback {}
 unsafe impl Sync for SafeMessageCallback {}
 
+/// A native Rust callback for time events.
+///
+/// The callback is `!Send`, so can only be called from the thread which owns the clock it was
+/// registered with (native Rust callbacks are not supported by a `LiveClock`).
 #[derive(Clone)]
 pub struct SafeTimeEventCallback {
-    pub callback: Arc<dyn Fn(TimeEvent) + Send>,
+    pub callback: Rc<dyn Fn(TimeEvent)>,
 }
 
-unsafe impl Send for SafeTimeEventCallback {}
-unsafe impl Sync for SafeTimeEventCallback {}
+impl SafeTimeEventCallback {
+    /// Creates a new [`SafeTimeEventCallback`] instance.
+    #[must_use]
+    pub fn new(callback: Rc<dyn Fn(TimeEvent)>) -> Self {
+        Self { callback }
+    }
+
+    /// Calls the callback with the given `event`.
+    pub fn call(&self, event: TimeEvent) {
+        (self.callback)(event);
+    }
+}
+
+/// Represents a callback for time events, either a Python callable or a native Rust closure.
+#[derive(Clone)]
+pub enum TimeEventCallback {
+    #[cfg(feature = "python")]
+    Python(PyObject),
+    Rust(SafeTimeEventCallback),
+}
 
 // TODO: Make this more generic
 #[derive(Clone)]
@@ -85,26 +109,53 @@ impl Debug for MessageHandler {
 #[derive(Clone)]
 #[cfg_attr(
     feature = "python",
-    pyo3::pyclass(module = "nautilus_trader.core.nautilus_pyo3.common")
+    pyo3::pyclass(module = "nautilus_trader.core.nautilus_pyo3.common", unsendable)
 )]
 pub struct EventHandler {
-    #[cfg(not(feature = "python"))]
-    pub callback: SafeTimeEventCallback,
-    #[cfg(feature = "python")]
-    pub callback: PyObject,
+    pub callback: TimeEventCallback,
 }
 
 impl EventHandler {
     #[cfg(not(feature = "python"))]
     #[must_use]
     pub fn new(callback: SafeTimeEventCallback) -> Self {
-        Self { callback }
+        Self::from_rust(callback)
     }
 
     #[cfg(feature = "python")]
     #[must_use]
     pub const fn new(callback: PyObject) -> Self {
-        Self { callback }
+        Self {
+            callback: TimeEventCallback::Python(callback),
+        }
+    }
+
+    /// Creates a new [`EventHandler`] instance from a native Rust callback.
+    #[must_use]
+    pub fn from_rust(callback: SafeTimeEventCallback) -> Self {
+        Self {
+            callback: TimeEventCallback::Rust(callback),
+        }
+    }
+
+    /// Returns the native Rust callback for the handler (if any).
+    #[must_use]
+    pub fn rust_callback(&self) -> Option<&SafeTimeEventCallback> {
+        match &self.callback {
+            #[cfg(feature = "python")]
+            TimeEventCallback::Python(_) => None,
+            TimeEventCallback::Rust(callback) => Some(callback),
+        }
+    }
+
+    /// Returns the Python callback for the handler (if any).
+    #[cfg(feature = "python")]
+    #[must_use]
+    pub fn python_callback(&self) -> Option<&PyObject> {
+        match &self.callback {
+            TimeEventCallback::Python(callback) => Some(callback),
+            TimeEventCallback::Rust(_) => None,
+        }
     }
 
     #[cfg(not(feature = "python"))]
@@ -117,6 +168,9 @@ impl EventHandler {
     #[cfg(feature = "python")]
     #[must_use]
     pub fn as_ptr(self) -> *mut pyo3::ffi::PyObject {
-        self.callback.as_ptr()
+        match self.callback {
+            TimeEventCallback::Python(callback) => callback.as_ptr(),
+            TimeEventCallback::Rust(_) => std::ptr::null_mut(),
+        }
     }
 }
@@ -17,6 +17,9 @@
 
 pub mod database;
 
+#[cfg(feature = "stubs")]
+pub mod stubs;
+
 use std::{
     any::Any,
     collections::HashMap,
@@ -0,0 +1,54 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Message handler stubs for testing components which communicate over the `MessageBus`.
+
+use std::{any::Any, cell::RefCell, rc::Rc};
+
+use ustr::Ustr;
+
+use super::{MessageHandler, ShareableMessageHandler};
+
+/// A message handler which saves all received messages of type `T`.
+pub struct MessageSavingHandler<T> {
+    id: Ustr,
+    messages: Rc<RefCell<Vec<T>>>,
+}
+
+impl<T: Clone + 'static> MessageHandler for MessageSavingHandler<T> {
+    fn id(&self) -> Ustr {
+        self.id
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        if let Some(message) = message.downcast_ref::<T>() {
+            self.messages.borrow_mut().push(message.clone());
+        }
+    }
+}
+
+/// Returns a shareable handler which saves messages of type `T`, along with
+/// a shared reference to the saved messages.
+#[must_use]
+pub fn get_message_saving_handler<T: Clone + 'static>(
+    id: Option<Ustr>,
+) -> (ShareableMessageHandler, Rc<RefCell<Vec<T>>>) {
+    let messages = Rc::new(RefCell::new(Vec::new()));
+    let handler = MessageSavingHandler {
+        id: id.unwrap_or_else(|| Ustr::from("message-saving-handler")),
+        messages: messages.clone(),
+    };
+    (ShareableMessageHandler(Rc::new(handler)), messages)
+}
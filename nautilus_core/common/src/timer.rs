@@ -36,7 +36,9 @@ use tokio::{
     sync::oneshot,
     time::{Duration, Instant},
 };
-use tracing::{debug, error, trace};
+#[cfg(feature = "python")]
+use tracing::error;
+use tracing::{debug, trace};
 use ustr::Ustr;
 
 use crate::{handlers::EventHandler, runtime::get_runtime};
@@ -230,7 +232,8 @@ pub struct LiveTimer {
     pub stop_time_ns: Option<UnixNanos>,
     next_time_ns: Arc<AtomicU64>,
     is_expired: Arc<AtomicBool>,
-    callback: EventHandler,
+    #[cfg(feature = "python")]
+    callback: PyObject,
     canceler: Option<oneshot::Sender<()>>,
 }
 
@@ -244,6 +247,16 @@ impl LiveTimer {
         callback: EventHandler,
     ) -> anyhow::Result<Self> {
         check_valid_string(name, stringify!(name))?;
+        // Native Rust callbacks are not `Send`, so cannot be called from the runtime
+        #[cfg(feature = "python")]
+        let callback = callback.python_callback().cloned().ok_or_else(|| {
+            anyhow::anyhow!("Native Rust callbacks are not supported by `LiveTimer`")
+        })?;
+        #[cfg(not(feature = "python"))]
+        anyhow::ensure!(
+            callback.rust_callback().is_none(),
+            "Native Rust callbacks are not supported by `LiveTimer`"
+        );
         // SAFETY: Guaranteed to be non-zero
         let interval_ns = NonZeroU64::new(std::cmp::max(interval_ns, 1)).unwrap();
 
@@ -255,6 +268,7 @@ impl LiveTimer {
             stop_time_ns,
             next_time_ns: Arc::new(AtomicU64::new(start_time_ns.as_u64() + interval_ns.get())),
             is_expired: Arc::new(AtomicBool::new(false)),
+            #[cfg(feature = "python")]
             callback,
             canceler: None,
         })
@@ -274,12 +288,14 @@ impl LiveTimer {
 
     /// Starts the timer.
     pub fn start(&mut self) {
+        #[cfg(feature = "python")]
         let event_name = self.name;
         let stop_time_ns = self.stop_time_ns;
         let next_time_ns = self.next_time_ns.load(atomic::Ordering::SeqCst);
         let next_time_atomic = self.next_time_ns.clone();
         let interval_ns = self.interval_ns.get();
         let is_expired = self.is_expired.clone();
+        #[cfg(feature = "python")]
         let callback = self.callback.clone();
 
         // Floor the next time to the nearest microsecond which is within the timers accuracy
@@ -311,7 +327,11 @@ impl LiveTimer {
                 tokio::select! {
                     _ = timer.tick() => {
                         let now_ns = clock.get_time_ns();
-                        call_python_with_time_event(event_name, next_time_ns, now_ns, &callback);
+                        #[cfg(feature = "python")]
+                        call_python_with_time_event(
+                            &callback,
+                            TimeEvent::new(event_name, UUID4::new(), next_time_ns, now_ns),
+                        );
 
                         // Prepare next time interval
                         next_time_ns += interval_ns;
@@ -351,36 +371,19 @@ impl LiveTimer {
 }
 
 #[cfg(feature = "python")]
-fn call_python_with_time_event(
-    name: Ustr,
-    ts_event: UnixNanos,
-    ts_init: UnixNanos,
-    handler: &EventHandler,
-) {
+fn call_python_with_time_event(callback: &PyObject, event: TimeEvent) {
     Python::with_gil(|py| {
-        // Create new time event
-        let event = TimeEvent::new(name, UUID4::new(), ts_event, ts_init);
         let capsule: PyObject = PyCapsule::new_bound(py, event, None)
             .expect("Error creating `PyCapsule`")
             .into_py(py);
 
-        match handler.callback.call1(py, (capsule,)) {
+        match callback.call1(py, (capsule,)) {
             Ok(_) => {}
             Err(e) => error!("Error on callback: {:?}", e),
         };
     });
 }
 
-#[cfg(not(feature = "python"))]
-fn call_python_with_time_event(
-    _name: Ustr,
-    _ts_event: UnixNanos,
-    _ts_init: UnixNanos,
-    _handler: EventHandler,
-) {
-    panic!("`python` feature is not enabled");
-}
-
 ////////////////////////////////////////////////////////////////////////////////
 // Tests
 ////////////////////////////////////////////////////////////////////////////////
@@ -393,8 +396,13 @@ mod tests {
     use rstest::*;
     use tokio::time::Duration;
 
+    use std::rc::Rc;
+
     use super::{LiveTimer, TestTimer, TimeEvent};
-    use crate::{handlers::EventHandler, testing::wait_until};
+    use crate::{
+        handlers::{EventHandler, SafeTimeEventCallback},
+        testing::wait_until,
+    };
 
     #[pyfunction]
     const fn receive_event(_py: Python, _event: TimeEvent) -> PyResult<()> {
@@ -469,6 +477,14 @@ mod tests {
         assert!(timer.is_expired);
     }
 
+    #[rstest]
+    fn test_live_timer_rejects_rust_callback() {
+        let handler = EventHandler::from_rust(SafeTimeEventCallback::new(Rc::new(|_| {})));
+        let result = LiveTimer::new("TEST_TIMER", 1, UnixNanos::default(), None, handler);
+
+        assert!(result.is_err());
+    }
+
     #[tokio::test]
     async fn test_live_timer_starts_and_stops() {
         pyo3::prepare_freethreaded_python();
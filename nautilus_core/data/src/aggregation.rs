@@ -19,10 +19,10 @@
 #![allow(dead_code)]
 #![allow(unused_variables)]
 
-use std::ops::Add;
+use std::{cell::RefCell, ops::Add, rc::Rc};
 
 use chrono::TimeDelta;
-use nautilus_common::{clock::Clock, timer::TimeEvent};
+use nautilus_common::{clock::Clock, handlers::EventHandler, timer::TimeEvent};
 use nautilus_core::{correctness, nanos::UnixNanos};
 use nautilus_model::{
     data::{
@@ -30,7 +30,7 @@ use nautilus_model::{
         quote::QuoteTick,
         trade::TradeTick,
     },
-    enums::AggregationSource,
+    enums::{AggregationSource, AggressorSide, BarAggregation},
     instruments::any::InstrumentAny,
     types::{fixed::FIXED_SCALAR, price::Price, quantity::Quantity},
 };
@@ -38,6 +38,14 @@ use nautilus_model::{
 pub trait BarAggregator {
     fn bar_type(&self) -> BarType;
     fn update(&mut self, price: Price, size: Quantity, ts_event: UnixNanos);
+    /// Start the aggregator, with the `handler` for its timer events (if driven by timers).
+    fn start(&mut self, handler: EventHandler) -> anyhow::Result<()> {
+        Ok(())
+    }
+    /// Stop the aggregator (if driven by timers).
+    fn stop(&mut self) {}
+    /// Handle a time event from the aggregator timer (if driven by timers).
+    fn on_time_event(&mut self, event: TimeEvent) {}
     /// Update the aggregator with the given quote.
     fn handle_quote_tick(&mut self, quote: QuoteTick) {
         self.update(
@@ -158,6 +166,14 @@ impl BarBuilder {
             }
         }
 
+        // Mid sizes carry one more digit than the instrument, so round them
+        // back to the instrument precision before accumulating volume
+        let size = if size.precision > self.size_precision {
+            Quantity::new(size.as_f64(), self.size_precision).unwrap()
+        } else {
+            size
+        };
+
         self.close = Some(price);
         self.volume = self.volume.add(size);
         self.count += 1;
@@ -211,7 +227,7 @@ impl BarBuilder {
 pub struct BarAggregatorCore {
     bar_type: BarType,
     builder: BarBuilder,
-    handler: fn(Bar),
+    handler: Box<dyn FnMut(Bar)>,
     await_partial: bool,
 }
 
@@ -225,7 +241,7 @@ impl BarAggregatorCore {
     pub fn new(
         instrument: &InstrumentAny,
         bar_type: BarType,
-        handler: fn(Bar),
+        handler: Box<dyn FnMut(Bar)>,
         await_partial: bool,
     ) -> Self {
         Self {
@@ -278,7 +294,7 @@ impl TickBarAggregator {
     pub fn new(
         instrument: &InstrumentAny,
         bar_type: BarType,
-        handler: fn(Bar),
+        handler: Box<dyn FnMut(Bar)>,
         await_partial: bool,
     ) -> Self {
         Self {
@@ -317,7 +333,7 @@ impl VolumeBarAggregator {
     pub fn new(
         instrument: &InstrumentAny,
         bar_type: BarType,
-        handler: fn(Bar),
+        handler: Box<dyn FnMut(Bar)>,
         await_partial: bool,
     ) -> Self {
         Self {
@@ -380,7 +396,7 @@ impl ValueBarAggregator {
     pub fn new(
         instrument: &InstrumentAny,
         bar_type: BarType,
-        handler: fn(Bar),
+        handler: Box<dyn FnMut(Bar)>,
         await_partial: bool,
     ) -> Self {
         Self {
@@ -432,15 +448,327 @@ impl BarAggregator for ValueBarAggregator {
     }
 }
 
+/// Provides an exponentially weighted moving average for the expected thresholds
+/// of information-driven bars.
+#[derive(Clone, Copy, Debug)]
+struct Ewma {
+    alpha: f64,
+    value: Option<f64>,
+}
+
+impl Ewma {
+    const fn new(alpha: f64) -> Self {
+        Self { alpha, value: None }
+    }
+
+    fn update(&mut self, x: f64) {
+        self.value = Some(match self.value {
+            Some(value) => self.alpha.mul_add(x, (1.0 - self.alpha) * value),
+            None => x,
+        });
+    }
+}
+
+/// Signs ticks for information-driven bars.
+///
+/// A tick is signed by the aggressor side of the trade, otherwise by the tick rule
+/// (the sign of the price change, or the previous sign when the price is unchanged).
+#[derive(Clone, Copy, Debug)]
+struct TickSigner {
+    last_price: Option<Price>,
+    last_sign: f64,
+}
+
+impl TickSigner {
+    const fn new() -> Self {
+        Self {
+            last_price: None,
+            last_sign: 1.0,
+        }
+    }
+
+    fn sign(&mut self, price: Price, aggressor_side: AggressorSide) -> f64 {
+        let sign = match aggressor_side {
+            AggressorSide::Buyer => 1.0,
+            AggressorSide::Seller => -1.0,
+            AggressorSide::NoAggressor => match self.last_price {
+                Some(last_price) if price > last_price => 1.0,
+                Some(last_price) if price < last_price => -1.0,
+                _ => self.last_sign,
+            },
+        };
+        self.last_price = Some(price);
+        self.last_sign = sign;
+        sign
+    }
+}
+
+/// Returns the information measure of a tick for the given information-driven `aggregation`.
+fn information_value(aggregation: BarAggregation, price: Price, size: Quantity) -> f64 {
+    match aggregation {
+        BarAggregation::TickImbalance | BarAggregation::TickRuns => 1.0,
+        BarAggregation::VolumeImbalance | BarAggregation::VolumeRuns => size.as_f64(),
+        BarAggregation::ValueImbalance | BarAggregation::ValueRuns => {
+            price.as_f64() * size.as_f64()
+        }
+        _ => panic!("Invalid information-driven aggregation, was {aggregation}"),
+    }
+}
+
+/// Returns the EWMA smoothing factor for the information-driven `bar_type`, using the
+/// bar step as the span.
+fn information_ewma_alpha(bar_type: &BarType) -> f64 {
+    2.0 / (bar_type.spec.step as f64 + 1.0)
+}
+
+/// Provides a means of building tick, volume and value imbalance bars aggregated from
+/// trade ticks, as defined by López de Prado in "Advances in Financial Machine Learning".
+///
+/// Each tick contributes its signed measure (one per tick, the volume or the value) to
+/// the imbalance of the bar. When the absolute imbalance reaches the expected imbalance
+/// `E[T] * |E[b * v]|`, then a bar is created and sent to the handler. The expected number
+/// of ticks per bar `E[T]` starts at the step of the bar specification, and both
+/// expectations are exponentially weighted moving averages with the step as the span.
+pub struct ImbalanceBarAggregator {
+    core: BarAggregatorCore,
+    signer: TickSigner,
+    imbalance: f64,
+    ticks: usize,
+    expected_ticks: Ewma,
+    expected_imbalance: Ewma,
+}
+
+impl ImbalanceBarAggregator {
+    /// Creates a new [`ImbalanceBarAggregator`] instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `instrument.id` is not equal to the `bar_type.instrument_id`.
+    /// Panics if `bar_type.aggregation_source` is not equal to `AggregationSource::Internal`.
+    /// Panics if `bar_type.spec.aggregation` is not an imbalance aggregation.
+    pub fn new(
+        instrument: &InstrumentAny,
+        bar_type: BarType,
+        handler: Box<dyn FnMut(Bar)>,
+        await_partial: bool,
+    ) -> Self {
+        correctness::check_predicate_true(
+            matches!(
+                bar_type.spec.aggregation,
+                BarAggregation::TickImbalance
+                    | BarAggregation::VolumeImbalance
+                    | BarAggregation::ValueImbalance
+            ),
+            "`bar_type.spec.aggregation` was not an imbalance aggregation",
+        )
+        .unwrap();
+
+        let alpha = information_ewma_alpha(&bar_type);
+        let mut expected_ticks = Ewma::new(alpha);
+        expected_ticks.update(bar_type.spec.step as f64);
+
+        Self {
+            core: BarAggregatorCore::new(instrument, bar_type, handler, await_partial),
+            signer: TickSigner::new(),
+            imbalance: 0.0,
+            ticks: 0,
+            expected_ticks,
+            expected_imbalance: Ewma::new(alpha),
+        }
+    }
+
+    /// Returns the imbalance of the bar being built.
+    #[must_use]
+    pub const fn imbalance(&self) -> f64 {
+        self.imbalance
+    }
+
+    /// Returns the absolute imbalance at which the next bar will be built.
+    #[must_use]
+    pub fn expected_imbalance(&self) -> f64 {
+        let expected_ticks = self.expected_ticks.value.unwrap_or_default();
+        let expected_imbalance = self.expected_imbalance.value.unwrap_or_default();
+        expected_ticks * expected_imbalance.abs()
+    }
+
+    fn apply_signed_update(
+        &mut self,
+        price: Price,
+        size: Quantity,
+        ts_event: UnixNanos,
+        sign: f64,
+    ) {
+        let signed_value =
+            sign * information_value(self.core.bar_type.spec.aggregation, price, size);
+
+        self.core.apply_update(price, size, ts_event);
+        self.imbalance += signed_value;
+        self.ticks += 1;
+        self.expected_imbalance.update(signed_value);
+
+        if self.imbalance.abs() >= self.expected_imbalance() {
+            self.core.build_now_and_send();
+            self.expected_ticks.update(self.ticks as f64);
+            self.imbalance = 0.0;
+            self.ticks = 0;
+        }
+    }
+}
+
+impl BarAggregator for ImbalanceBarAggregator {
+    fn bar_type(&self) -> BarType {
+        self.core.bar_type
+    }
+
+    /// Apply the given update to the aggregator, signing the tick by the tick rule.
+    fn update(&mut self, price: Price, size: Quantity, ts_event: UnixNanos) {
+        let sign = self.signer.sign(price, AggressorSide::NoAggressor);
+        self.apply_signed_update(price, size, ts_event, sign);
+    }
+
+    /// Update the aggregator with the given trade, signing the tick by the aggressor side.
+    fn handle_trade_tick(&mut self, trade: TradeTick) {
+        let sign = self.signer.sign(trade.price, trade.aggressor_side);
+        self.apply_signed_update(trade.price, trade.size, trade.ts_event, sign);
+    }
+}
+
+/// Provides a means of building tick, volume and value runs bars aggregated from
+/// trade ticks, as defined by López de Prado in "Advances in Financial Machine Learning".
+///
+/// The measures (one per tick, the volume or the value) of buy and sell ticks are summed
+/// separately for the bar. When the larger of the two runs reaches the expected run
+/// `E[T] * max(P[b = 1] * E[v | b = 1], (1 - P[b = 1]) * E[v | b = -1])`, then a bar is
+/// created and sent to the handler. The expected number of ticks per bar `E[T]` starts
+/// at the step of the bar specification, and all expectations are exponentially weighted
+/// moving averages with the step as the span.
+pub struct RunsBarAggregator {
+    core: BarAggregatorCore,
+    signer: TickSigner,
+    buy_run: f64,
+    sell_run: f64,
+    ticks: usize,
+    expected_ticks: Ewma,
+    expected_buy_proportion: Ewma,
+    expected_buy_value: Ewma,
+    expected_sell_value: Ewma,
+}
+
+impl RunsBarAggregator {
+    /// Creates a new [`RunsBarAggregator`] instance.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `instrument.id` is not equal to the `bar_type.instrument_id`.
+    /// Panics if `bar_type.aggregation_source` is not equal to `AggregationSource::Internal`.
+    /// Panics if `bar_type.spec.aggregation` is not a runs aggregation.
+    pub fn new(
+        instrument: &InstrumentAny,
+        bar_type: BarType,
+        handler: Box<dyn FnMut(Bar)>,
+        await_partial: bool,
+    ) -> Self {
+        correctness::check_predicate_true(
+            matches!(
+                bar_type.spec.aggregation,
+                BarAggregation::TickRuns | BarAggregation::VolumeRuns | BarAggregation::ValueRuns
+            ),
+            "`bar_type.spec.aggregation` was not a runs aggregation",
+        )
+        .unwrap();
+
+        let alpha = information_ewma_alpha(&bar_type);
+        let mut expected_ticks = Ewma::new(alpha);
+        expected_ticks.update(bar_type.spec.step as f64);
+
+        Self {
+            core: BarAggregatorCore::new(instrument, bar_type, handler, await_partial),
+            signer: TickSigner::new(),
+            buy_run: 0.0,
+            sell_run: 0.0,
+            ticks: 0,
+            expected_ticks,
+            expected_buy_proportion: Ewma::new(alpha),
+            expected_buy_value: Ewma::new(alpha),
+            expected_sell_value: Ewma::new(alpha),
+        }
+    }
+
+    /// Returns the buy and sell runs of the bar being built.
+    #[must_use]
+    pub const fn runs(&self) -> (f64, f64) {
+        (self.buy_run, self.sell_run)
+    }
+
+    /// Returns the run at which the next bar will be built.
+    #[must_use]
+    pub fn expected_run(&self) -> f64 {
+        let expected_ticks = self.expected_ticks.value.unwrap_or_default();
+        let buy_proportion = self.expected_buy_proportion.value.unwrap_or_default();
+        let buy_value = self.expected_buy_value.value.unwrap_or_default();
+        let sell_value = self.expected_sell_value.value.unwrap_or_default();
+        expected_ticks
+            * f64::max(
+                buy_proportion * buy_value,
+                (1.0 - buy_proportion) * sell_value,
+            )
+    }
+
+    fn apply_signed_update(
+        &mut self,
+        price: Price,
+        size: Quantity,
+        ts_event: UnixNanos,
+        sign: f64,
+    ) {
+        let value = information_value(self.core.bar_type.spec.aggregation, price, size);
+
+        self.core.apply_update(price, size, ts_event);
+        if sign > 0.0 {
+            self.buy_run += value;
+            self.expected_buy_value.update(value);
+            self.expected_buy_proportion.update(1.0);
+        } else {
+            self.sell_run += value;
+            self.expected_sell_value.update(value);
+            self.expected_buy_proportion.update(0.0);
+        }
+        self.ticks += 1;
+
+        if self.buy_run.max(self.sell_run) >= self.expected_run() {
+            self.core.build_now_and_send();
+            self.expected_ticks.update(self.ticks as f64);
+            self.buy_run = 0.0;
+            self.sell_run = 0.0;
+            self.ticks = 0;
+        }
+    }
+}
+
+impl BarAggregator for RunsBarAggregator {
+    fn bar_type(&self) -> BarType {
+        self.core.bar_type
+    }
+
+    /// Apply the given update to the aggregator, signing the tick by the tick rule.
+    fn update(&mut self, price: Price, size: Quantity, ts_event: UnixNanos) {
+        let sign = self.signer.sign(price, AggressorSide::NoAggressor);
+        self.apply_signed_update(price, size, ts_event, sign);
+    }
+
+    /// Update the aggregator with the given trade, signing the tick by the aggressor side.
+    fn handle_trade_tick(&mut self, trade: TradeTick) {
+        let sign = self.signer.sign(trade.price, trade.aggressor_side);
+        self.apply_signed_update(trade.price, trade.size, trade.ts_event, sign);
+    }
+}
+
 /// Provides a means of building time bars aggregated from quote and trade ticks.
 ///
 /// At each aggregation time interval, a bar is created and sent to the handler.
-pub struct TimeBarAggregator<C>
-where
-    C: Clock,
-{
+pub struct TimeBarAggregator {
     core: BarAggregatorCore,
-    clock: C,
+    clock: Rc<RefCell<dyn Clock>>,
     build_with_no_updates: bool,
     timestamp_on_close: bool,
     is_left_open: bool,
@@ -454,33 +782,37 @@ where
     next_close_ns: UnixNanos,
 }
 
-impl<C> TimeBarAggregator<C>
-where
-    C: Clock,
-{
+impl TimeBarAggregator {
     /// Creates a new [`TimeBarAggregator`] instance.
     ///
     /// # Panics
     ///
     /// Panics if `instrument.id` is not equal to the `bar_type.instrument_id`.
     /// Panics if `bar_type.aggregation_source` is not equal to `AggregationSource::Internal`.
+    /// Panics if `interval_type` is not 'left-open' or 'right-open'.
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         instrument: &InstrumentAny,
         bar_type: BarType,
-        handler: fn(Bar),
+        handler: Box<dyn FnMut(Bar)>,
         await_partial: bool,
-        clock: C,
+        clock: Rc<RefCell<dyn Clock>>,
         build_with_no_updates: bool,
         timestamp_on_close: bool,
         interval_type: &str, // TODO: Make this an enum
     ) -> Self {
+        let is_left_open = match interval_type {
+            "left-open" => true,
+            "right-open" => false,
+            _ => panic!("Invalid `interval_type`, was '{interval_type}'"),
+        };
+
         Self {
             core: BarAggregatorCore::new(instrument, bar_type, handler, await_partial),
             clock,
             build_with_no_updates,
             timestamp_on_close,
-            is_left_open: false,
+            is_left_open,
             build_on_next_tick: false,
             stored_open_ns: UnixNanos::default(),
             stored_close_ns: UnixNanos::default(),
@@ -492,34 +824,6 @@ where
         }
     }
 
-    /// Starts the time bar aggregator.
-    pub fn start(&mut self) -> anyhow::Result<()> {
-        let now = self.clock.utc_now();
-        let start_time = get_time_bar_start(now, &self.bar_type());
-        let start_time_ns = UnixNanos::from(start_time.timestamp_nanos_opt().unwrap() as u64);
-
-        // let callback = SafeTimeEventCallback {
-        //     callback: Box::new(move |event| self.build_bar(event)),
-        // };
-        // let handler = EventHandler { }
-
-        self.clock.set_timer_ns(
-            &self.timer_name,
-            self.interval_ns.as_u64(),
-            start_time_ns,
-            None,
-            None, // TODO: Implement Rust callback handlers properly (see above commented code)
-        )?;
-
-        log::debug!("Started timer {}", self.timer_name);
-        Ok(())
-    }
-
-    /// Stops the time bar aggregator.
-    pub fn stop(&mut self) {
-        self.clock.cancel_timer(&self.timer_name);
-    }
-
     fn build_bar(&mut self, event: TimeEvent) {
         if !self.core.builder.initialized {
             self.build_on_next_tick = true;
@@ -544,18 +848,47 @@ where
 
         self.core.build_and_send(ts_event, ts_init);
         self.stored_open_ns = event.ts_event;
-        self.next_close_ns = self.clock.next_time_ns(&self.timer_name);
+        self.next_close_ns = self.clock.borrow().next_time_ns(&self.timer_name);
     }
 }
 
-impl<C> BarAggregator for TimeBarAggregator<C>
-where
-    C: Clock,
-{
+impl BarAggregator for TimeBarAggregator {
     fn bar_type(&self) -> BarType {
         self.core.bar_type
     }
 
+    /// Starts the time bar aggregator.
+    ///
+    /// The `handler` must pass the timer events on to [`BarAggregator::on_time_event`].
+    fn start(&mut self, handler: EventHandler) -> anyhow::Result<()> {
+        let now = self.clock.borrow().utc_now();
+        let start_time = get_time_bar_start(now, &self.bar_type());
+        let start_time_ns = UnixNanos::from(start_time.timestamp_nanos_opt().unwrap() as u64);
+
+        self.clock.borrow_mut().set_timer_ns(
+            &self.timer_name,
+            self.interval_ns.as_u64(),
+            start_time_ns,
+            None,
+            Some(handler),
+        )?;
+
+        self.stored_open_ns = start_time_ns;
+        self.next_close_ns = self.clock.borrow().next_time_ns(&self.timer_name);
+
+        log::debug!("Started timer {}", self.timer_name);
+        Ok(())
+    }
+
+    /// Stops the time bar aggregator.
+    fn stop(&mut self) {
+        self.clock.borrow_mut().cancel_timer(&self.timer_name);
+    }
+
+    fn on_time_event(&mut self, event: TimeEvent) {
+        self.build_bar(event);
+    }
+
     fn update(&mut self, price: Price, size: Quantity, ts_event: UnixNanos) {
         self.core.apply_update(price, size, ts_event);
         if self.build_on_next_tick {
@@ -585,9 +918,11 @@ where
 mod tests {
     use std::panic::AssertUnwindSafe;
 
+    use nautilus_common::{clock::TestClock, handlers::SafeTimeEventCallback};
     use nautilus_model::{
         data::bar::{BarSpecification, BarType},
-        enums::{AggregationSource, BarAggregation, PriceType},
+        enums::{AggregationSource, AggressorSide, BarAggregation, PriceType},
+        identifiers::TradeId,
         instruments::{any::InstrumentAny, equity::Equity, stubs::*},
         types::{price::Price, quantity::Quantity},
     };
@@ -857,110 +1192,350 @@ mod tests {
         assert_eq!(bar.volume, Quantity::new(3.0, 0).unwrap());
     }
 
-    // #[rstest]
-    // fn test_tick_bar_aggregator_handle_quote_tick_when_count_below_threshold_updates(
-    //     equity_aapl: Equity,
-    // ) {
-    //     let instrument = InstrumentAny::Equity(equity_aapl);
-    //     let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Mid);
-    //     let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
-    //     let handler = Arc::new(Mutex::new(Vec::new()));
-    //     let mut aggregator = TickBarAggregator::new(&instrument, bar_type, Arc::clone(&handler));
-    //
-    //     let tick = QuoteTick::new(
-    //         instrument.id(),
-    //         Price::new(1.00001, 8).unwrap(),
-    //         Price::new(1.00004, 8).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         UnixNanos::from(0),
-    //         UnixNanos::from(0),
-    //     );
-    //
-    //     aggregator.handle_quote_tick(tick);
-    //
-    //     let handler_guard = handler.lock().unwrap();
-    //     assert_eq!(handler_guard.len(), 0);
-    // }
-
-    //
-    // #[rstest]
-    // fn test_tick_bar_aggregator_handle_trade_tick_when_count_below_threshold_updates(
-    //     equity_aapl: Equity,
-    // ) {
-    //     let instrument = InstrumentAny::Equity(equity_aapl);
-    //     let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Last);
-    //     let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
-    //     let handler = Arc::new(Mutex::new(Vec::new()));
-    //     let mut aggregator = TickBarAggregator::new(&instrument, bar_type, Arc::clone(&handler));
-    //
-    //     let tick = TradeTick::new(
-    //         instrument.id(),
-    //         Price::new(1.00001, 8).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         AggressorSide::Buyer,
-    //         TradeId::new("123456"),
-    //         UnixNanos::from(0),
-    //         UnixNanos::from(0),
-    //     );
-    //
-    //     aggregator.handle_trade_tick(tick);
-    //
-    //     let handler_guard = handler.lock().unwrap();
-    //     assert_eq!(handler_guard.len(), 0);
-    // }
-    //
-    // #[rstest]
-    // fn test_tick_bar_aggregator_handle_quote_tick_when_count_at_threshold_sends_bar_to_handler(
-    //     equity_aapl: Equity,
-    // ) {
-    //     let instrument = InstrumentAny::Equity(equity_aapl);
-    //     let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Mid);
-    //     let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
-    //     let handler = Arc::new(Mutex::new(Vec::new()));
-    //     let mut aggregator = TickBarAggregator::new(&instrument, bar_type, Arc::clone(&handler));
-    //
-    //     let tick1 = QuoteTick::new(
-    //         instrument.id(),
-    //         Price::new(1.00001, 8).unwrap(),
-    //         Price::new(1.00004, 8).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         UnixNanos::from(0),
-    //         UnixNanos::from(0),
-    //     );
-    //
-    //     let tick2 = QuoteTick::new(
-    //         instrument.id(),
-    //         Price::new(1.00002, 8).unwrap(),
-    //         Price::new(1.00005, 8).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         UnixNanos::from(0),
-    //         UnixNanos::from(0),
-    //     );
-    //
-    //     let tick3 = QuoteTick::new(
-    //         instrument.id(),
-    //         Price::new(1.00000, 8).unwrap(),
-    //         Price::new(1.00003, 8).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         Quantity::new(1.0, 0).unwrap(),
-    //         UnixNanos::from(0),
-    //         UnixNanos::from(0),
-    //     );
-    //
-    //     aggregator.handle_quote_tick(tick1);
-    //     aggregator.handle_quote_tick(tick2);
-    //     aggregator.handle_quote_tick(tick3);
-    //
-    //     let handler_guard = handler.lock().unwrap();
-    //     assert_eq!(handler_guard.len(), 1);
-    //     let bar = &handler_guard[0];
-    //     assert_eq!(bar.open, Price::new(1.000025, 8).unwrap());
-    //     assert_eq!(bar.high, Price::new(1.000035, 8).unwrap());
-    //     assert_eq!(bar.low, Price::new(1.000015, 8).unwrap());
-    //     assert_eq!(bar.close, Price::new(1.000015, 8).unwrap());
-    //     assert_eq!(bar.volume, Quantity::new(3.0, 0).unwrap());
-    // }
+    #[rstest]
+    fn test_tick_bar_aggregator_handle_quote_tick_when_count_below_threshold_updates(
+        equity_aapl: Equity,
+    ) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Mid);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = TickBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        let tick = QuoteTick::new(
+            instrument.id(),
+            Price::new(1.00001, 8).unwrap(),
+            Price::new(1.00004, 8).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            UnixNanos::from(0),
+            UnixNanos::from(0),
+        )
+        .unwrap();
+
+        aggregator.handle_quote_tick(tick);
+
+        assert_eq!(handler.borrow().len(), 0);
+    }
+
+    #[rstest]
+    fn test_tick_bar_aggregator_handle_trade_tick_when_count_below_threshold_updates(
+        equity_aapl: Equity,
+    ) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Last);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = TickBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        let tick = TradeTick::new(
+            instrument.id(),
+            Price::new(1.00001, 8).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            AggressorSide::Buyer,
+            TradeId::new("123456").unwrap(),
+            UnixNanos::from(0),
+            UnixNanos::from(0),
+        );
+
+        aggregator.handle_trade_tick(tick);
+
+        assert_eq!(handler.borrow().len(), 0);
+    }
+
+    #[rstest]
+    fn test_tick_bar_aggregator_handle_quote_tick_when_count_at_threshold_sends_bar_to_handler(
+        equity_aapl: Equity,
+    ) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::Tick, PriceType::Mid);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = TickBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        let tick1 = QuoteTick::new(
+            instrument.id(),
+            Price::new(1.00001, 8).unwrap(),
+            Price::new(1.00004, 8).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            UnixNanos::from(0),
+            UnixNanos::from(0),
+        )
+        .unwrap();
+
+        let tick2 = QuoteTick::new(
+            instrument.id(),
+            Price::new(1.00002, 8).unwrap(),
+            Price::new(1.00005, 8).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            UnixNanos::from(0),
+            UnixNanos::from(0),
+        )
+        .unwrap();
+
+        let tick3 = QuoteTick::new(
+            instrument.id(),
+            Price::new(1.00000, 8).unwrap(),
+            Price::new(1.00003, 8).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            Quantity::new(1.0, 0).unwrap(),
+            UnixNanos::from(0),
+            UnixNanos::from(0),
+        )
+        .unwrap();
+
+        aggregator.handle_quote_tick(tick1);
+        aggregator.handle_quote_tick(tick2);
+        aggregator.handle_quote_tick(tick3);
+
+        let bars = handler.borrow();
+        assert_eq!(bars.len(), 1);
+        let bar = &bars[0];
+        assert_eq!(bar.open, Price::new(1.000025, 8).unwrap());
+        assert_eq!(bar.high, Price::new(1.000035, 8).unwrap());
+        assert_eq!(bar.low, Price::new(1.000015, 8).unwrap());
+        assert_eq!(bar.close, Price::new(1.000015, 8).unwrap());
+        assert_eq!(bar.volume, Quantity::new(3.0, 0).unwrap());
+    }
+
+    fn start_time_bar_aggregator(
+        instrument: &InstrumentAny,
+        clock: &Rc<RefCell<TestClock>>,
+        build_with_no_updates: bool,
+        timestamp_on_close: bool,
+    ) -> (Rc<RefCell<TimeBarAggregator>>, Rc<RefCell<Vec<Bar>>>) {
+        let bar_spec = BarSpecification::new(1, BarAggregation::Second, PriceType::Last);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let bars = Rc::new(RefCell::new(Vec::new()));
+        let bars_clone = Rc::clone(&bars);
+        let aggregator = Rc::new(RefCell::new(TimeBarAggregator::new(
+            instrument,
+            bar_type,
+            Box::new(move |bar: Bar| bars_clone.borrow_mut().push(bar)),
+            false,
+            clock.clone(),
+            build_with_no_updates,
+            timestamp_on_close,
+            "left-open",
+        )));
+
+        let weak_aggregator = Rc::downgrade(&aggregator);
+        let callback = SafeTimeEventCallback::new(Rc::new(move |event: TimeEvent| {
+            if let Some(aggregator) = weak_aggregator.upgrade() {
+                aggregator.borrow_mut().on_time_event(event);
+            }
+        }));
+        aggregator
+            .borrow_mut()
+            .start(EventHandler::from_rust(callback))
+            .unwrap();
+
+        (aggregator, bars)
+    }
+
+    fn advance_clock(clock: &Rc<RefCell<TestClock>>, to_time_ns: u64) {
+        let events = clock
+            .borrow_mut()
+            .advance_time(UnixNanos::from(to_time_ns), true);
+        for event in events {
+            let callback = clock.borrow().rust_callback(&event).unwrap();
+            callback.call(event);
+        }
+    }
+
+    fn trade(instrument: &InstrumentAny, price: &str, ts_event: u64) -> TradeTick {
+        TradeTick::new(
+            instrument.id(),
+            Price::from(price),
+            Quantity::new(1.0, 0).unwrap(),
+            AggressorSide::Buyer,
+            TradeId::new("123456").unwrap(),
+            UnixNanos::from(ts_event),
+            UnixNanos::from(ts_event),
+        )
+    }
+
+    #[rstest]
+    fn test_time_bar_aggregator_builds_bar_on_interval(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let (aggregator, bars) = start_time_bar_aggregator(&instrument, &clock, false, false);
+
+        aggregator
+            .borrow_mut()
+            .handle_trade_tick(trade(&instrument, "100.00", 250_000_000));
+        aggregator
+            .borrow_mut()
+            .handle_trade_tick(trade(&instrument, "101.00", 500_000_000));
+        advance_clock(&clock, 1_000_000_000);
+
+        let bars = bars.borrow();
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].open, Price::from("100.00"));
+        assert_eq!(bars[0].high, Price::from("101.00"));
+        assert_eq!(bars[0].close, Price::from("101.00"));
+        assert_eq!(bars[0].volume, Quantity::new(2.0, 0).unwrap());
+        assert_eq!(bars[0].ts_event, 0);
+        assert_eq!(bars[0].ts_init, 1_000_000_000);
+    }
+
+    #[rstest]
+    fn test_time_bar_aggregator_skips_interval_with_no_updates(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let (aggregator, bars) = start_time_bar_aggregator(&instrument, &clock, false, false);
+
+        aggregator
+            .borrow_mut()
+            .handle_trade_tick(trade(&instrument, "100.00", 500_000_000));
+        advance_clock(&clock, 2_000_000_000);
+
+        assert_eq!(bars.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_time_bar_aggregator_build_with_no_updates_and_timestamp_on_close(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let (aggregator, bars) = start_time_bar_aggregator(&instrument, &clock, true, true);
+
+        aggregator
+            .borrow_mut()
+            .handle_trade_tick(trade(&instrument, "100.00", 500_000_000));
+        advance_clock(&clock, 2_000_000_000);
+
+        let bars = bars.borrow();
+        assert_eq!(bars.len(), 2);
+        assert_eq!(bars[0].ts_event, 1_000_000_000);
+        assert_eq!(bars[1].ts_event, 2_000_000_000);
+        assert_eq!(bars[1].open, Price::from("100.00"));
+        assert_eq!(bars[1].close, Price::from("100.00"));
+        assert_eq!(bars[1].volume, Quantity::new(0.0, 0).unwrap());
+    }
+
+    fn aggressor_trade(
+        instrument: &InstrumentAny,
+        price: &str,
+        aggressor_side: AggressorSide,
+    ) -> TradeTick {
+        TradeTick::new(
+            instrument.id(),
+            Price::from(price),
+            Quantity::new(1.0, 0).unwrap(),
+            aggressor_side,
+            TradeId::new("123456").unwrap(),
+            UnixNanos::default(),
+            UnixNanos::default(),
+        )
+    }
+
+    #[rstest]
+    fn test_imbalance_bar_aggregator_builds_bar_at_expected_imbalance(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::TickImbalance, PriceType::Last);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = ImbalanceBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.00", AggressorSide::Buyer));
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.01", AggressorSide::Buyer));
+
+        assert_eq!(handler.borrow().len(), 0);
+        assert_eq!(aggregator.imbalance(), 2.0);
+        assert_eq!(aggregator.expected_imbalance(), 3.0);
+
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.02", AggressorSide::Buyer));
+
+        let bars = handler.borrow();
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].open, Price::from("100.00"));
+        assert_eq!(bars[0].close, Price::from("100.02"));
+        assert_eq!(bars[0].volume, Quantity::new(3.0, 0).unwrap());
+        assert_eq!(aggregator.imbalance(), 0.0);
+    }
+
+    #[rstest]
+    fn test_imbalance_bar_aggregator_signs_by_tick_rule_without_aggressor(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::VolumeImbalance, PriceType::Last);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = ImbalanceBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        let side = AggressorSide::NoAggressor;
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.00", side));
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.01", side));
+
+        assert_eq!(handler.borrow().len(), 0);
+        assert_eq!(aggregator.imbalance(), 2.0);
+
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.02", side));
+
+        assert_eq!(handler.borrow().len(), 1);
+        assert_eq!(aggregator.imbalance(), 0.0);
+    }
+
+    #[rstest]
+    fn test_runs_bar_aggregator_builds_bar_at_expected_run(equity_aapl: Equity) {
+        let instrument = InstrumentAny::Equity(equity_aapl);
+        let bar_spec = BarSpecification::new(3, BarAggregation::TickRuns, PriceType::Last);
+        let bar_type = BarType::new(instrument.id(), bar_spec, AggregationSource::Internal);
+        let handler = Rc::new(RefCell::new(Vec::new()));
+        let handler_clone = Rc::clone(&handler);
+        let mut aggregator = RunsBarAggregator::new(
+            &instrument,
+            bar_type,
+            Box::new(move |bar: Bar| handler_clone.borrow_mut().push(bar)),
+            false,
+        );
+
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.00", AggressorSide::Buyer));
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "99.99", AggressorSide::Seller));
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.00", AggressorSide::Buyer));
+
+        assert_eq!(handler.borrow().len(), 0);
+        assert_eq!(aggregator.runs(), (2.0, 1.0));
+        assert_eq!(aggregator.expected_run(), 2.25);
+
+        aggregator.handle_trade_tick(aggressor_trade(&instrument, "100.01", AggressorSide::Buyer));
+
+        let bars = handler.borrow();
+        assert_eq!(bars.len(), 1);
+        assert_eq!(bars[0].high, Price::from("100.01"));
+        assert_eq!(bars[0].low, Price::from("99.99"));
+        assert_eq!(bars[0].volume, Quantity::new(4.0, 0).unwrap());
+        assert_eq!(aggregator.runs(), (0.0, 0.0));
+    }
 }
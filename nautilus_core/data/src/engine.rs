@@ -21,7 +21,7 @@
 
 use std::{
     any::Any,
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::{HashMap, HashSet},
     marker::PhantomData,
     ops::Deref,
@@ -35,11 +35,13 @@ use nautilus_common::{
     clock::Clock,
     component::{Disposed, PreInitialized, Ready, Running, Starting, State, Stopped, Stopping},
     enums::ComponentState,
+    handlers::{EventHandler, SafeTimeEventCallback},
     logging::{CMD, RECV, RES},
     messages::data::{DataCommand, DataCommandAction, DataRequest, DataResponse},
     msgbus::MessageBus,
+    timer::TimeEvent,
 };
-use nautilus_core::correctness;
+use nautilus_core::{correctness, uuid::UUID4};
 use nautilus_model::{
     data::{
         bar::{Bar, BarType},
@@ -50,11 +52,19 @@ use nautilus_model::{
         trade::TradeTick,
         Data, DataType,
     },
-    identifiers::{ClientId, InstrumentId, Venue},
+    enums::{AggregationSource, BarAggregation, BookType, PriceType},
+    identifiers::{ClientId, InstrumentId, TradeId, Venue},
     instruments::{any::InstrumentAny, synthetic::SyntheticInstrument},
+    orderbook::book::OrderBook,
 };
 
-use crate::client::DataClient;
+use crate::{
+    aggregation::{
+        BarAggregator, ImbalanceBarAggregator, RunsBarAggregator, TickBarAggregator,
+        TimeBarAggregator, ValueBarAggregator, VolumeBarAggregator,
+    },
+    client::DataClient,
+};
 
 pub struct DataEngineConfig {
     pub debug: bool,
@@ -65,18 +75,46 @@ pub struct DataEngineConfig {
     pub buffer_deltas: bool,
 }
 
+/// A periodic order book snapshot publication for an instrument, published each time
+/// its `OrderBook|{instrument_id}|{interval_ms}` clock timer fires.
+struct BookSnapshotInterval {
+    instrument_id: InstrumentId,
+    interval_ms: u64,
+    /// The maximum number of levels per side to publish (all levels if `None`).
+    depth: Cell<Option<usize>>,
+}
+
+impl BookSnapshotInterval {
+    fn timer_name(&self) -> String {
+        format!("OrderBook|{}|{}", self.instrument_id, self.interval_ms)
+    }
+}
+
+type SharedBarAggregator = Rc<RefCell<Box<dyn BarAggregator>>>;
+
+/// A synthetic instrument shared by the feeds of each of its components, mutable
+/// because evaluating its formula updates the formula context in place.
+type SharedSyntheticInstrument = Rc<RefCell<SyntheticInstrument>>;
+
+/// Provides a high-performance data engine for managing many `DataClient` instances.
+///
+/// Time bar aggregation and order book snapshot intervals are driven by native Rust timer
+/// callbacks, so require a `TestClock` (a `LiveClock` rejects these callbacks, in which case
+/// the error is logged and the aggregation or interval is not started).
 pub struct DataEngine<State = PreInitialized> {
     state: PhantomData<State>,
-    clock: Box<dyn Clock>,
+    clock: Rc<RefCell<dyn Clock>>,
     cache: Rc<RefCell<Cache>>,
     msgbus: Rc<RefCell<MessageBus>>,
     clients: HashMap<ClientId, Box<dyn DataClient>>,
     default_client: Option<Box<dyn DataClient>>,
     routing_map: HashMap<Venue, ClientId>,
-    // order_book_intervals: HashMap<(InstrumentId, usize), Vec<fn(&OrderBook)>>,  // TODO
-    // bar_aggregators:  // TODO
-    synthetic_quote_feeds: HashMap<InstrumentId, Vec<SyntheticInstrument>>,
-    synthetic_trade_feeds: HashMap<InstrumentId, Vec<SyntheticInstrument>>,
+    order_book_intervals: HashMap<(InstrumentId, u64), Rc<BookSnapshotInterval>>,
+    bar_aggregators: HashMap<InstrumentId, HashMap<BarType, SharedBarAggregator>>,
+    quote_tick_subscriptions: HashSet<InstrumentId>,
+    trade_tick_subscriptions: HashSet<InstrumentId>,
+    synthetic_quote_feeds: HashMap<InstrumentId, Vec<SharedSyntheticInstrument>>,
+    synthetic_trade_feeds: HashMap<InstrumentId, Vec<SharedSyntheticInstrument>>,
     buffered_deltas_map: HashMap<InstrumentId, Vec<OrderBookDelta>>,
     config: DataEngineConfig,
 }
@@ -84,7 +122,7 @@ pub struct DataEngine<State = PreInitialized> {
 impl DataEngine {
     #[must_use]
     pub fn new(
-        clock: Box<dyn Clock>,
+        clock: Rc<RefCell<dyn Clock>>,
         cache: Rc<RefCell<Cache>>,
         msgbus: Rc<RefCell<MessageBus>>,
         config: DataEngineConfig,
@@ -97,6 +135,10 @@ impl DataEngine {
             clients: HashMap::new(),
             default_client: None,
             routing_map: HashMap::new(),
+            order_book_intervals: HashMap::new(),
+            bar_aggregators: HashMap::new(),
+            quote_tick_subscriptions: HashSet::new(),
+            trade_tick_subscriptions: HashSet::new(),
             synthetic_quote_feeds: HashMap::new(),
             synthetic_trade_feeds: HashMap::new(),
             buffered_deltas_map: HashMap::new(),
@@ -115,6 +157,10 @@ impl<S: State> DataEngine<S> {
             clients: self.clients,
             default_client: self.default_client,
             routing_map: self.routing_map,
+            order_book_intervals: self.order_book_intervals,
+            bar_aggregators: self.bar_aggregators,
+            quote_tick_subscriptions: self.quote_tick_subscriptions,
+            trade_tick_subscriptions: self.trade_tick_subscriptions,
             synthetic_quote_feeds: self.synthetic_quote_feeds,
             synthetic_trade_feeds: self.synthetic_trade_feeds,
             buffered_deltas_map: self.buffered_deltas_map,
@@ -279,11 +325,11 @@ impl DataEngine<Ready> {
     }
 
     #[must_use]
-    pub fn dispose(mut self) -> DataEngine<Disposed> {
+    pub fn dispose(self) -> DataEngine<Disposed> {
         for client in self.clients.values() {
             client.dispose();
         }
-        self.clock.cancel_timers();
+        self.clock.borrow_mut().cancel_timers();
         self.transition()
     }
 }
@@ -310,6 +356,10 @@ impl DataEngine<Running> {
     }
 
     pub fn process(&self, data: Data) {
+        if matches!(data, Data::Delta(_) | Data::Deltas(_) | Data::Depth10(_)) {
+            self.update_order_book(&data);
+        }
+
         match data {
             Data::Delta(delta) => self.handle_delta(delta),
             Data::Deltas(deltas) => self.handle_deltas(deltas.deref().clone()), // TODO: Optimize
@@ -325,6 +375,15 @@ impl DataEngine<Running> {
             log::debug!("{}", format!("{RECV}{CMD} commmand")); // TODO: Display for command
         }
 
+        // Synthetic instruments are priced by the engine, not a data client
+        if command.venue.is_synthetic() {
+            match command.action {
+                DataCommandAction::Subscribe => self.handle_subscribe_synthetic(command),
+                DataCommandAction::Unsubscibe => self.handle_unsubscribe_synthetic(command),
+            }
+            return;
+        }
+
         // Determine the client ID
         let client_id = if self.clients.contains_key(&command.client_id) {
             Some(command.client_id)
@@ -427,7 +486,6 @@ impl DataEngine<Running> {
 
     fn handle_delta(&self, delta: OrderBookDelta) {
         // TODO: Manage buffered deltas
-        // TODO: Manage book
 
         let topic = format!(
             "data.book.deltas.{}.{}",
@@ -438,8 +496,6 @@ impl DataEngine<Running> {
     }
 
     fn handle_deltas(&self, deltas: OrderBookDeltas) {
-        // TODO: Manage book
-
         let topic = format!(
             "data.book.snapshots.{}.{}", // TODO: Revise snapshots topic component
             deltas.instrument_id.venue, deltas.instrument_id.symbol
@@ -448,8 +504,6 @@ impl DataEngine<Running> {
     }
 
     fn handle_depth10(&self, depth: OrderBookDepth10) {
-        // TODO: Manage book
-
         let topic = format!(
             "data.book.depth.{}.{}",
             depth.instrument_id.venue, depth.instrument_id.symbol
@@ -462,13 +516,23 @@ impl DataEngine<Running> {
             log::error!("Error on cache insert: {e}");
         }
 
-        // TODO: Handle synthetics
+        if let Some(synthetics) = self.synthetic_quote_feeds.get(&quote.instrument_id) {
+            self.update_synthetics_with_quote(synthetics, &quote);
+        }
 
         let topic = format!(
             "data.quotes.{}.{}",
             quote.instrument_id.venue, quote.instrument_id.symbol
         );
         self.msgbus.borrow().publish(&topic, &quote as &dyn Any); // TODO: Optimize
+
+        if let Some(aggregators) = self.bar_aggregators.get(&quote.instrument_id) {
+            for (bar_type, aggregator) in aggregators {
+                if bar_type.spec.price_type != PriceType::Last {
+                    aggregator.borrow_mut().handle_quote_tick(quote);
+                }
+            }
+        }
     }
 
     fn handle_trade(&self, trade: TradeTick) {
@@ -476,24 +540,28 @@ impl DataEngine<Running> {
             log::error!("Error on cache insert: {e}");
         }
 
-        // TODO: Handle synthetics
+        if let Some(synthetics) = self.synthetic_trade_feeds.get(&trade.instrument_id) {
+            self.update_synthetics_with_trade(synthetics, &trade);
+        }
 
         let topic = format!(
             "data.trades.{}.{}",
             trade.instrument_id.venue, trade.instrument_id.symbol
         );
         self.msgbus.borrow().publish(&topic, &trade as &dyn Any); // TODO: Optimize
-    }
 
-    fn handle_bar(&self, bar: Bar) {
-        if let Err(e) = self.cache.borrow_mut().add_bar(bar) {
-            log::error!("Error on cache insert: {e}");
+        if let Some(aggregators) = self.bar_aggregators.get(&trade.instrument_id) {
+            for (bar_type, aggregator) in aggregators {
+                if bar_type.spec.price_type == PriceType::Last {
+                    aggregator.borrow_mut().handle_trade_tick(trade);
+                }
+            }
         }
+    }
 
+    fn handle_bar(&self, bar: Bar) {
         // TODO: Handle additional bar logic
-
-        let topic = format!("data.bars.{}", bar.bar_type);
-        self.msgbus.borrow().publish(&topic, &bar as &dyn Any); // TODO: Optimize
+        publish_bar(&self.cache, &self.msgbus, bar);
     }
 
     // -- COMMAND HANDLERS ------------------------------------------------------------------------
@@ -557,6 +625,8 @@ impl DataEngine<Running> {
         let book_type = command.data_type.parse_book_type_from_metadata();
         let depth = command.data_type.parse_depth_from_metadata();
 
+        self.setup_order_book(instrument_id, book_type);
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -578,6 +648,13 @@ impl DataEngine<Running> {
 
         let book_type = command.data_type.parse_book_type_from_metadata();
         let depth = command.data_type.parse_depth_from_metadata();
+        let interval_ms = command.data_type.parse_interval_ms_from_metadata();
+
+        self.setup_order_book(instrument_id, book_type);
+
+        if let Some(interval_ms) = interval_ms {
+            self.setup_order_book_interval(instrument_id, interval_ms, depth);
+        }
 
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
@@ -601,6 +678,8 @@ impl DataEngine<Running> {
         let book_type = command.data_type.parse_book_type_from_metadata();
         let depth = command.data_type.parse_depth_from_metadata();
 
+        self.quote_tick_subscriptions.insert(instrument_id);
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -617,6 +696,8 @@ impl DataEngine<Running> {
             .parse_instrument_id_from_metadata()
             .expect("Error on subscribe: no 'instrument_id' in metadata");
 
+        self.trade_tick_subscriptions.insert(instrument_id);
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -630,6 +711,17 @@ impl DataEngine<Running> {
     fn handle_subscribe_bars(&mut self, client_id: ClientId, command: DataCommand) {
         let bar_type = command.data_type.parse_bar_type_from_metadata();
 
+        if bar_type.aggregation_source == AggregationSource::Internal {
+            let is_aggregating = self
+                .bar_aggregators
+                .get(&bar_type.instrument_id)
+                .is_some_and(|aggregators| aggregators.contains_key(&bar_type));
+            if !is_aggregating {
+                self.start_bar_aggregator(client_id, bar_type);
+            }
+            return;
+        }
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -651,6 +743,44 @@ impl DataEngine<Running> {
         }
     }
 
+    fn handle_subscribe_synthetic(&mut self, command: DataCommand) {
+        let instrument_id = command
+            .data_type
+            .parse_instrument_id_from_metadata()
+            .expect("Error on subscribe: no 'instrument_id' in metadata");
+
+        let synthetic = match self.cache.borrow().synthetic(&instrument_id) {
+            Some(synthetic) => synthetic.clone(),
+            None => {
+                log::error!("Cannot subscribe: no synthetic instrument found for {instrument_id}");
+                return;
+            }
+        };
+
+        let feeds = match command.data_type.type_name() {
+            stringify!(QuoteTick) => &mut self.synthetic_quote_feeds,
+            stringify!(TradeTick) => &mut self.synthetic_trade_feeds,
+            type_name => {
+                log::error!(
+                    "Cannot subscribe to {type_name} for synthetic instrument {instrument_id}"
+                );
+                return;
+            }
+        };
+
+        let components = synthetic.components.clone();
+        let synthetic = Rc::new(RefCell::new(synthetic));
+        for component_id in components {
+            let synthetics = feeds.entry(component_id).or_default();
+            if !synthetics
+                .iter()
+                .any(|existing| existing.borrow().id == instrument_id)
+            {
+                synthetics.push(synthetic.clone());
+            }
+        }
+    }
+
     fn handle_unsubscribe_instrument(&mut self, client_id: ClientId, command: DataCommand) {
         let instrument_id = command.data_type.parse_instrument_id_from_metadata();
         let venue = command.data_type.parse_venue_from_metadata();
@@ -700,6 +830,19 @@ impl DataEngine<Running> {
             .parse_instrument_id_from_metadata()
             .expect("Error on subscribe: no 'instrument_id' in metadata");
 
+        if let Some(interval_ms) = command.data_type.parse_interval_ms_from_metadata() {
+            self.stop_order_book_interval(instrument_id, interval_ms);
+        }
+
+        // Snapshots are still needed by the remaining intervals for the instrument
+        if self
+            .order_book_intervals
+            .keys()
+            .any(|(id, _)| *id == instrument_id)
+        {
+            return;
+        }
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -719,6 +862,11 @@ impl DataEngine<Running> {
             .parse_instrument_id_from_metadata()
             .expect("Error on subscribe: no 'instrument_id' in metadata");
 
+        self.quote_tick_subscriptions.remove(&instrument_id);
+        if self.is_aggregating_bars(&instrument_id, false) {
+            return; // Quotes still required for bar aggregation
+        }
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
         if client.subscribed_quote_ticks().contains(&instrument_id) {
@@ -734,6 +882,11 @@ impl DataEngine<Running> {
             .parse_instrument_id_from_metadata()
             .expect("Error on subscribe: no 'instrument_id' in metadata");
 
+        self.trade_tick_subscriptions.remove(&instrument_id);
+        if self.is_aggregating_bars(&instrument_id, true) {
+            return; // Trades still required for bar aggregation
+        }
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -747,6 +900,11 @@ impl DataEngine<Running> {
     fn handle_unsubscribe_bars(&mut self, client_id: ClientId, command: DataCommand) {
         let bar_type = command.data_type.parse_bar_type_from_metadata();
 
+        if bar_type.aggregation_source == AggregationSource::Internal {
+            self.stop_bar_aggregator(client_id, bar_type);
+            return;
+        }
+
         // SAFETY: client_id already determined
         let client = self.clients.get_mut(&client_id).unwrap();
 
@@ -770,6 +928,29 @@ impl DataEngine<Running> {
         }
     }
 
+    fn handle_unsubscribe_synthetic(&mut self, command: DataCommand) {
+        let instrument_id = command
+            .data_type
+            .parse_instrument_id_from_metadata()
+            .expect("Error on unsubscribe: no 'instrument_id' in metadata");
+
+        let feeds = match command.data_type.type_name() {
+            stringify!(QuoteTick) => &mut self.synthetic_quote_feeds,
+            stringify!(TradeTick) => &mut self.synthetic_trade_feeds,
+            type_name => {
+                log::error!(
+                    "Cannot unsubscribe from {type_name} for synthetic instrument {instrument_id}"
+                );
+                return;
+            }
+        };
+
+        feeds.retain(|_, synthetics| {
+            synthetics.retain(|synthetic| synthetic.borrow().id != instrument_id);
+            !synthetics.is_empty()
+        });
+    }
+
     // -- REQUEST HANDLERS ------------------------------------------------------------------------
 
     fn handle_request(&mut self, client_id: ClientId, request: DataRequest) {
@@ -871,6 +1052,368 @@ impl DataEngine<Running> {
 
     // -- INTERNAL --------------------------------------------------------------------------------
 
+    fn setup_order_book(&self, instrument_id: InstrumentId, book_type: BookType) {
+        let mut cache = self.cache.borrow_mut();
+        if cache.has_order_book(&instrument_id) {
+            return;
+        }
+
+        let book = OrderBook::new(book_type, instrument_id);
+        if let Err(e) = cache.add_order_book(book) {
+            log::error!("Error on cache insert: {e}");
+        }
+    }
+
+    fn setup_order_book_interval(
+        &mut self,
+        instrument_id: InstrumentId,
+        interval_ms: u64,
+        depth: Option<usize>,
+    ) {
+        let key = (instrument_id, interval_ms);
+        if let Some(interval) = self.order_book_intervals.get(&key) {
+            // Publish the deepest snapshot requested for the interval
+            interval.depth.set(match (interval.depth.get(), depth) {
+                (Some(current), Some(requested)) => Some(current.max(requested)),
+                _ => None,
+            });
+            return;
+        }
+
+        let interval = Rc::new(BookSnapshotInterval {
+            instrument_id,
+            interval_ms,
+            depth: Cell::new(depth),
+        });
+        let weak_interval = Rc::downgrade(&interval);
+        let cache = self.cache.clone();
+        let msgbus = self.msgbus.clone();
+        let callback = SafeTimeEventCallback::new(Rc::new(move |_event: TimeEvent| {
+            if let Some(interval) = weak_interval.upgrade() {
+                publish_order_book_snapshot(&cache, &msgbus, &interval);
+            }
+        }));
+
+        let mut clock = self.clock.borrow_mut();
+        let start_time_ns = clock.timestamp_ns();
+        if let Err(e) = clock.set_timer_ns(
+            &interval.timer_name(),
+            interval_ms * 1_000_000,
+            start_time_ns,
+            None,
+            Some(EventHandler::from_rust(callback)),
+        ) {
+            log::error!("Error setting order book snapshot timer: {e}");
+            return;
+        }
+
+        self.order_book_intervals.insert(key, interval);
+    }
+
+    fn stop_order_book_interval(&mut self, instrument_id: InstrumentId, interval_ms: u64) {
+        if let Some(interval) = self
+            .order_book_intervals
+            .remove(&(instrument_id, interval_ms))
+        {
+            self.clock.borrow_mut().cancel_timer(&interval.timer_name());
+        }
+    }
+
+    fn start_bar_aggregator(&mut self, client_id: ClientId, bar_type: BarType) {
+        let instrument = match self.cache.borrow().instrument(&bar_type.instrument_id) {
+            Some(instrument) => instrument.clone(),
+            None => {
+                log::error!(
+                    "Cannot start bar aggregation: no instrument found for {}",
+                    bar_type.instrument_id
+                );
+                return;
+            }
+        };
+
+        // Subscribe to the data the bars are aggregated from
+        let instrument_id = bar_type.instrument_id;
+        let uses_trades = bar_type.spec.price_type == PriceType::Last;
+        // SAFETY: client_id already determined
+        let client = self.clients.get_mut(&client_id).unwrap();
+        let result = if uses_trades {
+            if client.subscribed_trade_ticks().contains(&instrument_id) {
+                Ok(())
+            } else {
+                client.subscribe_trade_ticks(instrument_id)
+            }
+        } else if client.subscribed_quote_ticks().contains(&instrument_id) {
+            Ok(())
+        } else {
+            client.subscribe_quote_ticks(instrument_id)
+        };
+        if let Err(e) = result {
+            log::error!("Cannot start bar aggregation for {bar_type}: error on subscribe: {e}");
+            return;
+        }
+
+        let cache = self.cache.clone();
+        let msgbus = self.msgbus.clone();
+        let handler = Box::new(move |bar: Bar| publish_bar(&cache, &msgbus, bar));
+
+        let aggregator: Box<dyn BarAggregator> = match bar_type.spec.aggregation {
+            BarAggregation::Tick => Box::new(TickBarAggregator::new(
+                &instrument,
+                bar_type,
+                handler,
+                false,
+            )),
+            BarAggregation::Volume => Box::new(VolumeBarAggregator::new(
+                &instrument,
+                bar_type,
+                handler,
+                false,
+            )),
+            BarAggregation::Value => Box::new(ValueBarAggregator::new(
+                &instrument,
+                bar_type,
+                handler,
+                false,
+            )),
+            BarAggregation::TickImbalance
+            | BarAggregation::VolumeImbalance
+            | BarAggregation::ValueImbalance => Box::new(ImbalanceBarAggregator::new(
+                &instrument,
+                bar_type,
+                handler,
+                false,
+            )),
+            BarAggregation::TickRuns | BarAggregation::VolumeRuns | BarAggregation::ValueRuns => {
+                Box::new(RunsBarAggregator::new(
+                    &instrument,
+                    bar_type,
+                    handler,
+                    false,
+                ))
+            }
+            BarAggregation::Millisecond
+            | BarAggregation::Second
+            | BarAggregation::Minute
+            | BarAggregation::Hour
+            | BarAggregation::Day
+            | BarAggregation::Week
+            | BarAggregation::Month => Box::new(TimeBarAggregator::new(
+                &instrument,
+                bar_type,
+                handler,
+                false,
+                self.clock.clone(),
+                self.config.time_bars_build_with_no_updates,
+                self.config.time_bars_timestamp_on_close,
+                &self.config.time_bars_interval_type,
+            )),
+        };
+
+        let aggregator = Rc::new(RefCell::new(aggregator));
+        let weak_aggregator = Rc::downgrade(&aggregator);
+        let callback = SafeTimeEventCallback::new(Rc::new(move |event: TimeEvent| {
+            if let Some(aggregator) = weak_aggregator.upgrade() {
+                aggregator.borrow_mut().on_time_event(event);
+            }
+        }));
+
+        if let Err(e) = aggregator
+            .borrow_mut()
+            .start(EventHandler::from_rust(callback))
+        {
+            log::error!("Error starting bar aggregator for {bar_type}: {e}");
+            self.release_bar_aggregation_data(client_id, instrument_id, uses_trades);
+            return;
+        }
+
+        self.bar_aggregators
+            .entry(instrument_id)
+            .or_default()
+            .insert(bar_type, aggregator);
+    }
+
+    fn stop_bar_aggregator(&mut self, client_id: ClientId, bar_type: BarType) {
+        let instrument_id = bar_type.instrument_id;
+        let Some(aggregators) = self.bar_aggregators.get_mut(&instrument_id) else {
+            return;
+        };
+
+        let Some(aggregator) = aggregators.remove(&bar_type) else {
+            return;
+        };
+        aggregator.borrow_mut().stop();
+
+        if aggregators.is_empty() {
+            self.bar_aggregators.remove(&instrument_id);
+        }
+
+        let uses_trades = bar_type.spec.price_type == PriceType::Last;
+        self.release_bar_aggregation_data(client_id, instrument_id, uses_trades);
+    }
+
+    /// Returns whether any bars for the `instrument_id` are aggregated from trades (if
+    /// `uses_trades`), or otherwise from quotes.
+    fn is_aggregating_bars(&self, instrument_id: &InstrumentId, uses_trades: bool) -> bool {
+        self.bar_aggregators
+            .get(instrument_id)
+            .is_some_and(|aggregators| {
+                aggregators
+                    .keys()
+                    .any(|bar_type| (bar_type.spec.price_type == PriceType::Last) == uses_trades)
+            })
+    }
+
+    /// Unsubscribes the client from the trades (if `uses_trades`) or quotes for the
+    /// `instrument_id` subscribed for bar aggregation, unless still required by another
+    /// aggregator or a subscriber.
+    fn release_bar_aggregation_data(
+        &mut self,
+        client_id: ClientId,
+        instrument_id: InstrumentId,
+        uses_trades: bool,
+    ) {
+        let is_subscribed = if uses_trades {
+            self.trade_tick_subscriptions.contains(&instrument_id)
+        } else {
+            self.quote_tick_subscriptions.contains(&instrument_id)
+        };
+        if is_subscribed || self.is_aggregating_bars(&instrument_id, uses_trades) {
+            return;
+        }
+
+        let Some(client) = self.clients.get_mut(&client_id) else {
+            return;
+        };
+        let result = if uses_trades {
+            if client.subscribed_trade_ticks().contains(&instrument_id) {
+                client.unsubscribe_trade_ticks(instrument_id)
+            } else {
+                Ok(())
+            }
+        } else if client.subscribed_quote_ticks().contains(&instrument_id) {
+            client.unsubscribe_quote_ticks(instrument_id)
+        } else {
+            Ok(())
+        };
+        if let Err(e) = result {
+            log::error!("Error on unsubscribe from bar aggregation data for {instrument_id}: {e}");
+        }
+    }
+
+    fn update_synthetics_with_quote(
+        &self,
+        synthetics: &[SharedSyntheticInstrument],
+        update: &QuoteTick,
+    ) {
+        for synthetic in synthetics {
+            let mut synthetic = synthetic.borrow_mut();
+            let mut bids = Vec::with_capacity(synthetic.components.len());
+            let mut asks = Vec::with_capacity(synthetic.components.len());
+            let mut bid_sizes = Vec::with_capacity(synthetic.components.len());
+            let mut ask_sizes = Vec::with_capacity(synthetic.components.len());
+
+            for component_id in &synthetic.components {
+                let quote = if *component_id == update.instrument_id {
+                    *update
+                } else if let Some(quote) = self.cache.borrow().quote_tick(component_id) {
+                    *quote
+                } else {
+                    break; // Cannot price the synthetic until every component has quoted
+                };
+                bids.push(quote.bid_price.as_f64());
+                asks.push(quote.ask_price.as_f64());
+                bid_sizes.push(quote.bid_size);
+                ask_sizes.push(quote.ask_size);
+            }
+
+            if bids.len() != synthetic.components.len() {
+                continue;
+            }
+
+            let (bid_price, ask_price) =
+                match (synthetic.calculate(&bids), synthetic.calculate(&asks)) {
+                    (Ok(bid_price), Ok(ask_price)) => (bid_price, ask_price),
+                    (Err(e), _) | (_, Err(e)) => {
+                        log::error!(
+                            "Error calculating synthetic quote for {}: {e}",
+                            synthetic.id
+                        );
+                        continue;
+                    }
+                };
+
+            // Synthetic instruments have no order book of their own, so quotes carry the
+            // smallest size quoted across the components
+            // SAFETY: A synthetic instrument has at least two components
+            let bid_size = bid_sizes.into_iter().min().unwrap();
+            let ask_size = ask_sizes.into_iter().min().unwrap();
+            let quote = QuoteTick::new(
+                synthetic.id,
+                bid_price,
+                ask_price,
+                bid_size,
+                ask_size,
+                update.ts_event,
+                self.clock.borrow().timestamp_ns(),
+            );
+            let synthetic_id = synthetic.id;
+            drop(synthetic);
+
+            match quote {
+                Ok(quote) => self.handle_quote(quote),
+                Err(e) => log::error!("Error creating synthetic quote for {synthetic_id}: {e}"),
+            }
+        }
+    }
+
+    fn update_synthetics_with_trade(
+        &self,
+        synthetics: &[SharedSyntheticInstrument],
+        update: &TradeTick,
+    ) {
+        for synthetic in synthetics {
+            let mut synthetic = synthetic.borrow_mut();
+            let mut prices = Vec::with_capacity(synthetic.components.len());
+
+            for component_id in &synthetic.components {
+                if *component_id == update.instrument_id {
+                    prices.push(update.price.as_f64());
+                } else if let Some(trade) = self.cache.borrow().trade_tick(component_id) {
+                    prices.push(trade.price.as_f64());
+                } else {
+                    break; // Cannot price the synthetic until every component has traded
+                }
+            }
+
+            if prices.len() != synthetic.components.len() {
+                continue;
+            }
+
+            let trade = match synthetic.calculate(&prices) {
+                // The component's trade ID is not reused, as the synthetic trade is distinct
+                Ok(price) => TradeTick::new(
+                    synthetic.id,
+                    price,
+                    update.size,
+                    update.aggressor_side,
+                    TradeId::new(UUID4::new().to_string().as_str()).unwrap(),
+                    update.ts_event,
+                    self.clock.borrow().timestamp_ns(),
+                ),
+                Err(e) => {
+                    log::error!(
+                        "Error calculating synthetic trade for {}: {e}",
+                        synthetic.id
+                    );
+                    continue;
+                }
+            };
+            drop(synthetic);
+
+            self.handle_trade(trade);
+        }
+    }
+
     fn update_order_book(&self, data: &Data) {
         // Only apply data if there is a book being managed,
         // as it may be being managed manually.
@@ -885,6 +1428,54 @@ impl DataEngine<Running> {
     }
 }
 
+fn publish_bar(cache: &RefCell<Cache>, msgbus: &RefCell<MessageBus>, bar: Bar) {
+    if let Err(e) = cache.borrow_mut().add_bar(bar) {
+        log::error!("Error on cache insert: {e}");
+    }
+
+    let topic = format!("data.bars.{}", bar.bar_type);
+    msgbus.borrow().publish(&topic, &bar as &dyn Any); // TODO: Optimize
+}
+
+fn publish_order_book_snapshot(
+    cache: &RefCell<Cache>,
+    msgbus: &RefCell<MessageBus>,
+    interval: &BookSnapshotInterval,
+) {
+    let instrument_id = interval.instrument_id;
+    let snapshot = match cache.borrow_mut().order_book(&instrument_id) {
+        Some(book) => book_snapshot(book, interval.depth.get()),
+        None => {
+            log::error!("Cannot publish snapshot: no order book for {instrument_id}");
+            return;
+        }
+    };
+
+    let topic = format!(
+        "data.book.snapshots.{}.{}.{}",
+        instrument_id.venue, instrument_id.symbol, interval.interval_ms
+    );
+    msgbus.borrow().publish(&topic, &snapshot as &dyn Any);
+}
+
+/// Returns a copy of the `book` limited to the top `depth` levels per side.
+fn book_snapshot(book: &OrderBook, depth: Option<usize>) -> OrderBook {
+    let Some(depth) = depth else {
+        return book.clone();
+    };
+
+    let mut snapshot = OrderBook::new(book.book_type, book.instrument_id);
+    for level in book.bids().take(depth).chain(book.asks().take(depth)) {
+        for order in level.get_orders() {
+            snapshot.add(order, 0, book.sequence, book.ts_last);
+        }
+    }
+    snapshot.sequence = book.sequence;
+    snapshot.ts_last = book.ts_last;
+    snapshot.count = book.count;
+    snapshot
+}
+
 impl DataEngine<Stopping> {
     #[must_use]
     pub fn on_stop(self) -> DataEngine<Stopped> {
@@ -903,3 +1494,776 @@ impl DataEngine<Stopped> {
         self.transition()
     }
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+    use indexmap::IndexMap;
+    use nautilus_common::{
+        cache::Cache,
+        clock::{Clock, LiveClock, TestClock},
+        component::Running,
+        messages::data::{DataCommand, DataCommandAction},
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+    use nautilus_model::{
+        data::{
+            bar::{Bar, BarType},
+            delta::OrderBookDelta,
+            order::BookOrder,
+            quote::QuoteTick,
+            trade::TradeTick,
+            Data, DataType,
+        },
+        enums::{AggressorSide, BookAction, BookType, OrderSide},
+        identifiers::{ClientId, InstrumentId, TradeId, TraderId, Venue},
+        instruments::{any::InstrumentAny, stubs::audusd_sim, synthetic::SyntheticInstrument},
+        orderbook::book::OrderBook,
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+
+    use super::{DataEngine, DataEngineConfig};
+    use crate::client::DataClient;
+
+    #[derive(Default)]
+    struct StubDataClient {
+        generic: HashSet<DataType>,
+        instrument_venues: HashSet<Venue>,
+        instruments: HashSet<InstrumentId>,
+        order_book_deltas: HashSet<InstrumentId>,
+        order_book_snapshots: HashSet<InstrumentId>,
+        quote_ticks: HashSet<InstrumentId>,
+        trade_ticks: HashSet<InstrumentId>,
+        bars: HashSet<BarType>,
+        instrument_status: HashSet<InstrumentId>,
+        instrument_close: HashSet<InstrumentId>,
+    }
+
+    impl DataClient for StubDataClient {
+        fn client_id(&self) -> ClientId {
+            ClientId::from("SIM")
+        }
+
+        fn venue(&self) -> Option<Venue> {
+            Some(Venue::from("SIM"))
+        }
+
+        fn start(&self) {}
+
+        fn stop(&self) {}
+
+        fn reset(&self) {}
+
+        fn dispose(&self) {}
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn is_disconnected(&self) -> bool {
+            false
+        }
+
+        fn subscribed_generic_data(&self) -> &HashSet<DataType> {
+            &self.generic
+        }
+
+        fn subscribed_instrument_venues(&self) -> &HashSet<Venue> {
+            &self.instrument_venues
+        }
+
+        fn subscribed_instruments(&self) -> &HashSet<InstrumentId> {
+            &self.instruments
+        }
+
+        fn subscribed_order_book_deltas(&self) -> &HashSet<InstrumentId> {
+            &self.order_book_deltas
+        }
+
+        fn subscribed_order_book_snapshots(&self) -> &HashSet<InstrumentId> {
+            &self.order_book_snapshots
+        }
+
+        fn subscribed_quote_ticks(&self) -> &HashSet<InstrumentId> {
+            &self.quote_ticks
+        }
+
+        fn subscribed_trade_ticks(&self) -> &HashSet<InstrumentId> {
+            &self.trade_ticks
+        }
+
+        fn subscribed_bars(&self) -> &HashSet<BarType> {
+            &self.bars
+        }
+
+        fn subscribed_instrument_status(&self) -> &HashSet<InstrumentId> {
+            &self.instrument_status
+        }
+
+        fn subscribed_instrument_close(&self) -> &HashSet<InstrumentId> {
+            &self.instrument_close
+        }
+
+        fn subscribe(&mut self, data_type: DataType) -> anyhow::Result<()> {
+            self.generic.insert(data_type);
+            Ok(())
+        }
+
+        fn subscribe_instruments(&mut self, venue: Option<Venue>) -> anyhow::Result<()> {
+            self.instrument_venues.extend(venue);
+            Ok(())
+        }
+
+        fn subscribe_instrument(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.instruments.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_order_book_deltas(
+            &mut self,
+            instrument_id: InstrumentId,
+            book_type: BookType,
+            depth: Option<usize>,
+        ) -> anyhow::Result<()> {
+            self.order_book_deltas.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_order_book_snapshots(
+            &mut self,
+            instrument_id: InstrumentId,
+            book_type: BookType,
+            depth: Option<usize>,
+        ) -> anyhow::Result<()> {
+            self.order_book_snapshots.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_quote_ticks(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.quote_ticks.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_trade_ticks(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.trade_ticks.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_bars(&mut self, bar_type: BarType) -> anyhow::Result<()> {
+            self.bars.insert(bar_type);
+            Ok(())
+        }
+
+        fn subscribe_instrument_status(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.instrument_status.insert(instrument_id);
+            Ok(())
+        }
+
+        fn subscribe_instrument_close(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.instrument_close.insert(instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe(&mut self, data_type: DataType) -> anyhow::Result<()> {
+            self.generic.remove(&data_type);
+            Ok(())
+        }
+
+        fn unsubscribe_instruments(&mut self, venue: Option<Venue>) -> anyhow::Result<()> {
+            if let Some(venue) = venue {
+                self.instrument_venues.remove(&venue);
+            }
+            Ok(())
+        }
+
+        fn unsubscribe_instrument(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.instruments.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_order_book_deltas(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.order_book_deltas.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_order_book_snapshots(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.order_book_snapshots.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_quote_ticks(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.quote_ticks.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_trade_ticks(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+            self.trade_ticks.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_bars(&mut self, bar_type: BarType) -> anyhow::Result<()> {
+            self.bars.remove(&bar_type);
+            Ok(())
+        }
+
+        fn unsubscribe_instrument_status(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.instrument_status.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn unsubscribe_instrument_close(
+            &mut self,
+            instrument_id: InstrumentId,
+        ) -> anyhow::Result<()> {
+            self.instrument_close.remove(&instrument_id);
+            Ok(())
+        }
+
+        fn request(&mut self, correlation_id: UUID4, data_type: DataType) {}
+
+        fn request_instruments(
+            &mut self,
+            correlation_id: UUID4,
+            venue: Venue,
+            start: Option<UnixNanos>,
+            end: Option<UnixNanos>,
+        ) {
+        }
+
+        fn request_instrument(
+            &mut self,
+            correlation_id: UUID4,
+            instrument_id: InstrumentId,
+            start: Option<UnixNanos>,
+            end: Option<UnixNanos>,
+        ) {
+        }
+
+        fn request_order_book_snapshot(
+            &mut self,
+            correlation_id: UUID4,
+            instrument_id: InstrumentId,
+            depth: Option<usize>,
+        ) {
+        }
+
+        fn request_quote_ticks(
+            &mut self,
+            correlation_id: UUID4,
+            instrument_id: InstrumentId,
+            start: Option<UnixNanos>,
+            end: Option<UnixNanos>,
+            limit: Option<usize>,
+        ) {
+        }
+
+        fn request_trade_ticks(
+            &mut self,
+            correlation_id: UUID4,
+            instrument_id: InstrumentId,
+            start: Option<UnixNanos>,
+            end: Option<UnixNanos>,
+            limit: Option<usize>,
+        ) {
+        }
+
+        fn request_bars(
+            &mut self,
+            correlation_id: UUID4,
+            bar_type: BarType,
+            start: Option<UnixNanos>,
+            end: Option<UnixNanos>,
+            limit: Option<usize>,
+        ) {
+        }
+    }
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_engine(
+        instrument: &InstrumentAny,
+    ) -> (
+        DataEngine<Running>,
+        Rc<RefCell<TestClock>>,
+        Rc<RefCell<MessageBus>>,
+    ) {
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let (engine, msgbus) = get_engine_with_clock(instrument, clock.clone());
+        (engine, clock, msgbus)
+    }
+
+    fn get_engine_with_clock(
+        instrument: &InstrumentAny,
+        clock: Rc<RefCell<dyn Clock>>,
+    ) -> (DataEngine<Running>, Rc<RefCell<MessageBus>>) {
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let msgbus = Rc::new(RefCell::new(MessageBus::new(
+            TraderId::from("TRADER-001"),
+            UUID4::new(),
+            None,
+            None,
+        )));
+        let config = DataEngineConfig {
+            debug: false,
+            time_bars_build_with_no_updates: true,
+            time_bars_timestamp_on_close: true,
+            time_bars_interval_type: "left-open".to_string(),
+            validate_data_sequence: false,
+            buffer_deltas: false,
+        };
+
+        let mut engine =
+            DataEngine::new(clock, Rc::new(RefCell::new(cache)), msgbus.clone(), config);
+        engine.register_client(Box::<StubDataClient>::default(), None);
+        let engine = engine.initialize().start().on_start();
+        (engine, msgbus)
+    }
+
+    fn command(
+        action: DataCommandAction,
+        type_name: &str,
+        metadata: &[(&str, String)],
+    ) -> DataCommand {
+        let metadata: IndexMap<String, String> = metadata
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), value.clone()))
+            .collect();
+        DataCommand {
+            client_id: ClientId::from("SIM"),
+            venue: Venue::from("SIM"),
+            data_type: DataType::new(type_name, Some(metadata)),
+            action,
+            command_id: UUID4::new(),
+            ts_init: UnixNanos::default(),
+        }
+    }
+
+    fn snapshots_command(
+        action: DataCommandAction,
+        instrument: &InstrumentAny,
+        interval_ms: Option<u64>,
+        depth: Option<usize>,
+    ) -> DataCommand {
+        let mut metadata = vec![
+            ("instrument_id", instrument.id().to_string()),
+            ("book_type", BookType::L2_MBP.to_string()),
+        ];
+        if let Some(interval_ms) = interval_ms {
+            metadata.push(("interval_ms", interval_ms.to_string()));
+        }
+        if let Some(depth) = depth {
+            metadata.push(("depth", depth.to_string()));
+        }
+        command(action, "OrderBookDeltas", &metadata)
+    }
+
+    fn add_delta(instrument: &InstrumentAny, side: OrderSide, price: &str, sequence: u64) -> Data {
+        let order = BookOrder::new(side, Price::from(price), Quantity::from(100_000), sequence);
+        Data::Delta(OrderBookDelta::new(
+            instrument.id(),
+            BookAction::Add,
+            order,
+            0,
+            sequence,
+            UnixNanos::from(sequence),
+            UnixNanos::from(sequence),
+        ))
+    }
+
+    fn quote(instrument_id: InstrumentId, bid: &str, ask: &str, ts: u64) -> Data {
+        quote_with_sizes(instrument_id, bid, ask, 100_000, 100_000, ts)
+    }
+
+    fn quote_with_sizes(
+        instrument_id: InstrumentId,
+        bid: &str,
+        ask: &str,
+        bid_size: i64,
+        ask_size: i64,
+        ts: u64,
+    ) -> Data {
+        Data::Quote(
+            QuoteTick::new(
+                instrument_id,
+                Price::from(bid),
+                Price::from(ask),
+                Quantity::from(bid_size),
+                Quantity::from(ask_size),
+                UnixNanos::from(ts),
+                UnixNanos::from(ts),
+            )
+            .unwrap(),
+        )
+    }
+
+    fn trade(instrument_id: InstrumentId, price: &str, ts: u64) -> Data {
+        Data::Trade(TradeTick::new(
+            instrument_id,
+            Price::from(price),
+            Quantity::from(10),
+            AggressorSide::Buyer,
+            TradeId::from(ts.to_string().as_str()),
+            UnixNanos::from(ts),
+            UnixNanos::from(ts),
+        ))
+    }
+
+    fn synthetic_command(action: DataCommandAction, type_name: &str) -> DataCommand {
+        let synthetic_id = SyntheticInstrument::default().id;
+        DataCommand {
+            venue: Venue::synthetic(),
+            ..command(
+                action,
+                type_name,
+                &[("instrument_id", synthetic_id.to_string())],
+            )
+        }
+    }
+
+    fn advance_clock(clock: &Rc<RefCell<TestClock>>, to_time_ns: u64) {
+        let events = clock
+            .borrow_mut()
+            .advance_time(UnixNanos::from(to_time_ns), true);
+        for event in events {
+            let callback = clock.borrow().rust_callback(&event).unwrap();
+            callback.call(event);
+        }
+    }
+
+    #[rstest]
+    fn test_order_book_snapshots_published_on_interval(instrument: InstrumentAny) {
+        let (mut engine, clock, msgbus) = get_engine(&instrument);
+        let (handler, snapshots) = get_message_saving_handler::<OrderBook>(None);
+        msgbus
+            .borrow_mut()
+            .subscribe("data.book.snapshots.SIM.AUD/USD.1000", handler, None);
+
+        engine.execute(snapshots_command(
+            DataCommandAction::Subscribe,
+            &instrument,
+            Some(1_000),
+            Some(1),
+        ));
+        engine.process(add_delta(&instrument, OrderSide::Buy, "1.00000", 1));
+        engine.process(add_delta(&instrument, OrderSide::Buy, "0.99990", 2));
+        engine.process(add_delta(&instrument, OrderSide::Sell, "1.00010", 3));
+        advance_clock(&clock, 2_000_000_000);
+
+        assert_eq!(
+            clock.borrow().timer_names(),
+            vec!["OrderBook|AUD/USD.SIM|1000"]
+        );
+        assert!(engine
+            .subscribed_order_book_snapshots()
+            .contains(&instrument.id()));
+        let snapshots = snapshots.borrow();
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].bids().count(), 1);
+        assert_eq!(snapshots[0].best_bid_price(), Some(Price::from("1.00000")));
+        assert_eq!(snapshots[0].best_ask_price(), Some(Price::from("1.00010")));
+    }
+
+    #[rstest]
+    fn test_unsubscribe_order_book_interval_stops_snapshots(instrument: InstrumentAny) {
+        let (mut engine, clock, msgbus) = get_engine(&instrument);
+        let (handler, snapshots) = get_message_saving_handler::<OrderBook>(None);
+        msgbus.borrow_mut().subscribe(
+            "data.book.snapshots.SIM.AUD/USD.1000",
+            handler.clone(),
+            None,
+        );
+        msgbus
+            .borrow_mut()
+            .subscribe("data.book.snapshots.SIM.AUD/USD.500", handler, None);
+        engine.execute(snapshots_command(
+            DataCommandAction::Subscribe,
+            &instrument,
+            Some(1_000),
+            None,
+        ));
+        engine.execute(snapshots_command(
+            DataCommandAction::Subscribe,
+            &instrument,
+            Some(500),
+            None,
+        ));
+
+        engine.execute(snapshots_command(
+            DataCommandAction::Unsubscibe,
+            &instrument,
+            Some(1_000),
+            None,
+        ));
+        advance_clock(&clock, 1_000_000_000);
+
+        assert_eq!(
+            clock.borrow().timer_names(),
+            vec!["OrderBook|AUD/USD.SIM|500"]
+        );
+        assert_eq!(snapshots.borrow().len(), 2);
+        assert!(engine
+            .subscribed_order_book_snapshots()
+            .contains(&instrument.id()));
+
+        engine.execute(snapshots_command(
+            DataCommandAction::Unsubscibe,
+            &instrument,
+            Some(500),
+            None,
+        ));
+        advance_clock(&clock, 2_000_000_000);
+
+        assert_eq!(clock.borrow().timer_count(), 0);
+        assert_eq!(snapshots.borrow().len(), 2);
+        assert!(engine.subscribed_order_book_snapshots().is_empty());
+    }
+
+    #[rstest]
+    fn test_unsubscribe_order_book_snapshots_without_interval_keeps_intervals(
+        instrument: InstrumentAny,
+    ) {
+        let (mut engine, clock, _) = get_engine(&instrument);
+        engine.execute(snapshots_command(
+            DataCommandAction::Subscribe,
+            &instrument,
+            Some(1_000),
+            None,
+        ));
+
+        engine.execute(snapshots_command(
+            DataCommandAction::Unsubscibe,
+            &instrument,
+            None,
+            None,
+        ));
+
+        assert_eq!(
+            clock.borrow().timer_names(),
+            vec!["OrderBook|AUD/USD.SIM|1000"]
+        );
+        assert!(engine
+            .subscribed_order_book_snapshots()
+            .contains(&instrument.id()));
+    }
+
+    #[rstest]
+    fn test_internal_bar_subscription_aggregates_and_publishes_bars(instrument: InstrumentAny) {
+        let (mut engine, _, msgbus) = get_engine(&instrument);
+        let bar_type = BarType::from("AUD/USD.SIM-2-TICK-MID-INTERNAL");
+        let (handler, bars) = get_message_saving_handler::<Bar>(None);
+        msgbus
+            .borrow_mut()
+            .subscribe(&format!("data.bars.{bar_type}"), handler, None);
+        let metadata = [("bar_type", bar_type.to_string())];
+
+        engine.execute(command(DataCommandAction::Subscribe, "Bar", &metadata));
+        engine.process(quote(instrument.id(), "1.00000", "1.00002", 1));
+        engine.process(quote(instrument.id(), "1.00002", "1.00004", 2));
+
+        assert_eq!(engine.subscribed_quote_ticks(), vec![instrument.id()]);
+        assert!(engine.subscribed_bars().is_empty());
+        {
+            let bars = bars.borrow();
+            assert_eq!(bars.len(), 1);
+            assert_eq!(bars[0].bar_type, bar_type);
+            assert_eq!(bars[0].open, Price::from("1.00001"));
+            assert_eq!(bars[0].close, Price::from("1.00003"));
+            assert_eq!(bars[0].volume, Quantity::from(200_000));
+        }
+
+        engine.execute(command(DataCommandAction::Unsubscibe, "Bar", &metadata));
+        engine.process(quote(instrument.id(), "1.00000", "1.00002", 3));
+        engine.process(quote(instrument.id(), "1.00002", "1.00004", 4));
+
+        assert_eq!(bars.borrow().len(), 1);
+        assert!(engine.subscribed_quote_ticks().is_empty());
+    }
+
+    #[rstest]
+    fn test_bar_aggregation_quotes_released_with_last_aggregator_and_subscriber(
+        instrument: InstrumentAny,
+    ) {
+        let (mut engine, _, _) = get_engine(&instrument);
+        let bid_metadata = [("bar_type", "AUD/USD.SIM-2-TICK-BID-INTERNAL".to_string())];
+        let ask_metadata = [("bar_type", "AUD/USD.SIM-2-TICK-ASK-INTERNAL".to_string())];
+        let quote_metadata = [
+            ("instrument_id", instrument.id().to_string()),
+            ("book_type", BookType::L1_MBP.to_string()),
+        ];
+        engine.execute(command(DataCommandAction::Subscribe, "Bar", &bid_metadata));
+        engine.execute(command(DataCommandAction::Subscribe, "Bar", &ask_metadata));
+        engine.execute(command(
+            DataCommandAction::Subscribe,
+            "QuoteTick",
+            &quote_metadata,
+        ));
+
+        engine.execute(command(DataCommandAction::Unsubscibe, "Bar", &bid_metadata));
+        assert_eq!(engine.subscribed_quote_ticks(), vec![instrument.id()]);
+
+        engine.execute(command(
+            DataCommandAction::Unsubscibe,
+            "QuoteTick",
+            &quote_metadata,
+        ));
+        assert_eq!(engine.subscribed_quote_ticks(), vec![instrument.id()]);
+
+        engine.execute(command(DataCommandAction::Unsubscibe, "Bar", &ask_metadata));
+        assert!(engine.subscribed_quote_ticks().is_empty());
+    }
+
+    #[rstest]
+    fn test_bar_aggregation_trades_kept_for_subscriber(instrument: InstrumentAny) {
+        let (mut engine, _, _) = get_engine(&instrument);
+        let bar_metadata = [("bar_type", "AUD/USD.SIM-2-TICK-LAST-INTERNAL".to_string())];
+        let trade_metadata = [("instrument_id", instrument.id().to_string())];
+        engine.execute(command(
+            DataCommandAction::Subscribe,
+            "TradeTick",
+            &trade_metadata,
+        ));
+        engine.execute(command(DataCommandAction::Subscribe, "Bar", &bar_metadata));
+
+        engine.execute(command(DataCommandAction::Unsubscibe, "Bar", &bar_metadata));
+
+        assert_eq!(engine.subscribed_trade_ticks(), vec![instrument.id()]);
+    }
+
+    #[rstest]
+    fn test_time_bar_aggregation_not_started_with_live_clock(instrument: InstrumentAny) {
+        let clock = Rc::new(RefCell::new(LiveClock::new()));
+        let (mut engine, _) = get_engine_with_clock(&instrument, clock.clone());
+        let metadata = [("bar_type", "AUD/USD.SIM-1-SECOND-MID-INTERNAL".to_string())];
+
+        engine.execute(command(DataCommandAction::Subscribe, "Bar", &metadata));
+
+        assert_eq!(clock.borrow().timer_count(), 0);
+        assert!(engine.subscribed_quote_ticks().is_empty());
+    }
+
+    #[rstest]
+    fn test_synthetic_quotes_published_once_all_components_quoted(instrument: InstrumentAny) {
+        let (mut engine, _, msgbus) = get_engine(&instrument);
+        let synthetic = SyntheticInstrument::default();
+        engine.cache.borrow_mut().add_synthetic(synthetic).unwrap();
+        let (handler, quotes) = get_message_saving_handler::<QuoteTick>(None);
+        msgbus
+            .borrow_mut()
+            .subscribe("data.quotes.SYNTH.BTC-LTC", handler, None);
+
+        engine.execute(synthetic_command(DataCommandAction::Subscribe, "QuoteTick"));
+        engine.process(quote(
+            InstrumentId::from("BTC.BINANCE"),
+            "100.00",
+            "101.00",
+            1,
+        ));
+
+        assert!(quotes.borrow().is_empty());
+
+        engine.process(quote_with_sizes(
+            InstrumentId::from("LTC.BINANCE"),
+            "200.00",
+            "203.00",
+            50_000,
+            200_000,
+            2,
+        ));
+        engine.process(quote(
+            InstrumentId::from("BTC.BINANCE"),
+            "110.00",
+            "111.00",
+            3,
+        ));
+
+        let quotes = quotes.borrow();
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0].instrument_id, InstrumentId::from("BTC-LTC.SYNTH"));
+        assert_eq!(quotes[0].bid_price, Price::from("150.00"));
+        assert_eq!(quotes[0].ask_price, Price::from("152.00"));
+        assert_eq!(quotes[0].bid_size, Quantity::from(50_000));
+        assert_eq!(quotes[0].ask_size, Quantity::from(100_000));
+        assert_eq!(quotes[0].ts_event, UnixNanos::from(2));
+        assert_eq!(quotes[1].bid_price, Price::from("155.00"));
+        assert_eq!(quotes[1].ask_price, Price::from("157.00"));
+    }
+
+    #[rstest]
+    fn test_synthetic_trades_published_from_component_trades(instrument: InstrumentAny) {
+        let (mut engine, _, msgbus) = get_engine(&instrument);
+        let synthetic = SyntheticInstrument::default();
+        engine.cache.borrow_mut().add_synthetic(synthetic).unwrap();
+        let (handler, trades) = get_message_saving_handler::<TradeTick>(None);
+        msgbus
+            .borrow_mut()
+            .subscribe("data.trades.SYNTH.BTC-LTC", handler, None);
+
+        engine.execute(synthetic_command(DataCommandAction::Subscribe, "TradeTick"));
+        engine.process(trade(InstrumentId::from("BTC.BINANCE"), "100.00", 1));
+        engine.process(trade(InstrumentId::from("LTC.BINANCE"), "300.00", 2));
+
+        let trades = trades.borrow();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].instrument_id, InstrumentId::from("BTC-LTC.SYNTH"));
+        assert_eq!(trades[0].price, Price::from("200.00"));
+        assert_eq!(trades[0].size, Quantity::from(10));
+        assert_ne!(trades[0].trade_id, TradeId::from("2"));
+    }
+
+    #[rstest]
+    fn test_unsubscribe_synthetic_stops_publication(instrument: InstrumentAny) {
+        let (mut engine, _, msgbus) = get_engine(&instrument);
+        let synthetic = SyntheticInstrument::default();
+        engine.cache.borrow_mut().add_synthetic(synthetic).unwrap();
+        let (handler, quotes) = get_message_saving_handler::<QuoteTick>(None);
+        msgbus
+            .borrow_mut()
+            .subscribe("data.quotes.SYNTH.BTC-LTC", handler, None);
+        engine.execute(synthetic_command(DataCommandAction::Subscribe, "QuoteTick"));
+
+        engine.execute(synthetic_command(
+            DataCommandAction::Unsubscibe,
+            "QuoteTick",
+        ));
+        engine.process(quote(
+            InstrumentId::from("BTC.BINANCE"),
+            "100.00",
+            "101.00",
+            1,
+        ));
+        engine.process(quote(
+            InstrumentId::from("LTC.BINANCE"),
+            "200.00",
+            "203.00",
+            2,
+        ));
+
+        assert!(quotes.borrow().is_empty());
+        assert!(engine.synthetic_quote_feeds.is_empty());
+    }
+}
@@ -0,0 +1,353 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+use log::debug;
+use nautilus_common::{
+    cache::Cache, clock::Clock, handlers::EventHandler, msgbus::MessageBus, timer::TimeEvent,
+};
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::{
+    enums::{TimeInForce, TriggerType},
+    events::order::{OrderEventAny, OrderUpdated},
+    identifiers::{ClientOrderId, ExecAlgorithmId},
+    orders::{any::OrderAny, limit::LimitOrder, market::MarketOrder},
+    types::{price::Price, quantity::Quantity},
+};
+use ustr::Ustr;
+
+use crate::messages::{submit::SubmitOrder, TradingCommand};
+
+/// The behavior of an execution algorithm, which works primary orders by spawning
+/// child orders through its [`ExecAlgorithmCore`].
+pub trait ExecAlgorithm {
+    /// Returns the ID of the execution algorithm.
+    fn id(&self) -> ExecAlgorithmId;
+
+    /// Called when a primary order is received for the algorithm to execute.
+    fn on_order(&mut self, core: &mut ExecAlgorithmCore, order: OrderAny) -> anyhow::Result<()>;
+
+    /// Called when a timer set through the `core` fires.
+    fn on_time_event(
+        &mut self,
+        _core: &mut ExecAlgorithmCore,
+        _event: &TimeEvent,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Called with events for primary and spawned orders of the algorithm.
+    fn on_order_event(
+        &mut self,
+        _core: &mut ExecAlgorithmCore,
+        _event: &OrderEventAny,
+    ) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+/// Provides the common state and actions for execution algorithms.
+///
+/// Child orders spawned from a primary order are assigned the client order ID
+/// `{primary}-E{sequence}` and an `exec_spawn_id` of the primary client order ID. The
+/// spawned quantity is taken from the primary order, which is submitted last with
+/// whatever quantity remains.
+///
+/// Timers are set on the shared clock, with time events passed to the algorithm through
+/// the handler registered by its [`ExecAlgorithmHost`](super::host::ExecAlgorithmHost).
+pub struct ExecAlgorithmCore {
+    pub id: ExecAlgorithmId,
+    pub clock: Rc<RefCell<dyn Clock>>,
+    pub cache: Rc<RefCell<Cache>>,
+    pub msgbus: Rc<RefCell<MessageBus>>,
+    pub(crate) timer_handler: Option<EventHandler>,
+    exec_spawn_sequences: HashMap<ClientOrderId, u32>,
+}
+
+impl ExecAlgorithmCore {
+    /// Creates a new [`ExecAlgorithmCore`] instance.
+    #[must_use]
+    pub fn new(
+        id: ExecAlgorithmId,
+        clock: Rc<RefCell<dyn Clock>>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) -> Self {
+        Self {
+            id,
+            clock,
+            cache,
+            msgbus,
+            timer_handler: None,
+            exec_spawn_sequences: HashMap::new(),
+        }
+    }
+
+    // -- TIMERS --------------------------------------------------------------
+
+    /// Sets a timer to fire every `interval_ns` from now until the optional `stop_time_ns`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// - `name` is not a valid string.
+    /// - The algorithm is not registered with a handler for its time events.
+    pub fn set_timer(
+        &mut self,
+        name: &str,
+        interval_ns: u64,
+        stop_time_ns: Option<UnixNanos>,
+    ) -> anyhow::Result<()> {
+        let Some(handler) = self.timer_handler.clone() else {
+            anyhow::bail!("Cannot set timer {name}: {} is not registered", self.id);
+        };
+        let mut clock = self.clock.borrow_mut();
+        let start_time_ns = clock.timestamp_ns();
+        clock.set_timer_ns(
+            name,
+            interval_ns,
+            start_time_ns,
+            stop_time_ns,
+            Some(handler),
+        )
+    }
+
+    /// Cancels the timer with the given `name` (if found).
+    pub fn cancel_timer(&mut self, name: &str) {
+        self.clock.borrow_mut().cancel_timer(name);
+    }
+
+    // -- SPAWNING ------------------------------------------------------------
+
+    /// Spawns a `MARKET` order for `quantity` from the `primary` order.
+    ///
+    /// If `reduce_primary` is true then the `primary` quantity is reduced by `quantity`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// - The `primary` order is not for this algorithm.
+    /// - Reducing the `primary` order would leave no quantity remaining.
+    /// - The spawned order is invalid.
+    pub fn spawn_market(
+        &mut self,
+        primary: &mut OrderAny,
+        quantity: Quantity,
+        time_in_force: TimeInForce,
+        reduce_only: bool,
+        tags: Option<Vec<Ustr>>,
+        reduce_primary: bool,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.spawn_client_order_id(primary)?;
+        if reduce_primary {
+            self.reduce_primary_order(primary, quantity)?;
+        }
+
+        let order = MarketOrder::new(
+            primary.trader_id(),
+            primary.strategy_id(),
+            primary.instrument_id(),
+            client_order_id,
+            primary.order_side(),
+            quantity,
+            time_in_force,
+            UUID4::new(),
+            self.clock.borrow().timestamp_ns(),
+            reduce_only,
+            primary.is_quote_quantity(),
+            None,
+            None,
+            None,
+            None,
+            Some(self.id),
+            None,
+            Some(primary.client_order_id()),
+            tags,
+        )?;
+        Ok(OrderAny::Market(order))
+    }
+
+    /// Spawns a `LIMIT` order for `quantity` at `price` from the `primary` order.
+    ///
+    /// If `reduce_primary` is true then the `primary` quantity is reduced by `quantity`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// - The `primary` order is not for this algorithm.
+    /// - Reducing the `primary` order would leave no quantity remaining.
+    /// - The spawned order is invalid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn spawn_limit(
+        &mut self,
+        primary: &mut OrderAny,
+        quantity: Quantity,
+        price: Price,
+        time_in_force: TimeInForce,
+        expire_time: Option<UnixNanos>,
+        post_only: bool,
+        reduce_only: bool,
+        display_qty: Option<Quantity>,
+        emulation_trigger: Option<TriggerType>,
+        tags: Option<Vec<Ustr>>,
+        reduce_primary: bool,
+    ) -> anyhow::Result<OrderAny> {
+        let client_order_id = self.spawn_client_order_id(primary)?;
+        if reduce_primary {
+            self.reduce_primary_order(primary, quantity)?;
+        }
+
+        let order = LimitOrder::new(
+            primary.trader_id(),
+            primary.strategy_id(),
+            primary.instrument_id(),
+            client_order_id,
+            primary.order_side(),
+            quantity,
+            price,
+            time_in_force,
+            expire_time,
+            post_only,
+            reduce_only,
+            primary.is_quote_quantity(),
+            display_qty,
+            emulation_trigger,
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(self.id),
+            None,
+            Some(primary.client_order_id()),
+            tags,
+            UUID4::new(),
+            self.clock.borrow().timestamp_ns(),
+        )?;
+        Ok(OrderAny::Limit(order))
+    }
+
+    fn spawn_client_order_id(&mut self, primary: &OrderAny) -> anyhow::Result<ClientOrderId> {
+        if primary.exec_algorithm_id() != Some(self.id) {
+            anyhow::bail!(
+                "Cannot spawn from {}: not a primary order for {}",
+                primary.client_order_id(),
+                self.id
+            );
+        }
+
+        let sequence = self
+            .exec_spawn_sequences
+            .entry(primary.client_order_id())
+            .or_default();
+        *sequence += 1;
+
+        Ok(ClientOrderId::from(
+            format!("{}-E{sequence}", primary.client_order_id()).as_str(),
+        ))
+    }
+
+    fn reduce_primary_order(
+        &mut self,
+        primary: &mut OrderAny,
+        spawn_qty: Quantity,
+    ) -> anyhow::Result<()> {
+        if spawn_qty >= primary.leaves_qty() {
+            anyhow::bail!(
+                "Cannot reduce {} by {spawn_qty}: leaves quantity is {}",
+                primary.client_order_id(),
+                primary.leaves_qty()
+            );
+        }
+
+        let ts_now = self.clock.borrow().timestamp_ns();
+        let event = OrderEventAny::Updated(
+            OrderUpdated::new(
+                primary.trader_id(),
+                primary.strategy_id(),
+                primary.instrument_id(),
+                primary.client_order_id(),
+                primary.quantity() - spawn_qty,
+                UUID4::new(),
+                ts_now,
+                ts_now,
+                false,
+                primary.venue_order_id(),
+                primary.account_id(),
+                None,
+                None,
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        primary.apply(event)?;
+        self.cache.borrow_mut().update_order(primary)
+    }
+
+    // -- COMMANDS ------------------------------------------------------------
+
+    /// Submits the given `order`, which is either a spawned order or the primary order.
+    ///
+    /// Emulated orders are sent to the `OrderEmulator`, all others to the `RiskEngine`.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if a spawned order cannot be added to the cache.
+    pub fn submit_order(&mut self, order: OrderAny) -> anyhow::Result<()> {
+        let command = SubmitOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            exec_algorith_id: Some(self.id),
+            position_id: order.position_id(),
+            command_id: UUID4::new(),
+            ts_init: self.clock.borrow().timestamp_ns(),
+            ..Default::default()
+        };
+
+        let is_emulated = order
+            .emulation_trigger()
+            .is_some_and(|trigger| trigger != TriggerType::NoTrigger);
+
+        {
+            let mut cache = self.cache.borrow_mut();
+            if cache.order_exists(&order.client_order_id()) {
+                cache.update_order(&order)?;
+            } else {
+                cache.add_order(order, None, None, false)?;
+            }
+        }
+
+        debug!("Submitting {}", command.client_order_id);
+        let endpoint = if is_emulated {
+            "OrderEmulator.execute"
+        } else {
+            "RiskEngine.execute"
+        };
+        self.send_command(endpoint, &TradingCommand::SubmitOrder(command));
+        Ok(())
+    }
+
+    pub(crate) fn send_command(&self, endpoint: &str, command: &TradingCommand) {
+        self.msgbus.borrow().send(endpoint, command as &dyn Any);
+    }
+
+    pub(crate) fn send_exec_event(&self, event: &OrderEventAny) {
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", event as &dyn Any);
+    }
+}
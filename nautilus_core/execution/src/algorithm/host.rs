@@ -0,0 +1,594 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashSet, VecDeque},
+    rc::{Rc, Weak},
+};
+
+use log::{debug, error, warn};
+use nautilus_common::{
+    cache::Cache,
+    clock::Clock,
+    handlers::{EventHandler, SafeTimeEventCallback},
+    logging::{CMD, EVT, RECV},
+    msgbus::{MessageBus, MessageHandler, ShareableMessageHandler},
+    timer::TimeEvent,
+};
+use nautilus_core::uuid::UUID4;
+use nautilus_model::{
+    enums::OrderStatus,
+    events::order::{OrderCanceled, OrderEventAny},
+    identifiers::{ClientOrderId, ExecAlgorithmId, StrategyId},
+    orders::any::OrderAny,
+    types::quantity::Quantity,
+};
+use ustr::Ustr;
+
+use super::base::{ExecAlgorithm, ExecAlgorithmCore};
+use crate::messages::{
+    cancel::CancelOrder, submit::SubmitOrder, submit_list::SubmitOrderList, TradingCommand,
+};
+
+/// Hosts an [`ExecAlgorithm`], dispatching the commands, order events and time events
+/// for its orders.
+///
+/// Once registered, commands are received on the `{exec_algorithm_id}.execute` endpoint
+/// and algorithm timers fire through Rust callbacks on the clock. Once started, order
+/// events are received from the `events.order.{strategy_id}` topic of each strategy.
+///
+/// Messages received while the host is already handling a message (such as an event for
+/// an order the algorithm has just submitted) are handled in the order received once the
+/// host has finished.
+pub struct ExecAlgorithmHost<A: ExecAlgorithm> {
+    core: ExecAlgorithmCore,
+    algorithm: A,
+    deferred: Rc<RefCell<VecDeque<HostMessage>>>,
+    handler: Option<ShareableMessageHandler>,
+    subscribed_strategies: HashSet<StrategyId>,
+}
+
+impl<A: ExecAlgorithm> ExecAlgorithmHost<A> {
+    /// Creates a new [`ExecAlgorithmHost`] instance.
+    #[must_use]
+    pub fn new(
+        algorithm: A,
+        clock: Rc<RefCell<dyn Clock>>,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+    ) -> Self {
+        Self {
+            core: ExecAlgorithmCore::new(algorithm.id(), clock, cache, msgbus),
+            algorithm,
+            deferred: Rc::new(RefCell::new(VecDeque::new())),
+            handler: None,
+            subscribed_strategies: HashSet::new(),
+        }
+    }
+
+    /// Registers the `host` on the message bus at its [`ExecAlgorithmHost::endpoint`],
+    /// and as the handler for the time events of its algorithm timers.
+    pub fn register(host: &Rc<RefCell<Self>>)
+    where
+        A: 'static,
+    {
+        let weak_host = Rc::downgrade(host);
+        let callback = SafeTimeEventCallback::new(Rc::new(move |event: TimeEvent| {
+            if let Some(host) = weak_host.upgrade() {
+                let mut host = host.borrow_mut();
+                host.on_time_event(&event);
+                host.handle_deferred();
+            }
+        }));
+
+        let mut host_ref = host.borrow_mut();
+        host_ref.core.timer_handler = Some(EventHandler::from_rust(callback));
+
+        let handler = ShareableMessageHandler(Rc::new(ExecAlgorithmHandler {
+            id: Ustr::from(&host_ref.endpoint()),
+            host: Rc::downgrade(host),
+            deferred: host_ref.deferred.clone(),
+        }));
+        host_ref
+            .core
+            .msgbus
+            .borrow_mut()
+            .register(&host_ref.endpoint(), handler.clone());
+        host_ref.handler = Some(handler);
+    }
+
+    /// Starts the `host`, subscribing to the order events of each of the `strategy_ids` so
+    /// events for the primary and spawned orders of the algorithm are handled.
+    ///
+    /// The host must already be registered.
+    pub fn start(host: &Rc<RefCell<Self>>, strategy_ids: &[StrategyId]) {
+        let mut host = host.borrow_mut();
+        let Some(handler) = host.handler.clone() else {
+            error!("Cannot start {}: not registered", host.core.id);
+            return;
+        };
+        let msgbus = host.core.msgbus.clone();
+        for strategy_id in strategy_ids {
+            if host.subscribed_strategies.insert(*strategy_id) {
+                let topic = format!("events.order.{strategy_id}");
+                msgbus.borrow_mut().subscribe(&topic, handler.clone(), None);
+            }
+        }
+    }
+
+    /// Stops the `host`, unsubscribing from the order events of all strategies.
+    pub fn stop(host: &Rc<RefCell<Self>>) {
+        let mut host = host.borrow_mut();
+        let Some(handler) = host.handler.clone() else {
+            return;
+        };
+        let msgbus = host.core.msgbus.clone();
+        for strategy_id in host.subscribed_strategies.drain() {
+            let topic = format!("events.order.{strategy_id}");
+            msgbus.borrow_mut().unsubscribe(&topic, handler.clone());
+        }
+    }
+
+    #[must_use]
+    pub fn id(&self) -> ExecAlgorithmId {
+        self.core.id
+    }
+
+    /// Returns the message bus endpoint for commands to the algorithm.
+    #[must_use]
+    pub fn endpoint(&self) -> String {
+        format!("{}.execute", self.core.id)
+    }
+
+    #[must_use]
+    pub const fn core(&self) -> &ExecAlgorithmCore {
+        &self.core
+    }
+
+    #[must_use]
+    pub const fn algorithm(&self) -> &A {
+        &self.algorithm
+    }
+
+    pub fn execute(&mut self, command: TradingCommand) {
+        debug!("{RECV}{CMD} {command:?}");
+
+        match command {
+            TradingCommand::SubmitOrder(cmd) => self.handle_submit_order(cmd),
+            TradingCommand::SubmitOrderList(cmd) => self.handle_submit_order_list(cmd),
+            TradingCommand::CancelOrder(cmd) => self.handle_cancel_order(cmd),
+            _ => self.core.send_command("RiskEngine.execute", &command),
+        }
+    }
+
+    /// Handles the given order `event`, passing events for the algorithms primary and
+    /// spawned orders through to the algorithm.
+    pub fn handle_event(&mut self, event: &OrderEventAny) {
+        let order = self
+            .core
+            .cache
+            .borrow()
+            .order(&event.client_order_id())
+            .cloned();
+        let Some(order) = order else {
+            return;
+        };
+        if order.exec_algorithm_id() != Some(self.core.id) {
+            return;
+        }
+
+        debug!("{RECV}{EVT} {event:?}");
+
+        if let Err(e) = self.algorithm.on_order_event(&mut self.core, event) {
+            error!("Error handling order event: {e}");
+        }
+    }
+
+    /// Handles the given time `event` from an algorithm timer.
+    pub fn on_time_event(&mut self, event: &TimeEvent) {
+        if let Err(e) = self.algorithm.on_time_event(&mut self.core, event) {
+            error!("Error handling time event {}: {e}", event.name);
+        }
+    }
+
+    /// Returns the total filled quantity of the orders spawned from `primary_id`,
+    /// including the primary order itself.
+    #[must_use]
+    pub fn spawned_filled_qty(&self, primary_id: &ClientOrderId) -> Option<Quantity> {
+        self.core
+            .cache
+            .borrow()
+            .exec_spawn_total_filled_qty(primary_id, false)
+    }
+
+    /// Handles the messages received while the host was busy, in the order received.
+    fn handle_deferred(&mut self) {
+        loop {
+            let message = self.deferred.borrow_mut().pop_front();
+            match message {
+                Some(HostMessage::Command(command)) => self.execute(command),
+                Some(HostMessage::Event(event)) => self.handle_event(&event),
+                None => break,
+            }
+        }
+    }
+
+    // -- COMMAND HANDLERS ----------------------------------------------------
+
+    fn handle_submit_order(&mut self, command: SubmitOrder) {
+        let order = self
+            .core
+            .cache
+            .borrow()
+            .order(&command.client_order_id)
+            .cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot execute order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+        self.on_order(order, command);
+    }
+
+    fn handle_submit_order_list(&mut self, command: SubmitOrderList) {
+        for order in &command.order_list.orders {
+            let command = SubmitOrder {
+                trader_id: command.trader_id,
+                client_id: command.client_id,
+                strategy_id: command.strategy_id,
+                instrument_id: command.instrument_id,
+                client_order_id: order.client_order_id(),
+                exec_algorith_id: order.exec_algorithm_id(),
+                position_id: command.position_id,
+                command_id: UUID4::new(),
+                ts_init: command.ts_init,
+                ..Default::default()
+            };
+            self.on_order(order.clone(), command);
+        }
+    }
+
+    fn on_order(&mut self, order: OrderAny, command: SubmitOrder) {
+        if order.exec_algorithm_id() != Some(self.core.id) {
+            error!(
+                "Cannot execute order: {} is not for {}",
+                order.client_order_id(),
+                self.core.id
+            );
+            return;
+        }
+
+        // Spawned orders come back from the `OrderEmulator` once released
+        let is_spawned = order
+            .exec_spawn_id()
+            .is_some_and(|exec_spawn_id| exec_spawn_id != order.client_order_id());
+        if is_spawned {
+            self.core
+                .send_command("RiskEngine.execute", &TradingCommand::SubmitOrder(command));
+            return;
+        }
+
+        if let Err(e) = self.algorithm.on_order(&mut self.core, order) {
+            error!("Error executing order: {e}");
+        }
+    }
+
+    fn handle_cancel_order(&mut self, command: CancelOrder) {
+        let order = self
+            .core
+            .cache
+            .borrow()
+            .order(&command.client_order_id)
+            .cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot cancel order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        if order.is_closed() {
+            warn!("Cannot cancel order: already closed");
+            return;
+        }
+
+        if !matches!(
+            order.status(),
+            OrderStatus::Initialized | OrderStatus::Released
+        ) {
+            // Order is working at the venue
+            self.core
+                .send_command("RiskEngine.execute", &TradingCommand::CancelOrder(command));
+            return;
+        }
+
+        // Order is still held by the algorithm
+        let ts_now = self.core.clock.borrow().timestamp_ns();
+        let event = OrderEventAny::Canceled(
+            OrderCanceled::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+                false,
+                order.venue_order_id(),
+                order.account_id(),
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        self.core.send_exec_event(&event);
+
+        if let Err(e) = self.algorithm.on_order_event(&mut self.core, &event) {
+            error!("Error handling order event: {e}");
+        }
+    }
+}
+
+enum HostMessage {
+    Command(TradingCommand),
+    Event(Box<OrderEventAny>),
+}
+
+/// Passes commands received on the endpoint of an [`ExecAlgorithmHost`], and the order
+/// events it is subscribed to, to the host.
+struct ExecAlgorithmHandler<A: ExecAlgorithm> {
+    id: Ustr,
+    host: Weak<RefCell<ExecAlgorithmHost<A>>>,
+    deferred: Rc<RefCell<VecDeque<HostMessage>>>,
+}
+
+impl<A: ExecAlgorithm> MessageHandler for ExecAlgorithmHandler<A> {
+    fn id(&self) -> Ustr {
+        self.id
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        let message = if let Some(command) = message.downcast_ref::<TradingCommand>() {
+            HostMessage::Command(command.clone())
+        } else if let Some(event) = message.downcast_ref::<OrderEventAny>() {
+            HostMessage::Event(Box::new(event.clone()))
+        } else {
+            error!("Cannot handle message for {}: unrecognized type", self.id);
+            return;
+        };
+        let Some(host) = self.host.upgrade() else {
+            return;
+        };
+
+        self.deferred.borrow_mut().push_back(message);
+        let Ok(mut host) = host.try_borrow_mut() else {
+            return; // Handled once the host has finished the current message
+        };
+        host.handle_deferred();
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        clock::TestClock,
+        msgbus::{MessageBus, MessageHandler, ShareableMessageHandler},
+    };
+    use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+    use nautilus_model::{
+        enums::{OrderSide, TimeInForce},
+        events::order::OrderEventAny,
+        identifiers::{AccountId, ClientOrderId, ExecAlgorithmId, StrategyId, TraderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{any::OrderAny, market::MarketOrder, stubs::TestOrderEventStubs},
+        types::quantity::Quantity,
+    };
+    use rstest::{fixture, rstest};
+    use ustr::Ustr;
+
+    use super::ExecAlgorithmHost;
+    use crate::{
+        algorithm::base::{ExecAlgorithm, ExecAlgorithmCore},
+        messages::{submit::SubmitOrder, TradingCommand},
+    };
+
+    type SavedEvents = Rc<RefCell<Vec<OrderEventAny>>>;
+    type SharedHost = Rc<RefCell<ExecAlgorithmHost<SpawningExecAlgorithm>>>;
+
+    /// Spawns a single market order for half of each primary order, saving the order
+    /// events received.
+    struct SpawningExecAlgorithm {
+        events: SavedEvents,
+    }
+
+    impl ExecAlgorithm for SpawningExecAlgorithm {
+        fn id(&self) -> ExecAlgorithmId {
+            ExecAlgorithmId::from("SPAWN")
+        }
+
+        fn on_order(
+            &mut self,
+            core: &mut ExecAlgorithmCore,
+            mut order: OrderAny,
+        ) -> anyhow::Result<()> {
+            let quantity = Quantity::from(order.quantity().as_f64() as i64 / 2);
+            let spawned =
+                core.spawn_market(&mut order, quantity, TimeInForce::Gtc, false, None, true)?;
+            core.submit_order(spawned)
+        }
+
+        fn on_order_event(
+            &mut self,
+            _core: &mut ExecAlgorithmCore,
+            event: &OrderEventAny,
+        ) -> anyhow::Result<()> {
+            self.events.borrow_mut().push(event.clone());
+            Ok(())
+        }
+    }
+
+    /// Publishes an `OrderSubmitted` event for each order submitted, as the execution
+    /// engine would for an order routed to a venue.
+    struct SubmittingHandler {
+        msgbus: Rc<RefCell<MessageBus>>,
+        cache: Rc<RefCell<Cache>>,
+    }
+
+    impl MessageHandler for SubmittingHandler {
+        fn id(&self) -> Ustr {
+            Ustr::from("RiskEngine.execute")
+        }
+
+        fn handle(&self, message: &dyn Any) {
+            let Some(TradingCommand::SubmitOrder(command)) =
+                message.downcast_ref::<TradingCommand>()
+            else {
+                return;
+            };
+            let order = self
+                .cache
+                .borrow()
+                .order(&command.client_order_id)
+                .cloned()
+                .unwrap();
+            let event = TestOrderEventStubs::order_submitted(&order, AccountId::from("SIM-001"));
+            let topic = format!("events.order.{}", order.strategy_id());
+            self.msgbus.borrow().publish(&topic, &event as &dyn Any);
+        }
+    }
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_host(instrument: &InstrumentAny) -> (SharedHost, Rc<RefCell<Cache>>, SavedEvents) {
+        let msgbus = Rc::new(RefCell::new(MessageBus::new(
+            TraderId::from("TRADER-001"),
+            UUID4::new(),
+            None,
+            None,
+        )));
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+        let handler = SubmittingHandler {
+            msgbus: msgbus.clone(),
+            cache: cache.clone(),
+        };
+        msgbus.borrow_mut().register(
+            "RiskEngine.execute",
+            ShareableMessageHandler(Rc::new(handler)),
+        );
+
+        let events = SavedEvents::default();
+        let host = Rc::new(RefCell::new(ExecAlgorithmHost::new(
+            SpawningExecAlgorithm {
+                events: events.clone(),
+            },
+            Rc::new(RefCell::new(TestClock::new())),
+            cache.clone(),
+            msgbus,
+        )));
+        ExecAlgorithmHost::register(&host);
+        (host, cache, events)
+    }
+
+    fn primary_order(instrument: &InstrumentAny) -> OrderAny {
+        let client_order_id = ClientOrderId::from("O-1");
+        let order = MarketOrder::new(
+            TraderId::from("TRADER-001"),
+            StrategyId::from("S-001"),
+            instrument.id(),
+            client_order_id,
+            OrderSide::Buy,
+            Quantity::from(200_000),
+            TimeInForce::Gtc,
+            UUID4::new(),
+            UnixNanos::default(),
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some(ExecAlgorithmId::from("SPAWN")),
+            None,
+            Some(client_order_id),
+            None,
+        )
+        .unwrap();
+        OrderAny::Market(order)
+    }
+
+    fn submit(host: &SharedHost, cache: &Rc<RefCell<Cache>>, order: &OrderAny) {
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        let command = TradingCommand::SubmitOrder(SubmitOrder {
+            client_order_id: order.client_order_id(),
+            exec_algorith_id: order.exec_algorithm_id(),
+            ..Default::default()
+        });
+        let (msgbus, endpoint) = {
+            let host = host.borrow();
+            (host.core().msgbus.clone(), host.endpoint())
+        };
+        msgbus.borrow().send(&endpoint, &command as &dyn Any);
+    }
+
+    #[rstest]
+    fn test_started_host_handles_events_for_spawned_orders(instrument: InstrumentAny) {
+        let (host, cache, events) = get_host(&instrument);
+        ExecAlgorithmHost::start(&host, &[StrategyId::from("S-001")]);
+
+        submit(&host, &cache, &primary_order(&instrument));
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].client_order_id(), ClientOrderId::from("O-1-E1"));
+    }
+
+    #[rstest]
+    fn test_host_without_start_or_stopped_does_not_handle_events(instrument: InstrumentAny) {
+        let (host, cache, events) = get_host(&instrument);
+        submit(&host, &cache, &primary_order(&instrument));
+
+        ExecAlgorithmHost::start(&host, &[StrategyId::from("S-001")]);
+        ExecAlgorithmHost::stop(&host);
+        let spawned = cache
+            .borrow()
+            .order(&ClientOrderId::from("O-1-E1"))
+            .cloned()
+            .unwrap();
+        let event = TestOrderEventStubs::order_submitted(&spawned, AccountId::from("SIM-001"));
+        let msgbus = host.borrow().core().msgbus.clone();
+        msgbus
+            .borrow()
+            .publish("events.order.S-001", &event as &dyn Any);
+
+        assert!(events.borrow().is_empty());
+    }
+}
@@ -0,0 +1,203 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::{HashMap, VecDeque};
+
+use log::{debug, error};
+use nautilus_common::timer::TimeEvent;
+use nautilus_model::{
+    enums::OrderType, identifiers::ClientOrderId, instruments::any::InstrumentAny,
+    orders::any::OrderAny, types::quantity::Quantity,
+};
+use ustr::Ustr;
+
+use super::base::ExecAlgorithmCore;
+
+/// The horizon and interval for a scheduled execution, parsed from the primary orders
+/// `exec_algorithm_params`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ScheduleParams {
+    pub num_intervals: usize,
+    pub interval_ns: u64,
+}
+
+impl ScheduleParams {
+    /// Parses the `horizon_secs` and `interval_secs` parameters of the `order`.
+    pub fn from_order(order: &OrderAny) -> anyhow::Result<Self> {
+        let param = |key: &str| -> anyhow::Result<f64> {
+            let value = order
+                .exec_algorithm_params()
+                .and_then(|params| params.get(&Ustr::from(key)))
+                .ok_or_else(|| anyhow::anyhow!("Missing `{key}` param"))?;
+            let value: f64 = value.parse()?;
+            if value <= 0.0 {
+                anyhow::bail!("Invalid `{key}` param {value}: must be positive");
+            }
+            Ok(value)
+        };
+
+        let horizon_secs = param("horizon_secs")?;
+        let interval_secs = param("interval_secs")?;
+        if horizon_secs < interval_secs {
+            anyhow::bail!(
+                "Invalid `horizon_secs` {horizon_secs}: less than `interval_secs` {interval_secs}"
+            );
+        }
+
+        Ok(Self {
+            num_intervals: (horizon_secs / interval_secs).floor() as usize,
+            interval_ns: (interval_secs * 1_000_000_000.0) as u64,
+        })
+    }
+}
+
+/// Slices the `total` quantity in proportion to the `weights`, rounding down to the
+/// instruments size increment with any remainder going to the final slice.
+///
+/// Trailing zero slices are removed.
+pub(crate) fn slice_quantity(
+    instrument: &InstrumentAny,
+    total: Quantity,
+    weights: &[f64],
+) -> anyhow::Result<Vec<Quantity>> {
+    let total_weight: f64 = weights.iter().sum();
+    if weights.is_empty() || total_weight <= 0.0 || weights.iter().any(|w| *w < 0.0) {
+        anyhow::bail!("Invalid weights {weights:?}");
+    }
+
+    let precision = instrument.size_precision();
+    let increment = instrument.size_increment().as_f64();
+    let mut sizes = weights
+        .iter()
+        .map(|weight| {
+            let value = total.as_f64() * weight / total_weight;
+            Quantity::new((value / increment).floor() * increment, precision)
+        })
+        .collect::<anyhow::Result<Vec<Quantity>>>()?;
+
+    let sliced = sizes
+        .iter()
+        .fold(Quantity::zero(precision), |acc, size| acc + *size);
+    if let Some(last) = sizes.last_mut() {
+        *last += total - sliced;
+    }
+
+    while sizes.last().is_some_and(Quantity::is_zero) {
+        sizes.pop();
+    }
+    Ok(sizes)
+}
+
+/// Executes primary `MARKET` orders as a schedule of spawned `MARKET` orders, one per
+/// timer interval, with the primary order submitted for the final slice.
+#[derive(Debug, Default)]
+pub(crate) struct SliceScheduler {
+    scheduled_sizes: HashMap<ClientOrderId, VecDeque<Quantity>>,
+}
+
+impl SliceScheduler {
+    pub fn scheduled_sizes(&self, primary_id: &ClientOrderId) -> Option<Vec<Quantity>> {
+        self.scheduled_sizes
+            .get(primary_id)
+            .map(|sizes| sizes.iter().copied().collect())
+    }
+
+    pub fn start(
+        &mut self,
+        core: &mut ExecAlgorithmCore,
+        mut primary: OrderAny,
+        sizes: Vec<Quantity>,
+        interval_ns: u64,
+    ) -> anyhow::Result<()> {
+        if primary.order_type() != OrderType::Market {
+            anyhow::bail!(
+                "Cannot execute {} order: only MARKET orders supported",
+                primary.order_type()
+            );
+        }
+
+        let mut sizes: VecDeque<Quantity> = sizes.into();
+        if sizes.len() <= 1 {
+            debug!("Submitting {} as a single slice", primary.client_order_id());
+            return core.submit_order(primary);
+        }
+
+        let primary_id = primary.client_order_id();
+        // SAFETY: Checked there are at least two sizes
+        let quantity = sizes.pop_front().unwrap();
+        if quantity.is_positive() {
+            self.spawn(core, &mut primary, quantity)?;
+        }
+
+        self.scheduled_sizes.insert(primary_id, sizes);
+        core.set_timer(primary_id.as_str(), interval_ns, None)
+    }
+
+    pub fn on_time_event(
+        &mut self,
+        core: &mut ExecAlgorithmCore,
+        event: &TimeEvent,
+    ) -> anyhow::Result<()> {
+        let primary_id = ClientOrderId::from(event.name.as_str());
+        let primary = core.cache.borrow().order(&primary_id).cloned();
+        let Some(mut primary) = primary else {
+            self.complete_sequence(core, &primary_id);
+            anyhow::bail!("Primary order {primary_id} not found in the cache");
+        };
+
+        if primary.is_closed() {
+            self.complete_sequence(core, &primary_id);
+            return Ok(());
+        }
+
+        let Some(sizes) = self.scheduled_sizes.get_mut(&primary_id) else {
+            error!("No scheduled sizes for {primary_id}");
+            core.cancel_timer(primary_id.as_str());
+            return Ok(());
+        };
+
+        // SAFETY: Sequences are completed once no sizes remain
+        let quantity = sizes.pop_front().unwrap();
+        if sizes.is_empty() {
+            // Final slice is the primary order with its remaining quantity
+            self.complete_sequence(core, &primary_id);
+            return core.submit_order(primary);
+        }
+
+        if quantity.is_positive() {
+            self.spawn(core, &mut primary, quantity)?;
+        }
+        Ok(())
+    }
+
+    pub fn complete_sequence(&mut self, core: &mut ExecAlgorithmCore, primary_id: &ClientOrderId) {
+        core.cancel_timer(primary_id.as_str());
+        self.scheduled_sizes.remove(primary_id);
+    }
+
+    fn spawn(
+        &self,
+        core: &mut ExecAlgorithmCore,
+        primary: &mut OrderAny,
+        quantity: Quantity,
+    ) -> anyhow::Result<()> {
+        let time_in_force = primary.time_in_force();
+        let reduce_only = primary.is_reduce_only();
+        let tags = primary.tags().map(<[Ustr]>::to_vec);
+        let spawned =
+            core.spawn_market(primary, quantity, time_in_force, reduce_only, tags, true)?;
+        core.submit_order(spawned)
+    }
+}
@@ -0,0 +1,316 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use nautilus_common::timer::TimeEvent;
+use nautilus_model::{
+    identifiers::{ClientOrderId, ExecAlgorithmId},
+    orders::any::OrderAny,
+    types::quantity::Quantity,
+};
+
+use super::{
+    base::{ExecAlgorithm, ExecAlgorithmCore},
+    schedule::{slice_quantity, ScheduleParams, SliceScheduler},
+};
+
+/// Provides a Time-Weighted Average Price (TWAP) execution algorithm.
+///
+/// The primary `MARKET` order is split into equal slices spawned at regular intervals
+/// over the horizon, with any remainder submitted in the final slice. The primary order
+/// must provide the `horizon_secs` and `interval_secs` execution algorithm parameters.
+#[derive(Debug)]
+pub struct TwapExecAlgorithm {
+    id: ExecAlgorithmId,
+    scheduler: SliceScheduler,
+}
+
+impl TwapExecAlgorithm {
+    /// Creates a new [`TwapExecAlgorithm`] instance.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            id: ExecAlgorithmId::from("TWAP"),
+            scheduler: SliceScheduler::default(),
+        }
+    }
+
+    /// Returns the sizes still scheduled for the given `primary_id` (if executing).
+    #[must_use]
+    pub fn scheduled_sizes(&self, primary_id: &ClientOrderId) -> Option<Vec<Quantity>> {
+        self.scheduler.scheduled_sizes(primary_id)
+    }
+}
+
+impl Default for TwapExecAlgorithm {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExecAlgorithm for TwapExecAlgorithm {
+    fn id(&self) -> ExecAlgorithmId {
+        self.id
+    }
+
+    fn on_order(&mut self, core: &mut ExecAlgorithmCore, order: OrderAny) -> anyhow::Result<()> {
+        let params = ScheduleParams::from_order(&order)?;
+        let instrument = core
+            .cache
+            .borrow()
+            .instrument(&order.instrument_id())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No instrument for {}", order.instrument_id()))?;
+
+        let weights = vec![1.0; params.num_intervals];
+        let mut sizes = slice_quantity(&instrument, order.quantity(), &weights)?;
+        if instrument
+            .min_quantity()
+            .is_some_and(|min_quantity| sizes.iter().any(|size| *size < min_quantity))
+        {
+            sizes.truncate(1); // Slices too small, so execute in one
+        }
+
+        self.scheduler.start(core, order, sizes, params.interval_ns)
+    }
+
+    fn on_time_event(
+        &mut self,
+        core: &mut ExecAlgorithmCore,
+        event: &TimeEvent,
+    ) -> anyhow::Result<()> {
+        self.scheduler.on_time_event(core, event)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, cell::RefCell, collections::HashMap, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        clock::{Clock, TestClock},
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+    use nautilus_model::{
+        enums::{OrderSide, OrderType, TimeInForce},
+        identifiers::{ClientOrderId, ExecAlgorithmId, StrategyId, TraderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{any::OrderAny, market::MarketOrder},
+        types::quantity::Quantity,
+    };
+    use rstest::{fixture, rstest};
+    use ustr::Ustr;
+
+    use super::TwapExecAlgorithm;
+    use crate::{
+        algorithm::host::ExecAlgorithmHost,
+        messages::{submit::SubmitOrder, TradingCommand},
+    };
+
+    type SavedCommands = Rc<RefCell<Vec<TradingCommand>>>;
+    type SharedHost = Rc<RefCell<ExecAlgorithmHost<TwapExecAlgorithm>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_host(
+        instrument: &InstrumentAny,
+    ) -> (
+        SharedHost,
+        Rc<RefCell<TestClock>>,
+        Rc<RefCell<Cache>>,
+        SavedCommands,
+    ) {
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, commands) = get_message_saving_handler::<TradingCommand>(None);
+        msgbus.register("RiskEngine.execute", handler);
+
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+
+        let host = Rc::new(RefCell::new(ExecAlgorithmHost::new(
+            TwapExecAlgorithm::new(),
+            clock.clone(),
+            cache.clone(),
+            Rc::new(RefCell::new(msgbus)),
+        )));
+        ExecAlgorithmHost::register(&host);
+        (host, clock, cache, commands)
+    }
+
+    fn advance_clock(clock: &Rc<RefCell<TestClock>>, to_time_ns: u64) {
+        let events = clock
+            .borrow_mut()
+            .advance_time(UnixNanos::from(to_time_ns), true);
+        for event in events {
+            let callback = clock.borrow().rust_callback(&event).unwrap();
+            callback.call(event);
+        }
+    }
+
+    fn primary_order(instrument: &InstrumentAny, quantity: &str, horizon_secs: &str) -> OrderAny {
+        let client_order_id = ClientOrderId::from("O-1");
+        let params = HashMap::from([
+            (Ustr::from("horizon_secs"), Ustr::from(horizon_secs)),
+            (Ustr::from("interval_secs"), Ustr::from("1")),
+        ]);
+        let order = MarketOrder::new(
+            TraderId::from("TRADER-001"),
+            StrategyId::from("S-001"),
+            instrument.id(),
+            client_order_id,
+            OrderSide::Buy,
+            Quantity::from(quantity),
+            TimeInForce::Gtc,
+            UUID4::new(),
+            UnixNanos::default(),
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some(ExecAlgorithmId::from("TWAP")),
+            Some(params),
+            Some(client_order_id),
+            None,
+        )
+        .unwrap();
+        OrderAny::Market(order)
+    }
+
+    fn submit(host: &SharedHost, cache: &Rc<RefCell<Cache>>, order: &OrderAny) {
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        let command = TradingCommand::SubmitOrder(SubmitOrder {
+            client_order_id: order.client_order_id(),
+            exec_algorith_id: order.exec_algorithm_id(),
+            ..Default::default()
+        });
+        let (msgbus, endpoint) = {
+            let host = host.borrow();
+            (host.core().msgbus.clone(), host.endpoint())
+        };
+        msgbus.borrow().send(&endpoint, &command as &dyn Any);
+    }
+
+    fn submitted_ids(commands: &SavedCommands) -> Vec<String> {
+        commands
+            .borrow()
+            .iter()
+            .map(|command| match command {
+                TradingCommand::SubmitOrder(cmd) => cmd.client_order_id.to_string(),
+                _ => panic!("Unexpected command {command:?}"),
+            })
+            .collect()
+    }
+
+    #[rstest]
+    fn test_twap_spawns_first_slice(instrument: InstrumentAny) {
+        let (host, clock, cache, commands) = get_host(&instrument);
+        let order = primary_order(&instrument, "300000", "3");
+
+        submit(&host, &cache, &order);
+
+        assert_eq!(submitted_ids(&commands), vec!["O-1-E1"]);
+        assert_eq!(clock.borrow().timer_names(), vec!["O-1"]);
+        assert_eq!(
+            host.borrow()
+                .algorithm()
+                .scheduled_sizes(&order.client_order_id()),
+            Some(vec![Quantity::from("100000"), Quantity::from("100000")])
+        );
+        let cache = cache.borrow();
+        assert_eq!(
+            cache.order(&order.client_order_id()).unwrap().quantity(),
+            Quantity::from("200000")
+        );
+        let spawned = cache.order(&ClientOrderId::from("O-1-E1")).unwrap();
+        assert_eq!(spawned.order_type(), OrderType::Market);
+        assert_eq!(spawned.quantity(), Quantity::from("100000"));
+        assert_eq!(spawned.exec_spawn_id(), Some(order.client_order_id()));
+        assert_eq!(
+            cache.exec_spawn_total_quantity(&order.client_order_id(), false),
+            Some(Quantity::from("300000"))
+        );
+    }
+
+    #[rstest]
+    fn test_twap_completes_with_primary_order(instrument: InstrumentAny) {
+        let (host, clock, cache, commands) = get_host(&instrument);
+        let order = primary_order(&instrument, "300000", "3");
+        submit(&host, &cache, &order);
+
+        advance_clock(&clock, 1_000_000_000);
+        advance_clock(&clock, 2_000_000_000);
+
+        assert_eq!(submitted_ids(&commands), vec!["O-1-E1", "O-1-E2", "O-1"]);
+        assert_eq!(clock.borrow().timer_count(), 0);
+        assert_eq!(
+            cache
+                .borrow()
+                .order(&order.client_order_id())
+                .unwrap()
+                .quantity(),
+            Quantity::from("100000")
+        );
+    }
+
+    #[rstest]
+    fn test_twap_remainder_in_final_slice(instrument: InstrumentAny) {
+        let (host, _, cache, _) = get_host(&instrument);
+        let order = primary_order(&instrument, "100000", "3");
+
+        submit(&host, &cache, &order);
+
+        assert_eq!(
+            host.borrow()
+                .algorithm()
+                .scheduled_sizes(&order.client_order_id()),
+            Some(vec![Quantity::from("33333"), Quantity::from("33334")])
+        );
+    }
+
+    #[rstest]
+    fn test_twap_single_interval_submits_primary(instrument: InstrumentAny) {
+        let (host, clock, cache, commands) = get_host(&instrument);
+        let order = primary_order(&instrument, "100000", "1");
+
+        submit(&host, &cache, &order);
+
+        assert_eq!(submitted_ids(&commands), vec!["O-1"]);
+        assert_eq!(clock.borrow().timer_count(), 0);
+    }
+
+    #[rstest]
+    fn test_twap_invalid_params_does_not_submit(instrument: InstrumentAny) {
+        let (host, _, cache, commands) = get_host(&instrument);
+        let order = primary_order(&instrument, "100000", "0");
+
+        submit(&host, &cache, &order);
+
+        assert!(commands.borrow().is_empty());
+    }
+}
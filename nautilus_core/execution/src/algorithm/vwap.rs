@@ -0,0 +1,264 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use nautilus_common::timer::TimeEvent;
+use nautilus_model::{
+    data::bar::Bar,
+    identifiers::{ClientOrderId, ExecAlgorithmId},
+    orders::any::OrderAny,
+    types::quantity::Quantity,
+};
+
+use super::{
+    base::{ExecAlgorithm, ExecAlgorithmCore},
+    schedule::{slice_quantity, ScheduleParams, SliceScheduler},
+};
+
+/// Provides a Volume-Weighted Average Price (VWAP) execution algorithm.
+///
+/// The primary `MARKET` order is split into slices spawned at regular intervals over the
+/// horizon, sized in proportion to a historical volume profile. The profile is resampled
+/// onto the number of intervals, so a profile of per-minute volumes can drive a schedule
+/// of any length. The primary order must provide the `horizon_secs` and `interval_secs`
+/// execution algorithm parameters.
+#[derive(Debug)]
+pub struct VwapExecAlgorithm {
+    id: ExecAlgorithmId,
+    volume_profile: Vec<f64>,
+    scheduler: SliceScheduler,
+}
+
+impl VwapExecAlgorithm {
+    /// Creates a new [`VwapExecAlgorithm`] instance.
+    #[must_use]
+    pub fn new(volume_profile: Vec<f64>) -> Self {
+        Self {
+            id: ExecAlgorithmId::from("VWAP"),
+            volume_profile,
+            scheduler: SliceScheduler::default(),
+        }
+    }
+
+    /// Creates a new [`VwapExecAlgorithm`] instance with the volume profile of the `bars`.
+    #[must_use]
+    pub fn from_bars(bars: &[Bar]) -> Self {
+        Self::new(bars.iter().map(|bar| bar.volume.as_f64()).collect())
+    }
+
+    #[must_use]
+    pub fn volume_profile(&self) -> &[f64] {
+        &self.volume_profile
+    }
+
+    /// Returns the sizes still scheduled for the given `primary_id` (if executing).
+    #[must_use]
+    pub fn scheduled_sizes(&self, primary_id: &ClientOrderId) -> Option<Vec<Quantity>> {
+        self.scheduler.scheduled_sizes(primary_id)
+    }
+
+    /// Returns the volume profile resampled onto `num_intervals` weights.
+    #[must_use]
+    pub fn weights(&self, num_intervals: usize) -> Vec<f64> {
+        let len = self.volume_profile.len();
+        if len == 0 {
+            return Vec::new();
+        }
+
+        (0..num_intervals)
+            .map(|i| {
+                let start = i * len / num_intervals;
+                let end = ((i + 1) * len / num_intervals).max(start + 1);
+                self.volume_profile[start..end].iter().sum::<f64>() / (end - start) as f64
+            })
+            .collect()
+    }
+}
+
+impl ExecAlgorithm for VwapExecAlgorithm {
+    fn id(&self) -> ExecAlgorithmId {
+        self.id
+    }
+
+    fn on_order(&mut self, core: &mut ExecAlgorithmCore, order: OrderAny) -> anyhow::Result<()> {
+        let params = ScheduleParams::from_order(&order)?;
+        let instrument = core
+            .cache
+            .borrow()
+            .instrument(&order.instrument_id())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No instrument for {}", order.instrument_id()))?;
+
+        let weights = self.weights(params.num_intervals);
+        let sizes = slice_quantity(&instrument, order.quantity(), &weights)?;
+
+        self.scheduler.start(core, order, sizes, params.interval_ns)
+    }
+
+    fn on_time_event(
+        &mut self,
+        core: &mut ExecAlgorithmCore,
+        event: &TimeEvent,
+    ) -> anyhow::Result<()> {
+        self.scheduler.on_time_event(core, event)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        clock::TestClock,
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+    use nautilus_model::{
+        enums::{OrderSide, TimeInForce},
+        identifiers::{ClientOrderId, ExecAlgorithmId, StrategyId, TraderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{any::OrderAny, market::MarketOrder},
+        types::quantity::Quantity,
+    };
+    use rstest::{fixture, rstest};
+    use ustr::Ustr;
+
+    use super::VwapExecAlgorithm;
+    use crate::{
+        algorithm::host::ExecAlgorithmHost,
+        messages::{submit::SubmitOrder, TradingCommand},
+    };
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn primary_order(instrument: &InstrumentAny) -> OrderAny {
+        let client_order_id = ClientOrderId::from("O-1");
+        let params = HashMap::from([
+            (Ustr::from("horizon_secs"), Ustr::from("4")),
+            (Ustr::from("interval_secs"), Ustr::from("1")),
+        ]);
+        let order = MarketOrder::new(
+            TraderId::from("TRADER-001"),
+            StrategyId::from("S-001"),
+            instrument.id(),
+            client_order_id,
+            OrderSide::Sell,
+            Quantity::from("100000"),
+            TimeInForce::Gtc,
+            UUID4::new(),
+            UnixNanos::default(),
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            Some(ExecAlgorithmId::from("VWAP")),
+            Some(params),
+            Some(client_order_id),
+            None,
+        )
+        .unwrap();
+        OrderAny::Market(order)
+    }
+
+    #[rstest]
+    #[case(vec![1.0, 2.0, 3.0, 4.0], 4, vec![1.0, 2.0, 3.0, 4.0])]
+    #[case(vec![1.0, 3.0, 5.0, 7.0], 2, vec![2.0, 6.0])]
+    #[case(vec![1.0, 2.0], 4, vec![1.0, 1.0, 2.0, 2.0])]
+    #[case(vec![], 2, vec![])]
+    fn test_weights_resample_profile(
+        #[case] profile: Vec<f64>,
+        #[case] num_intervals: usize,
+        #[case] expected: Vec<f64>,
+    ) {
+        let algorithm = VwapExecAlgorithm::new(profile);
+
+        assert_eq!(algorithm.weights(num_intervals), expected);
+    }
+
+    #[rstest]
+    fn test_vwap_slices_by_volume_profile(instrument: InstrumentAny) {
+        let clock = Rc::new(RefCell::new(TestClock::new()));
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, commands) = get_message_saving_handler::<TradingCommand>(None);
+        msgbus.register("RiskEngine.execute", handler);
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+        let host = Rc::new(RefCell::new(ExecAlgorithmHost::new(
+            VwapExecAlgorithm::new(vec![10.0, 40.0, 30.0, 20.0]),
+            clock.clone(),
+            cache.clone(),
+            Rc::new(RefCell::new(msgbus)),
+        )));
+        ExecAlgorithmHost::register(&host);
+        let order = primary_order(&instrument);
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+
+        host.borrow_mut()
+            .execute(TradingCommand::SubmitOrder(SubmitOrder {
+                client_order_id: order.client_order_id(),
+                ..Default::default()
+            }));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert_eq!(
+            cache
+                .borrow()
+                .order(&ClientOrderId::from("O-1-E1"))
+                .unwrap()
+                .quantity(),
+            Quantity::from("10000")
+        );
+        assert_eq!(
+            host.borrow()
+                .algorithm()
+                .scheduled_sizes(&order.client_order_id()),
+            Some(vec![
+                Quantity::from("40000"),
+                Quantity::from("30000"),
+                Quantity::from("20000"),
+            ])
+        );
+
+        for secs in 1..=3_u64 {
+            let events = clock
+                .borrow_mut()
+                .advance_time(UnixNanos::from(secs * 1_000_000_000), true);
+            for event in events {
+                let callback = clock.borrow().rust_callback(&event).unwrap();
+                callback.call(event);
+            }
+        }
+
+        assert_eq!(commands.borrow().len(), 4);
+        assert_eq!(
+            cache
+                .borrow()
+                .exec_spawn_total_quantity(&order.client_order_id(), false),
+            Some(Quantity::from("100000"))
+        );
+    }
+}
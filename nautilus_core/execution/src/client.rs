@@ -38,12 +38,34 @@ use nautilus_model::{
     },
 };
 
-use crate::messages::{
-    cancel::CancelOrder, cancel_batch::BatchCancelOrders, modify::ModifyOrder, query::QueryOrder,
-    submit::SubmitOrder, submit_list::SubmitOrderList,
+use crate::{
+    messages::{
+        cancel::CancelOrder, cancel_all::CancelAllOrders, cancel_batch::BatchCancelOrders,
+        modify::ModifyOrder, query::QueryOrder, submit::SubmitOrder, submit_list::SubmitOrderList,
+    },
+    reports::mass_status::ExecutionMassStatus,
 };
 
-pub struct ExecutionClient {
+pub trait ExecutionClient {
+    fn client_id(&self) -> ClientId;
+    fn account_id(&self) -> AccountId;
+    fn venue(&self) -> Venue;
+    fn oms_type(&self) -> OmsType;
+    fn is_connected(&self) -> bool;
+    fn submit_order(&self, command: SubmitOrder) -> anyhow::Result<()>;
+    fn submit_order_list(&self, command: SubmitOrderList) -> anyhow::Result<()>;
+    fn modify_order(&self, command: ModifyOrder) -> anyhow::Result<()>;
+    fn cancel_order(&self, command: CancelOrder) -> anyhow::Result<()>;
+    fn cancel_all_orders(&self, command: CancelAllOrders) -> anyhow::Result<()>;
+    fn batch_cancel_orders(&self, command: BatchCancelOrders) -> anyhow::Result<()>;
+    fn query_order(&self, command: QueryOrder) -> anyhow::Result<()>;
+    fn generate_mass_status(
+        &self,
+        lookback_mins: Option<u64>,
+    ) -> anyhow::Result<Option<ExecutionMassStatus>>;
+}
+
+pub struct BaseExecutionClient {
     pub client_id: ClientId,
     pub venue: Venue,
     pub oms_type: OmsType,
@@ -55,38 +77,12 @@ pub struct ExecutionClient {
     cache: &'static Cache,
 }
 
-impl ExecutionClient {
+impl BaseExecutionClient {
     #[must_use]
     pub fn get_account(&self) -> &AccountAny {
         todo!();
     }
 
-    // -- COMMAND HANDLERS ----------------------------------------------------
-
-    pub fn submit_order(&self, command: SubmitOrder) -> anyhow::Result<()> {
-        todo!();
-    }
-
-    pub fn submit_order_list(&self, command: SubmitOrderList) -> anyhow::Result<()> {
-        todo!();
-    }
-
-    pub fn modify_order(&self, command: ModifyOrder) -> anyhow::Result<()> {
-        todo!();
-    }
-
-    pub fn cancel_order(&self, command: CancelOrder) -> anyhow::Result<()> {
-        todo!();
-    }
-
-    pub fn batch_cancel_orders(&self, command: BatchCancelOrders) -> anyhow::Result<()> {
-        todo!();
-    }
-
-    pub fn query_order(&self, command: QueryOrder) -> anyhow::Result<()> {
-        todo!();
-    }
-
     pub fn generate_account_state(
         &self,
         balances: Vec<AccountBalance>,
@@ -0,0 +1,1163 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Provides an `OrderEmulator` which holds orders locally until their trigger conditions are met.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use indexmap::IndexMap;
+use log::{debug, error, info, warn};
+use nautilus_common::{
+    cache::Cache,
+    logging::{CMD, RECV},
+    messages::data::{DataCommand, DataCommandAction},
+    msgbus::MessageBus,
+};
+use nautilus_core::{time::AtomicTime, uuid::UUID4};
+use nautilus_model::{
+    data::{quote::QuoteTick, trade::TradeTick, DataType},
+    enums::{OrderSide, OrderSideSpecified, OrderType, TriggerType},
+    events::order::{
+        canceled::OrderCanceled, denied::OrderDenied, emulated::OrderEmulated,
+        released::OrderReleased, updated::OrderUpdated, OrderEventAny,
+    },
+    identifiers::{ClientId, ClientOrderId, ExecAlgorithmId, InstrumentId},
+    orders::{
+        any::{OrderAny, PassiveOrderAny},
+        limit::LimitOrder,
+        market::MarketOrder,
+    },
+    types::price::Price,
+};
+
+use crate::{
+    matching_core::OrderMatchingCore,
+    messages::{
+        cancel::CancelOrder, cancel_all::CancelAllOrders, modify::ModifyOrder, submit::SubmitOrder,
+        submit_list::SubmitOrderList, TradingCommand,
+    },
+    trailing::TrailingStopCalculator,
+};
+
+/// Configuration for `OrderEmulator` instances.
+#[derive(Debug, Default)]
+pub struct OrderEmulatorConfig {
+    /// If debug logging is enabled.
+    pub debug: bool,
+}
+
+/// Provides order emulation for order types and trigger types which are not natively
+/// supported by a venue.
+///
+/// Emulated orders are held locally with an `OrderMatchingCore` per trigger instrument, and
+/// are released to the `ExecutionEngine` as `MARKET` or `LIMIT` orders once triggered.
+/// Market data must be passed in through `on_quote_tick` and `on_trade_tick`, with the
+/// required subscriptions requested from the `DataEngine` as orders are emulated.
+pub struct OrderEmulator {
+    clock: &'static AtomicTime,
+    cache: Rc<RefCell<Cache>>,
+    msgbus: Rc<RefCell<MessageBus>>,
+    matching_cores: HashMap<InstrumentId, OrderMatchingCore>,
+    commands_submit_order: HashMap<ClientOrderId, SubmitOrder>,
+    subscribed_quotes: HashSet<InstrumentId>,
+    subscribed_trades: HashSet<InstrumentId>,
+    config: OrderEmulatorConfig,
+}
+
+impl OrderEmulator {
+    /// Creates a new [`OrderEmulator`] instance.
+    #[must_use]
+    pub fn new(
+        clock: &'static AtomicTime,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+        config: OrderEmulatorConfig,
+    ) -> Self {
+        Self {
+            clock,
+            cache,
+            msgbus,
+            matching_cores: HashMap::new(),
+            commands_submit_order: HashMap::new(),
+            subscribed_quotes: HashSet::new(),
+            subscribed_trades: HashSet::new(),
+            config,
+        }
+    }
+
+    #[must_use]
+    pub fn subscribed_quotes(&self) -> Vec<InstrumentId> {
+        let mut instrument_ids: Vec<InstrumentId> =
+            self.subscribed_quotes.iter().copied().collect();
+        instrument_ids.sort();
+        instrument_ids
+    }
+
+    #[must_use]
+    pub fn subscribed_trades(&self) -> Vec<InstrumentId> {
+        let mut instrument_ids: Vec<InstrumentId> =
+            self.subscribed_trades.iter().copied().collect();
+        instrument_ids.sort();
+        instrument_ids
+    }
+
+    #[must_use]
+    pub fn get_submit_order_commands(&self) -> &HashMap<ClientOrderId, SubmitOrder> {
+        &self.commands_submit_order
+    }
+
+    #[must_use]
+    pub fn get_matching_core(&self, instrument_id: &InstrumentId) -> Option<&OrderMatchingCore> {
+        self.matching_cores.get(instrument_id)
+    }
+
+    pub fn execute(&mut self, command: TradingCommand) {
+        if self.config.debug {
+            debug!("{RECV}{CMD} {command:?}");
+        }
+
+        match command {
+            TradingCommand::SubmitOrder(cmd) => self.handle_submit_order(cmd),
+            TradingCommand::SubmitOrderList(cmd) => self.handle_submit_order_list(cmd),
+            TradingCommand::ModifyOrder(cmd) => self.handle_modify_order(cmd),
+            TradingCommand::CancelOrder(cmd) => self.handle_cancel_order(cmd),
+            TradingCommand::CancelAllOrders(cmd) => self.handle_cancel_all_orders(cmd),
+            _ => self.send_exec_command(&command),
+        }
+    }
+
+    pub fn on_quote_tick(&mut self, quote: &QuoteTick) {
+        let Some(matching_core) = self.matching_cores.get_mut(&quote.instrument_id) else {
+            return;
+        };
+
+        matching_core.bid = Some(quote.bid_price);
+        matching_core.ask = Some(quote.ask_price);
+
+        self.iterate_orders(&quote.instrument_id);
+    }
+
+    pub fn on_trade_tick(&mut self, trade: &TradeTick) {
+        let Some(matching_core) = self.matching_cores.get_mut(&trade.instrument_id) else {
+            return;
+        };
+
+        matching_core.last = Some(trade.price);
+        if !self.subscribed_quotes.contains(&trade.instrument_id) {
+            // Triggering off trades only, so the last price stands in for the top of book
+            matching_core.bid = Some(trade.price);
+            matching_core.ask = Some(trade.price);
+        }
+
+        self.iterate_orders(&trade.instrument_id);
+    }
+
+    // -- COMMAND HANDLERS ----------------------------------------------------
+
+    fn handle_submit_order(&mut self, command: SubmitOrder) {
+        let order = self.cache.borrow().order(&command.client_order_id).cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot emulate order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        let emulation_trigger = match order.emulation_trigger() {
+            Some(TriggerType::NoTrigger) | None => {
+                error!(
+                    "Cannot emulate order: no emulation trigger for {}",
+                    order.client_order_id()
+                );
+                return;
+            }
+            Some(trigger) => trigger,
+        };
+
+        if !matches!(
+            emulation_trigger,
+            TriggerType::Default | TriggerType::BidAsk | TriggerType::LastTrade
+        ) {
+            self.deny_order(
+                &order,
+                &format!("Emulation trigger {emulation_trigger} not supported"),
+            );
+            return;
+        }
+
+        if matches!(
+            order.order_type(),
+            OrderType::Market | OrderType::MarketToLimit
+        ) {
+            self.deny_order(
+                &order,
+                &format!("Cannot emulate {} orders", order.order_type()),
+            );
+            return;
+        }
+
+        let trigger_instrument_id = order
+            .trigger_instrument_id()
+            .unwrap_or(order.instrument_id());
+        if !self.matching_cores.contains_key(&trigger_instrument_id) {
+            if let Err(e) = self.create_matching_core(trigger_instrument_id) {
+                self.deny_order(&order, &e.to_string());
+                return;
+            }
+        }
+
+        self.subscribe(trigger_instrument_id, emulation_trigger);
+
+        // SAFETY: Matching core was created above
+        let matching_core = self.matching_cores.get_mut(&trigger_instrument_id).unwrap();
+        if let Err(e) = matching_core.add_order(PassiveOrderAny::from(order.clone())) {
+            error!("Cannot emulate order: {e}");
+            return;
+        }
+        self.commands_submit_order
+            .insert(order.client_order_id(), command);
+
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Emulated(
+            OrderEmulated::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        let mut order = order;
+        if let Err(e) = order.apply(event.clone()) {
+            error!("Cannot emulate order: {e}");
+            return;
+        }
+        self.update_and_publish(&order, &event);
+
+        info!(
+            "Emulating {} {} order {} with {emulation_trigger} trigger",
+            order.order_side(),
+            order.order_type(),
+            order.client_order_id(),
+        );
+
+        // The order may already be triggered by the current market
+        self.iterate_orders(&trigger_instrument_id);
+    }
+
+    fn handle_submit_order_list(&mut self, command: SubmitOrderList) {
+        // Orders are emulated individually, there is no support for contingent emulated orders
+        for order in &command.order_list.orders {
+            let submit = SubmitOrder {
+                trader_id: command.trader_id,
+                client_id: command.client_id,
+                strategy_id: command.strategy_id,
+                instrument_id: order.instrument_id(),
+                client_order_id: order.client_order_id(),
+                venue_order_id: command.venue_order_id,
+                exec_algorith_id: command.exec_algorith_id,
+                position_id: command.position_id,
+                command_id: UUID4::new(),
+                ts_init: command.ts_init,
+            };
+
+            match order.emulation_trigger() {
+                Some(TriggerType::NoTrigger) | None => {
+                    self.send_exec_command(&TradingCommand::SubmitOrder(submit));
+                }
+                Some(_) => self.handle_submit_order(submit),
+            }
+        }
+    }
+
+    fn handle_modify_order(&mut self, command: ModifyOrder) {
+        let order = self.cache.borrow().order(&command.client_order_id).cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot modify order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        let trigger_instrument_id = order
+            .trigger_instrument_id()
+            .unwrap_or(order.instrument_id());
+        if !self.is_emulating(&trigger_instrument_id, &order.client_order_id()) {
+            // Order has already been released
+            self.send_exec_command(&TradingCommand::ModifyOrder(command));
+            return;
+        }
+
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Updated(
+            OrderUpdated::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                command.quantity.unwrap_or(order.quantity()),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+                false,
+                order.venue_order_id(),
+                order.account_id(),
+                command.price.filter(|_| order.price().is_some()),
+                command
+                    .trigger_price
+                    .filter(|_| order.trigger_price().is_some()),
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+
+        let mut updated = order;
+        if let Err(e) = updated.apply(event.clone()) {
+            error!("Cannot modify emulated order: {e}");
+            return;
+        }
+
+        // SAFETY: Emulating order so the matching core exists
+        let matching_core = self.matching_cores.get_mut(&trigger_instrument_id).unwrap();
+        if let Err(e) = matching_core.update_order(PassiveOrderAny::from(updated)) {
+            error!("Cannot modify emulated order: {e}");
+            return;
+        }
+
+        self.send_exec_event(&event);
+        self.iterate_orders(&trigger_instrument_id);
+    }
+
+    fn handle_cancel_order(&mut self, command: CancelOrder) {
+        let order = self.cache.borrow().order(&command.client_order_id).cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot cancel order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        let trigger_instrument_id = order
+            .trigger_instrument_id()
+            .unwrap_or(order.instrument_id());
+        if self.is_emulating(&trigger_instrument_id, &order.client_order_id()) {
+            self.cancel_order(&order);
+        } else {
+            // Order has already been released
+            self.send_exec_command(&TradingCommand::CancelOrder(command));
+        }
+    }
+
+    fn handle_cancel_all_orders(&mut self, command: CancelAllOrders) {
+        let side = match command.order_side {
+            OrderSide::NoOrderSide => None,
+            side => Some(side),
+        };
+        let orders: Vec<OrderAny> = self
+            .cache
+            .borrow()
+            .orders_emulated(
+                None,
+                Some(&command.instrument_id),
+                Some(&command.strategy_id),
+                side,
+            )
+            .into_iter()
+            .cloned()
+            .collect();
+
+        for order in &orders {
+            self.cancel_order(order);
+        }
+
+        // Any released orders are canceled by the venue
+        self.send_exec_command(&TradingCommand::CancelAllOrders(command));
+    }
+
+    // -- EMULATION -----------------------------------------------------------
+
+    fn create_matching_core(&mut self, instrument_id: InstrumentId) -> anyhow::Result<()> {
+        let cache = self.cache.borrow();
+        let Some(instrument) = cache.instrument(&instrument_id) else {
+            anyhow::bail!("Cannot emulate order: no instrument for {instrument_id}");
+        };
+
+        let mut matching_core = OrderMatchingCore::new(
+            instrument_id,
+            instrument.price_increment(),
+            None,
+            None,
+            None,
+        );
+
+        // Initialize the top of book from the most recent market data
+        if let Some(quote) = cache.quote_tick(&instrument_id) {
+            matching_core.bid = Some(quote.bid_price);
+            matching_core.ask = Some(quote.ask_price);
+        }
+        if let Some(trade) = cache.trade_tick(&instrument_id) {
+            matching_core.last = Some(trade.price);
+        }
+
+        debug!("Creating matching core for {instrument_id}");
+        drop(cache);
+        self.matching_cores.insert(instrument_id, matching_core);
+        Ok(())
+    }
+
+    fn iterate_orders(&mut self, instrument_id: &InstrumentId) {
+        let Some(matching_core) = self.matching_cores.get(instrument_id) else {
+            return;
+        };
+
+        let client_order_ids: Vec<ClientOrderId> = matching_core
+            .get_orders_bid()
+            .iter()
+            .chain(matching_core.get_orders_ask())
+            .map(PassiveOrderAny::client_order_id)
+            .collect();
+        for client_order_id in client_order_ids {
+            let order = self.cache.borrow().order(&client_order_id).cloned();
+            if let Some(order) = order.filter(|order| {
+                matches!(
+                    order.order_type(),
+                    OrderType::TrailingStopMarket | OrderType::TrailingStopLimit
+                )
+            }) {
+                self.update_trailing_stop_order(instrument_id, order);
+            }
+        }
+
+        // SAFETY: Matching core checked above
+        let matching_core = self.matching_cores.get(instrument_id).unwrap();
+
+        let triggered: Vec<PassiveOrderAny> = matching_core
+            .get_orders_bid()
+            .iter()
+            .chain(matching_core.get_orders_ask())
+            .filter(|order| match order {
+                PassiveOrderAny::Limit(order) => matching_core.is_limit_matched(order),
+                PassiveOrderAny::Stop(order) => matching_core.is_stop_matched(order),
+            })
+            .cloned()
+            .collect();
+
+        for order in triggered {
+            self.trigger_order(instrument_id, &order);
+        }
+    }
+
+    fn update_trailing_stop_order(&mut self, instrument_id: &InstrumentId, order: OrderAny) {
+        // SAFETY: Only called while iterating orders for an existing matching core
+        let matching_core = self.matching_cores.get_mut(instrument_id).unwrap();
+        let calculator = TrailingStopCalculator::new(matching_core.price_increment, None);
+        let (new_trigger_price, new_price) = match calculator.calculate(
+            &order,
+            matching_core.bid,
+            matching_core.ask,
+            matching_core.last,
+        ) {
+            Ok(prices) => prices,
+            Err(e) => {
+                error!("Cannot update trailing stop order: {e}");
+                return;
+            }
+        };
+
+        if new_trigger_price.is_none() && new_price.is_none() {
+            return; // No updates
+        }
+
+        let price = match order.order_type() {
+            OrderType::TrailingStopLimit => new_price.or(order.price()),
+            _ => None,
+        };
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Updated(
+            OrderUpdated::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                order.quantity(),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+                false,
+                order.venue_order_id(),
+                order.account_id(),
+                price,
+                new_trigger_price.or(order.trigger_price()),
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+
+        let mut updated = order;
+        if let Err(e) = updated.apply(event.clone()) {
+            error!("Cannot update trailing stop order: {e}");
+            return;
+        }
+        if let Err(e) = matching_core.update_order(PassiveOrderAny::from(updated.clone())) {
+            error!("Cannot update trailing stop order: {e}");
+            return;
+        }
+
+        self.update_and_publish(&updated, &event);
+    }
+
+    fn trigger_order(&mut self, instrument_id: &InstrumentId, passive: &PassiveOrderAny) {
+        // SAFETY: Only called while iterating orders for an existing matching core
+        let matching_core = self.matching_cores.get_mut(instrument_id).unwrap();
+        if let Err(e) = matching_core.delete_order(passive) {
+            error!("Cannot release order: {e}");
+            return;
+        }
+        let (bid, ask) = (matching_core.bid, matching_core.ask);
+
+        let client_order_id = passive.client_order_id();
+        let order = self.cache.borrow().order(&client_order_id).cloned();
+        let Some(order) = order else {
+            error!("Cannot release order: {client_order_id} not found in the cache");
+            return;
+        };
+
+        let ts_now = self.clock.get_time_ns();
+        let (transformed, released_price) = match order.order_type() {
+            OrderType::StopMarket | OrderType::MarketIfTouched | OrderType::TrailingStopMarket => {
+                let released_price = match order.order_side_specified() {
+                    OrderSideSpecified::Buy => ask,
+                    OrderSideSpecified::Sell => bid,
+                };
+                (
+                    MarketOrder::transform(&order, ts_now).map(OrderAny::Market),
+                    released_price,
+                )
+            }
+            _ => (
+                LimitOrder::transform(&order, None, ts_now).map(OrderAny::Limit),
+                order.price(),
+            ),
+        };
+
+        let mut transformed = match transformed {
+            Ok(transformed) => transformed,
+            Err(e) => {
+                error!("Cannot release order: {e}");
+                return;
+            }
+        };
+
+        // SAFETY: Order triggered so a price is available
+        self.release_order(&mut transformed, released_price.unwrap());
+    }
+
+    fn release_order(&mut self, order: &mut OrderAny, released_price: Price) {
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Released(
+            OrderReleased::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                released_price,
+                UUID4::new(),
+                ts_now,
+                ts_now,
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        if let Err(e) = order.apply(event.clone()) {
+            error!("Cannot release order: {e}");
+            return;
+        }
+
+        if let Err(e) = self
+            .cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, true)
+        {
+            error!("Cannot release order: {e}");
+            return;
+        }
+        self.publish_event(&event);
+
+        info!(
+            "Releasing {} as {} order @ {released_price}",
+            order.client_order_id(),
+            order.order_type(),
+        );
+
+        let Some(command) = self.commands_submit_order.remove(&order.client_order_id()) else {
+            error!(
+                "Cannot release order: no submit order command for {}",
+                order.client_order_id()
+            );
+            return;
+        };
+
+        // Orders for execution algorithms are released to the algorithm, which forwards them on
+        let command = TradingCommand::SubmitOrder(command);
+        match order.exec_algorithm_id() {
+            Some(exec_algorithm_id) => self.send_algo_command(&command, &exec_algorithm_id),
+            None => self.send_exec_command(&command),
+        }
+    }
+
+    fn cancel_order(&mut self, order: &OrderAny) {
+        let trigger_instrument_id = order
+            .trigger_instrument_id()
+            .unwrap_or(order.instrument_id());
+        if let Some(matching_core) = self.matching_cores.get_mut(&trigger_instrument_id) {
+            if matching_core.order_exists(order.client_order_id()) {
+                if let Err(e) = matching_core.delete_order(&PassiveOrderAny::from(order.clone())) {
+                    error!("Error deleting emulated order: {e}");
+                }
+            }
+        }
+        self.commands_submit_order.remove(&order.client_order_id());
+
+        if order.is_closed() {
+            warn!("Cannot cancel order: already closed");
+            return;
+        }
+
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Canceled(
+            OrderCanceled::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+                false,
+                order.venue_order_id(),
+                order.account_id(),
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        self.send_exec_event(&event);
+    }
+
+    fn deny_order(&self, order: &OrderAny, reason: &str) {
+        warn!("Cannot emulate order {}: {reason}", order.client_order_id());
+
+        let ts_now = self.clock.get_time_ns();
+        let event = OrderEventAny::Denied(
+            OrderDenied::new(
+                order.trader_id(),
+                order.strategy_id(),
+                order.instrument_id(),
+                order.client_order_id(),
+                reason.into(),
+                UUID4::new(),
+                ts_now,
+                ts_now,
+            )
+            .unwrap(), // SAFETY: No validation conditions
+        );
+        self.send_exec_event(&event);
+    }
+
+    // -- INTERNAL ------------------------------------------------------------
+
+    fn is_emulating(&self, instrument_id: &InstrumentId, client_order_id: &ClientOrderId) -> bool {
+        self.matching_cores
+            .get(instrument_id)
+            .map_or(false, |core| core.order_exists(*client_order_id))
+    }
+
+    fn subscribe(&mut self, instrument_id: InstrumentId, trigger_type: TriggerType) {
+        let type_name = match trigger_type {
+            TriggerType::LastTrade => {
+                if !self.subscribed_trades.insert(instrument_id) {
+                    return;
+                }
+                stringify!(TradeTick)
+            }
+            _ => {
+                if !self.subscribed_quotes.insert(instrument_id) {
+                    return;
+                }
+                stringify!(QuoteTick)
+            }
+        };
+
+        let metadata = IndexMap::from([("instrument_id".to_string(), instrument_id.to_string())]);
+        let command = DataCommand {
+            client_id: ClientId::from(instrument_id.venue.as_str()),
+            venue: instrument_id.venue,
+            data_type: DataType::new(type_name, Some(metadata)),
+            action: DataCommandAction::Subscribe,
+            command_id: UUID4::new(),
+            ts_init: self.clock.get_time_ns(),
+        };
+        self.msgbus
+            .borrow()
+            .send("DataEngine.execute", &command as &dyn Any);
+    }
+
+    fn update_and_publish(&self, order: &OrderAny, event: &OrderEventAny) {
+        if let Err(e) = self.cache.borrow_mut().update_order(order) {
+            error!("Error updating order in cache: {e}");
+        }
+        self.publish_event(event);
+    }
+
+    fn publish_event(&self, event: &OrderEventAny) {
+        let topic = format!("events.order.{}", event.strategy_id());
+        self.msgbus.borrow().publish(&topic, event as &dyn Any);
+    }
+
+    fn send_exec_command(&self, command: &TradingCommand) {
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.execute", command as &dyn Any);
+    }
+
+    fn send_algo_command(&self, command: &TradingCommand, exec_algorithm_id: &ExecAlgorithmId) {
+        let endpoint = format!("{exec_algorithm_id}.execute");
+        self.msgbus.borrow().send(&endpoint, command as &dyn Any);
+    }
+
+    fn send_exec_event(&self, event: &OrderEventAny) {
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", event as &dyn Any);
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, time::AtomicTime, uuid::UUID4};
+    use nautilus_model::{
+        data::{quote::QuoteTick, trade::TradeTick},
+        enums::{
+            AggressorSide, OrderSide, OrderStatus, OrderType, TrailingOffsetType, TriggerType,
+        },
+        events::order::OrderEventAny,
+        identifiers::{ClientOrderId, ExecAlgorithmId, TradeId, TraderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{any::OrderAny, stubs::TestOrderStubs},
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+
+    use super::{OrderEmulator, OrderEmulatorConfig};
+    use crate::messages::{
+        cancel::CancelOrder, modify::ModifyOrder, submit::SubmitOrder, TradingCommand,
+    };
+
+    type SavedCommands = Rc<RefCell<Vec<TradingCommand>>>;
+    type SavedEvents = Rc<RefCell<Vec<OrderEventAny>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_emulator(
+        instrument: &InstrumentAny,
+    ) -> (
+        OrderEmulator,
+        Rc<RefCell<Cache>>,
+        SavedCommands,
+        SavedEvents,
+    ) {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, commands) = get_message_saving_handler::<TradingCommand>(None);
+        msgbus.register("ExecEngine.execute", handler);
+        let (handler, events) = get_message_saving_handler::<OrderEventAny>(None);
+        msgbus.register("ExecEngine.process", handler);
+
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+
+        let emulator = OrderEmulator::new(
+            clock,
+            cache.clone(),
+            Rc::new(RefCell::new(msgbus)),
+            OrderEmulatorConfig::default(),
+        );
+        (emulator, cache, commands, events)
+    }
+
+    fn emulated_order(
+        instrument: &InstrumentAny,
+        order_type: OrderType,
+        order_side: OrderSide,
+        price: &str,
+        trigger_type: TriggerType,
+    ) -> OrderAny {
+        let client_order_id = Some(ClientOrderId::from("O-1"));
+        let mut order = match order_type {
+            OrderType::Limit => TestOrderStubs::limit_order(
+                instrument.id(),
+                order_side,
+                Price::from(price),
+                Quantity::from("100000"),
+                client_order_id,
+                None,
+            ),
+            OrderType::StopMarket => TestOrderStubs::stop_market_order(
+                instrument.id(),
+                order_side,
+                Price::from(price),
+                Quantity::from("100000"),
+                None,
+                client_order_id,
+                None,
+            ),
+            OrderType::TrailingStopMarket => TestOrderStubs::trailing_stop_market_order(
+                instrument.id(),
+                order_side,
+                Price::from(price),
+                Quantity::from("100000"),
+                trigger_type,
+                Price::from("0.10000"),
+                TrailingOffsetType::Price,
+            ),
+            _ => TestOrderStubs::market_order(
+                instrument.id(),
+                order_side,
+                Quantity::from("100000"),
+                client_order_id,
+                None,
+            ),
+        };
+        match order {
+            OrderAny::Limit(ref mut order) => order.emulation_trigger = Some(trigger_type),
+            OrderAny::StopMarket(ref mut order) => order.emulation_trigger = Some(trigger_type),
+            OrderAny::Market(ref mut order) => order.emulation_trigger = Some(trigger_type),
+            OrderAny::TrailingStopMarket(ref mut order) => {
+                order.emulation_trigger = Some(trigger_type);
+            }
+            _ => unreachable!(),
+        }
+        order
+    }
+
+    fn submit(emulator: &mut OrderEmulator, cache: &Rc<RefCell<Cache>>, order: &OrderAny) {
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        emulator.execute(TradingCommand::SubmitOrder(SubmitOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        }));
+    }
+
+    fn quote(instrument: &InstrumentAny, bid: &str, ask: &str) -> QuoteTick {
+        QuoteTick::new(
+            instrument.id(),
+            Price::from(bid),
+            Price::from(ask),
+            Quantity::from("1000000"),
+            Quantity::from("1000000"),
+            UnixNanos::default(),
+            UnixNanos::default(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_submit_order_is_emulated(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Sell,
+            "0.99000",
+            TriggerType::BidAsk,
+        );
+
+        submit(&mut emulator, &cache, &order);
+
+        let cache = cache.borrow();
+        let cached = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(cached.status(), OrderStatus::Emulated);
+        assert!(cache.is_order_emulated(&order.client_order_id()));
+        assert_eq!(emulator.subscribed_quotes(), vec![instrument.id()]);
+        assert!(emulator.subscribed_trades().is_empty());
+        assert!(emulator
+            .get_matching_core(&instrument.id())
+            .unwrap()
+            .order_exists(order.client_order_id()));
+        assert!(commands.borrow().is_empty());
+    }
+
+    #[rstest]
+    fn test_stop_market_not_triggered(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Sell,
+            "0.99000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        emulator.on_quote_tick(&quote(&instrument, "0.99001", "0.99002"));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(
+            cache
+                .borrow()
+                .order(&order.client_order_id())
+                .unwrap()
+                .status(),
+            OrderStatus::Emulated
+        );
+    }
+
+    #[rstest]
+    fn test_stop_market_triggered_is_released_as_market(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Sell,
+            "0.99000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        emulator.on_quote_tick(&quote(&instrument, "0.99000", "0.99002"));
+
+        let cache = cache.borrow();
+        let released = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(released.order_type(), OrderType::Market);
+        assert_eq!(released.status(), OrderStatus::Released);
+        assert!(!cache.is_order_emulated(&order.client_order_id()));
+        assert!(emulator.get_submit_order_commands().is_empty());
+        assert!(matches!(
+            commands.borrow().as_slice(),
+            [TradingCommand::SubmitOrder(cmd)] if cmd.client_order_id == order.client_order_id()
+        ));
+    }
+
+    #[rstest]
+    fn test_limit_matched_is_released_as_limit(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::Limit,
+            OrderSide::Buy,
+            "1.00000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        emulator.on_quote_tick(&quote(&instrument, "0.99998", "1.00000"));
+
+        let cache = cache.borrow();
+        let released = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(released.order_type(), OrderType::Limit);
+        assert_eq!(released.price(), Some(Price::from("1.00000")));
+        assert_eq!(released.emulation_trigger(), None);
+        assert_eq!(commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_released_order_with_exec_algorithm_is_sent_to_algorithm(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let (handler, algo_commands) = get_message_saving_handler::<TradingCommand>(None);
+        emulator
+            .msgbus
+            .borrow_mut()
+            .register("TWAP.execute", handler);
+        let mut order = emulated_order(
+            &instrument,
+            OrderType::Limit,
+            OrderSide::Buy,
+            "1.00000",
+            TriggerType::BidAsk,
+        );
+        if let OrderAny::Limit(ref mut order) = order {
+            order.exec_algorithm_id = Some(ExecAlgorithmId::from("TWAP"));
+        }
+        submit(&mut emulator, &cache, &order);
+
+        emulator.on_quote_tick(&quote(&instrument, "0.99998", "1.00000"));
+
+        assert!(commands.borrow().is_empty());
+        assert!(matches!(
+            algo_commands.borrow().as_slice(),
+            [TradingCommand::SubmitOrder(cmd)] if cmd.client_order_id == order.client_order_id()
+        ));
+    }
+
+    #[rstest]
+    fn test_last_price_trigger_uses_trades(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Buy,
+            "1.01000",
+            TriggerType::LastTrade,
+        );
+        submit(&mut emulator, &cache, &order);
+        assert_eq!(emulator.subscribed_trades(), vec![instrument.id()]);
+
+        emulator.on_trade_tick(&TradeTick::new(
+            instrument.id(),
+            Price::from("1.01000"),
+            Quantity::from("1000"),
+            AggressorSide::Buyer,
+            TradeId::from("T-1"),
+            UnixNanos::default(),
+            UnixNanos::default(),
+        ));
+
+        assert_eq!(commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_cancel_emulated_order(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, events) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Sell,
+            "0.99000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        emulator.execute(TradingCommand::CancelOrder(CancelOrder {
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        }));
+        emulator.on_quote_tick(&quote(&instrument, "0.98000", "0.98002"));
+
+        assert!(commands.borrow().is_empty());
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [OrderEventAny::Canceled(_)]
+        ));
+    }
+
+    #[rstest]
+    fn test_trailing_stop_market_trails_and_triggers(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, _) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::TrailingStopMarket,
+            OrderSide::Sell,
+            "0.80000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        // The stub instrument has a price increment of 0.1
+        emulator.on_quote_tick(&quote(&instrument, "1.00000", "1.00002"));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(
+            cache
+                .borrow()
+                .order(&order.client_order_id())
+                .unwrap()
+                .trigger_price(),
+            Some(Price::from("0.90000"))
+        );
+
+        emulator.on_quote_tick(&quote(&instrument, "0.90000", "0.90002"));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert_eq!(
+            cache
+                .borrow()
+                .order(&order.client_order_id())
+                .unwrap()
+                .order_type(),
+            OrderType::Market
+        );
+    }
+
+    #[rstest]
+    fn test_modify_emulated_order_trigger_price(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, events) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::StopMarket,
+            OrderSide::Sell,
+            "0.99000",
+            TriggerType::BidAsk,
+        );
+        submit(&mut emulator, &cache, &order);
+
+        emulator.execute(TradingCommand::ModifyOrder(ModifyOrder {
+            client_order_id: order.client_order_id(),
+            trigger_price: Some(Price::from("0.99500")),
+            ..Default::default()
+        }));
+        emulator.on_quote_tick(&quote(&instrument, "0.99400", "0.99402"));
+
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [OrderEventAny::Updated(_)]
+        ));
+        assert_eq!(commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_submit_market_order_is_denied(instrument: InstrumentAny) {
+        let (mut emulator, cache, commands, events) = get_emulator(&instrument);
+        let order = emulated_order(
+            &instrument,
+            OrderType::Market,
+            OrderSide::Buy,
+            "1.00000",
+            TriggerType::BidAsk,
+        );
+
+        submit(&mut emulator, &cache, &order);
+
+        assert!(commands.borrow().is_empty());
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [OrderEventAny::Denied(_)]
+        ));
+    }
+}
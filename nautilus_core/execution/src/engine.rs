@@ -20,24 +20,37 @@
 #![allow(unused_variables)]
 
 use std::{
+    any::Any,
     cell::RefCell,
-    collections::{HashMap, HashSet},
-    rc::Rc,
+    collections::{HashMap, HashSet, VecDeque},
+    rc::{Rc, Weak},
 };
 
-use log::debug;
+use log::{debug, error, info, warn};
 use nautilus_common::{
-    cache::Cache, clock::Clock, generators::position_id::PositionIdGenerator, msgbus::MessageBus,
+    cache::Cache,
+    generators::position_id::PositionIdGenerator,
+    logging::{CMD, EVT, RECV},
+    msgbus::{MessageBus, MessageHandler, ShareableMessageHandler},
 };
+use nautilus_core::{correctness, time::AtomicTime, uuid::UUID4};
 use nautilus_model::{
-    enums::{OmsType, OrderSide},
-    events::order::{filled::OrderFilled, OrderEventAny},
-    identifiers::{ClientId, InstrumentId, StrategyId, Venue},
+    enums::{OmsType, OrderSide, OrderStatus},
+    events::{
+        order::{denied::OrderDenied, filled::OrderFilled, OrderEventAny},
+        position::{
+            changed::PositionChanged, closed::PositionClosed, opened::PositionOpened, PositionEvent,
+        },
+    },
+    identifiers::{
+        ClientId, ClientOrderId, InstrumentId, PositionId, StrategyId, Venue, VenueOrderId,
+    },
     instruments::any::InstrumentAny,
-    orders::any::OrderAny,
+    orders::{any::OrderAny, base::OrderError},
     position::Position,
-    types::quantity::Quantity,
+    types::{money::Money, price::Price, quantity::Quantity},
 };
+use ustr::Ustr;
 
 use crate::{
     client::ExecutionClient,
@@ -46,21 +59,28 @@ use crate::{
         modify::ModifyOrder, query::QueryOrder, submit::SubmitOrder, submit_list::SubmitOrderList,
         TradingCommand,
     },
+    reconciliation,
+    reports::{
+        fill::FillReport, mass_status::ExecutionMassStatus, order::OrderStatusReport,
+        position::PositionStatusReport,
+    },
 };
 
+#[derive(Debug, Default)]
 pub struct ExecutionEngineConfig {
     pub debug: bool,
+    pub snapshot_orders: bool,
+    pub snapshot_positions: bool,
+    /// If own order books should be maintained from order events (for filtering own liquidity).
+    pub manage_own_order_books: bool,
 }
 
-pub struct ExecutionEngine<C>
-where
-    C: Clock,
-{
-    clock: C,
+pub struct ExecutionEngine {
+    clock: &'static AtomicTime,
     cache: Rc<RefCell<Cache>>,
     msgbus: Rc<RefCell<MessageBus>>,
-    clients: HashMap<ClientId, ExecutionClient>,
-    default_client: Option<ExecutionClient>,
+    clients: HashMap<ClientId, Box<dyn ExecutionClient>>,
+    default_client: Option<Box<dyn ExecutionClient>>,
     routing_map: HashMap<Venue, ClientId>,
     oms_overrides: HashMap<StrategyId, OmsType>,
     external_order_claims: HashMap<InstrumentId, StrategyId>,
@@ -68,56 +88,133 @@ where
     config: ExecutionEngineConfig,
 }
 
-impl<C> ExecutionEngine<C>
-where
-    C: Clock,
-{
+impl ExecutionEngine {
+    /// Creates a new [`ExecutionEngine`] instance.
+    #[must_use]
+    pub fn new(
+        clock: &'static AtomicTime,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+        config: ExecutionEngineConfig,
+    ) -> Self {
+        let trader_id = msgbus.borrow().trader_id;
+        Self {
+            clock,
+            cache,
+            msgbus,
+            clients: HashMap::new(),
+            default_client: None,
+            routing_map: HashMap::new(),
+            oms_overrides: HashMap::new(),
+            external_order_claims: HashMap::new(),
+            pos_id_generator: PositionIdGenerator::new(trader_id, clock),
+            config,
+        }
+    }
+
     #[must_use]
-    pub fn position_id_count(&self, strategy_id: StrategyId) -> u64 {
-        todo!();
+    pub fn position_id_count(&self, strategy_id: StrategyId) -> usize {
+        self.pos_id_generator.count(strategy_id)
     }
 
     #[must_use]
     pub fn check_integrity(&self) -> bool {
-        todo!();
+        self.cache.borrow_mut().check_integrity()
     }
 
     #[must_use]
     pub fn check_connected(&self) -> bool {
-        todo!();
+        self.clients.values().all(|client| client.is_connected())
     }
 
     #[must_use]
     pub fn check_disconnected(&self) -> bool {
-        todo!();
+        self.clients.values().all(|client| !client.is_connected())
     }
 
     #[must_use]
     pub fn check_residuals(&self) -> bool {
-        todo!();
+        self.cache.borrow().check_residuals()
     }
 
     #[must_use]
     pub fn get_external_order_claims_instruments(&self) -> HashSet<InstrumentId> {
-        todo!();
+        self.external_order_claims.keys().copied().collect()
     }
 
     // -- REGISTRATION --------------------------------------------------------
 
-    pub fn register_client(&mut self, client: ExecutionClient) -> anyhow::Result<()> {
-        todo!();
+    /// Registers the `engine` on the message bus at the `ExecEngine.execute` endpoint for
+    /// trading commands, and the `ExecEngine.process` endpoint for order events.
+    ///
+    /// Messages received while the engine is already handling a message (such as a command
+    /// sent in reaction to an order event published by the engine) are handled in the order
+    /// received once the engine has finished.
+    pub fn register(engine: &Rc<RefCell<Self>>) {
+        let deferred = Rc::new(RefCell::new(VecDeque::new()));
+        let msgbus = engine.borrow().msgbus.clone();
+        let mut msgbus = msgbus.borrow_mut();
+        for endpoint in ["ExecEngine.execute", "ExecEngine.process"] {
+            let handler = ExecEngineHandler {
+                id: Ustr::from(endpoint),
+                engine: Rc::downgrade(engine),
+                deferred: deferred.clone(),
+            };
+            msgbus.register(endpoint, ShareableMessageHandler(Rc::new(handler)));
+        }
+    }
+
+    /// Registers the given execution `client` with the engine, routing commands for the
+    /// clients venue to it.
+    pub fn register_client(&mut self, client: Box<dyn ExecutionClient>) -> anyhow::Result<()> {
+        correctness::check_key_not_in_map(
+            &client.client_id(),
+            &self.clients,
+            "client_id",
+            "clients",
+        )?;
+
+        let client_id = client.client_id();
+        let venue = client.venue();
+        self.routing_map.insert(venue, client_id);
+        self.clients.insert(client_id, client);
+        info!("Registered client {client_id} with routing for {venue}");
+        Ok(())
     }
 
-    pub fn register_default_client(&mut self, client: ExecutionClient) -> anyhow::Result<()> {
-        todo!();
+    /// Registers the given execution `client` with the engine as the default routing client.
+    ///
+    /// When a specific venue routing cannot be found, this client will receive commands.
+    ///
+    /// # Warnings
+    ///
+    /// Any existing default routing client will be overwritten.
+    pub fn register_default_client(
+        &mut self,
+        client: Box<dyn ExecutionClient>,
+    ) -> anyhow::Result<()> {
+        info!("Registered default client {}", client.client_id());
+        self.default_client = Some(client);
+        Ok(())
     }
 
+    /// Registers the `venue` routing for the client with the given `client_id`.
     pub fn register_venue_routing(
         &mut self,
         client_id: ClientId,
         venue: Venue,
     ) -> anyhow::Result<()> {
-        todo!();
+        correctness::check_key_in_map(&client_id, &self.clients, "client_id", "clients")?;
+
+        self.routing_map.insert(venue, client_id);
+        info!("Set client {client_id} routing for {venue}");
+        Ok(())
+    }
+
+    /// Registers the given `oms_type` for the strategy, overriding the OMS type of the venue.
+    pub fn register_oms_type(&mut self, strategy_id: StrategyId, oms_type: OmsType) {
+        self.oms_overrides.insert(strategy_id, oms_type);
+        info!("Registered OMS type {oms_type} for {strategy_id}");
     }
 
     // TODO: Implement `Strategy`
@@ -125,44 +222,368 @@ where
     //     todo!();
     // }
 
+    /// Deregisters the execution client with the given `client_id` from the engine.
     pub fn deregister_client(&mut self, client_id: ClientId) -> anyhow::Result<()> {
-        todo!();
+        correctness::check_key_in_map(&client_id, &self.clients, "client_id", "clients")?;
+
+        self.clients.remove(&client_id);
+        self.routing_map
+            .retain(|_, routing_id| *routing_id != client_id);
+        info!("Deregistered client {client_id}");
+        Ok(())
     }
 
     // -- COMMANDS ------------------------------------------------------------
 
-    pub fn load_cache(&self) {
-        todo!();
+    pub fn load_cache(&mut self) -> anyhow::Result<()> {
+        {
+            let mut cache = self.cache.borrow_mut();
+            cache.cache_general()?;
+            cache.cache_currencies()?;
+            cache.cache_instruments()?;
+            cache.cache_accounts()?;
+            cache.cache_orders()?;
+            cache.cache_positions()?;
+            cache.build_index();
+        }
+
+        self.set_position_id_counts();
+        info!("Loaded cache");
+        Ok(())
     }
 
     pub fn flush_db(&self) {
-        todo!();
+        if let Err(e) = self.cache.borrow_mut().flush_db() {
+            error!("Error flushing database: {e}");
+        }
     }
 
     pub fn execute(&mut self, command: TradingCommand) {
         self.execute_command(command);
     }
 
-    pub fn process(&self, event: &OrderEventAny) {
-        todo!();
+    pub fn process(&mut self, event: &OrderEventAny) {
+        self.handle_event(event.clone());
     }
 
-    // -- COMMAND HANDLERS ----------------------------------------------------
+    // -- RECONCILIATION ------------------------------------------------------
 
-    fn execute_command(&self, command: TradingCommand) {
-        debug!("<--[CMD] {:?}", command); // TODO: Log constants
-
-        // TODO: Refine getting the client (no need for two expects)
-        let client = if let Some(client) = self.clients.get(&command.client_id()) {
-            client
-        } else if let Some(client_id) = self.routing_map.get(&command.instrument_id().venue) {
-            if let Some(client) = self.clients.get(client_id) {
-                client
-            } else {
-                self.default_client.as_ref().expect("No client found")
+    /// Reconciles the execution state with the mass status generated by each registered
+    /// client, returning true if all reports were reconciled.
+    ///
+    /// This should be called on startup and after reconnecting, before trading resumes.
+    pub fn reconcile_state(&mut self, lookback_mins: Option<u64>) -> bool {
+        let mut reconciled = true;
+        let mut mass_statuses = Vec::new();
+        for client in self.clients.values().chain(self.default_client.iter()) {
+            match client.generate_mass_status(lookback_mins) {
+                Ok(Some(mass_status)) => mass_statuses.push(mass_status),
+                Ok(None) => warn!("No mass status generated by {}", client.client_id()),
+                Err(e) => {
+                    error!(
+                        "Error generating mass status for {}: {e}",
+                        client.client_id()
+                    );
+                    reconciled = false;
+                }
             }
+        }
+
+        for mass_status in &mass_statuses {
+            reconciled &= self.reconcile_mass_status(mass_status);
+        }
+        reconciled
+    }
+
+    /// Reconciles the execution state with the given venue `mass_status`, returning true
+    /// if all reports were reconciled.
+    pub fn reconcile_mass_status(&mut self, mass_status: &ExecutionMassStatus) -> bool {
+        info!("Reconciling {mass_status}");
+
+        let mut reconciled = true;
+        let mut order_reports: Vec<&OrderStatusReport> =
+            mass_status.order_reports().values().collect();
+        order_reports.sort_by_key(|report| (report.ts_accepted, report.venue_order_id));
+        for report in order_reports {
+            let trades = mass_status
+                .fill_reports()
+                .get(&report.venue_order_id)
+                .map_or(&[][..], Vec::as_slice);
+            reconciled &= self.reconcile_order_report(report, trades);
+        }
+
+        // Fills for orders without an order status report
+        for (venue_order_id, trades) in mass_status.fill_reports() {
+            if mass_status.order_reports().contains_key(venue_order_id) {
+                continue;
+            }
+            for trade in trades {
+                reconciled &= self.reconcile_fill_report(trade);
+            }
+        }
+
+        for reports in mass_status.position_reports().values() {
+            for report in reports {
+                reconciled &= self.reconcile_position_report(report);
+            }
+        }
+
+        self.check_unreported_orders(mass_status);
+
+        if reconciled {
+            info!("Reconciled execution state for {}", mass_status.venue);
         } else {
-            self.default_client.as_ref().expect("No client found")
+            error!(
+                "Failed to reconcile execution state for {}",
+                mass_status.venue
+            );
+        }
+        reconciled
+    }
+
+    /// Reconciles the cached order for the given order status `report` and its `trades`,
+    /// generating the events which bring the order in line with the venue.
+    ///
+    /// Orders not found in the cache are generated as external orders, claimed by the
+    /// strategy registered for the instrument (if any).
+    pub fn reconcile_order_report(
+        &mut self,
+        report: &OrderStatusReport,
+        trades: &[FillReport],
+    ) -> bool {
+        match self.try_reconcile_order_report(report, trades) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Cannot reconcile {report}: {e}");
+                false
+            }
+        }
+    }
+
+    /// Reconciles the given fill `report` with its cached order, generating the fill if
+    /// the trade has not already been applied.
+    pub fn reconcile_fill_report(&mut self, report: &FillReport) -> bool {
+        let Some(client_order_id) =
+            self.find_client_order_id(report.client_order_id, &report.venue_order_id)
+        else {
+            error!("Cannot reconcile {report}: no order found in the cache");
+            return false;
+        };
+
+        match self.try_reconcile_fill_report(&client_order_id, report) {
+            Ok(()) => true,
+            Err(e) => {
+                error!("Cannot reconcile {report}: {e}");
+                false
+            }
+        }
+    }
+
+    /// Checks the given position status `report` against the cached positions, returning
+    /// true if the quantities agree.
+    ///
+    /// Reports with a `venue_position_id` are checked against that position (`HEDGING`),
+    /// otherwise against the net of all open positions for the instrument (`NETTING`).
+    #[must_use]
+    pub fn reconcile_position_report(&self, report: &PositionStatusReport) -> bool {
+        let cache = self.cache.borrow();
+        let cached_signed_qty = match report.venue_position_id {
+            Some(position_id) => match cache.position(&position_id) {
+                Some(position) => position.signed_qty,
+                None if report.is_flat() => return true,
+                None => {
+                    error!("Cannot reconcile {report}: {position_id} not found in the cache");
+                    return false;
+                }
+            },
+            None => cache
+                .positions_open(None, Some(&report.instrument_id), None, None)
+                .iter()
+                .map(|position| position.signed_qty)
+                .sum(),
+        };
+
+        let tolerance = 0.5 * 10_f64.powi(-i32::from(report.quantity.precision));
+        if (cached_signed_qty - report.signed_qty()).abs() >= tolerance {
+            error!(
+                "Cannot reconcile {report}: cached signed quantity {cached_signed_qty} does not match"
+            );
+            return false;
+        }
+        true
+    }
+
+    fn try_reconcile_order_report(
+        &mut self,
+        report: &OrderStatusReport,
+        trades: &[FillReport],
+    ) -> anyhow::Result<()> {
+        let client_order_id =
+            match self.find_client_order_id(report.client_order_id, &report.venue_order_id) {
+                Some(client_order_id) => client_order_id,
+                None => self.generate_external_order(report)?,
+            };
+        let ts_now = self.clock.get_time_ns();
+        let mut order = self.cached_order(&client_order_id)?;
+
+        if report.order_status == OrderStatus::Rejected {
+            if order.status() != OrderStatus::Rejected {
+                self.handle_event(reconciliation::generate_order_rejected(
+                    &order, report, ts_now,
+                )?);
+            }
+            return Ok(());
+        }
+
+        // The order has been accepted by the venue from this point
+        let needs_accepted = match order.status() {
+            OrderStatus::Initialized | OrderStatus::Submitted => true,
+            OrderStatus::PendingUpdate | OrderStatus::PendingCancel => {
+                report.order_status == OrderStatus::Accepted
+            }
+            _ => false,
+        };
+        if needs_accepted {
+            self.handle_event(reconciliation::generate_order_accepted(
+                &order, report, ts_now,
+            )?);
+            order = self.cached_order(&client_order_id)?;
+        }
+
+        if report.order_status == OrderStatus::Triggered && order.status() != OrderStatus::Triggered
+        {
+            self.handle_event(reconciliation::generate_order_triggered(
+                &order, report, ts_now,
+            )?);
+            order = self.cached_order(&client_order_id)?;
+        }
+
+        if report.is_open() {
+            if let Some(event) = reconciliation::generate_order_updated(&order, report, ts_now)? {
+                self.handle_event(event);
+            }
+        }
+
+        for trade in trades {
+            self.try_reconcile_fill_report(&client_order_id, trade)?;
+        }
+        order = self.cached_order(&client_order_id)?;
+
+        if report.filled_qty < order.filled_qty() {
+            anyhow::bail!(
+                "reported filled_qty {} less than cached filled_qty {}",
+                report.filled_qty,
+                order.filled_qty()
+            );
+        }
+        if report.filled_qty > order.filled_qty() {
+            let instrument = self.cached_instrument(&order)?;
+            self.handle_event(reconciliation::generate_inferred_fill(
+                &order,
+                report,
+                &instrument,
+                ts_now,
+            )?);
+            order = self.cached_order(&client_order_id)?;
+        }
+
+        if !order.is_closed() {
+            match report.order_status {
+                OrderStatus::Canceled => self.handle_event(
+                    reconciliation::generate_order_canceled(&order, report, ts_now)?,
+                ),
+                OrderStatus::Expired => self.handle_event(reconciliation::generate_order_expired(
+                    &order, report, ts_now,
+                )?),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn try_reconcile_fill_report(
+        &mut self,
+        client_order_id: &ClientOrderId,
+        report: &FillReport,
+    ) -> anyhow::Result<()> {
+        let order = self.cached_order(client_order_id)?;
+        if order.trade_ids().contains(&&report.trade_id) {
+            return Ok(()); // Already applied
+        }
+
+        let instrument = self.cached_instrument(&order)?;
+        let ts_now = self.clock.get_time_ns();
+        self.handle_event(reconciliation::generate_order_filled(
+            &order,
+            report,
+            &instrument,
+            ts_now,
+        )?);
+        Ok(())
+    }
+
+    fn generate_external_order(
+        &mut self,
+        report: &OrderStatusReport,
+    ) -> anyhow::Result<ClientOrderId> {
+        let strategy_id = self
+            .external_order_claims
+            .get(&report.instrument_id)
+            .copied()
+            .unwrap_or_else(StrategyId::external);
+        let trader_id = self.msgbus.borrow().trader_id;
+        let initialized = reconciliation::generate_external_order_initialized(
+            report,
+            trader_id,
+            strategy_id,
+            self.clock.get_time_ns(),
+        )?;
+
+        let client_order_id = initialized.client_order_id;
+        let order = OrderAny::from(initialized.clone());
+        self.cache
+            .borrow_mut()
+            .add_order(order, None, None, false)?;
+        info!("Generated external order {client_order_id} for {strategy_id}");
+
+        let event = OrderEventAny::Initialized(initialized);
+        let topic = format!("events.order.{strategy_id}");
+        self.msgbus.borrow().publish(&topic, &event as &dyn Any);
+        Ok(client_order_id)
+    }
+
+    fn check_unreported_orders(&self, mass_status: &ExecutionMassStatus) {
+        let cache = self.cache.borrow();
+        for order in cache.orders_open(Some(&mass_status.venue), None, None, None) {
+            let is_reported = order.venue_order_id().is_some_and(|venue_order_id| {
+                mass_status.order_reports().contains_key(&venue_order_id)
+            });
+            if !is_reported {
+                warn!(
+                    "Open order {} not reported by {}, query the order to reconcile",
+                    order.client_order_id(),
+                    mass_status.venue
+                );
+            }
+        }
+    }
+
+    // -- COMMAND HANDLERS ----------------------------------------------------
+
+    fn execute_command(&self, command: TradingCommand) {
+        if self.config.debug {
+            debug!("{RECV}{CMD} {command:?}");
+        }
+
+        let Some(client) =
+            self.get_client(Some(&command.client_id()), &command.instrument_id().venue)
+        else {
+            error!(
+                "Cannot execute command: no execution client found for {} or {}, {command:?}",
+                command.client_id(),
+                command.instrument_id().venue,
+            );
+            return;
         };
 
         match command {
@@ -176,121 +597,1387 @@ where
         }
     }
 
-    fn handle_submit_order(&self, client: &ExecutionClient, command: SubmitOrder) {
-        todo!();
+    fn handle_submit_order(&self, client: &dyn ExecutionClient, command: SubmitOrder) {
+        let Some(mut order) = self.cache.borrow().order(&command.client_order_id).cloned() else {
+            error!(
+                "Cannot submit order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        if let Some(position_id) = command.position_id {
+            if let Err(e) = self.cache.borrow_mut().add_position_id(
+                &position_id,
+                &command.instrument_id.venue,
+                &command.client_order_id,
+                &command.strategy_id,
+            ) {
+                error!("Error indexing {position_id} for order: {e}");
+            }
+        }
+
+        if order.is_quote_quantity() {
+            let instrument = self
+                .cache
+                .borrow()
+                .instrument(&order.instrument_id())
+                .cloned();
+            let Some(instrument) = instrument else {
+                self.deny_order(
+                    &order,
+                    &format!("instrument-not-found {}", order.instrument_id()),
+                );
+                return;
+            };
+
+            if !instrument.is_inverse() {
+                let Some(last_px) =
+                    self.last_px_for_conversion(&order.instrument_id(), order.order_side())
+                else {
+                    self.deny_order(
+                        &order,
+                        &format!("no-price-to-convert-quote-qty {}", order.instrument_id()),
+                    );
+                    return;
+                };
+                let base_qty = instrument.calculate_base_quantity(order.quantity(), last_px);
+                self.set_order_base_qty(&mut order, base_qty);
+            }
+        }
+
+        if let Err(e) = client.submit_order(command) {
+            error!("Error submitting order to client: {e}");
+            self.deny_order(&order, &format!("failed-to-submit-order-to-client: {e}"));
+        }
     }
 
-    fn handle_submit_order_list(&self, client: &ExecutionClient, command: SubmitOrderList) {
-        todo!();
+    fn handle_submit_order_list(&self, client: &dyn ExecutionClient, command: SubmitOrderList) {
+        for order in &command.order_list.orders {
+            if self.cache.borrow().order_exists(&order.client_order_id()) {
+                continue;
+            }
+            if let Err(e) = self.cache.borrow_mut().add_order(
+                order.clone(),
+                command.position_id,
+                Some(client.client_id()),
+                false,
+            ) {
+                error!(
+                    "Error adding order {} to cache: {e}",
+                    order.client_order_id()
+                );
+                return;
+            }
+        }
+
+        if let Err(e) = client.submit_order_list(command) {
+            error!("Error submitting order list to client: {e}");
+        }
     }
 
-    fn handle_modify_order(&self, client: &ExecutionClient, command: ModifyOrder) {
-        todo!();
+    fn handle_modify_order(&self, client: &dyn ExecutionClient, command: ModifyOrder) {
+        if let Err(e) = client.modify_order(command) {
+            error!("Error modifying order: {e}");
+        }
     }
 
-    fn handle_cancel_order(&self, client: &ExecutionClient, command: CancelOrder) {
-        todo!();
+    fn handle_cancel_order(&self, client: &dyn ExecutionClient, command: CancelOrder) {
+        if let Err(e) = client.cancel_order(command) {
+            error!("Error canceling order: {e}");
+        }
     }
 
-    fn handle_cancel_all_orders(&self, client: &ExecutionClient, command: CancelAllOrders) {
-        todo!();
+    fn handle_cancel_all_orders(&self, client: &dyn ExecutionClient, command: CancelAllOrders) {
+        if let Err(e) = client.cancel_all_orders(command) {
+            error!("Error canceling all orders: {e}");
+        }
     }
 
-    fn handle_batch_cancel_orders(&self, client: &ExecutionClient, command: BatchCancelOrders) {
-        todo!();
+    fn handle_batch_cancel_orders(&self, client: &dyn ExecutionClient, command: BatchCancelOrders) {
+        if let Err(e) = client.batch_cancel_orders(command) {
+            error!("Error batch canceling orders: {e}");
+        }
     }
 
-    fn handle_query_order(&self, client: &ExecutionClient, command: QueryOrder) {
-        todo!();
+    fn handle_query_order(&self, client: &dyn ExecutionClient, command: QueryOrder) {
+        if let Err(e) = client.query_order(command) {
+            error!("Error querying order: {e}");
+        }
     }
 
     // -- EVENT HANDLERS ----------------------------------------------------
 
-    fn handle_event(&self, event: OrderEventAny) {
-        todo!();
+    fn handle_event(&mut self, event: OrderEventAny) {
+        if self.config.debug {
+            debug!("{RECV}{EVT} {event:?}");
+        }
+
+        let client_order_id = event.client_order_id();
+        let Some(mut order) = self.cache.borrow().order(&client_order_id).cloned() else {
+            warn!("Order with {client_order_id} not found in the cache to apply {event}");
+            return;
+        };
+
+        let mut fill = match event {
+            OrderEventAny::PartiallyFilled(fill) | OrderEventAny::Filled(fill) => fill,
+            _ => {
+                self.apply_event_to_order(&mut order, event);
+                return;
+            }
+        };
+
+        let oms_type = self.determine_oms_type(&fill);
+        fill.position_id = Some(self.determine_position_id(&fill, oms_type));
+
+        let event = match event {
+            OrderEventAny::PartiallyFilled(_) => OrderEventAny::PartiallyFilled(fill),
+            _ => OrderEventAny::Filled(fill),
+        };
+
+        if self.apply_event_to_order(&mut order, event) {
+            self.handle_order_fill(&fill, oms_type);
+        }
     }
 
-    fn determine_oms_type(&self, fill: OrderFilled) {
-        todo!();
+    fn determine_oms_type(&self, fill: &OrderFilled) -> OmsType {
+        // Check for strategy OMS override
+        if let Some(oms_type) = self.oms_overrides.get(&fill.strategy_id) {
+            return *oms_type;
+        }
+
+        // Use native venue OMS
+        self.get_client(None, &fill.instrument_id.venue)
+            .map_or(OmsType::Netting, |client| client.oms_type())
     }
 
-    fn determine_position_id(&self, fill: OrderFilled, oms_type: OmsType) {
-        todo!();
+    fn determine_position_id(&mut self, fill: &OrderFilled, oms_type: OmsType) -> PositionId {
+        let position_id = match oms_type {
+            OmsType::Hedging => self.determine_hedging_position_id(fill),
+            _ => self.determine_netting_position_id(fill),
+        };
+
+        if let Err(e) = self.cache.borrow_mut().add_position_id(
+            &position_id,
+            &fill.instrument_id.venue,
+            &fill.client_order_id,
+            &fill.strategy_id,
+        ) {
+            error!("Error indexing {position_id} for order: {e}");
+        }
+
+        position_id
     }
 
-    fn determine_hedging_position_id(&self, fill: OrderFilled) {
-        todo!();
+    fn determine_hedging_position_id(&mut self, fill: &OrderFilled) -> PositionId {
+        // Check if position ID already assigned by the venue
+        if let Some(position_id) = fill.position_id {
+            if self.config.debug {
+                debug!("Already had a position ID of: {position_id}");
+            }
+            return position_id;
+        }
+
+        // Check for an existing position ID for the order
+        if let Some(position_id) = self.cache.borrow().position_id(&fill.client_order_id) {
+            if self.config.debug {
+                debug!("Position ID {position_id} found in the cache");
+            }
+            return *position_id;
+        }
+
+        // Generate a virtual position ID
+        let position_id = self.pos_id_generator.generate(fill.strategy_id, false);
+        if self.config.debug {
+            debug!("Generated {position_id} for {}", fill.client_order_id);
+        }
+        position_id
     }
 
-    fn determine_netting_position_id(&self, fill: OrderFilled) {
-        todo!();
+    fn determine_netting_position_id(&self, fill: &OrderFilled) -> PositionId {
+        PositionId::from(format!("{}-{}", fill.instrument_id, fill.strategy_id).as_str())
     }
 
-    fn apply_event_to_order(&self, order: &OrderAny, event: OrderEventAny) {
-        todo!();
+    fn apply_event_to_order(&self, order: &mut OrderAny, event: OrderEventAny) -> bool {
+        if let Err(e) = order.apply(event.clone()) {
+            match e {
+                OrderError::InvalidStateTransition => {
+                    warn!("InvalidStateTrigger: {e}, did not apply {event}");
+                }
+                _ => {
+                    error!("Error applying event: {e}, did not apply {event}");
+                }
+            }
+            return false;
+        }
+
+        if let Err(e) = self.cache.borrow_mut().update_order(order) {
+            error!("Error updating order in cache: {e}");
+        }
+
+        if self.config.manage_own_order_books {
+            if let Err(e) = self.cache.borrow_mut().update_own_order_book(order) {
+                error!("Error updating own order book: {e}");
+            }
+        }
+
+        let topic = format!("events.order.{}", event.strategy_id());
+        self.msgbus.borrow().publish(&topic, &event as &dyn Any);
+
+        self.publish_order_snapshot(order);
+        true
     }
 
-    fn handle_order_fill(&self, order: &OrderAny, fill: OrderFilled, oms_type: OmsType) {
-        todo!();
+    fn handle_order_fill(&mut self, fill: &OrderFilled, oms_type: OmsType) {
+        let Some(instrument) = self.cache.borrow().instrument(&fill.instrument_id).cloned() else {
+            error!(
+                "Cannot handle order fill: no instrument found for {}",
+                fill.instrument_id
+            );
+            return;
+        };
+
+        let Some(position_id) = fill.position_id else {
+            error!(
+                "Cannot handle order fill: no position ID for {}",
+                fill.client_order_id
+            );
+            return;
+        };
+
+        let position = self.cache.borrow().position(&position_id).cloned();
+        match position {
+            Some(position) if position.is_open() => {
+                if self.will_flip_position(&position, fill) {
+                    self.flip_position(&instrument, position, *fill, oms_type);
+                } else {
+                    self.update_position(position, fill);
+                }
+            }
+            position => self.open_position(&instrument, position, fill, oms_type),
+        }
     }
 
     fn open_position(
         &self,
-        instrument: InstrumentAny,
-        position: &Position,
-        fill: OrderFilled,
+        instrument: &InstrumentAny,
+        position: Option<Position>,
+        fill: &OrderFilled,
         oms_type: OmsType,
     ) {
-        todo!();
+        let position = if let Some(mut position) = position {
+            // Reopening a closed position (NETTING OMS)
+            position.apply(fill);
+            if let Err(e) = self.cache.borrow_mut().update_position(&position) {
+                error!("Error updating position {}: {e}", position.id);
+                return;
+            }
+            position
+        } else {
+            let position = match Position::new(instrument, *fill) {
+                Ok(position) => position,
+                Err(e) => {
+                    error!("Error opening position: {e}");
+                    return;
+                }
+            };
+            if let Err(e) = self
+                .cache
+                .borrow_mut()
+                .add_position(position.clone(), oms_type)
+            {
+                error!("Error adding position {}: {e}", position.id);
+                return;
+            }
+            position
+        };
+
+        let event = PositionOpened::create(&position, fill, self.clock.get_time_ns());
+        self.publish_position_event(&position, PositionEvent::PositionOpened(event));
     }
 
-    fn update_position(
-        &self,
-        instrument: InstrumentAny,
-        position: &Position,
-        fill: OrderFilled,
-        oms_type: OmsType,
-    ) {
-        todo!();
+    fn update_position(&self, mut position: Position, fill: &OrderFilled) {
+        position.apply(fill);
+        if let Err(e) = self.cache.borrow_mut().update_position(&position) {
+            error!("Error updating position {}: {e}", position.id);
+            return;
+        }
+
+        let ts_init = self.clock.get_time_ns();
+        let event = if position.is_closed() {
+            PositionEvent::PositionClosed(PositionClosed::create(&position, fill, ts_init))
+        } else {
+            PositionEvent::PositionChanged(PositionChanged::create(&position, fill, ts_init))
+        };
+        self.publish_position_event(&position, event);
     }
 
-    fn will_flip_position(&self, position: &Position, fill: OrderFilled) {
-        todo!();
+    fn will_flip_position(&self, position: &Position, fill: &OrderFilled) -> bool {
+        position.is_opposite_side(fill.order_side) && fill.last_qty.raw > position.quantity.raw
     }
 
     fn flip_position(
-        &self,
-        instrument: InstrumentAny,
-        position: &Position,
+        &mut self,
+        instrument: &InstrumentAny,
+        position: Position,
         fill: OrderFilled,
         oms_type: OmsType,
     ) {
-        todo!();
+        let difference = fill.last_qty - position.quantity;
+
+        // Split commission between the two fills
+        let ratio = position.quantity.as_f64() / fill.last_qty.as_f64();
+        let (commission1, commission2) = fill.commission.map_or((None, None), |commission| {
+            let commission1 = Money::new(commission * ratio, commission.currency)
+                .expect("Commission split should be within the `Money` range");
+            (Some(commission1), Some(commission - commission1))
+        });
+
+        // Close the original position
+        let mut fill_split1 = fill;
+        fill_split1.position_id = Some(position.id);
+        fill_split1.last_qty = position.quantity;
+        fill_split1.commission = commission1;
+        self.update_position(position, &fill_split1);
+
+        if difference.raw == 0 {
+            warn!("Zero fill size during position flip calculation, this could be caused by a mismatch between instrument `size_precision` and a quantity `size_precision`");
+            return;
+        }
+
+        let mut position_id_flip = fill.position_id;
+        if oms_type == OmsType::Hedging {
+            if let Some(position_id) = fill.position_id {
+                if position_id.is_virtual() {
+                    // Generate new position ID for flipped virtual position
+                    position_id_flip = Some(self.pos_id_generator.generate(fill.strategy_id, true));
+                }
+            }
+        }
+
+        // Open the flipped position with the remaining quantity
+        let mut fill_split2 = fill;
+        fill_split2.position_id = position_id_flip;
+        fill_split2.last_qty = difference;
+        fill_split2.commission = commission2;
+
+        if oms_type == OmsType::Hedging && fill.position_id.is_some_and(|id| id.is_virtual()) {
+            warn!("Closing position {fill_split1}");
+            warn!("Flipping position {fill_split2}");
+        }
+
+        self.open_position(instrument, None, &fill_split2, oms_type);
+    }
+
+    fn publish_position_event(&self, position: &Position, event: PositionEvent) {
+        let topic = format!("events.position.{}", position.strategy_id);
+        self.msgbus.borrow().publish(&topic, &event as &dyn Any);
+
+        self.publish_position_snapshot(position);
     }
 
     fn publish_order_snapshot(&self, order: &OrderAny) {
-        todo!();
+        if !self.config.snapshot_orders {
+            return;
+        }
+
+        let topic = format!("snapshots.orders.{}", order.strategy_id());
+        self.msgbus.borrow().publish(&topic, order as &dyn Any);
     }
 
     fn publish_position_snapshot(&self, position: &Position) {
-        todo!();
+        if !self.config.snapshot_positions {
+            return;
+        }
+
+        let topic = format!("snapshots.positions.{}", position.strategy_id);
+        self.msgbus.borrow().publish(&topic, position as &dyn Any);
     }
 
     // -- INTERNAL ------------------------------------------------------------
 
-    fn set_position_id_counts(&self) {
-        todo!();
+    fn get_client(
+        &self,
+        client_id: Option<&ClientId>,
+        venue: &Venue,
+    ) -> Option<&dyn ExecutionClient> {
+        client_id
+            .and_then(|client_id| self.clients.get(client_id))
+            .or_else(|| {
+                self.routing_map
+                    .get(venue)
+                    .and_then(|client_id| self.clients.get(client_id))
+            })
+            .or(self.default_client.as_ref())
+            .map(|client| &**client)
+    }
+
+    fn find_client_order_id(
+        &self,
+        client_order_id: Option<ClientOrderId>,
+        venue_order_id: &VenueOrderId,
+    ) -> Option<ClientOrderId> {
+        let cache = self.cache.borrow();
+        client_order_id
+            .filter(|client_order_id| cache.order_exists(client_order_id))
+            .or_else(|| cache.client_order_id(venue_order_id).copied())
     }
 
-    fn last_px_for_conversion(&self, instrument_id: InstrumentId, side: OrderSide) {
-        todo!();
+    fn cached_order(&self, client_order_id: &ClientOrderId) -> anyhow::Result<OrderAny> {
+        self.cache
+            .borrow()
+            .order(client_order_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("{client_order_id} not found in the cache"))
     }
 
-    fn set_order_base_qty(&self, order: &OrderAny, base_qty: Quantity) {
-        todo!();
+    fn cached_instrument(&self, order: &OrderAny) -> anyhow::Result<InstrumentAny> {
+        self.cache
+            .borrow()
+            .instrument(&order.instrument_id())
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("no instrument found for {}", order.instrument_id()))
+    }
+
+    fn set_position_id_counts(&mut self) {
+        let cache = self.cache.borrow();
+        let mut counts: HashMap<StrategyId, usize> = HashMap::new();
+        for position in cache.positions(None, None, None, None) {
+            *counts.entry(position.strategy_id).or_default() += 1;
+        }
+
+        self.pos_id_generator.reset();
+        for (strategy_id, count) in counts {
+            self.pos_id_generator.set_count(count, strategy_id);
+            info!("Set PositionId count for {strategy_id} to {count}");
+        }
+    }
+
+    fn last_px_for_conversion(
+        &self,
+        instrument_id: &InstrumentId,
+        side: OrderSide,
+    ) -> Option<Price> {
+        let cache = self.cache.borrow();
+        if let Some(trade) = cache.trade_tick(instrument_id) {
+            return Some(trade.price);
+        }
+
+        cache.quote_tick(instrument_id).map(|quote| match side {
+            OrderSide::Buy => quote.ask_price,
+            _ => quote.bid_price,
+        })
+    }
+
+    fn set_order_base_qty(&self, order: &mut OrderAny, base_qty: Quantity) {
+        info!(
+            "Setting {} order quote quantity {} to base quantity {base_qty}",
+            order.client_order_id(),
+            order.quantity(),
+        );
+
+        order.set_base_quantity(base_qty);
+        if let Err(e) = self.cache.borrow_mut().update_order(order) {
+            error!("Error updating order in cache: {e}");
+        }
     }
 
     fn deny_order(&self, order: &OrderAny, reason: &str) {
-        todo!();
+        error!("Order denied: {reason}");
+
+        let ts_now = self.clock.get_time_ns();
+        let denied = match OrderDenied::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            reason.into(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+        ) {
+            Ok(denied) => denied,
+            Err(e) => {
+                error!("Error creating order denied event: {e}");
+                return;
+            }
+        };
+
+        let mut order = order.clone();
+        self.apply_event_to_order(&mut order, OrderEventAny::Denied(denied));
+    }
+}
+
+enum ExecEngineMessage {
+    Command(TradingCommand),
+    Event(Box<OrderEventAny>),
+}
+
+/// Handles the messages for an [`ExecutionEngine`] endpoint, see [`ExecutionEngine::register`].
+struct ExecEngineHandler {
+    id: Ustr,
+    engine: Weak<RefCell<ExecutionEngine>>,
+    deferred: Rc<RefCell<VecDeque<ExecEngineMessage>>>,
+}
+
+impl MessageHandler for ExecEngineHandler {
+    fn id(&self) -> Ustr {
+        self.id
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        let message = if let Some(command) = message.downcast_ref::<TradingCommand>() {
+            ExecEngineMessage::Command(command.clone())
+        } else if let Some(event) = message.downcast_ref::<OrderEventAny>() {
+            ExecEngineMessage::Event(Box::new(event.clone()))
+        } else {
+            error!("Cannot handle message for {}: unrecognized type", self.id);
+            return;
+        };
+        let Some(engine) = self.engine.upgrade() else {
+            return;
+        };
+
+        self.deferred.borrow_mut().push_back(message);
+        let Ok(mut engine) = engine.try_borrow_mut() else {
+            return; // Handled once the engine has finished the current message
+        };
+        loop {
+            let message = self.deferred.borrow_mut().pop_front();
+            match message {
+                Some(ExecEngineMessage::Command(command)) => engine.execute(command),
+                Some(ExecEngineMessage::Event(event)) => engine.process(&event),
+                None => break,
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{any::Any, cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        msgbus::{
+            stubs::get_message_saving_handler, MessageBus, MessageHandler, ShareableMessageHandler,
+        },
+    };
+    use nautilus_core::{nanos::UnixNanos, time::AtomicTime, uuid::UUID4};
+    use nautilus_model::{
+        enums::{
+            LiquiditySide, OmsType, OrderSide, OrderStatus, OrderType, PositionSide, TimeInForce,
+        },
+        events::{order::OrderEventAny, position::PositionEvent},
+        identifiers::{
+            AccountId, ClientId, ClientOrderId, PositionId, StrategyId, TradeId, TraderId, Venue,
+            VenueOrderId,
+        },
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{
+            any::OrderAny,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        types::{money::Money, price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+    use ustr::Ustr;
+
+    use super::{ExecutionEngine, ExecutionEngineConfig};
+    use crate::{
+        client::ExecutionClient,
+        messages::{
+            cancel::CancelOrder, cancel_all::CancelAllOrders, cancel_batch::BatchCancelOrders,
+            modify::ModifyOrder, query::QueryOrder, submit::SubmitOrder,
+            submit_list::SubmitOrderList, TradingCommand,
+        },
+        reports::{
+            fill::FillReport, mass_status::ExecutionMassStatus, order::OrderStatusReport,
+            position::PositionStatusReport,
+        },
+    };
+
+    type SavedCommands = Rc<RefCell<Vec<TradingCommand>>>;
+
+    struct StubExecutionClient {
+        client_id: ClientId,
+        venue: Venue,
+        oms_type: OmsType,
+        commands: SavedCommands,
+        mass_status: Option<ExecutionMassStatus>,
+    }
+
+    impl StubExecutionClient {
+        fn new(client_id: &str, venue: &str, oms_type: OmsType) -> (Box<Self>, SavedCommands) {
+            let commands = SavedCommands::default();
+            let client = Self {
+                client_id: ClientId::from(client_id),
+                venue: Venue::from(venue),
+                oms_type,
+                commands: commands.clone(),
+                mass_status: None,
+            };
+            (Box::new(client), commands)
+        }
+
+        fn save(&self, command: TradingCommand) -> anyhow::Result<()> {
+            self.commands.borrow_mut().push(command);
+            Ok(())
+        }
+    }
+
+    impl ExecutionClient for StubExecutionClient {
+        fn client_id(&self) -> ClientId {
+            self.client_id
+        }
+
+        fn account_id(&self) -> AccountId {
+            AccountId::from("SIM-001")
+        }
+
+        fn venue(&self) -> Venue {
+            self.venue
+        }
+
+        fn oms_type(&self) -> OmsType {
+            self.oms_type
+        }
+
+        fn is_connected(&self) -> bool {
+            true
+        }
+
+        fn submit_order(&self, command: SubmitOrder) -> anyhow::Result<()> {
+            self.save(TradingCommand::SubmitOrder(command))
+        }
+
+        fn submit_order_list(&self, command: SubmitOrderList) -> anyhow::Result<()> {
+            self.save(TradingCommand::SubmitOrderList(command))
+        }
+
+        fn modify_order(&self, command: ModifyOrder) -> anyhow::Result<()> {
+            self.save(TradingCommand::ModifyOrder(command))
+        }
+
+        fn cancel_order(&self, command: CancelOrder) -> anyhow::Result<()> {
+            self.save(TradingCommand::CancelOrder(command))
+        }
+
+        fn cancel_all_orders(&self, command: CancelAllOrders) -> anyhow::Result<()> {
+            self.save(TradingCommand::CancelAllOrders(command))
+        }
+
+        fn batch_cancel_orders(&self, command: BatchCancelOrders) -> anyhow::Result<()> {
+            self.save(TradingCommand::BatchCancelOrders(command))
+        }
+
+        fn query_order(&self, command: QueryOrder) -> anyhow::Result<()> {
+            self.save(TradingCommand::QueryOrder(command))
+        }
+
+        fn generate_mass_status(
+            &self,
+            lookback_mins: Option<u64>,
+        ) -> anyhow::Result<Option<ExecutionMassStatus>> {
+            Ok(self.mass_status.clone())
+        }
+    }
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_engine(instrument: &InstrumentAny) -> (ExecutionEngine, Rc<RefCell<Cache>>) {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+
+        let engine = ExecutionEngine::new(
+            clock,
+            cache.clone(),
+            Rc::new(RefCell::new(msgbus)),
+            ExecutionEngineConfig::default(),
+        );
+        (engine, cache)
+    }
+
+    fn submit_order_command(client_id: &str, order: &OrderAny) -> TradingCommand {
+        TradingCommand::SubmitOrder(SubmitOrder {
+            trader_id: order.trader_id(),
+            client_id: ClientId::from(client_id),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        })
+    }
+
+    fn accepted_market_order(
+        instrument: &InstrumentAny,
+        cache: &Rc<RefCell<Cache>>,
+        client_order_id: &str,
+        order_side: OrderSide,
+        quantity: i64,
+    ) -> OrderAny {
+        let order = TestOrderStubs::market_order(
+            instrument.id(),
+            order_side,
+            Quantity::from(quantity),
+            Some(ClientOrderId::from(client_order_id)),
+            None,
+        );
+        let order = TestOrderStubs::make_accepted_order(&order);
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        order
+    }
+
+    fn fill_event(
+        instrument: &InstrumentAny,
+        order: &OrderAny,
+        trade_id: &str,
+        position_id: Option<PositionId>,
+    ) -> OrderEventAny {
+        let event = TestOrderEventStubs::order_filled(
+            order,
+            instrument,
+            Some(TradeId::from(trade_id)),
+            None,
+            Some(Price::from("1.00000")),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        // Clear the stub position ID so the engine determines it
+        match event {
+            OrderEventAny::Filled(mut fill) => {
+                fill.position_id = position_id;
+                OrderEventAny::Filled(fill)
+            }
+            _ => event,
+        }
+    }
+
+    fn order_report(
+        instrument: &InstrumentAny,
+        client_order_id: Option<&str>,
+        venue_order_id: &str,
+        order_status: OrderStatus,
+        filled_qty: i64,
+    ) -> OrderStatusReport {
+        let mut report = OrderStatusReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            client_order_id.map(ClientOrderId::from),
+            VenueOrderId::from(venue_order_id),
+            OrderSide::Buy,
+            OrderType::Market,
+            TimeInForce::Gtc,
+            order_status,
+            Quantity::from(100_000),
+            Quantity::from(filled_qty),
+            UnixNanos::default(),
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        );
+        if filled_qty > 0 {
+            report.avg_px = Some(1.0);
+        }
+        report
+    }
+
+    fn fill_report(
+        instrument: &InstrumentAny,
+        venue_order_id: &str,
+        trade_id: &str,
+        last_qty: i64,
+    ) -> FillReport {
+        FillReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            VenueOrderId::from(venue_order_id),
+            TradeId::from(trade_id),
+            OrderSide::Buy,
+            Quantity::from(last_qty),
+            Price::from("1.00000"),
+            Money::from("2 USD"),
+            LiquiditySide::Taker,
+            None,
+            None,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        )
+    }
+
+    #[rstest]
+    fn test_register_client_twice_errors(instrument: InstrumentAny) {
+        let (mut engine, _) = get_engine(&instrument);
+        let (client1, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        let (client2, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+
+        engine.register_client(client1).unwrap();
+        let result = engine.register_client(client2);
+
+        assert!(result.is_err());
+        assert!(engine.check_connected());
+    }
+
+    #[rstest]
+    fn test_deregister_client_removes_routing(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        engine.deregister_client(ClientId::from("SIM")).unwrap();
+        engine.execute(submit_order_command("OTHER", &order));
+
+        assert!(commands.borrow().is_empty());
+        assert!(engine.deregister_client(ClientId::from("SIM")).is_err());
+    }
+
+    #[rstest]
+    #[case("SIM")]
+    #[case("OTHER")]
+    fn test_submit_order_routes_to_venue_client(
+        instrument: InstrumentAny,
+        #[case] command_client_id: &str,
+    ) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        engine.execute(submit_order_command(command_client_id, &order));
+
+        assert_eq!(commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_submit_order_routes_to_default_client(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("BINANCE", "BINANCE", OmsType::Netting);
+        let (default_client, default_commands) =
+            StubExecutionClient::new("DEFAULT", "DEFAULT", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        engine.register_default_client(default_client).unwrap();
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        engine.execute(submit_order_command("OTHER", &order));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(default_commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_register_handles_endpoints_on_msgbus(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let msgbus = engine.msgbus.clone();
+        let engine = Rc::new(RefCell::new(engine));
+        ExecutionEngine::register(&engine);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        msgbus
+            .borrow()
+            .send("ExecEngine.execute", &submit_order_command("SIM", &order));
+        msgbus.borrow().send(
+            "ExecEngine.process",
+            &fill_event(&instrument, &order, "T-1", None),
+        );
+
+        assert_eq!(commands.borrow().len(), 1);
+        let order = cache
+            .borrow()
+            .order(&order.client_order_id())
+            .cloned()
+            .unwrap();
+        assert_eq!(order.status(), OrderStatus::Filled);
+    }
+
+    struct ResubmitHandler {
+        msgbus: Rc<RefCell<MessageBus>>,
+        command: TradingCommand,
+    }
+
+    impl MessageHandler for ResubmitHandler {
+        fn id(&self) -> Ustr {
+            Ustr::from("ResubmitHandler")
+        }
+
+        fn handle(&self, message: &dyn Any) {
+            if let Some(OrderEventAny::Filled(_)) = message.downcast_ref::<OrderEventAny>() {
+                self.msgbus
+                    .borrow()
+                    .send("ExecEngine.execute", &self.command);
+            }
+        }
+    }
+
+    #[rstest]
+    fn test_register_handles_command_sent_while_processing(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let msgbus = engine.msgbus.clone();
+        let engine = Rc::new(RefCell::new(engine));
+        ExecutionEngine::register(&engine);
+        let order1 = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let order2 = accepted_market_order(&instrument, &cache, "O-2", OrderSide::Sell, 100_000);
+        let handler = ResubmitHandler {
+            msgbus: msgbus.clone(),
+            command: submit_order_command("SIM", &order2),
+        };
+        msgbus.borrow_mut().subscribe(
+            &format!("events.order.{}", order1.strategy_id()),
+            ShareableMessageHandler(Rc::new(handler)),
+            None,
+        );
+
+        msgbus.borrow().send(
+            "ExecEngine.process",
+            &fill_event(&instrument, &order1, "T-1", None),
+        );
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert!(matches!(
+            &commands.borrow()[0],
+            TradingCommand::SubmitOrder(command) if command.client_order_id == order2.client_order_id()
+        ));
+    }
+
+    #[rstest]
+    fn test_submit_quote_quantity_order_without_price_is_denied(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, commands) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let mut order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Quantity::from(100_000),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        if let OrderAny::Market(ref mut market) = order {
+            market.is_quote_quantity = true;
+        }
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+
+        engine.execute(submit_order_command("SIM", &order));
+
+        assert!(commands.borrow().is_empty());
+        assert!(cache.borrow().is_order_closed(&order.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_fill_opens_netting_position(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let (handler, events) = get_message_saving_handler::<PositionEvent>(None);
+        engine.msgbus.borrow_mut().subscribe(
+            &format!("events.position.{}", StrategyId::default()),
+            handler,
+            None,
+        );
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        engine.process(&fill_event(&instrument, &order, "E-1", None));
+
+        let position_id = PositionId::from("AUD/USD.SIM-S-001");
+        let cache = cache.borrow();
+        let position = cache.position(&position_id).unwrap();
+        assert_eq!(position.side, PositionSide::Long);
+        assert_eq!(position.quantity, Quantity::from(100_000));
+        assert_eq!(
+            cache.position_id(&order.client_order_id()),
+            Some(&position_id)
+        );
+        assert!(cache.order(&order.client_order_id()).unwrap().is_closed());
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [PositionEvent::PositionOpened(_)]
+        ));
+    }
+
+    #[rstest]
+    fn test_netting_fills_close_position(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let (handler, events) = get_message_saving_handler::<PositionEvent>(None);
+        engine.msgbus.borrow_mut().subscribe(
+            &format!("events.position.{}", StrategyId::default()),
+            handler,
+            None,
+        );
+        let order1 = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let order2 = accepted_market_order(&instrument, &cache, "O-2", OrderSide::Sell, 100_000);
+
+        engine.process(&fill_event(&instrument, &order1, "E-1", None));
+        engine.process(&fill_event(&instrument, &order2, "E-2", None));
+
+        let cache = cache.borrow();
+        let position = cache
+            .position(&PositionId::from("AUD/USD.SIM-S-001"))
+            .unwrap();
+        assert!(position.is_closed());
+        assert_eq!(cache.positions_closed_count(None, None, None, None), 1);
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [
+                PositionEvent::PositionOpened(_),
+                PositionEvent::PositionClosed(_)
+            ]
+        ));
+    }
+
+    #[rstest]
+    fn test_hedging_fills_open_separate_positions(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Hedging);
+        engine.register_client(client).unwrap();
+        let order1 = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let order2 = accepted_market_order(&instrument, &cache, "O-2", OrderSide::Sell, 100_000);
+
+        engine.process(&fill_event(&instrument, &order1, "E-1", None));
+        engine.process(&fill_event(&instrument, &order2, "E-2", None));
+
+        let cache = cache.borrow();
+        let position_id1 = *cache.position_id(&order1.client_order_id()).unwrap();
+        let position_id2 = *cache.position_id(&order2.client_order_id()).unwrap();
+        assert_ne!(position_id1, position_id2);
+        assert!(position_id1.is_virtual());
+        assert_eq!(cache.positions_open_count(None, None, None, None), 2);
+        assert_eq!(engine.position_id_count(StrategyId::default()), 2);
+    }
+
+    #[rstest]
+    fn test_hedging_fill_with_venue_position_id(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Hedging);
+        engine.register_client(client).unwrap();
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let position_id = PositionId::from("SIM-POS-1");
+
+        engine.process(&fill_event(&instrument, &order, "E-1", Some(position_id)));
+
+        assert!(cache.borrow().position(&position_id).unwrap().is_open());
+        assert_eq!(engine.position_id_count(StrategyId::default()), 0);
+    }
+
+    #[rstest]
+    fn test_strategy_oms_override(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        engine.register_oms_type(StrategyId::default(), OmsType::Hedging);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+
+        engine.process(&fill_event(&instrument, &order, "E-1", None));
+
+        let cache = cache.borrow();
+        assert!(cache
+            .position_id(&order.client_order_id())
+            .unwrap()
+            .is_virtual());
+    }
+
+    #[rstest]
+    fn test_netting_fill_flips_position(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let (handler, events) = get_message_saving_handler::<PositionEvent>(None);
+        engine.msgbus.borrow_mut().subscribe(
+            &format!("events.position.{}", StrategyId::default()),
+            handler,
+            None,
+        );
+        let order1 = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let order2 = accepted_market_order(&instrument, &cache, "O-2", OrderSide::Sell, 150_000);
+
+        engine.process(&fill_event(&instrument, &order1, "E-1", None));
+        engine.process(&fill_event(&instrument, &order2, "E-2", None));
+
+        let cache = cache.borrow();
+        let position = cache
+            .position(&PositionId::from("AUD/USD.SIM-S-001"))
+            .unwrap();
+        assert_eq!(position.side, PositionSide::Short);
+        assert_eq!(position.quantity, Quantity::from(50_000));
+        assert_eq!(position.opening_order_id, order2.client_order_id());
+        assert!(matches!(
+            events.borrow().as_slice(),
+            [
+                PositionEvent::PositionOpened(_),
+                PositionEvent::PositionClosed(_),
+                PositionEvent::PositionOpened(_)
+            ]
+        ));
+    }
+
+    #[rstest]
+    fn test_hedging_fill_flips_virtual_position(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Hedging);
+        engine.register_client(client).unwrap();
+        let order1 = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        engine.process(&fill_event(&instrument, &order1, "E-1", None));
+        let position_id = *cache
+            .borrow()
+            .position_id(&order1.client_order_id())
+            .unwrap();
+        let order2 = accepted_market_order(&instrument, &cache, "O-2", OrderSide::Sell, 150_000);
+
+        engine.process(&fill_event(&instrument, &order2, "E-2", Some(position_id)));
+
+        let cache = cache.borrow();
+        assert!(cache.position(&position_id).unwrap().is_closed());
+        let flipped_id = *cache.position_id(&order2.client_order_id()).unwrap();
+        assert_ne!(flipped_id, position_id);
+        assert!(flipped_id.as_str().ends_with('F'));
+        let flipped = cache.position(&flipped_id).unwrap();
+        assert_eq!(flipped.side, PositionSide::Short);
+        assert_eq!(flipped.quantity, Quantity::from(50_000));
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_generates_external_order(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let mut report = order_report(&instrument, None, "V-100", OrderStatus::Accepted, 0);
+        report.order_type = OrderType::Limit;
+        report.price = Some(Price::from("0.90000"));
+
+        assert!(engine.reconcile_order_report(&report, &[]));
+
+        let cache = cache.borrow();
+        let client_order_id = *cache.client_order_id(&VenueOrderId::from("V-100")).unwrap();
+        assert_eq!(client_order_id, ClientOrderId::from("O-V-100"));
+        let order = cache.order(&client_order_id).unwrap();
+        assert_eq!(order.strategy_id(), StrategyId::external());
+        assert_eq!(order.status(), OrderStatus::Accepted);
+        assert_eq!(order.price(), Some(Price::from("0.90000")));
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_with_missing_price_fails(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let mut report = order_report(&instrument, None, "V-100", OrderStatus::Accepted, 0);
+        report.order_type = OrderType::Limit;
+
+        assert!(!engine.reconcile_order_report(&report, &[]));
+        assert!(cache.borrow().orders(None, None, None, None).is_empty());
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_applies_missing_fills(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let report = order_report(
+            &instrument,
+            Some("O-1"),
+            "V-001",
+            OrderStatus::Filled,
+            100_000,
+        );
+        let trades = vec![
+            fill_report(&instrument, "V-001", "T-1", 40_000),
+            fill_report(&instrument, "V-001", "T-2", 60_000),
+        ];
+
+        assert!(engine.reconcile_order_report(&report, &trades));
+        // Reconciling again is idempotent
+        assert!(engine.reconcile_order_report(&report, &trades));
+
+        let cache = cache.borrow();
+        let order = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(order.status(), OrderStatus::Filled);
+        assert_eq!(order.trade_ids().len(), 2);
+        let position = cache
+            .position(&PositionId::from("AUD/USD.SIM-S-001"))
+            .unwrap();
+        assert_eq!(position.quantity, Quantity::from(100_000));
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_infers_fill(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let report = order_report(
+            &instrument,
+            Some("O-1"),
+            "V-001",
+            OrderStatus::PartiallyFilled,
+            40_000,
+        );
+
+        assert!(engine.reconcile_order_report(&report, &[]));
+
+        let cache = cache.borrow();
+        let order = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(order.status(), OrderStatus::PartiallyFilled);
+        assert_eq!(order.filled_qty(), Quantity::from(40_000));
+        assert_eq!(order.avg_px(), Some(1.0));
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_infers_fill_price_from_avg_px(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let trades = vec![fill_report(&instrument, "V-001", "T-1", 50_000)];
+        let mut report = order_report(
+            &instrument,
+            Some("O-1"),
+            "V-001",
+            OrderStatus::Filled,
+            100_000,
+        );
+        report.avg_px = Some(1.1);
+
+        assert!(engine.reconcile_order_report(&report, &trades));
+
+        let cache = cache.borrow();
+        let order = cache.order(&order.client_order_id()).unwrap();
+        assert_eq!(order.status(), OrderStatus::Filled);
+        let events = order.events();
+        let Some(OrderEventAny::Filled(fill)) = events.last().copied() else {
+            panic!("Expected inferred fill");
+        };
+        assert_eq!(fill.last_qty, Quantity::from(50_000));
+        assert_eq!(fill.last_px, Price::from("1.20000"));
+        assert!(fill.reconciliation);
+    }
+
+    #[rstest]
+    #[case(OrderStatus::Canceled)]
+    #[case(OrderStatus::Expired)]
+    #[case(OrderStatus::Rejected)]
+    fn test_reconcile_order_report_status_drift(
+        instrument: InstrumentAny,
+        #[case] order_status: OrderStatus,
+    ) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        let report = order_report(&instrument, Some("O-1"), "V-001", order_status, 0);
+
+        assert!(engine.reconcile_order_report(&report, &[]));
+
+        let cache = cache.borrow();
+        assert_eq!(
+            cache.order(&order.client_order_id()).unwrap().status(),
+            order_status
+        );
+        assert!(cache.is_order_closed(&order.client_order_id()));
+    }
+
+    #[rstest]
+    fn test_reconcile_order_report_with_less_filled_fails(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        engine.process(&fill_event(&instrument, &order, "E-1", None));
+        let report = order_report(
+            &instrument,
+            Some("O-1"),
+            "V-001",
+            OrderStatus::PartiallyFilled,
+            40_000,
+        );
+
+        assert!(!engine.reconcile_order_report(&report, &[]));
+    }
+
+    #[rstest]
+    fn test_reconcile_fill_report_without_order_fails(instrument: InstrumentAny) {
+        let (mut engine, _) = get_engine(&instrument);
+        let report = fill_report(&instrument, "V-100", "T-1", 100_000);
+
+        assert!(!engine.reconcile_fill_report(&report));
+    }
+
+    #[rstest]
+    #[case(PositionSide::Long, 100_000, true)]
+    #[case(PositionSide::Long, 50_000, false)]
+    #[case(PositionSide::Short, 100_000, false)]
+    fn test_reconcile_netting_position_report(
+        instrument: InstrumentAny,
+        #[case] position_side: PositionSide,
+        #[case] quantity: i64,
+        #[case] expected: bool,
+    ) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        engine.register_client(client).unwrap();
+        let order = accepted_market_order(&instrument, &cache, "O-1", OrderSide::Buy, 100_000);
+        engine.process(&fill_event(&instrument, &order, "E-1", None));
+        let report = PositionStatusReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            position_side,
+            Quantity::from(quantity),
+            None,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        );
+
+        assert_eq!(engine.reconcile_position_report(&report), expected);
+    }
+
+    #[rstest]
+    fn test_reconcile_state_from_client_mass_status(instrument: InstrumentAny) {
+        let (mut engine, cache) = get_engine(&instrument);
+        let (mut client, _) = StubExecutionClient::new("SIM", "SIM", OmsType::Netting);
+        let mut mass_status = ExecutionMassStatus::new(
+            ClientId::from("SIM"),
+            AccountId::from("SIM-001"),
+            Venue::from("SIM"),
+            UnixNanos::default(),
+            None,
+        );
+        mass_status.add_order_reports(vec![order_report(
+            &instrument,
+            None,
+            "V-100",
+            OrderStatus::Filled,
+            100_000,
+        )]);
+        mass_status.add_fill_reports(vec![fill_report(&instrument, "V-100", "T-1", 100_000)]);
+        mass_status.add_position_reports(vec![PositionStatusReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            PositionSide::Long,
+            Quantity::from(100_000),
+            None,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        )]);
+        client.mass_status = Some(mass_status);
+        engine.register_client(client).unwrap();
+
+        assert!(engine.reconcile_state(None));
+
+        let cache = cache.borrow();
+        let order = cache.order(&ClientOrderId::from("O-V-100")).unwrap();
+        assert_eq!(order.status(), OrderStatus::Filled);
+        assert_eq!(order.strategy_id(), StrategyId::external());
+        assert_eq!(cache.positions_open_count(None, None, None, None), 1);
     }
 }
@@ -124,6 +124,21 @@ impl OrderMatchingCore {
         }
     }
 
+    /// Replaces the stored order with the given `order` (matched on client order ID),
+    /// keeping its position in the queue.
+    pub fn update_order(&mut self, order: PassiveOrderAny) -> Result<(), OrderError> {
+        let orders = match order.order_side_specified() {
+            OrderSideSpecified::Buy => &mut self.orders_bid,
+            OrderSideSpecified::Sell => &mut self.orders_ask,
+        };
+        let existing = orders
+            .iter_mut()
+            .find(|o| **o == order)
+            .ok_or(OrderError::NotFound(order.client_order_id()))?;
+        *existing = order;
+        Ok(())
+    }
+
     pub fn delete_order(&mut self, order: &PassiveOrderAny) -> Result<(), OrderError> {
         match order.order_side_specified() {
             OrderSideSpecified::Buy => {
@@ -193,17 +208,49 @@ impl OrderMatchingCore {
 
     #[must_use]
     pub fn is_limit_matched(&self, order: &LimitOrderAny) -> bool {
-        match order.order_side_specified() {
-            OrderSideSpecified::Buy => self.ask.map_or(false, |a| a <= order.limit_px()),
-            OrderSideSpecified::Sell => self.bid.map_or(false, |b| b >= order.limit_px()),
+        match order {
+            // A market-to-limit order has no limit price until its initial fill
+            LimitOrderAny::MarketToLimit(mtl) => mtl.price.map_or(false, |price| {
+                self.is_limit_price_matched(order.order_side_specified(), price)
+            }),
+            _ => self.is_limit_price_matched(order.order_side_specified(), order.limit_px()),
         }
     }
 
     #[must_use]
     pub fn is_stop_matched(&self, order: &StopOrderAny) -> bool {
-        match order.order_side_specified() {
-            OrderSideSpecified::Buy => self.ask.map_or(false, |a| a >= order.stop_px()),
-            OrderSideSpecified::Sell => self.bid.map_or(false, |b| b <= order.stop_px()),
+        match order {
+            StopOrderAny::LimitIfTouched(_) | StopOrderAny::MarketIfTouched(_) => {
+                self.is_touch_triggered(order.order_side_specified(), order.stop_px())
+            }
+            _ => self.is_stop_triggered(order.order_side_specified(), order.stop_px()),
+        }
+    }
+
+    /// Returns whether a limit order at the given `price` would match the current market.
+    #[must_use]
+    pub fn is_limit_price_matched(&self, side: OrderSideSpecified, price: Price) -> bool {
+        match side {
+            OrderSideSpecified::Buy => self.ask.map_or(false, |a| a <= price),
+            OrderSideSpecified::Sell => self.bid.map_or(false, |b| b >= price),
+        }
+    }
+
+    /// Returns whether a stop order at the given `trigger_price` would trigger.
+    #[must_use]
+    pub fn is_stop_triggered(&self, side: OrderSideSpecified, trigger_price: Price) -> bool {
+        match side {
+            OrderSideSpecified::Buy => self.ask.map_or(false, |a| a >= trigger_price),
+            OrderSideSpecified::Sell => self.bid.map_or(false, |b| b <= trigger_price),
+        }
+    }
+
+    /// Returns whether an if-touched order at the given `trigger_price` would trigger.
+    #[must_use]
+    pub fn is_touch_triggered(&self, side: OrderSideSpecified, trigger_price: Price) -> bool {
+        match side {
+            OrderSideSpecified::Buy => self.ask.map_or(false, |a| a <= trigger_price),
+            OrderSideSpecified::Sell => self.bid.map_or(false, |b| b >= trigger_price),
         }
     }
 }
@@ -310,6 +357,56 @@ mod tests {
         assert!(!matching_core.order_exists(client_order_id));
     }
 
+    #[rstest]
+    fn test_update_order_when_not_exists() {
+        let instrument_id = InstrumentId::from("AAPL.XNAS");
+        let mut matching_core = create_matching_core(instrument_id, Price::from("0.01"));
+
+        let order = TestOrderStubs::limit_order(
+            instrument_id,
+            OrderSide::Buy,
+            Price::from("100.00"),
+            Quantity::from("100"),
+            None,
+            None,
+        );
+
+        let result = matching_core.update_order(order.into());
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_update_order_keeps_queue_position() {
+        let instrument_id = InstrumentId::from("AAPL.XNAS");
+        let mut matching_core = create_matching_core(instrument_id, Price::from("0.01"));
+
+        let order1 = TestOrderStubs::limit_order(
+            instrument_id,
+            OrderSide::Buy,
+            Price::from("100.00"),
+            Quantity::from("100"),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        let order2 = TestOrderStubs::limit_order(
+            instrument_id,
+            OrderSide::Buy,
+            Price::from("99.00"),
+            Quantity::from("100"),
+            Some(ClientOrderId::from("O-2")),
+            None,
+        );
+        matching_core.add_order(order1.clone().into()).unwrap();
+        matching_core.add_order(order2.into()).unwrap();
+
+        matching_core.update_order(order1.into()).unwrap();
+
+        let orders = matching_core.get_orders_bid();
+        assert_eq!(orders.len(), 2);
+        assert_eq!(orders[0].client_order_id(), ClientOrderId::from("O-1"));
+        assert_eq!(orders[1].client_order_id(), ClientOrderId::from("O-2"));
+    }
+
     #[rstest]
     fn test_delete_order_when_not_exists() {
         let instrument_id = InstrumentId::from("AAPL.XNAS");
@@ -492,6 +589,29 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[rstest]
+    #[case(Price::from("100.00"), OrderSideSpecified::Buy, false)]
+    #[case(Price::from("101.00"), OrderSideSpecified::Buy, true)] // <-- Trigger at ask
+    #[case(Price::from("102.00"), OrderSideSpecified::Buy, true)]
+    #[case(Price::from("99.00"), OrderSideSpecified::Sell, true)]
+    #[case(Price::from("100.00"), OrderSideSpecified::Sell, true)] // <-- Trigger at bid
+    #[case(Price::from("101.00"), OrderSideSpecified::Sell, false)]
+    fn test_is_touch_triggered(
+        #[case] trigger_price: Price,
+        #[case] side: OrderSideSpecified,
+        #[case] expected: bool,
+    ) {
+        let instrument_id = InstrumentId::from("AAPL.XNAS");
+        let mut matching_core = create_matching_core(instrument_id, Price::from("0.01"));
+        matching_core.bid = Some(Price::from("100.00"));
+        matching_core.ask = Some(Price::from("101.00"));
+
+        assert_eq!(
+            matching_core.is_touch_triggered(side, trigger_price),
+            expected
+        );
+    }
+
     #[rstest]
     #[case(OrderSide::Buy)]
     #[case(OrderSide::Sell)]
@@ -0,0 +1,705 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Functions for generating the order events which reconcile cached orders with the
+//! execution reports of a venue.
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::{
+    enums::{LiquiditySide, OrderType, TimeInForce, TrailingOffsetType, TriggerType},
+    events::order::{
+        accepted::OrderAccepted, canceled::OrderCanceled, expired::OrderExpired,
+        filled::OrderFilled, initialized::OrderInitialized, rejected::OrderRejected,
+        triggered::OrderTriggered, updated::OrderUpdated, OrderEventAny,
+    },
+    identifiers::{ClientOrderId, StrategyId, TradeId, TraderId},
+    instruments::any::InstrumentAny,
+    orders::any::OrderAny,
+    types::{money::Money, quantity::Quantity},
+};
+use rust_decimal::prelude::ToPrimitive;
+use ustr::Ustr;
+
+use crate::reports::{fill::FillReport, order::OrderStatusReport};
+
+/// Generates the initialization event for an external order from the given `report`.
+///
+/// # Errors
+///
+/// This function returns an error if the `report` is missing the prices, trailing offsets or
+/// expire time required to initialize an order of the reported type.
+pub fn generate_external_order_initialized(
+    report: &OrderStatusReport,
+    trader_id: TraderId,
+    strategy_id: StrategyId,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderInitialized> {
+    let order_type = report.order_type;
+    let has_price = matches!(
+        order_type,
+        OrderType::Limit
+            | OrderType::StopLimit
+            | OrderType::LimitIfTouched
+            | OrderType::TrailingStopLimit
+    );
+    let has_trigger_price = matches!(
+        order_type,
+        OrderType::StopMarket
+            | OrderType::StopLimit
+            | OrderType::MarketIfTouched
+            | OrderType::LimitIfTouched
+            | OrderType::TrailingStopMarket
+            | OrderType::TrailingStopLimit
+    );
+    let is_trailing = matches!(
+        order_type,
+        OrderType::TrailingStopMarket | OrderType::TrailingStopLimit
+    );
+
+    if has_price && report.price.is_none() {
+        anyhow::bail!("No price reported for {order_type} order");
+    }
+    if has_trigger_price && report.trigger_price.is_none() {
+        anyhow::bail!("No trigger price reported for {order_type} order");
+    }
+    if is_trailing
+        && (report.trailing_offset.is_none()
+            || report.trailing_offset_type == TrailingOffsetType::NoTrailingOffset)
+    {
+        anyhow::bail!("No trailing offset reported for {order_type} order");
+    }
+    if order_type == OrderType::TrailingStopLimit && report.limit_offset.is_none() {
+        anyhow::bail!("No limit offset reported for {order_type} order");
+    }
+    if report.time_in_force == TimeInForce::Gtd && report.expire_time.is_none() {
+        anyhow::bail!("No expire time reported for GTD order");
+    }
+
+    let client_order_id = match report.client_order_id {
+        Some(client_order_id) => client_order_id,
+        None => ClientOrderId::new(&format!("O-{}", report.venue_order_id))?,
+    };
+    let trigger_type = if has_trigger_price {
+        Some(report.trigger_type.unwrap_or(TriggerType::Default))
+    } else {
+        None
+    };
+
+    Ok(OrderInitialized {
+        trader_id,
+        strategy_id,
+        instrument_id: report.instrument_id,
+        client_order_id,
+        order_side: report.order_side,
+        order_type,
+        quantity: report.quantity,
+        time_in_force: report.time_in_force,
+        post_only: report.post_only,
+        reduce_only: report.reduce_only,
+        quote_quantity: false,
+        reconciliation: true,
+        event_id: UUID4::new(),
+        ts_event: report.ts_accepted,
+        ts_init,
+        price: report.price,
+        trigger_price: report.trigger_price,
+        trigger_type,
+        limit_offset: report.limit_offset,
+        trailing_offset: report.trailing_offset,
+        trailing_offset_type: is_trailing.then_some(report.trailing_offset_type),
+        expire_time: report.expire_time,
+        display_qty: report.display_qty,
+        contingency_type: Some(report.contingency_type),
+        order_list_id: report.order_list_id,
+        ..Default::default()
+    })
+}
+
+/// Generates an `OrderAccepted` event for the `order` from the given `report`.
+pub fn generate_order_accepted(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let event = OrderAccepted::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        report.venue_order_id,
+        report.account_id,
+        UUID4::new(),
+        report.ts_accepted,
+        ts_init,
+        true,
+    )?;
+    Ok(OrderEventAny::Accepted(event))
+}
+
+/// Generates an `OrderRejected` event for the `order` from the given `report`.
+pub fn generate_order_rejected(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let reason = report.cancel_reason.as_deref().unwrap_or("UNKNOWN");
+    let event = OrderRejected::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        report.account_id,
+        Ustr::from(reason),
+        UUID4::new(),
+        report.ts_last,
+        ts_init,
+        true,
+    )?;
+    Ok(OrderEventAny::Rejected(event))
+}
+
+/// Generates an `OrderTriggered` event for the `order` from the given `report`.
+pub fn generate_order_triggered(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let event = OrderTriggered::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        UUID4::new(),
+        report.ts_triggered.unwrap_or(report.ts_last),
+        ts_init,
+        true,
+        Some(report.venue_order_id),
+        Some(report.account_id),
+    )?;
+    Ok(OrderEventAny::Triggered(event))
+}
+
+/// Generates an `OrderCanceled` event for the `order` from the given `report`.
+pub fn generate_order_canceled(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let event = OrderCanceled::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        UUID4::new(),
+        report.ts_last,
+        ts_init,
+        true,
+        Some(report.venue_order_id),
+        Some(report.account_id),
+    )?;
+    Ok(OrderEventAny::Canceled(event))
+}
+
+/// Generates an `OrderExpired` event for the `order` from the given `report`.
+pub fn generate_order_expired(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let event = OrderExpired::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        UUID4::new(),
+        report.ts_last,
+        ts_init,
+        true,
+        Some(report.venue_order_id),
+        Some(report.account_id),
+    )?;
+    Ok(OrderEventAny::Expired(event))
+}
+
+/// Generates an `OrderUpdated` event for the `order` if the quantity, price or trigger
+/// price in the given `report` has drifted from the cached order, otherwise returns `None`.
+pub fn generate_order_updated(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    ts_init: UnixNanos,
+) -> anyhow::Result<Option<OrderEventAny>> {
+    let price = report.price.filter(|price| Some(*price) != order.price());
+    let trigger_price = report
+        .trigger_price
+        .filter(|trigger_price| Some(*trigger_price) != order.trigger_price());
+    if report.quantity == order.quantity() && price.is_none() && trigger_price.is_none() {
+        return Ok(None);
+    }
+
+    let event = OrderUpdated::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        report.quantity,
+        UUID4::new(),
+        report.ts_last,
+        ts_init,
+        true,
+        Some(report.venue_order_id),
+        Some(report.account_id),
+        price,
+        trigger_price,
+    )?;
+    Ok(Some(OrderEventAny::Updated(event)))
+}
+
+/// Generates a fill event for the `order` from the given fill `report`.
+pub fn generate_order_filled(
+    order: &OrderAny,
+    report: &FillReport,
+    instrument: &InstrumentAny,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    let fill = OrderFilled::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        report.venue_order_id,
+        report.account_id,
+        report.trade_id,
+        order.order_side(),
+        order.order_type(),
+        report.last_qty,
+        report.last_px,
+        instrument.quote_currency(),
+        report.liquidity_side,
+        UUID4::new(),
+        report.ts_event,
+        ts_init,
+        true,
+        report.venue_position_id,
+        Some(report.commission),
+    )?;
+    Ok(fill_event(order, fill))
+}
+
+/// Generates a fill event for the `order` inferred from the difference between the filled
+/// quantity in the given `report` and the cached order.
+///
+/// The fill price is derived from the reported average price where available, and the
+/// commission is calculated from the instrument fees.
+///
+/// # Errors
+///
+/// This function returns an error if:
+/// - The reported filled quantity is not greater than the cached filled quantity.
+/// - No average price or price was reported to infer the fill price from.
+pub fn generate_inferred_fill(
+    order: &OrderAny,
+    report: &OrderStatusReport,
+    instrument: &InstrumentAny,
+    ts_init: UnixNanos,
+) -> anyhow::Result<OrderEventAny> {
+    if report.filled_qty <= order.filled_qty() {
+        anyhow::bail!(
+            "Cannot infer fill: reported filled_qty {} not greater than {}",
+            report.filled_qty,
+            order.filled_qty()
+        );
+    }
+
+    let last_qty: Quantity = report.filled_qty - order.filled_qty();
+    let last_px = match (report.avg_px, order.avg_px()) {
+        (Some(report_avg_px), Some(order_avg_px)) => {
+            let report_notional = report_avg_px * report.filled_qty.as_f64();
+            let order_notional = order_avg_px * order.filled_qty().as_f64();
+            (report_notional - order_notional) / last_qty.as_f64()
+        }
+        (Some(report_avg_px), None) => report_avg_px,
+        (None, _) => report
+            .price
+            .or(order.price())
+            .map(|price| price.as_f64())
+            .ok_or_else(|| anyhow::anyhow!("Cannot infer fill: no avg_px or price reported"))?,
+    };
+    let last_px = instrument.make_price(last_px)?;
+
+    let liquidity_side = match order.order_type() {
+        OrderType::Market
+        | OrderType::MarketToLimit
+        | OrderType::StopMarket
+        | OrderType::TrailingStopMarket => LiquiditySide::Taker,
+        _ if report.post_only => LiquiditySide::Maker,
+        _ => LiquiditySide::NoLiquiditySide,
+    };
+    let fee = match liquidity_side {
+        LiquiditySide::Maker => instrument.maker_fee(),
+        _ => instrument.taker_fee(),
+    };
+    let notional = instrument.calculate_notional_value(last_qty, last_px, Some(false));
+    let commission = Money::new(notional * fee.to_f64().unwrap_or(0.0), notional.currency)?;
+
+    let fill = OrderFilled::new(
+        order.trader_id(),
+        order.strategy_id(),
+        order.instrument_id(),
+        order.client_order_id(),
+        report.venue_order_id,
+        report.account_id,
+        TradeId::new(&UUID4::new().to_string())?,
+        order.order_side(),
+        order.order_type(),
+        last_qty,
+        last_px,
+        instrument.quote_currency(),
+        liquidity_side,
+        UUID4::new(),
+        report.ts_last,
+        ts_init,
+        true,
+        None,
+        Some(commission),
+    )?;
+    Ok(fill_event(order, fill))
+}
+
+fn fill_event(order: &OrderAny, fill: OrderFilled) -> OrderEventAny {
+    if fill.last_qty >= order.leaves_qty() {
+        OrderEventAny::Filled(fill)
+    } else {
+        OrderEventAny::PartiallyFilled(fill)
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::nanos::UnixNanos;
+    use nautilus_model::{
+        enums::{LiquiditySide, OrderSide, OrderStatus, OrderType, TimeInForce},
+        events::order::OrderEventAny,
+        identifiers::{AccountId, ClientOrderId, StrategyId, TradeId, TraderId, VenueOrderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{
+            any::OrderAny,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        types::{money::Money, price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+
+    use super::*;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn accepted_order(instrument: &InstrumentAny, order_type: OrderType) -> OrderAny {
+        let order = match order_type {
+            OrderType::Market => TestOrderStubs::market_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Quantity::from(100_000),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+            _ => TestOrderStubs::limit_order(
+                instrument.id(),
+                OrderSide::Buy,
+                Price::from("1.00000"),
+                Quantity::from(100_000),
+                Some(ClientOrderId::from("O-1")),
+                None,
+            ),
+        };
+        TestOrderStubs::make_accepted_order(&order)
+    }
+
+    fn order_report(
+        instrument: &InstrumentAny,
+        order_type: OrderType,
+        order_status: OrderStatus,
+        filled_qty: i64,
+    ) -> OrderStatusReport {
+        OrderStatusReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            Some(ClientOrderId::from("O-1")),
+            VenueOrderId::from("V-001"),
+            OrderSide::Buy,
+            order_type,
+            TimeInForce::Gtc,
+            order_status,
+            Quantity::from(100_000),
+            Quantity::from(filled_qty),
+            UnixNanos::from(1),
+            UnixNanos::from(2),
+            UnixNanos::from(3),
+            None,
+        )
+    }
+
+    fn fill_report(instrument: &InstrumentAny, last_qty: i64) -> FillReport {
+        FillReport::new(
+            AccountId::from("SIM-001"),
+            instrument.id(),
+            VenueOrderId::from("V-001"),
+            TradeId::from("T-1"),
+            OrderSide::Buy,
+            Quantity::from(last_qty),
+            Price::from("1.00000"),
+            Money::from("2 USD"),
+            LiquiditySide::Taker,
+            None,
+            None,
+            UnixNanos::from(2),
+            UnixNanos::from(3),
+            None,
+        )
+    }
+
+    #[rstest]
+    fn test_generate_external_order_initialized(instrument: InstrumentAny) {
+        let mut report = order_report(&instrument, OrderType::Limit, OrderStatus::Accepted, 0);
+        report.client_order_id = None;
+        report.price = Some(Price::from("1.00000"));
+
+        let event = generate_external_order_initialized(
+            &report,
+            TraderId::from("TRADER-001"),
+            StrategyId::from("EXTERNAL"),
+            UnixNanos::from(3),
+        )
+        .unwrap();
+
+        assert_eq!(event.client_order_id, ClientOrderId::from("O-V-001"));
+        assert_eq!(event.order_type, OrderType::Limit);
+        assert_eq!(event.price, Some(Price::from("1.00000")));
+        assert_eq!(event.trigger_type, None);
+        assert_eq!(event.ts_event, UnixNanos::from(1));
+        assert!(event.reconciliation);
+    }
+
+    #[rstest]
+    #[case(
+        OrderType::Limit,
+        TimeInForce::Gtc,
+        "No price reported for LIMIT order"
+    )]
+    #[case(
+        OrderType::StopMarket,
+        TimeInForce::Gtc,
+        "No trigger price reported for STOP_MARKET order"
+    )]
+    #[case(
+        OrderType::Market,
+        TimeInForce::Gtd,
+        "No expire time reported for GTD order"
+    )]
+    fn test_generate_external_order_initialized_with_missing_fields_fails(
+        instrument: InstrumentAny,
+        #[case] order_type: OrderType,
+        #[case] time_in_force: TimeInForce,
+        #[case] expected: &str,
+    ) {
+        let mut report = order_report(&instrument, order_type, OrderStatus::Accepted, 0);
+        report.time_in_force = time_in_force;
+
+        let result = generate_external_order_initialized(
+            &report,
+            TraderId::from("TRADER-001"),
+            StrategyId::from("EXTERNAL"),
+            UnixNanos::from(3),
+        );
+
+        assert_eq!(result.unwrap_err().to_string(), expected);
+    }
+
+    #[rstest]
+    fn test_generate_order_rejected_without_reason(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Market);
+        let report = order_report(&instrument, OrderType::Market, OrderStatus::Rejected, 0);
+
+        let event = generate_order_rejected(&order, &report, UnixNanos::from(3)).unwrap();
+
+        let OrderEventAny::Rejected(rejected) = event else {
+            panic!("Expected `OrderRejected`, was {event:?}");
+        };
+        assert_eq!(rejected.reason.as_str(), "UNKNOWN");
+        assert_eq!(rejected.ts_event, UnixNanos::from(2));
+        assert_eq!(rejected.reconciliation, 1);
+    }
+
+    #[rstest]
+    fn test_generate_order_triggered_uses_ts_triggered(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Limit);
+        let mut report = order_report(&instrument, OrderType::Limit, OrderStatus::Triggered, 0);
+        report.ts_triggered = Some(UnixNanos::from(1));
+
+        let event = generate_order_triggered(&order, &report, UnixNanos::from(3)).unwrap();
+
+        let OrderEventAny::Triggered(triggered) = event else {
+            panic!("Expected `OrderTriggered`, was {event:?}");
+        };
+        assert_eq!(triggered.ts_event, UnixNanos::from(1));
+        assert_eq!(triggered.venue_order_id, Some(VenueOrderId::from("V-001")));
+    }
+
+    #[rstest]
+    fn test_generate_order_updated_without_drift_is_none(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Limit);
+        let mut report = order_report(&instrument, OrderType::Limit, OrderStatus::Accepted, 0);
+        report.price = Some(Price::from("1.00000"));
+
+        let event = generate_order_updated(&order, &report, UnixNanos::from(3)).unwrap();
+
+        assert!(event.is_none());
+    }
+
+    #[rstest]
+    fn test_generate_order_updated_with_drift(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Limit);
+        let mut report = order_report(&instrument, OrderType::Limit, OrderStatus::Accepted, 0);
+        report.quantity = Quantity::from(50_000);
+        report.price = Some(Price::from("1.00010"));
+
+        let event = generate_order_updated(&order, &report, UnixNanos::from(3))
+            .unwrap()
+            .unwrap();
+
+        let OrderEventAny::Updated(updated) = event else {
+            panic!("Expected `OrderUpdated`, was {event:?}");
+        };
+        assert_eq!(updated.quantity, Quantity::from(50_000));
+        assert_eq!(updated.price, Some(Price::from("1.00010")));
+        assert_eq!(updated.trigger_price, None);
+    }
+
+    #[rstest]
+    #[case(40_000, false)]
+    #[case(100_000, true)]
+    fn test_generate_order_filled(
+        instrument: InstrumentAny,
+        #[case] last_qty: i64,
+        #[case] is_filled: bool,
+    ) {
+        let order = accepted_order(&instrument, OrderType::Market);
+        let report = fill_report(&instrument, last_qty);
+
+        let event =
+            generate_order_filled(&order, &report, &instrument, UnixNanos::from(3)).unwrap();
+
+        let fill = match event {
+            OrderEventAny::Filled(fill) if is_filled => fill,
+            OrderEventAny::PartiallyFilled(fill) if !is_filled => fill,
+            _ => panic!("Unexpected fill event {event:?}"),
+        };
+        assert_eq!(fill.trade_id, TradeId::from("T-1"));
+        assert_eq!(fill.last_qty, Quantity::from(last_qty));
+        assert_eq!(fill.commission, Some(Money::from("2 USD")));
+        assert!(fill.reconciliation);
+    }
+
+    #[rstest]
+    fn test_generate_inferred_fill_from_avg_px(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Market);
+        let mut report = order_report(
+            &instrument,
+            OrderType::Market,
+            OrderStatus::PartiallyFilled,
+            40_000,
+        );
+        report.avg_px = Some(1.0);
+
+        let event =
+            generate_inferred_fill(&order, &report, &instrument, UnixNanos::from(3)).unwrap();
+
+        let OrderEventAny::PartiallyFilled(fill) = event else {
+            panic!("Expected partial fill, was {event:?}");
+        };
+        assert_eq!(fill.last_qty, Quantity::from(40_000));
+        assert_eq!(fill.last_px, Price::from("1.00000"));
+        assert_eq!(fill.liquidity_side, LiquiditySide::Taker);
+        assert_eq!(fill.commission, Some(Money::from("0.80 USD")));
+        assert_eq!(fill.ts_event, UnixNanos::from(2));
+        assert!(fill.reconciliation);
+    }
+
+    #[rstest]
+    fn test_generate_inferred_fill_derives_price_from_prior_fills(instrument: InstrumentAny) {
+        let mut order = accepted_order(&instrument, OrderType::Market);
+        let fill = TestOrderEventStubs::order_filled(
+            &order,
+            &instrument,
+            None,
+            None,
+            Some(Price::from("1.00000")),
+            Some(Quantity::from(50_000)),
+            None,
+            None,
+            None,
+            None,
+        );
+        order.apply(fill).unwrap();
+        let mut report = order_report(&instrument, OrderType::Market, OrderStatus::Filled, 100_000);
+        report.avg_px = Some(1.1);
+
+        let event =
+            generate_inferred_fill(&order, &report, &instrument, UnixNanos::from(3)).unwrap();
+
+        let OrderEventAny::Filled(fill) = event else {
+            panic!("Expected fill, was {event:?}");
+        };
+        assert_eq!(fill.last_qty, Quantity::from(50_000));
+        assert_eq!(fill.last_px, Price::from("1.20000"));
+    }
+
+    #[rstest]
+    fn test_generate_inferred_fill_from_price_for_post_only(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Limit);
+        let mut report = order_report(
+            &instrument,
+            OrderType::Limit,
+            OrderStatus::PartiallyFilled,
+            40_000,
+        );
+        report.price = Some(Price::from("0.99990"));
+        report.post_only = true;
+
+        let event =
+            generate_inferred_fill(&order, &report, &instrument, UnixNanos::from(3)).unwrap();
+
+        let OrderEventAny::PartiallyFilled(fill) = event else {
+            panic!("Expected partial fill, was {event:?}");
+        };
+        assert_eq!(fill.last_px, Price::from("0.99990"));
+        assert_eq!(fill.liquidity_side, LiquiditySide::Maker);
+    }
+
+    #[rstest]
+    fn test_generate_inferred_fill_without_more_filled_fails(instrument: InstrumentAny) {
+        let order = accepted_order(&instrument, OrderType::Market);
+        let report = order_report(&instrument, OrderType::Market, OrderStatus::Accepted, 0);
+
+        let result = generate_inferred_fill(&order, &report, &instrument, UnixNanos::from(3));
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,109 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::fmt::Display;
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::{
+    enums::{LiquiditySide, OrderSide},
+    identifiers::{AccountId, ClientOrderId, InstrumentId, PositionId, TradeId, VenueOrderId},
+    types::{money::Money, price::Price, quantity::Quantity},
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents a fill of an order at the venue, as reported by the venue.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FillReport {
+    pub account_id: AccountId,
+    pub instrument_id: InstrumentId,
+    pub venue_order_id: VenueOrderId,
+    pub trade_id: TradeId,
+    pub order_side: OrderSide,
+    pub last_qty: Quantity,
+    pub last_px: Price,
+    pub commission: Money,
+    pub liquidity_side: LiquiditySide,
+    pub report_id: UUID4,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+    pub client_order_id: Option<ClientOrderId>,
+    pub venue_position_id: Option<PositionId>,
+}
+
+impl FillReport {
+    /// Creates a new [`FillReport`] instance.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        account_id: AccountId,
+        instrument_id: InstrumentId,
+        venue_order_id: VenueOrderId,
+        trade_id: TradeId,
+        order_side: OrderSide,
+        last_qty: Quantity,
+        last_px: Price,
+        commission: Money,
+        liquidity_side: LiquiditySide,
+        client_order_id: Option<ClientOrderId>,
+        venue_position_id: Option<PositionId>,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+        report_id: Option<UUID4>,
+    ) -> Self {
+        Self {
+            account_id,
+            instrument_id,
+            venue_order_id,
+            trade_id,
+            order_side,
+            last_qty,
+            last_px,
+            commission,
+            liquidity_side,
+            report_id: report_id.unwrap_or_default(),
+            ts_event,
+            ts_init,
+            client_order_id,
+            venue_position_id,
+        }
+    }
+}
+
+impl Display for FillReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "FillReport(account_id={}, instrument_id={}, client_order_id={}, \
+            venue_order_id={}, venue_position_id={}, trade_id={}, order_side={}, \
+            last_qty={}, last_px={}, commission={}, liquidity_side={}, report_id={}, \
+            ts_event={})",
+            self.account_id,
+            self.instrument_id,
+            self.client_order_id
+                .map_or("None".to_string(), |id| format!("{id}")),
+            self.venue_order_id,
+            self.venue_position_id
+                .map_or("None".to_string(), |id| format!("{id}")),
+            self.trade_id,
+            self.order_side,
+            self.last_qty,
+            self.last_px,
+            self.commission,
+            self.liquidity_side,
+            self.report_id,
+            self.ts_event,
+        )
+    }
+}
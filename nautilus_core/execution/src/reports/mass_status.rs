@@ -0,0 +1,177 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::{collections::HashMap, fmt::Display};
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::identifiers::{AccountId, ClientId, InstrumentId, Venue, VenueOrderId};
+use serde::{Deserialize, Serialize};
+
+use super::{fill::FillReport, order::OrderStatusReport, position::PositionStatusReport};
+
+/// Represents the execution state of a venue account at a point in time, as a collection
+/// of order, fill and position reports.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionMassStatus {
+    pub client_id: ClientId,
+    pub account_id: AccountId,
+    pub venue: Venue,
+    pub report_id: UUID4,
+    pub ts_init: UnixNanos,
+    order_reports: HashMap<VenueOrderId, OrderStatusReport>,
+    fill_reports: HashMap<VenueOrderId, Vec<FillReport>>,
+    position_reports: HashMap<InstrumentId, Vec<PositionStatusReport>>,
+}
+
+impl ExecutionMassStatus {
+    /// Creates a new [`ExecutionMassStatus`] instance.
+    #[must_use]
+    pub fn new(
+        client_id: ClientId,
+        account_id: AccountId,
+        venue: Venue,
+        ts_init: UnixNanos,
+        report_id: Option<UUID4>,
+    ) -> Self {
+        Self {
+            client_id,
+            account_id,
+            venue,
+            report_id: report_id.unwrap_or_default(),
+            ts_init,
+            order_reports: HashMap::new(),
+            fill_reports: HashMap::new(),
+            position_reports: HashMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn order_reports(&self) -> &HashMap<VenueOrderId, OrderStatusReport> {
+        &self.order_reports
+    }
+
+    #[must_use]
+    pub fn fill_reports(&self) -> &HashMap<VenueOrderId, Vec<FillReport>> {
+        &self.fill_reports
+    }
+
+    #[must_use]
+    pub fn position_reports(&self) -> &HashMap<InstrumentId, Vec<PositionStatusReport>> {
+        &self.position_reports
+    }
+
+    /// Adds the order `reports`, replacing any existing report for the same venue order ID.
+    pub fn add_order_reports(&mut self, reports: Vec<OrderStatusReport>) {
+        for report in reports {
+            self.order_reports.insert(report.venue_order_id, report);
+        }
+    }
+
+    /// Adds the fill `reports`, grouped by venue order ID.
+    pub fn add_fill_reports(&mut self, reports: Vec<FillReport>) {
+        for report in reports {
+            self.fill_reports
+                .entry(report.venue_order_id)
+                .or_default()
+                .push(report);
+        }
+    }
+
+    /// Adds the position `reports`, grouped by instrument ID.
+    pub fn add_position_reports(&mut self, reports: Vec<PositionStatusReport>) {
+        for report in reports {
+            self.position_reports
+                .entry(report.instrument_id)
+                .or_default()
+                .push(report);
+        }
+    }
+}
+
+impl Display for ExecutionMassStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ExecutionMassStatus(client_id={}, account_id={}, venue={}, order_reports={}, \
+            fill_reports={}, position_reports={}, report_id={}, ts_init={})",
+            self.client_id,
+            self.account_id,
+            self.venue,
+            self.order_reports.len(),
+            self.fill_reports.values().map(Vec::len).sum::<usize>(),
+            self.position_reports.values().map(Vec::len).sum::<usize>(),
+            self.report_id,
+            self.ts_init,
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::nanos::UnixNanos;
+    use nautilus_model::{
+        enums::{LiquiditySide, OrderSide},
+        identifiers::{AccountId, ClientId, InstrumentId, TradeId, Venue, VenueOrderId},
+        types::{money::Money, price::Price, quantity::Quantity},
+    };
+    use rstest::rstest;
+
+    use super::ExecutionMassStatus;
+    use crate::reports::fill::FillReport;
+
+    fn fill_report(venue_order_id: &str, trade_id: &str) -> FillReport {
+        FillReport::new(
+            AccountId::from("SIM-001"),
+            InstrumentId::from("AUD/USD.SIM"),
+            VenueOrderId::from(venue_order_id),
+            TradeId::from(trade_id),
+            OrderSide::Buy,
+            Quantity::from("50000"),
+            Price::from("1.00000"),
+            Money::from("2 USD"),
+            LiquiditySide::Taker,
+            None,
+            None,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        )
+    }
+
+    #[rstest]
+    fn test_add_fill_reports_groups_by_venue_order_id() {
+        let mut mass_status = ExecutionMassStatus::new(
+            ClientId::from("SIM"),
+            AccountId::from("SIM-001"),
+            Venue::from("SIM"),
+            UnixNanos::default(),
+            None,
+        );
+
+        mass_status.add_fill_reports(vec![
+            fill_report("V-1", "T-1"),
+            fill_report("V-1", "T-2"),
+            fill_report("V-2", "T-3"),
+        ]);
+
+        let fill_reports = mass_status.fill_reports();
+        assert_eq!(fill_reports.len(), 2);
+        assert_eq!(fill_reports[&VenueOrderId::from("V-1")].len(), 2);
+        assert_eq!(fill_reports[&VenueOrderId::from("V-2")].len(), 1);
+    }
+}
@@ -0,0 +1,224 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::fmt::Display;
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::{
+    enums::{
+        ContingencyType, OrderSide, OrderStatus, OrderType, TimeInForce, TrailingOffsetType,
+        TriggerType,
+    },
+    identifiers::{AccountId, ClientOrderId, InstrumentId, OrderListId, VenueOrderId},
+    types::{price::Price, quantity::Quantity},
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents an order status at a point in time, as reported by the venue.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrderStatusReport {
+    pub account_id: AccountId,
+    pub instrument_id: InstrumentId,
+    pub client_order_id: Option<ClientOrderId>,
+    pub venue_order_id: VenueOrderId,
+    pub order_side: OrderSide,
+    pub order_type: OrderType,
+    pub time_in_force: TimeInForce,
+    pub order_status: OrderStatus,
+    pub quantity: Quantity,
+    pub filled_qty: Quantity,
+    pub report_id: UUID4,
+    pub ts_accepted: UnixNanos,
+    pub ts_last: UnixNanos,
+    pub ts_init: UnixNanos,
+    pub order_list_id: Option<OrderListId>,
+    pub contingency_type: ContingencyType,
+    pub expire_time: Option<UnixNanos>,
+    pub price: Option<Price>,
+    pub trigger_price: Option<Price>,
+    pub trigger_type: Option<TriggerType>,
+    pub limit_offset: Option<Price>,
+    pub trailing_offset: Option<Price>,
+    pub trailing_offset_type: TrailingOffsetType,
+    pub avg_px: Option<f64>,
+    pub display_qty: Option<Quantity>,
+    pub post_only: bool,
+    pub reduce_only: bool,
+    pub cancel_reason: Option<String>,
+    pub ts_triggered: Option<UnixNanos>,
+}
+
+impl OrderStatusReport {
+    /// Creates a new [`OrderStatusReport`] instance.
+    ///
+    /// The optional order properties default to unset, and should be assigned directly
+    /// when reported by the venue.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        account_id: AccountId,
+        instrument_id: InstrumentId,
+        client_order_id: Option<ClientOrderId>,
+        venue_order_id: VenueOrderId,
+        order_side: OrderSide,
+        order_type: OrderType,
+        time_in_force: TimeInForce,
+        order_status: OrderStatus,
+        quantity: Quantity,
+        filled_qty: Quantity,
+        ts_accepted: UnixNanos,
+        ts_last: UnixNanos,
+        ts_init: UnixNanos,
+        report_id: Option<UUID4>,
+    ) -> Self {
+        Self {
+            account_id,
+            instrument_id,
+            client_order_id,
+            venue_order_id,
+            order_side,
+            order_type,
+            time_in_force,
+            order_status,
+            quantity,
+            filled_qty,
+            report_id: report_id.unwrap_or_default(),
+            ts_accepted,
+            ts_last,
+            ts_init,
+            order_list_id: None,
+            contingency_type: ContingencyType::default(),
+            expire_time: None,
+            price: None,
+            trigger_price: None,
+            trigger_type: None,
+            limit_offset: None,
+            trailing_offset: None,
+            trailing_offset_type: TrailingOffsetType::NoTrailingOffset,
+            avg_px: None,
+            display_qty: None,
+            post_only: false,
+            reduce_only: false,
+            cancel_reason: None,
+            ts_triggered: None,
+        }
+    }
+
+    /// Returns the quantity of the order which is still open at the venue.
+    #[must_use]
+    pub fn leaves_qty(&self) -> Quantity {
+        if self.filled_qty >= self.quantity {
+            return Quantity::zero(self.quantity.precision);
+        }
+        self.quantity - self.filled_qty
+    }
+
+    /// Returns true if the reported order is open at the venue.
+    #[must_use]
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.order_status,
+            OrderStatus::Accepted
+                | OrderStatus::Triggered
+                | OrderStatus::PendingCancel
+                | OrderStatus::PendingUpdate
+                | OrderStatus::PartiallyFilled
+        )
+    }
+}
+
+impl Display for OrderStatusReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "OrderStatusReport(account_id={}, instrument_id={}, client_order_id={}, \
+            venue_order_id={}, order_side={}, order_type={}, time_in_force={}, \
+            order_status={}, price={}, trigger_price={}, quantity={}, filled_qty={}, \
+            avg_px={}, report_id={}, ts_accepted={}, ts_last={})",
+            self.account_id,
+            self.instrument_id,
+            self.client_order_id
+                .map_or("None".to_string(), |id| format!("{id}")),
+            self.venue_order_id,
+            self.order_side,
+            self.order_type,
+            self.time_in_force,
+            self.order_status,
+            self.price
+                .map_or("None".to_string(), |price| format!("{price}")),
+            self.trigger_price
+                .map_or("None".to_string(), |price| format!("{price}")),
+            self.quantity,
+            self.filled_qty,
+            self.avg_px
+                .map_or("None".to_string(), |avg_px| format!("{avg_px}")),
+            self.report_id,
+            self.ts_accepted,
+            self.ts_last,
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::nanos::UnixNanos;
+    use nautilus_model::{
+        enums::{OrderSide, OrderStatus, OrderType, TimeInForce},
+        identifiers::{AccountId, InstrumentId, VenueOrderId},
+        types::quantity::Quantity,
+    };
+    use rstest::rstest;
+
+    use super::OrderStatusReport;
+
+    fn report(order_status: OrderStatus, filled_qty: &str) -> OrderStatusReport {
+        OrderStatusReport::new(
+            AccountId::from("SIM-001"),
+            InstrumentId::from("AUD/USD.SIM"),
+            None,
+            VenueOrderId::from("V-1"),
+            OrderSide::Buy,
+            OrderType::Limit,
+            TimeInForce::Gtc,
+            order_status,
+            Quantity::from("100000"),
+            Quantity::from(filled_qty),
+            UnixNanos::default(),
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        )
+    }
+
+    #[rstest]
+    #[case(OrderStatus::Accepted, true)]
+    #[case(OrderStatus::PartiallyFilled, true)]
+    #[case(OrderStatus::Filled, false)]
+    #[case(OrderStatus::Canceled, false)]
+    #[case(OrderStatus::Rejected, false)]
+    fn test_is_open(#[case] order_status: OrderStatus, #[case] expected: bool) {
+        assert_eq!(report(order_status, "0").is_open(), expected);
+    }
+
+    #[rstest]
+    fn test_leaves_qty() {
+        let report = report(OrderStatus::PartiallyFilled, "40000");
+
+        assert_eq!(report.leaves_qty(), Quantity::from("60000"));
+    }
+}
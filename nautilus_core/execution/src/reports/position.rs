@@ -0,0 +1,140 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::fmt::Display;
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use nautilus_model::{
+    enums::PositionSide,
+    identifiers::{AccountId, InstrumentId, PositionId},
+    types::quantity::Quantity,
+};
+use serde::{Deserialize, Serialize};
+
+/// Represents a position status at a point in time, as reported by the venue.
+///
+/// A `venue_position_id` is only expected for venues using a `HEDGING` OMS.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PositionStatusReport {
+    pub account_id: AccountId,
+    pub instrument_id: InstrumentId,
+    pub position_side: PositionSide,
+    pub quantity: Quantity,
+    pub report_id: UUID4,
+    pub ts_last: UnixNanos,
+    pub ts_init: UnixNanos,
+    pub venue_position_id: Option<PositionId>,
+}
+
+impl PositionStatusReport {
+    /// Creates a new [`PositionStatusReport`] instance.
+    #[allow(clippy::too_many_arguments)]
+    #[must_use]
+    pub fn new(
+        account_id: AccountId,
+        instrument_id: InstrumentId,
+        position_side: PositionSide,
+        quantity: Quantity,
+        venue_position_id: Option<PositionId>,
+        ts_last: UnixNanos,
+        ts_init: UnixNanos,
+        report_id: Option<UUID4>,
+    ) -> Self {
+        Self {
+            account_id,
+            instrument_id,
+            position_side,
+            quantity,
+            report_id: report_id.unwrap_or_default(),
+            ts_last,
+            ts_init,
+            venue_position_id,
+        }
+    }
+
+    /// Returns the reported quantity signed by the position side (negative for `SHORT`).
+    #[must_use]
+    pub fn signed_qty(&self) -> f64 {
+        match self.position_side {
+            PositionSide::Short => -self.quantity.as_f64(),
+            PositionSide::Long => self.quantity.as_f64(),
+            _ => 0.0,
+        }
+    }
+
+    #[must_use]
+    pub fn is_flat(&self) -> bool {
+        self.signed_qty() == 0.0
+    }
+}
+
+impl Display for PositionStatusReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PositionStatusReport(account_id={}, instrument_id={}, venue_position_id={}, \
+            position_side={}, quantity={}, signed_qty={}, report_id={}, ts_last={})",
+            self.account_id,
+            self.instrument_id,
+            self.venue_position_id
+                .map_or("None".to_string(), |id| format!("{id}")),
+            self.position_side,
+            self.quantity,
+            self.signed_qty(),
+            self.report_id,
+            self.ts_last,
+        )
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::nanos::UnixNanos;
+    use nautilus_model::{
+        enums::PositionSide,
+        identifiers::{AccountId, InstrumentId},
+        types::quantity::Quantity,
+    };
+    use rstest::rstest;
+
+    use super::PositionStatusReport;
+
+    #[rstest]
+    #[case(PositionSide::Long, "100000", 100_000.0)]
+    #[case(PositionSide::Short, "100000", -100_000.0)]
+    #[case(PositionSide::Flat, "0", 0.0)]
+    fn test_signed_qty(
+        #[case] position_side: PositionSide,
+        #[case] quantity: &str,
+        #[case] expected: f64,
+    ) {
+        let report = PositionStatusReport::new(
+            AccountId::from("SIM-001"),
+            InstrumentId::from("AUD/USD.SIM"),
+            position_side,
+            Quantity::from(quantity),
+            None,
+            UnixNanos::default(),
+            UnixNanos::default(),
+            None,
+        );
+
+        assert_eq!(report.signed_qty(), expected);
+        assert_eq!(report.is_flat(), expected == 0.0);
+    }
+}
@@ -0,0 +1,488 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Provides a `TrailingStopCalculator` for updating the prices of trailing stop orders.
+
+use nautilus_model::{
+    enums::{OrderSideSpecified, TrailingOffsetType, TriggerType},
+    orders::any::OrderAny,
+    types::price::Price,
+};
+
+/// Represents a venue price tier, where prices from `min_price` upwards trail in steps
+/// of `increment`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceTier {
+    /// The minimum market price for the tier (inclusive).
+    pub min_price: Price,
+    /// The price increment for the tier.
+    pub increment: Price,
+}
+
+impl PriceTier {
+    /// Creates a new [`PriceTier`] instance.
+    #[must_use]
+    pub const fn new(min_price: Price, increment: Price) -> Self {
+        Self {
+            min_price,
+            increment,
+        }
+    }
+}
+
+/// Calculates updated trigger and limit prices for trailing stop orders.
+///
+/// The market price trailed depends on the orders `TriggerType`:
+/// - `DEFAULT`, `LAST_TRADE` and `DOUBLE_LAST` trail the last price.
+/// - `BID_ASK` and `DOUBLE_BID_ASK` trail the ask for buy orders and the bid for sell orders.
+/// - `MID_POINT` trails the mid price between the bid and ask.
+/// - `LAST_OR_BID_ASK` trails whichever of the last or bid/ask prices gives the tighter stop.
+///
+/// Mark and index prices are not tracked, so orders triggered by `MARK_PRICE` or
+/// `INDEX_PRICE` are rejected.
+#[derive(Clone, Debug)]
+pub struct TrailingStopCalculator {
+    price_increment: Price,
+    price_tiers: Vec<PriceTier>,
+}
+
+impl TrailingStopCalculator {
+    /// Creates a new [`TrailingStopCalculator`] instance.
+    ///
+    /// The `price_tiers` are used for `PRICE_TIER` offsets, which fall back to `price_increment`
+    /// where no tier applies.
+    #[must_use]
+    pub fn new(price_increment: Price, price_tiers: Option<Vec<PriceTier>>) -> Self {
+        let mut price_tiers = price_tiers.unwrap_or_default();
+        price_tiers.sort_by_key(|tier| tier.min_price);
+        Self {
+            price_increment,
+            price_tiers,
+        }
+    }
+
+    /// Returns the updated `(trigger_price, price)` for the given trailing stop `order`.
+    ///
+    /// Each price is `None` where it would not move closer to the market. No updates are
+    /// returned if the market prices required by the orders trigger type are not available.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if:
+    /// - The `order` is not a trailing stop order.
+    /// - The orders trigger type or trailing offset type is not specified.
+    /// - The orders trigger type is `MARK_PRICE` or `INDEX_PRICE`.
+    /// - An updated price is outside the valid range for a price.
+    pub fn calculate(
+        &self,
+        order: &OrderAny,
+        bid: Option<Price>,
+        ask: Option<Price>,
+        last: Option<Price>,
+    ) -> anyhow::Result<(Option<Price>, Option<Price>)> {
+        let (trigger_type, trailing_offset, trailing_offset_type, limit_offset) = match order {
+            OrderAny::TrailingStopMarket(order) => (
+                order.trigger_type,
+                order.trailing_offset,
+                order.trailing_offset_type,
+                None,
+            ),
+            OrderAny::TrailingStopLimit(order) => (
+                order.trigger_type,
+                order.trailing_offset,
+                order.trailing_offset_type,
+                Some(order.limit_offset),
+            ),
+            _ => anyhow::bail!(
+                "Cannot calculate trailing stop prices for {} order",
+                order.order_type()
+            ),
+        };
+
+        if trailing_offset_type == TrailingOffsetType::NoTrailingOffset {
+            anyhow::bail!(
+                "Invalid `TrailingOffsetType` {trailing_offset_type} for {}",
+                order.client_order_id()
+            );
+        }
+
+        let side = order.order_side_specified();
+        let bid_ask = match side {
+            OrderSideSpecified::Buy => ask,
+            OrderSideSpecified::Sell => bid,
+        };
+        let market_prices = match trigger_type {
+            TriggerType::Default | TriggerType::LastTrade | TriggerType::DoubleLast => vec![last],
+            TriggerType::BidAsk | TriggerType::DoubleBidAsk => vec![bid_ask],
+            TriggerType::MidPoint => vec![self.mid_price(bid, ask)?],
+            TriggerType::LastOrBidAsk => vec![last, bid_ask],
+            TriggerType::MarkPrice | TriggerType::IndexPrice => anyhow::bail!(
+                "Unsupported `TriggerType` {trigger_type} for {}: mark and index prices are not tracked",
+                order.client_order_id()
+            ),
+            TriggerType::NoTrigger => anyhow::bail!(
+                "Invalid `TriggerType` {trigger_type} for {}",
+                order.client_order_id()
+            ),
+        };
+        if market_prices.iter().any(Option::is_none) {
+            return Ok((None, None)); // No market yet
+        }
+
+        let mut trigger_price = order.trigger_price();
+        let mut price = order.price();
+        let mut new_trigger_price = None;
+        let mut new_price = None;
+
+        for market_price in market_prices.into_iter().flatten() {
+            let candidate =
+                self.offset_price(market_price, trailing_offset, trailing_offset_type, side)?;
+            if is_improvement(side, candidate, trigger_price) {
+                trigger_price = Some(candidate);
+                new_trigger_price = Some(candidate);
+            }
+
+            if let Some(limit_offset) = limit_offset {
+                let candidate =
+                    self.offset_price(market_price, limit_offset, trailing_offset_type, side)?;
+                if is_improvement(side, candidate, price) {
+                    price = Some(candidate);
+                    new_price = Some(candidate);
+                }
+            }
+        }
+
+        Ok((new_trigger_price, new_price))
+    }
+
+    fn offset_price(
+        &self,
+        market_price: Price,
+        offset: Price,
+        offset_type: TrailingOffsetType,
+        side: OrderSideSpecified,
+    ) -> anyhow::Result<Price> {
+        let offset = match offset_type {
+            TrailingOffsetType::Price => offset.as_f64(),
+            TrailingOffsetType::BasisPoints => market_price.as_f64() * offset.as_f64() / 10_000.0,
+            TrailingOffsetType::Ticks => offset.as_f64() * self.price_increment.as_f64(),
+            TrailingOffsetType::PriceTier => {
+                offset.as_f64() * self.tier_increment(market_price).as_f64()
+            }
+            TrailingOffsetType::NoTrailingOffset => 0.0,
+        };
+
+        let value = match side {
+            OrderSideSpecified::Buy => market_price.as_f64() + offset,
+            OrderSideSpecified::Sell => market_price.as_f64() - offset,
+        };
+        self.make_price(value)
+    }
+
+    fn tier_increment(&self, market_price: Price) -> Price {
+        self.price_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.min_price <= market_price)
+            .map_or(self.price_increment, |tier| tier.increment)
+    }
+
+    fn mid_price(&self, bid: Option<Price>, ask: Option<Price>) -> anyhow::Result<Option<Price>> {
+        match (bid, ask) {
+            (Some(bid), Some(ask)) => self
+                .make_price((bid.as_f64() + ask.as_f64()) / 2.0)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Returns the `value` rounded to the price increment.
+    fn make_price(&self, value: f64) -> anyhow::Result<Price> {
+        let increment = self.price_increment.as_f64();
+        let value = (value / increment).round() * increment;
+        Price::new(value, self.price_increment.precision)
+    }
+}
+
+/// Returns whether the `candidate` price is closer to the market than the `current` price.
+fn is_improvement(side: OrderSideSpecified, candidate: Price, current: Option<Price>) -> bool {
+    match (side, current) {
+        (_, None) => true,
+        (OrderSideSpecified::Buy, Some(current)) => candidate < current,
+        (OrderSideSpecified::Sell, Some(current)) => candidate > current,
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_model::{
+        enums::{OrderSide, TrailingOffsetType, TriggerType},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::stubs::TestOrderStubs,
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+
+    use super::{PriceTier, TrailingStopCalculator};
+
+    #[fixture]
+    fn calculator() -> TrailingStopCalculator {
+        TrailingStopCalculator::new(Price::from("0.00001"), None)
+    }
+
+    #[rstest]
+    #[case(TrailingOffsetType::Price, "0.00100", "0.99900")]
+    #[case(TrailingOffsetType::BasisPoints, "10", "0.99900")]
+    #[case(TrailingOffsetType::Ticks, "100", "0.99900")]
+    #[case(TrailingOffsetType::PriceTier, "100", "0.99900")]
+    fn test_sell_trailing_stop_market_offset_types(
+        calculator: TrailingStopCalculator,
+        #[case] offset_type: TrailingOffsetType,
+        #[case] offset: &str,
+        #[case] expected: &str,
+    ) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Price::from("0.99000"),
+            Quantity::from(100_000),
+            TriggerType::BidAsk,
+            Price::from(offset),
+            offset_type,
+        );
+
+        let (trigger_price, price) = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(trigger_price, Some(Price::from(expected)));
+        assert_eq!(price, None);
+    }
+
+    #[rstest]
+    #[case(TriggerType::LastTrade, "1.00100")]
+    #[case(TriggerType::DoubleLast, "1.00100")]
+    #[case(TriggerType::BidAsk, "1.00102")]
+    #[case(TriggerType::DoubleBidAsk, "1.00102")]
+    #[case(TriggerType::MidPoint, "1.00101")]
+    #[case(TriggerType::LastOrBidAsk, "1.00100")]
+    fn test_buy_trailing_stop_market_trigger_types(
+        calculator: TrailingStopCalculator,
+        #[case] trigger_type: TriggerType,
+        #[case] expected: &str,
+    ) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("1.01000"),
+            Quantity::from(100_000),
+            trigger_type,
+            Price::from("0.00100"),
+            TrailingOffsetType::Price,
+        );
+
+        let (trigger_price, _) = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                Some(Price::from("1.00000")),
+            )
+            .unwrap();
+
+        assert_eq!(trigger_price, Some(Price::from(expected)));
+    }
+
+    #[rstest]
+    fn test_no_update_when_not_an_improvement(calculator: TrailingStopCalculator) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Price::from("0.99950"),
+            Quantity::from(100_000),
+            TriggerType::BidAsk,
+            Price::from("0.00100"),
+            TrailingOffsetType::Price,
+        );
+
+        let result = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(result, (None, None));
+    }
+
+    #[rstest]
+    fn test_no_update_without_market(calculator: TrailingStopCalculator) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Price::from("0.99000"),
+            Quantity::from(100_000),
+            TriggerType::LastTrade,
+            Price::from("0.00100"),
+            TrailingOffsetType::Price,
+        );
+
+        let result = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(result, (None, None));
+    }
+
+    #[rstest]
+    fn test_trailing_stop_limit_updates_price(calculator: TrailingStopCalculator) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_limit_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Price::from("0.98900"),
+            Price::from("0.99000"),
+            Quantity::from(100_000),
+            TriggerType::BidAsk,
+            Price::from("0.00150"),
+            Price::from("0.00100"),
+            TrailingOffsetType::Price,
+        );
+
+        let (trigger_price, price) = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(trigger_price, Some(Price::from("0.99900")));
+        assert_eq!(price, Some(Price::from("0.99850")));
+    }
+
+    #[rstest]
+    fn test_price_tier_uses_tier_for_market_price() {
+        let calculator = TrailingStopCalculator::new(
+            Price::from("0.00001"),
+            Some(vec![
+                PriceTier::new(Price::from("0.50000"), Price::from("0.00005")),
+                PriceTier::new(Price::from("2.00000"), Price::from("0.00010")),
+            ]),
+        );
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Price::from("0.99000"),
+            Quantity::from(100_000),
+            TriggerType::BidAsk,
+            Price::from("10"),
+            TrailingOffsetType::PriceTier,
+        );
+
+        let (trigger_price, _) = calculator
+            .calculate(
+                &order,
+                Some(Price::from("1.00000")),
+                Some(Price::from("1.00002")),
+                None,
+            )
+            .unwrap();
+
+        assert_eq!(trigger_price, Some(Price::from("0.99950")));
+    }
+
+    #[rstest]
+    fn test_non_trailing_order_is_error(calculator: TrailingStopCalculator) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Quantity::from(100_000),
+            None,
+            None,
+        );
+
+        assert!(calculator.calculate(&order, None, None, None).is_err());
+    }
+
+    #[rstest]
+    #[case(TriggerType::MarkPrice)]
+    #[case(TriggerType::IndexPrice)]
+    fn test_mark_and_index_trigger_types_are_error(
+        calculator: TrailingStopCalculator,
+        #[case] trigger_type: TriggerType,
+    ) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("1.01000"),
+            Quantity::from(100_000),
+            trigger_type,
+            Price::from("0.00100"),
+            TrailingOffsetType::Price,
+        );
+
+        let result = calculator.calculate(
+            &order,
+            Some(Price::from("1.00000")),
+            Some(Price::from("1.00002")),
+            Some(Price::from("1.00000")),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[rstest]
+    fn test_price_out_of_range_is_error(calculator: TrailingStopCalculator) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim());
+        let order = TestOrderStubs::trailing_stop_market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("9100000000.00000"),
+            Quantity::from(100_000),
+            TriggerType::LastTrade,
+            Price::from("500000000"),
+            TrailingOffsetType::Price,
+        );
+
+        let result =
+            calculator.calculate(&order, None, None, Some(Price::from("9000000000.00000")));
+
+        assert!(result.is_err());
+    }
+}
@@ -13,6 +13,8 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
 use crate::{
@@ -20,6 +22,7 @@ use crate::{
     enums::AccountType,
     events::account::state::AccountState,
     identifiers::AccountId,
+    types::{balance::AccountBalance, currency::Currency, money::Money},
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +40,14 @@ impl AccountAny {
         }
     }
 
+    #[must_use]
+    pub fn base_currency(&self) -> Option<Currency> {
+        match self {
+            AccountAny::Margin(margin) => margin.base_currency,
+            AccountAny::Cash(cash) => cash.base_currency,
+        }
+    }
+
     pub fn last_event(&self) -> Option<AccountState> {
         match self {
             AccountAny::Margin(margin) => margin.last_event(),
@@ -51,6 +62,30 @@ impl AccountAny {
         }
     }
 
+    #[must_use]
+    pub fn balances(&self) -> HashMap<Currency, AccountBalance> {
+        match self {
+            AccountAny::Margin(margin) => margin.balances(),
+            AccountAny::Cash(cash) => cash.balances(),
+        }
+    }
+
+    #[must_use]
+    pub fn balances_total(&self) -> HashMap<Currency, Money> {
+        match self {
+            AccountAny::Margin(margin) => margin.balances_total(),
+            AccountAny::Cash(cash) => cash.balances_total(),
+        }
+    }
+
+    #[must_use]
+    pub fn balance_total(&self, currency: Option<Currency>) -> Option<Money> {
+        match self {
+            AccountAny::Margin(margin) => margin.balance_total(currency),
+            AccountAny::Cash(cash) => cash.balance_total(currency),
+        }
+    }
+
     pub fn apply(&mut self, event: AccountState) {
         match self {
             AccountAny::Margin(margin) => margin.apply(event),
@@ -0,0 +1,130 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! A `FundingRateUpdate` data type representing a funding rate for a perpetual instrument.
+
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    hash::Hash,
+};
+
+use derive_builder::Builder;
+use nautilus_core::{nanos::UnixNanos, serialization::Serializable};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::GetTsInit;
+use crate::identifiers::InstrumentId;
+
+/// Represents a funding rate applied to open positions in a perpetual instrument
+/// at a funding timestamp.
+///
+/// A positive rate means long positions pay funding to short positions, and a
+/// negative rate means short positions pay funding to long positions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Builder)]
+#[serde(tag = "type")]
+pub struct FundingRateUpdate {
+    /// The instrument ID for the funding rate.
+    pub instrument_id: InstrumentId,
+    /// The funding rate (as a fraction of the position notional value).
+    pub rate: Decimal,
+    /// UNIX timestamp (nanoseconds) of the next funding (if known).
+    pub next_funding_ns: Option<UnixNanos>,
+    /// UNIX timestamp (nanoseconds) of the funding.
+    pub ts_event: UnixNanos,
+    /// UNIX timestamp (nanoseconds) when the struct was initialized.
+    pub ts_init: UnixNanos,
+}
+
+impl FundingRateUpdate {
+    /// Creates a new [`FundingRateUpdate`] instance.
+    #[must_use]
+    pub fn new(
+        instrument_id: InstrumentId,
+        rate: Decimal,
+        next_funding_ns: Option<UnixNanos>,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+    ) -> Self {
+        Self {
+            instrument_id,
+            rate,
+            next_funding_ns,
+            ts_event,
+            ts_init,
+        }
+    }
+
+    /// Returns the metadata for the type, for use with serialization formats.
+    #[must_use]
+    pub fn get_metadata(instrument_id: &InstrumentId) -> HashMap<String, String> {
+        let mut metadata = HashMap::new();
+        metadata.insert("instrument_id".to_string(), instrument_id.to_string());
+        metadata
+    }
+}
+
+impl Display for FundingRateUpdate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.instrument_id, self.rate, self.ts_event, self.ts_init,
+        )
+    }
+}
+
+impl Serializable for FundingRateUpdate {}
+
+impl GetTsInit for FundingRateUpdate {
+    fn ts_init(&self) -> UnixNanos {
+        self.ts_init
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::serialization::Serializable;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::data::stubs::stub_funding_rate_update;
+
+    #[rstest]
+    fn test_to_string(stub_funding_rate_update: FundingRateUpdate) {
+        assert_eq!(
+            stub_funding_rate_update.to_string(),
+            "ETHUSDT-PERP.BINANCE,0.0001,1,2"
+        );
+    }
+
+    #[rstest]
+    fn test_json_serialization(stub_funding_rate_update: FundingRateUpdate) {
+        let serialized = stub_funding_rate_update.as_json_bytes().unwrap();
+        let deserialized = FundingRateUpdate::from_json_bytes(serialized.as_ref()).unwrap();
+        assert_eq!(deserialized, stub_funding_rate_update);
+    }
+
+    #[rstest]
+    fn test_msgpack_serialization(stub_funding_rate_update: FundingRateUpdate) {
+        let serialized = stub_funding_rate_update.as_msgpack_bytes().unwrap();
+        let deserialized = FundingRateUpdate::from_msgpack_bytes(serialized.as_ref()).unwrap();
+        assert_eq!(deserialized, stub_funding_rate_update);
+    }
+}
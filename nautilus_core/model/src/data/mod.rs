@@ -19,6 +19,7 @@ pub mod bar;
 pub mod delta;
 pub mod deltas;
 pub mod depth;
+pub mod funding;
 pub mod order;
 pub mod quote;
 pub mod status;
@@ -253,6 +254,16 @@ impl DataType {
                 .expect("Invalid `usize` for depth"),
         )
     }
+
+    pub fn parse_interval_ms_from_metadata(&self) -> Option<u64> {
+        let metadata = self.metadata.as_ref()?;
+        let interval_str = metadata.get("interval_ms")?;
+        Some(
+            interval_str
+                .parse::<u64>()
+                .expect("Invalid `u64` for interval_ms"),
+        )
+    }
 }
 
 impl PartialEq for DataType {
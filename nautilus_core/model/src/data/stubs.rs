@@ -17,11 +17,13 @@
 
 use nautilus_core::nanos::UnixNanos;
 use rstest::fixture;
+use rust_decimal_macros::dec;
 
 use super::{
     bar::{Bar, BarSpecification, BarType},
     deltas::OrderBookDeltas,
     depth::DEPTH10_LEN,
+    funding::FundingRateUpdate,
     quote::QuoteTick,
     status::InstrumentStatus,
     trade::TradeTick,
@@ -365,3 +367,14 @@ pub fn stub_instrument_status() -> InstrumentStatus {
         None,
     )
 }
+
+#[fixture]
+pub fn stub_funding_rate_update() -> FundingRateUpdate {
+    FundingRateUpdate::new(
+        InstrumentId::from("ETHUSDT-PERP.BINANCE"),
+        dec!(0.0001),
+        None,
+        UnixNanos::from(1),
+        UnixNanos::from(2),
+    )
+}
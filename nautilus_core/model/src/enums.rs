@@ -757,6 +757,7 @@ impl FromU8 for OrderSide {
 }
 
 /// The specified order side (BUY or SELL).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum OrderSideSpecified {
     /// The order is a BUY.
     Buy = 1,
@@ -18,3 +18,4 @@
 pub mod account;
 pub mod order;
 pub mod position;
+pub mod risk;
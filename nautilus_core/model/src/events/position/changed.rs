@@ -17,7 +17,9 @@ use nautilus_core::nanos::UnixNanos;
 
 use crate::{
     enums::{OrderSide, PositionSide},
+    events::order::OrderFilled,
     identifiers::{AccountId, ClientOrderId, InstrumentId, PositionId, StrategyId, TraderId},
+    position::Position,
     types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
 };
 
@@ -47,3 +49,36 @@ pub struct PositionChanged {
     pub ts_event: UnixNanos,
     pub ts_init: UnixNanos,
 }
+
+impl PositionChanged {
+    /// Creates a new [`PositionChanged`] event for the `position` changed by the `fill`.
+    #[must_use]
+    pub fn create(position: &Position, fill: &OrderFilled, ts_init: UnixNanos) -> Self {
+        Self {
+            trader_id: position.trader_id,
+            strategy_id: position.strategy_id,
+            instrument_id: position.instrument_id,
+            position_id: position.id,
+            account_id: position.account_id,
+            opening_order_id: position.opening_order_id,
+            entry: position.entry,
+            side: position.side,
+            signed_qty: position.signed_qty,
+            quantity: position.quantity,
+            peak_quantity: position.peak_qty,
+            last_qty: fill.last_qty,
+            last_px: fill.last_px,
+            currency: position.quote_currency,
+            avg_px_open: position.avg_px_open,
+            avg_px_closed: position.avg_px_close.unwrap_or(0.0),
+            realized_return: position.realized_return,
+            realized_pnl: position
+                .realized_pnl
+                .unwrap_or_else(|| Money::from_raw(0, position.settlement_currency)),
+            unrealized_pnl: position.unrealized_pnl(fill.last_px),
+            ts_opened: position.ts_opened,
+            ts_event: fill.ts_event,
+            ts_init,
+        }
+    }
+}
@@ -23,6 +23,7 @@ pub mod opened;
 
 pub mod state;
 
+#[derive(Clone, PartialEq, Debug)]
 pub enum PositionEvent {
     PositionOpened(PositionOpened),
     PositionChanged(PositionChanged),
@@ -0,0 +1,65 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::fmt::{Display, Formatter};
+
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
+use serde::{Deserialize, Serialize};
+
+use crate::{enums::TradingState, identifiers::TraderId};
+
+/// Represents an event where the trading state of the risk engine for a trader has changed.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub struct TradingStateChanged {
+    pub trader_id: TraderId,
+    pub state: TradingState,
+    pub event_id: UUID4,
+    pub ts_event: UnixNanos,
+    pub ts_init: UnixNanos,
+}
+
+impl TradingStateChanged {
+    /// Creates a new [`TradingStateChanged`] instance.
+    #[must_use]
+    pub const fn new(
+        trader_id: TraderId,
+        state: TradingState,
+        event_id: UUID4,
+        ts_event: UnixNanos,
+        ts_init: UnixNanos,
+    ) -> Self {
+        Self {
+            trader_id,
+            state,
+            event_id,
+            ts_event,
+            ts_init,
+        }
+    }
+}
+
+impl Display for TradingStateChanged {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}(trader_id={}, state={}, event_id={})",
+            stringify!(TradingStateChanged),
+            self.trader_id,
+            self.state,
+            self.event_id
+        )
+    }
+}
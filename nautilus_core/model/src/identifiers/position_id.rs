@@ -60,6 +60,13 @@ impl PositionId {
     pub fn as_str(&self) -> &str {
         self.0.as_str()
     }
+
+    /// Returns whether the position ID is virtual, having been generated by the
+    /// system rather than assigned by the venue.
+    #[must_use]
+    pub fn is_virtual(&self) -> bool {
+        self.0.starts_with("P-")
+    }
 }
 
 impl Debug for PositionId {
@@ -94,4 +101,11 @@ mod tests {
         assert_eq!(position_id_test.as_str(), "P-123456789");
         assert_eq!(format!("{position_id_test}"), "P-123456789");
     }
+
+    #[rstest]
+    #[case("P-123456789", true)]
+    #[case("ETHUSDT-PERP.BINANCE-S-001", false)]
+    fn test_is_virtual(#[case] value: &str, #[case] expected: bool) {
+        assert_eq!(PositionId::from(value).is_virtual(), expected);
+    }
 }
@@ -13,6 +13,7 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
+use nautilus_core::nanos::UnixNanos;
 use rust_decimal::Decimal;
 
 use super::{
@@ -21,6 +22,7 @@ use super::{
     options_contract::OptionsContract, options_spread::OptionsSpread, Instrument,
 };
 use crate::{
+    enums::InstrumentClass,
     identifiers::InstrumentId,
     types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
 };
@@ -193,6 +195,90 @@ impl InstrumentAny {
         }
     }
 
+    #[must_use]
+    pub fn instrument_class(&self) -> InstrumentClass {
+        match self {
+            Self::CryptoFuture(inst) => inst.instrument_class(),
+            Self::CryptoPerpetual(inst) => inst.instrument_class(),
+            Self::CurrencyPair(inst) => inst.instrument_class(),
+            Self::Equity(inst) => inst.instrument_class(),
+            Self::FuturesContract(inst) => inst.instrument_class(),
+            Self::FuturesSpread(inst) => inst.instrument_class(),
+            Self::OptionsContract(inst) => inst.instrument_class(),
+            Self::OptionsSpread(inst) => inst.instrument_class(),
+        }
+    }
+
+    #[must_use]
+    pub fn max_quantity(&self) -> Option<Quantity> {
+        match self {
+            Self::CryptoFuture(inst) => inst.max_quantity(),
+            Self::CryptoPerpetual(inst) => inst.max_quantity(),
+            Self::CurrencyPair(inst) => inst.max_quantity(),
+            Self::Equity(inst) => inst.max_quantity(),
+            Self::FuturesContract(inst) => inst.max_quantity(),
+            Self::FuturesSpread(inst) => inst.max_quantity(),
+            Self::OptionsContract(inst) => inst.max_quantity(),
+            Self::OptionsSpread(inst) => inst.max_quantity(),
+        }
+    }
+
+    #[must_use]
+    pub fn min_quantity(&self) -> Option<Quantity> {
+        match self {
+            Self::CryptoFuture(inst) => inst.min_quantity(),
+            Self::CryptoPerpetual(inst) => inst.min_quantity(),
+            Self::CurrencyPair(inst) => inst.min_quantity(),
+            Self::Equity(inst) => inst.min_quantity(),
+            Self::FuturesContract(inst) => inst.min_quantity(),
+            Self::FuturesSpread(inst) => inst.min_quantity(),
+            Self::OptionsContract(inst) => inst.min_quantity(),
+            Self::OptionsSpread(inst) => inst.min_quantity(),
+        }
+    }
+
+    #[must_use]
+    pub fn max_notional(&self) -> Option<Money> {
+        match self {
+            Self::CryptoFuture(inst) => inst.max_notional(),
+            Self::CryptoPerpetual(inst) => inst.max_notional(),
+            Self::CurrencyPair(inst) => inst.max_notional(),
+            Self::Equity(inst) => inst.max_notional(),
+            Self::FuturesContract(inst) => inst.max_notional(),
+            Self::FuturesSpread(inst) => inst.max_notional(),
+            Self::OptionsContract(inst) => inst.max_notional(),
+            Self::OptionsSpread(inst) => inst.max_notional(),
+        }
+    }
+
+    #[must_use]
+    pub fn min_notional(&self) -> Option<Money> {
+        match self {
+            Self::CryptoFuture(inst) => inst.min_notional(),
+            Self::CryptoPerpetual(inst) => inst.min_notional(),
+            Self::CurrencyPair(inst) => inst.min_notional(),
+            Self::Equity(inst) => inst.min_notional(),
+            Self::FuturesContract(inst) => inst.min_notional(),
+            Self::FuturesSpread(inst) => inst.min_notional(),
+            Self::OptionsContract(inst) => inst.min_notional(),
+            Self::OptionsSpread(inst) => inst.min_notional(),
+        }
+    }
+
+    #[must_use]
+    pub fn expiration_ns(&self) -> Option<UnixNanos> {
+        match self {
+            Self::CryptoFuture(inst) => inst.expiration_ns(),
+            Self::CryptoPerpetual(inst) => inst.expiration_ns(),
+            Self::CurrencyPair(inst) => inst.expiration_ns(),
+            Self::Equity(inst) => inst.expiration_ns(),
+            Self::FuturesContract(inst) => inst.expiration_ns(),
+            Self::FuturesSpread(inst) => inst.expiration_ns(),
+            Self::OptionsContract(inst) => inst.expiration_ns(),
+            Self::OptionsSpread(inst) => inst.expiration_ns(),
+        }
+    }
+
     pub fn make_price(&self, value: f64) -> anyhow::Result<Price> {
         match self {
             Self::CryptoFuture(inst) => inst.make_price(value),
@@ -219,6 +305,20 @@ impl InstrumentAny {
         }
     }
 
+    #[must_use]
+    pub fn calculate_base_quantity(&self, quantity: Quantity, last_px: Price) -> Quantity {
+        match self {
+            Self::CryptoFuture(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::CryptoPerpetual(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::CurrencyPair(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::Equity(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::FuturesContract(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::FuturesSpread(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::OptionsContract(inst) => inst.calculate_base_quantity(quantity, last_px),
+            Self::OptionsSpread(inst) => inst.calculate_base_quantity(quantity, last_px),
+        }
+    }
+
     #[must_use]
     pub fn calculate_notional_value(
         &self,
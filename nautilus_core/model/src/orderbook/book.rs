@@ -17,14 +17,24 @@
 
 use nautilus_core::nanos::UnixNanos;
 
-use super::{aggregation::pre_process_order, analysis, display::pprint_book, level::Level};
+use super::{
+    aggregation::{pre_process_order, update_book_with_quote_tick, update_book_with_trade_tick},
+    analysis,
+    display::pprint_book,
+    level::Level,
+    own::OwnOrderBook,
+};
 use crate::{
     data::{
         delta::OrderBookDelta, deltas::OrderBookDeltas, depth::OrderBookDepth10, order::BookOrder,
+        quote::QuoteTick, trade::TradeTick,
     },
     enums::{BookAction, BookType, OrderSide, OrderSideSpecified},
     identifiers::InstrumentId,
-    orderbook::{error::BookIntegrityError, ladder::Ladder},
+    orderbook::{
+        error::{BookIntegrityError, InvalidBookOperation},
+        ladder::{BookPrice, Ladder},
+    },
     types::{price::Price, quantity::Quantity},
 };
 
@@ -163,6 +173,20 @@ impl OrderBook {
         }
     }
 
+    /// Updates the top-of-book with the given `quote` (only valid for `L1_MBP` books).
+    pub fn update_quote_tick(&mut self, quote: &QuoteTick) -> Result<(), InvalidBookOperation> {
+        update_book_with_quote_tick(self, quote)?;
+        self.increment(self.sequence, quote.ts_event);
+        Ok(())
+    }
+
+    /// Updates the top-of-book with the given `trade` (only valid for `L1_MBP` books).
+    pub fn update_trade_tick(&mut self, trade: &TradeTick) -> Result<(), InvalidBookOperation> {
+        update_book_with_trade_tick(self, trade)?;
+        self.increment(self.sequence, trade.ts_event);
+        Ok(())
+    }
+
     pub fn bids(&self) -> impl Iterator<Item = &Level> {
         self.bids.levels.values()
     }
@@ -181,6 +205,16 @@ impl OrderBook {
         self.asks.top().map_or(false, |top| !top.orders.is_empty())
     }
 
+    /// Returns the price level for the given `side` and `price` (if found).
+    #[must_use]
+    pub fn get_level(&self, side: OrderSide, price: Price) -> Option<&Level> {
+        let book_price = BookPrice::new(price, side);
+        match side.as_specified() {
+            OrderSideSpecified::Buy => self.bids.levels.get(&book_price),
+            OrderSideSpecified::Sell => self.asks.levels.get(&book_price),
+        }
+    }
+
     #[must_use]
     pub fn best_bid_price(&self) -> Option<Price> {
         self.bids.top().map(|top| top.price.value)
@@ -252,6 +286,48 @@ impl OrderBook {
         }
     }
 
+    /// Returns a view of the order book with our own liquidity in `own_book` subtracted.
+    ///
+    /// Each price level of the view is aggregated to a single order (MBP), and levels left
+    /// with no external liquidity are removed, so the view reflects the external best
+    /// bid/ask and depth. Analysis such as [`OrderBook::simulate_fills`] and
+    /// [`OrderBook::get_avg_px_for_quantity`] can then be performed against the view.
+    #[must_use]
+    pub fn filtered_view(&self, own_book: &OwnOrderBook) -> Self {
+        let book_type = match self.book_type {
+            BookType::L1_MBP => BookType::L1_MBP,
+            _ => BookType::L2_MBP,
+        };
+        let mut view = Self::new(book_type, self.instrument_id);
+
+        for level in self.bids().chain(self.asks()) {
+            let Some(first) = level.first() else {
+                continue;
+            };
+            let side = level.price.side;
+            let price = level.price.value;
+            let size_raw = level
+                .size_raw()
+                .saturating_sub(own_book.size_raw_at(side, price));
+            if size_raw == 0 {
+                continue;
+            }
+
+            // SAFETY: Precision is from a valid order quantity
+            let size = Quantity::from_raw(size_raw, first.size.precision).unwrap();
+            let order = pre_process_order(book_type, BookOrder::new(side, price, size, 0), 0);
+            match side.as_specified() {
+                OrderSideSpecified::Buy => view.bids.add(order),
+                OrderSideSpecified::Sell => view.asks.add(order),
+            }
+        }
+
+        view.sequence = self.sequence;
+        view.ts_last = self.ts_last;
+        view.count = self.count;
+        view
+    }
+
     /// Return a [`String`] representation of the order book in a human-readable table format.
     #[must_use]
     pub fn pprint(&self, num_levels: usize) -> String {
@@ -278,11 +354,14 @@ mod tests {
         },
         enums::{AggressorSide, BookType, OrderSide},
         identifiers::{InstrumentId, TradeId},
+        instruments::{currency_pair::CurrencyPair, stubs::audusd_sim},
         orderbook::{
             aggregation::{update_book_with_quote_tick, update_book_with_trade_tick},
             analysis::book_check_integrity,
             book::OrderBook,
+            own::OwnOrderBook,
         },
+        orders::stubs::TestOrderStubs,
         types::{price::Price, quantity::Quantity},
     };
 
@@ -333,6 +412,38 @@ mod tests {
         assert!(book.has_ask());
     }
 
+    #[rstest]
+    fn test_get_level() {
+        let instrument_id = InstrumentId::from("ETHUSDT-PERP.BINANCE");
+        let mut book = OrderBook::new(BookType::L3_MBO, instrument_id);
+        let order1 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("1.0"),
+            1,
+        );
+        let order2 = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.000"),
+            Quantity::from("2.0"),
+            2,
+        );
+        book.add(order1, 0, 1, 100.into());
+        book.add(order2, 0, 2, 200.into());
+
+        let level = book
+            .get_level(OrderSide::Buy, Price::from("1.000"))
+            .unwrap();
+
+        assert_eq!(level.get_orders(), vec![order1, order2]);
+        assert!(book
+            .get_level(OrderSide::Sell, Price::from("1.000"))
+            .is_none());
+        assert!(book
+            .get_level(OrderSide::Buy, Price::from("2.000"))
+            .is_none());
+    }
+
     #[rstest]
     fn test_spread_with_no_bids_or_asks() {
         let instrument_id = InstrumentId::from("ETHUSDT-PERP.BINANCE");
@@ -696,4 +807,80 @@ mod tests {
         println!("{pprint_output}");
         assert_eq!(pprint_output, expected_output);
     }
+
+    #[rstest]
+    fn test_filtered_view_subtracts_own_liquidity(audusd_sim: CurrencyPair) {
+        let mut book = OrderBook::new(BookType::L3_MBO, audusd_sim.id);
+        let orders = [
+            (OrderSide::Buy, "1.00000", 100_000, 1),
+            (OrderSide::Buy, "1.00000", 50_000, 2),
+            (OrderSide::Buy, "0.99990", 200_000, 3),
+            (OrderSide::Sell, "1.00010", 100_000, 4),
+            (OrderSide::Sell, "1.00020", 300_000, 5),
+        ];
+        for (side, price, size, order_id) in orders {
+            let order = BookOrder::new(side, Price::from(price), Quantity::from(size), order_id);
+            book.add(order, 0, order_id, 100.into());
+        }
+
+        let mut own_book = OwnOrderBook::new(audusd_sim.id);
+        for (side, price, size, client_order_id) in [
+            (OrderSide::Buy, "1.00000", 50_000, "O-1"),
+            (OrderSide::Sell, "1.00010", 100_000, "O-2"),
+        ] {
+            let order = TestOrderStubs::limit_order(
+                audusd_sim.id,
+                side,
+                Price::from(price),
+                Quantity::from(size),
+                Some(client_order_id.into()),
+                None,
+            );
+            own_book
+                .update(&TestOrderStubs::make_accepted_order(&order))
+                .unwrap();
+        }
+
+        let view = book.filtered_view(&own_book);
+
+        assert_eq!(view.book_type, BookType::L2_MBP);
+        assert_eq!(view.sequence, book.sequence);
+        assert_eq!(view.best_bid_price(), Some(Price::from("1.00000")));
+        assert_eq!(view.best_bid_size(), Some(Quantity::from(100_000)));
+        assert_eq!(view.best_ask_price(), Some(Price::from("1.00020")));
+        assert_eq!(view.best_ask_size(), Some(Quantity::from(300_000)));
+        assert_eq!(
+            view.simulate_fills(&BookOrder::new(
+                OrderSide::Buy,
+                Price::from("1.00020"),
+                Quantity::from(100_000),
+                0,
+            )),
+            vec![(Price::from("1.00020"), Quantity::from(100_000))]
+        );
+        assert_eq!(
+            view.get_avg_px_for_quantity(Quantity::from(200_000), OrderSide::Sell),
+            0.999_95
+        );
+        // Original book is unchanged
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.00010")));
+    }
+
+    #[rstest]
+    fn test_filtered_view_with_empty_own_book(audusd_sim: CurrencyPair) {
+        let mut book = OrderBook::new(BookType::L2_MBP, audusd_sim.id);
+        let order = BookOrder::new(
+            OrderSide::Buy,
+            Price::from("1.00000"),
+            Quantity::from(100_000),
+            0,
+        );
+        book.add(order, 0, 1, 100.into());
+
+        let view = book.filtered_view(&OwnOrderBook::new(audusd_sim.id));
+
+        assert_eq!(view.best_bid_price(), book.best_bid_price());
+        assert_eq!(view.best_bid_size(), book.best_bid_size());
+        assert!(!view.has_ask());
+    }
 }
@@ -22,3 +22,4 @@ pub mod display;
 pub mod error;
 pub mod ladder;
 pub mod level;
+pub mod own;
@@ -0,0 +1,494 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! An order book of our own working orders, for filtering own liquidity from public books.
+
+use std::collections::{BTreeMap, HashMap};
+
+use indexmap::IndexMap;
+use nautilus_core::nanos::UnixNanos;
+
+use crate::{
+    enums::{OrderSide, OrderSideSpecified},
+    identifiers::{ClientOrderId, InstrumentId},
+    orderbook::ladder::BookPrice,
+    orders::any::OrderAny,
+    types::{fixed::FIXED_SCALAR, price::Price, quantity::Quantity},
+};
+
+/// Represents one of our own working orders resting in an [`OwnOrderBook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OwnBookOrder {
+    pub client_order_id: ClientOrderId,
+    pub side: OrderSide,
+    pub price: Price,
+    /// The leaves (remaining open) quantity of the order.
+    pub size: Quantity,
+    pub ts_last: UnixNanos,
+}
+
+impl OwnBookOrder {
+    /// Creates a new [`OwnBookOrder`] instance.
+    #[must_use]
+    pub fn new(
+        client_order_id: ClientOrderId,
+        side: OrderSide,
+        price: Price,
+        size: Quantity,
+        ts_last: UnixNanos,
+    ) -> Self {
+        Self {
+            client_order_id,
+            side,
+            price,
+            size,
+            ts_last,
+        }
+    }
+}
+
+/// Represents a price level of our own working orders, in insertion order.
+#[derive(Clone, Debug)]
+pub struct OwnBookLevel {
+    pub price: BookPrice,
+    pub orders: IndexMap<ClientOrderId, OwnBookOrder>,
+}
+
+impl OwnBookLevel {
+    /// Creates a new [`OwnBookLevel`] instance.
+    #[must_use]
+    pub fn new(price: BookPrice) -> Self {
+        Self {
+            price,
+            orders: IndexMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.orders.is_empty()
+    }
+
+    #[must_use]
+    pub fn size(&self) -> f64 {
+        self.orders.values().map(|o| o.size.as_f64()).sum()
+    }
+
+    #[must_use]
+    pub fn size_raw(&self) -> u64 {
+        self.orders.values().map(|o| o.size.raw).sum()
+    }
+
+    #[must_use]
+    pub fn exposure(&self) -> f64 {
+        self.size() * self.price.value.as_f64()
+    }
+}
+
+/// Provides an order book of our own working orders for a single instrument.
+///
+/// The book is kept up to date by passing orders to [`OwnOrderBook::update`] as their
+/// events are applied. Only open orders with a price (and which are not pending a
+/// trigger) are tracked, each at its leaves quantity.
+#[derive(Clone, Debug)]
+pub struct OwnOrderBook {
+    /// The instrument ID for the order book.
+    pub instrument_id: InstrumentId,
+    /// The timestamp of the last order update applied to the order book.
+    pub ts_last: UnixNanos,
+    /// The current count of order updates applied to the order book.
+    pub count: u64,
+    bids: BTreeMap<BookPrice, OwnBookLevel>,
+    asks: BTreeMap<BookPrice, OwnBookLevel>,
+    index: HashMap<ClientOrderId, BookPrice>,
+}
+
+impl OwnOrderBook {
+    /// Creates a new [`OwnOrderBook`] instance.
+    #[must_use]
+    pub fn new(instrument_id: InstrumentId) -> Self {
+        Self {
+            instrument_id,
+            ts_last: UnixNanos::default(),
+            count: 0,
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            index: HashMap::new(),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.index.clear();
+        self.ts_last = UnixNanos::default();
+        self.count = 0;
+    }
+
+    /// Updates the book with the current state of the given `order`.
+    ///
+    /// Working orders are added (or moved to their current price and leaves quantity),
+    /// all other orders are removed from the book.
+    ///
+    /// # Errors
+    ///
+    /// This function returns an error if the order is for a different instrument.
+    pub fn update(&mut self, order: &OrderAny) -> anyhow::Result<()> {
+        if order.instrument_id() != self.instrument_id {
+            anyhow::bail!(
+                "Invalid order {} for {}: instrument ID {} did not match",
+                order.client_order_id(),
+                self.instrument_id,
+                order.instrument_id(),
+            );
+        }
+
+        let client_order_id = order.client_order_id();
+        self.remove(&client_order_id);
+
+        if let Some(price) = Self::working_price(order) {
+            let own_order = OwnBookOrder::new(
+                client_order_id,
+                order.order_side(),
+                price,
+                order.leaves_qty(),
+                order.ts_last(),
+            );
+            self.add(own_order);
+        }
+
+        self.ts_last = order.ts_last();
+        self.count += 1;
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn contains(&self, client_order_id: &ClientOrderId) -> bool {
+        self.index.contains_key(client_order_id)
+    }
+
+    pub fn bids(&self) -> impl Iterator<Item = &OwnBookLevel> {
+        self.bids.values()
+    }
+
+    pub fn asks(&self) -> impl Iterator<Item = &OwnBookLevel> {
+        self.asks.values()
+    }
+
+    /// Returns the price level for the given `side` and `price` (if found).
+    #[must_use]
+    pub fn get_level(&self, side: OrderSide, price: Price) -> Option<&OwnBookLevel> {
+        let book_price = BookPrice::new(price, side);
+        match side.as_specified() {
+            OrderSideSpecified::Buy => self.bids.get(&book_price),
+            OrderSideSpecified::Sell => self.asks.get(&book_price),
+        }
+    }
+
+    /// Returns the total raw size of our own orders at the given `side` and `price`.
+    #[must_use]
+    pub fn size_raw_at(&self, side: OrderSide, price: Price) -> u64 {
+        self.get_level(side, price)
+            .map_or(0, OwnBookLevel::size_raw)
+    }
+
+    #[must_use]
+    pub fn best_bid_price(&self) -> Option<Price> {
+        self.bids.keys().next().map(|price| price.value)
+    }
+
+    #[must_use]
+    pub fn best_ask_price(&self) -> Option<Price> {
+        self.asks.keys().next().map(|price| price.value)
+    }
+
+    /// Returns the total size of our own bids.
+    #[must_use]
+    pub fn bid_size(&self) -> f64 {
+        self.bids.values().map(OwnBookLevel::size_raw).sum::<u64>() as f64 / FIXED_SCALAR
+    }
+
+    /// Returns the total size of our own asks.
+    #[must_use]
+    pub fn ask_size(&self) -> f64 {
+        self.asks.values().map(OwnBookLevel::size_raw).sum::<u64>() as f64 / FIXED_SCALAR
+    }
+
+    /// Returns the number of our own orders in the book.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    fn working_price(order: &OrderAny) -> Option<Price> {
+        // Untriggered stop orders are not yet working at their limit price
+        if !order.is_open() || order.is_triggered() == Some(false) || order.leaves_qty().is_zero() {
+            return None;
+        }
+        order.price()
+    }
+
+    fn add(&mut self, order: OwnBookOrder) {
+        let book_price = BookPrice::new(order.price, order.side);
+        let levels = match order.side.as_specified() {
+            OrderSideSpecified::Buy => &mut self.bids,
+            OrderSideSpecified::Sell => &mut self.asks,
+        };
+        levels
+            .entry(book_price)
+            .or_insert_with(|| OwnBookLevel::new(book_price))
+            .orders
+            .insert(order.client_order_id, order);
+        self.index.insert(order.client_order_id, book_price);
+    }
+
+    fn remove(&mut self, client_order_id: &ClientOrderId) {
+        let Some(book_price) = self.index.remove(client_order_id) else {
+            return;
+        };
+        let levels = match book_price.side.as_specified() {
+            OrderSideSpecified::Buy => &mut self.bids,
+            OrderSideSpecified::Sell => &mut self.asks,
+        };
+        if let Some(level) = levels.get_mut(&book_price) {
+            level.orders.shift_remove(client_order_id);
+            if level.is_empty() {
+                levels.remove(&book_price);
+            }
+        }
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::uuid::UUID4;
+    use rstest::rstest;
+
+    use super::OwnOrderBook;
+    use crate::{
+        enums::{OrderSide, OrderStatus, TimeInForce, TriggerType},
+        events::order::OrderEventAny,
+        identifiers::{ClientOrderId, InstrumentId},
+        instruments::{any::InstrumentAny, currency_pair::CurrencyPair, stubs::*},
+        orders::{
+            any::OrderAny,
+            stop_limit::StopLimitOrder,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        types::{price::Price, quantity::Quantity},
+    };
+
+    fn accepted_limit(
+        instrument_id: InstrumentId,
+        side: OrderSide,
+        price: &str,
+        quantity: i64,
+        client_order_id: &str,
+    ) -> OrderAny {
+        let order = TestOrderStubs::limit_order(
+            instrument_id,
+            side,
+            Price::from(price),
+            Quantity::from(quantity),
+            Some(ClientOrderId::from(client_order_id)),
+            None,
+        );
+        TestOrderStubs::make_accepted_order(&order)
+    }
+
+    #[rstest]
+    fn test_update_adds_working_orders(audusd_sim: CurrencyPair) {
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let bid1 = accepted_limit(audusd_sim.id, OrderSide::Buy, "1.00000", 100_000, "O-1");
+        let bid2 = accepted_limit(audusd_sim.id, OrderSide::Buy, "1.00000", 50_000, "O-2");
+        let ask = accepted_limit(audusd_sim.id, OrderSide::Sell, "1.00010", 20_000, "O-3");
+
+        book.update(&bid1).unwrap();
+        book.update(&bid2).unwrap();
+        book.update(&ask).unwrap();
+
+        assert_eq!(book.len(), 3);
+        assert_eq!(book.count, 3);
+        assert_eq!(book.best_bid_price(), Some(Price::from("1.00000")));
+        assert_eq!(book.best_ask_price(), Some(Price::from("1.00010")));
+        let level = book
+            .get_level(OrderSide::Buy, Price::from("1.00000"))
+            .unwrap();
+        assert_eq!(level.len(), 2);
+        assert_eq!(level.size(), 150_000.0);
+        assert_eq!(
+            level.orders.keys().copied().collect::<Vec<_>>(),
+            vec![ClientOrderId::from("O-1"), ClientOrderId::from("O-2")]
+        );
+        assert_eq!(book.bid_size(), 150_000.0);
+        assert_eq!(book.ask_size(), 20_000.0);
+    }
+
+    #[rstest]
+    fn test_update_with_initialized_order_not_added(audusd_sim: CurrencyPair) {
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let order = TestOrderStubs::limit_order(
+            audusd_sim.id,
+            OrderSide::Buy,
+            Price::from("1.00000"),
+            Quantity::from(100_000),
+            None,
+            None,
+        );
+
+        book.update(&order).unwrap();
+
+        assert!(book.is_empty());
+        assert_eq!(book.best_bid_price(), None);
+    }
+
+    #[rstest]
+    fn test_update_with_partial_fill_reduces_size(audusd_sim: CurrencyPair) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim);
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let mut order = accepted_limit(audusd_sim.id, OrderSide::Sell, "1.00010", 100_000, "O-1");
+        book.update(&order).unwrap();
+
+        let fill = TestOrderEventStubs::order_filled(
+            &order,
+            &instrument,
+            None,
+            None,
+            Some(Price::from("1.00010")),
+            Some(Quantity::from(40_000)),
+            None,
+            None,
+            None,
+            None,
+        );
+        let OrderEventAny::Filled(fill) = fill else {
+            panic!("Expected fill, was {fill:?}");
+        };
+        order.apply(OrderEventAny::PartiallyFilled(fill)).unwrap();
+        book.update(&order).unwrap();
+
+        assert_eq!(order.status(), OrderStatus::PartiallyFilled);
+        assert_eq!(
+            book.size_raw_at(OrderSide::Sell, Price::from("1.00010")),
+            Quantity::from(60_000).raw
+        );
+    }
+
+    #[rstest]
+    fn test_update_with_closed_order_removes_level(audusd_sim: CurrencyPair) {
+        let instrument = InstrumentAny::CurrencyPair(audusd_sim);
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let mut order = accepted_limit(audusd_sim.id, OrderSide::Buy, "1.00000", 100_000, "O-1");
+        book.update(&order).unwrap();
+
+        let fill = TestOrderEventStubs::order_filled(
+            &order,
+            &instrument,
+            None,
+            None,
+            Some(Price::from("1.00000")),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        order.apply(fill).unwrap();
+        book.update(&order).unwrap();
+
+        assert!(book.is_empty());
+        assert!(!book.contains(&order.client_order_id()));
+        assert!(book
+            .get_level(OrderSide::Buy, Price::from("1.00000"))
+            .is_none());
+    }
+
+    #[rstest]
+    fn test_update_with_untriggered_stop_limit_not_added(audusd_sim: CurrencyPair) {
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let order = OrderAny::StopLimit(
+            StopLimitOrder::new(
+                Default::default(),
+                Default::default(),
+                audusd_sim.id,
+                ClientOrderId::from("O-1"),
+                OrderSide::Buy,
+                Quantity::from(100_000),
+                Price::from("1.00010"),
+                Price::from("1.00005"),
+                TriggerType::Default,
+                TimeInForce::Gtc,
+                None,
+                false,
+                false,
+                false,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                UUID4::new(),
+                Default::default(),
+            )
+            .unwrap(),
+        );
+        let order = TestOrderStubs::make_accepted_order(&order);
+
+        book.update(&order).unwrap();
+
+        assert!(book.is_empty());
+    }
+
+    #[rstest]
+    fn test_update_with_other_instrument_errors(audusd_sim: CurrencyPair) {
+        let mut book = OwnOrderBook::new(InstrumentId::from("ETHUSDT-PERP.BINANCE"));
+        let order = accepted_limit(audusd_sim.id, OrderSide::Buy, "1.00000", 100_000, "O-1");
+
+        assert!(book.update(&order).is_err());
+        assert!(book.is_empty());
+    }
+
+    #[rstest]
+    fn test_reset(audusd_sim: CurrencyPair) {
+        let mut book = OwnOrderBook::new(audusd_sim.id);
+        let order = accepted_limit(audusd_sim.id, OrderSide::Buy, "1.00000", 100_000, "O-1");
+        book.update(&order).unwrap();
+
+        book.reset();
+
+        assert!(book.is_empty());
+        assert_eq!(book.count, 0);
+        assert_eq!(book.bids().count(), 0);
+    }
+}
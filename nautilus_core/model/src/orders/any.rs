@@ -13,13 +13,14 @@
 //  limitations under the License.
 // -------------------------------------------------------------------------------------------------
 
-use std::fmt::Display;
+use std::{collections::HashMap, fmt::Display};
 
-use nautilus_core::nanos::UnixNanos;
+use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
 use serde::{Deserialize, Serialize};
+use ustr::Ustr;
 
 use super::{
-    base::{Order, OrderError},
+    base::{Order, OrderCore, OrderError},
     limit::LimitOrder,
     limit_if_touched::LimitIfTouchedOrder,
     market::MarketOrder,
@@ -31,11 +32,14 @@ use super::{
     trailing_stop_market::TrailingStopMarketOrder,
 };
 use crate::{
-    enums::{LiquiditySide, OrderSide, OrderSideSpecified, OrderStatus, OrderType, TriggerType},
+    enums::{
+        ContingencyType, LiquiditySide, OrderSide, OrderSideSpecified, OrderStatus, OrderType,
+        PositionSide, TimeInForce, TriggerType,
+    },
     events::order::OrderEventAny,
     identifiers::{
-        AccountId, ClientOrderId, ExecAlgorithmId, InstrumentId, PositionId, StrategyId, TraderId,
-        VenueOrderId,
+        AccountId, ClientOrderId, ExecAlgorithmId, InstrumentId, OrderListId, PositionId,
+        StrategyId, TradeId, TraderId, VenueOrderId,
     },
     types::{price::Price, quantity::Quantity},
 };
@@ -408,6 +412,415 @@ impl OrderAny {
         }
     }
 
+    #[must_use]
+    pub fn time_in_force(&self) -> TimeInForce {
+        match self {
+            Self::Limit(order) => order.time_in_force(),
+            Self::LimitIfTouched(order) => order.time_in_force(),
+            Self::Market(order) => order.time_in_force(),
+            Self::MarketIfTouched(order) => order.time_in_force(),
+            Self::MarketToLimit(order) => order.time_in_force(),
+            Self::StopLimit(order) => order.time_in_force(),
+            Self::StopMarket(order) => order.time_in_force(),
+            Self::TrailingStopLimit(order) => order.time_in_force(),
+            Self::TrailingStopMarket(order) => order.time_in_force(),
+        }
+    }
+
+    #[must_use]
+    pub fn expire_time(&self) -> Option<UnixNanos> {
+        match self {
+            Self::Limit(order) => order.expire_time(),
+            Self::LimitIfTouched(order) => order.expire_time(),
+            Self::Market(order) => order.expire_time(),
+            Self::MarketIfTouched(order) => order.expire_time(),
+            Self::MarketToLimit(order) => order.expire_time(),
+            Self::StopLimit(order) => order.expire_time(),
+            Self::StopMarket(order) => order.expire_time(),
+            Self::TrailingStopLimit(order) => order.expire_time(),
+            Self::TrailingStopMarket(order) => order.expire_time(),
+        }
+    }
+
+    #[must_use]
+    pub fn price(&self) -> Option<Price> {
+        match self {
+            Self::Limit(order) => order.price(),
+            Self::LimitIfTouched(order) => order.price(),
+            Self::Market(order) => order.price(),
+            Self::MarketIfTouched(order) => order.price(),
+            Self::MarketToLimit(order) => order.price(),
+            Self::StopLimit(order) => order.price(),
+            Self::StopMarket(order) => order.price(),
+            Self::TrailingStopLimit(order) => order.price(),
+            Self::TrailingStopMarket(order) => order.price(),
+        }
+    }
+
+    #[must_use]
+    pub fn trigger_price(&self) -> Option<Price> {
+        match self {
+            Self::Limit(order) => order.trigger_price(),
+            Self::LimitIfTouched(order) => order.trigger_price(),
+            Self::Market(order) => order.trigger_price(),
+            Self::MarketIfTouched(order) => order.trigger_price(),
+            Self::MarketToLimit(order) => order.trigger_price(),
+            Self::StopLimit(order) => order.trigger_price(),
+            Self::StopMarket(order) => order.trigger_price(),
+            Self::TrailingStopLimit(order) => order.trigger_price(),
+            Self::TrailingStopMarket(order) => order.trigger_price(),
+        }
+    }
+
+    #[must_use]
+    pub fn trigger_type(&self) -> Option<TriggerType> {
+        match self {
+            Self::Limit(order) => order.trigger_type(),
+            Self::LimitIfTouched(order) => order.trigger_type(),
+            Self::Market(order) => order.trigger_type(),
+            Self::MarketIfTouched(order) => order.trigger_type(),
+            Self::MarketToLimit(order) => order.trigger_type(),
+            Self::StopLimit(order) => order.trigger_type(),
+            Self::StopMarket(order) => order.trigger_type(),
+            Self::TrailingStopLimit(order) => order.trigger_type(),
+            Self::TrailingStopMarket(order) => order.trigger_type(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_post_only(&self) -> bool {
+        match self {
+            Self::Limit(order) => order.is_post_only(),
+            Self::LimitIfTouched(order) => order.is_post_only(),
+            Self::Market(order) => order.is_post_only(),
+            Self::MarketIfTouched(order) => order.is_post_only(),
+            Self::MarketToLimit(order) => order.is_post_only(),
+            Self::StopLimit(order) => order.is_post_only(),
+            Self::StopMarket(order) => order.is_post_only(),
+            Self::TrailingStopLimit(order) => order.is_post_only(),
+            Self::TrailingStopMarket(order) => order.is_post_only(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_reduce_only(&self) -> bool {
+        match self {
+            Self::Limit(order) => order.is_reduce_only(),
+            Self::LimitIfTouched(order) => order.is_reduce_only(),
+            Self::Market(order) => order.is_reduce_only(),
+            Self::MarketIfTouched(order) => order.is_reduce_only(),
+            Self::MarketToLimit(order) => order.is_reduce_only(),
+            Self::StopLimit(order) => order.is_reduce_only(),
+            Self::StopMarket(order) => order.is_reduce_only(),
+            Self::TrailingStopLimit(order) => order.is_reduce_only(),
+            Self::TrailingStopMarket(order) => order.is_reduce_only(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_quote_quantity(&self) -> bool {
+        match self {
+            Self::Limit(order) => order.is_quote_quantity(),
+            Self::LimitIfTouched(order) => order.is_quote_quantity(),
+            Self::Market(order) => order.is_quote_quantity(),
+            Self::MarketIfTouched(order) => order.is_quote_quantity(),
+            Self::MarketToLimit(order) => order.is_quote_quantity(),
+            Self::StopLimit(order) => order.is_quote_quantity(),
+            Self::StopMarket(order) => order.is_quote_quantity(),
+            Self::TrailingStopLimit(order) => order.is_quote_quantity(),
+            Self::TrailingStopMarket(order) => order.is_quote_quantity(),
+        }
+    }
+
+    #[must_use]
+    pub fn contingency_type(&self) -> Option<ContingencyType> {
+        match self {
+            Self::Limit(order) => order.contingency_type(),
+            Self::LimitIfTouched(order) => order.contingency_type(),
+            Self::Market(order) => order.contingency_type(),
+            Self::MarketIfTouched(order) => order.contingency_type(),
+            Self::MarketToLimit(order) => order.contingency_type(),
+            Self::StopLimit(order) => order.contingency_type(),
+            Self::StopMarket(order) => order.contingency_type(),
+            Self::TrailingStopLimit(order) => order.contingency_type(),
+            Self::TrailingStopMarket(order) => order.contingency_type(),
+        }
+    }
+
+    #[must_use]
+    pub fn order_list_id(&self) -> Option<OrderListId> {
+        match self {
+            Self::Limit(order) => order.order_list_id(),
+            Self::LimitIfTouched(order) => order.order_list_id(),
+            Self::Market(order) => order.order_list_id(),
+            Self::MarketIfTouched(order) => order.order_list_id(),
+            Self::MarketToLimit(order) => order.order_list_id(),
+            Self::StopLimit(order) => order.order_list_id(),
+            Self::StopMarket(order) => order.order_list_id(),
+            Self::TrailingStopLimit(order) => order.order_list_id(),
+            Self::TrailingStopMarket(order) => order.order_list_id(),
+        }
+    }
+
+    #[must_use]
+    pub fn linked_order_ids(&self) -> Option<&[ClientOrderId]> {
+        match self {
+            Self::Limit(order) => order.linked_order_ids(),
+            Self::LimitIfTouched(order) => order.linked_order_ids(),
+            Self::Market(order) => order.linked_order_ids(),
+            Self::MarketIfTouched(order) => order.linked_order_ids(),
+            Self::MarketToLimit(order) => order.linked_order_ids(),
+            Self::StopLimit(order) => order.linked_order_ids(),
+            Self::StopMarket(order) => order.linked_order_ids(),
+            Self::TrailingStopLimit(order) => order.linked_order_ids(),
+            Self::TrailingStopMarket(order) => order.linked_order_ids(),
+        }
+    }
+
+    #[must_use]
+    pub fn parent_order_id(&self) -> Option<ClientOrderId> {
+        match self {
+            Self::Limit(order) => order.parent_order_id(),
+            Self::LimitIfTouched(order) => order.parent_order_id(),
+            Self::Market(order) => order.parent_order_id(),
+            Self::MarketIfTouched(order) => order.parent_order_id(),
+            Self::MarketToLimit(order) => order.parent_order_id(),
+            Self::StopLimit(order) => order.parent_order_id(),
+            Self::StopMarket(order) => order.parent_order_id(),
+            Self::TrailingStopLimit(order) => order.parent_order_id(),
+            Self::TrailingStopMarket(order) => order.parent_order_id(),
+        }
+    }
+
+    #[must_use]
+    pub fn exec_algorithm_params(&self) -> Option<&HashMap<Ustr, Ustr>> {
+        match self {
+            Self::Limit(order) => order.exec_algorithm_params(),
+            Self::LimitIfTouched(order) => order.exec_algorithm_params(),
+            Self::Market(order) => order.exec_algorithm_params(),
+            Self::MarketIfTouched(order) => order.exec_algorithm_params(),
+            Self::MarketToLimit(order) => order.exec_algorithm_params(),
+            Self::StopLimit(order) => order.exec_algorithm_params(),
+            Self::StopMarket(order) => order.exec_algorithm_params(),
+            Self::TrailingStopLimit(order) => order.exec_algorithm_params(),
+            Self::TrailingStopMarket(order) => order.exec_algorithm_params(),
+        }
+    }
+
+    #[must_use]
+    pub fn tags(&self) -> Option<&[Ustr]> {
+        match self {
+            Self::Limit(order) => order.tags(),
+            Self::LimitIfTouched(order) => order.tags(),
+            Self::Market(order) => order.tags(),
+            Self::MarketIfTouched(order) => order.tags(),
+            Self::MarketToLimit(order) => order.tags(),
+            Self::StopLimit(order) => order.tags(),
+            Self::StopMarket(order) => order.tags(),
+            Self::TrailingStopLimit(order) => order.tags(),
+            Self::TrailingStopMarket(order) => order.tags(),
+        }
+    }
+
+    #[must_use]
+    pub fn display_qty(&self) -> Option<Quantity> {
+        match self {
+            Self::Limit(order) => order.display_qty(),
+            Self::LimitIfTouched(order) => order.display_qty(),
+            Self::Market(order) => order.display_qty(),
+            Self::MarketIfTouched(order) => order.display_qty(),
+            Self::MarketToLimit(order) => order.display_qty(),
+            Self::StopLimit(order) => order.display_qty(),
+            Self::StopMarket(order) => order.display_qty(),
+            Self::TrailingStopLimit(order) => order.display_qty(),
+            Self::TrailingStopMarket(order) => order.display_qty(),
+        }
+    }
+
+    #[must_use]
+    pub fn trigger_instrument_id(&self) -> Option<InstrumentId> {
+        match self {
+            Self::Limit(order) => order.trigger_instrument_id(),
+            Self::LimitIfTouched(order) => order.trigger_instrument_id(),
+            Self::Market(order) => order.trigger_instrument_id(),
+            Self::MarketIfTouched(order) => order.trigger_instrument_id(),
+            Self::MarketToLimit(order) => order.trigger_instrument_id(),
+            Self::StopLimit(order) => order.trigger_instrument_id(),
+            Self::StopMarket(order) => order.trigger_instrument_id(),
+            Self::TrailingStopLimit(order) => order.trigger_instrument_id(),
+            Self::TrailingStopMarket(order) => order.trigger_instrument_id(),
+        }
+    }
+
+    #[must_use]
+    pub fn init_id(&self) -> UUID4 {
+        match self {
+            Self::Limit(order) => order.init_id(),
+            Self::LimitIfTouched(order) => order.init_id(),
+            Self::Market(order) => order.init_id(),
+            Self::MarketIfTouched(order) => order.init_id(),
+            Self::MarketToLimit(order) => order.init_id(),
+            Self::StopLimit(order) => order.init_id(),
+            Self::StopMarket(order) => order.init_id(),
+            Self::TrailingStopLimit(order) => order.init_id(),
+            Self::TrailingStopMarket(order) => order.init_id(),
+        }
+    }
+
+    #[must_use]
+    pub fn events(&self) -> Vec<&OrderEventAny> {
+        match self {
+            Self::Limit(order) => order.events(),
+            Self::LimitIfTouched(order) => order.events(),
+            Self::Market(order) => order.events(),
+            Self::MarketIfTouched(order) => order.events(),
+            Self::MarketToLimit(order) => order.events(),
+            Self::StopLimit(order) => order.events(),
+            Self::StopMarket(order) => order.events(),
+            Self::TrailingStopLimit(order) => order.events(),
+            Self::TrailingStopMarket(order) => order.events(),
+        }
+    }
+
+    #[must_use]
+    pub fn trade_ids(&self) -> Vec<&TradeId> {
+        match self {
+            Self::Limit(order) => order.trade_ids(),
+            Self::LimitIfTouched(order) => order.trade_ids(),
+            Self::Market(order) => order.trade_ids(),
+            Self::MarketIfTouched(order) => order.trade_ids(),
+            Self::MarketToLimit(order) => order.trade_ids(),
+            Self::StopLimit(order) => order.trade_ids(),
+            Self::StopMarket(order) => order.trade_ids(),
+            Self::TrailingStopLimit(order) => order.trade_ids(),
+            Self::TrailingStopMarket(order) => order.trade_ids(),
+        }
+    }
+
+    #[must_use]
+    pub fn avg_px(&self) -> Option<f64> {
+        match self {
+            Self::Limit(order) => order.avg_px(),
+            Self::LimitIfTouched(order) => order.avg_px(),
+            Self::Market(order) => order.avg_px(),
+            Self::MarketIfTouched(order) => order.avg_px(),
+            Self::MarketToLimit(order) => order.avg_px(),
+            Self::StopLimit(order) => order.avg_px(),
+            Self::StopMarket(order) => order.avg_px(),
+            Self::TrailingStopLimit(order) => order.avg_px(),
+            Self::TrailingStopMarket(order) => order.avg_px(),
+        }
+    }
+
+    #[must_use]
+    pub fn ts_init(&self) -> UnixNanos {
+        match self {
+            Self::Limit(order) => order.ts_init(),
+            Self::LimitIfTouched(order) => order.ts_init(),
+            Self::Market(order) => order.ts_init(),
+            Self::MarketIfTouched(order) => order.ts_init(),
+            Self::MarketToLimit(order) => order.ts_init(),
+            Self::StopLimit(order) => order.ts_init(),
+            Self::StopMarket(order) => order.ts_init(),
+            Self::TrailingStopLimit(order) => order.ts_init(),
+            Self::TrailingStopMarket(order) => order.ts_init(),
+        }
+    }
+
+    #[must_use]
+    pub fn ts_last(&self) -> UnixNanos {
+        match self {
+            Self::Limit(order) => order.ts_last(),
+            Self::LimitIfTouched(order) => order.ts_last(),
+            Self::Market(order) => order.ts_last(),
+            Self::MarketIfTouched(order) => order.ts_last(),
+            Self::MarketToLimit(order) => order.ts_last(),
+            Self::StopLimit(order) => order.ts_last(),
+            Self::StopMarket(order) => order.ts_last(),
+            Self::TrailingStopLimit(order) => order.ts_last(),
+            Self::TrailingStopMarket(order) => order.ts_last(),
+        }
+    }
+
+    #[must_use]
+    pub fn is_triggered(&self) -> Option<bool> {
+        match self {
+            Self::Limit(_) => None,
+            Self::LimitIfTouched(order) => Some(order.is_triggered),
+            Self::Market(_) => None,
+            Self::MarketIfTouched(order) => Some(order.is_triggered),
+            Self::MarketToLimit(_) => None,
+            Self::StopLimit(order) => Some(order.is_triggered),
+            Self::StopMarket(order) => Some(order.is_triggered),
+            Self::TrailingStopLimit(order) => Some(order.is_triggered),
+            Self::TrailingStopMarket(order) => Some(order.is_triggered),
+        }
+    }
+
+    #[must_use]
+    pub fn is_buy(&self) -> bool {
+        self.order_side() == OrderSide::Buy
+    }
+
+    #[must_use]
+    pub fn is_sell(&self) -> bool {
+        self.order_side() == OrderSide::Sell
+    }
+
+    #[must_use]
+    pub fn is_passive(&self) -> bool {
+        self.order_type() != OrderType::Market
+    }
+
+    #[must_use]
+    pub fn would_reduce_only(&self, side: PositionSide, position_qty: Quantity) -> bool {
+        match self {
+            Self::Limit(order) => order.would_reduce_only(side, position_qty),
+            Self::LimitIfTouched(order) => order.would_reduce_only(side, position_qty),
+            Self::Market(order) => order.would_reduce_only(side, position_qty),
+            Self::MarketIfTouched(order) => order.would_reduce_only(side, position_qty),
+            Self::MarketToLimit(order) => order.would_reduce_only(side, position_qty),
+            Self::StopLimit(order) => order.would_reduce_only(side, position_qty),
+            Self::StopMarket(order) => order.would_reduce_only(side, position_qty),
+            Self::TrailingStopLimit(order) => order.would_reduce_only(side, position_qty),
+            Self::TrailingStopMarket(order) => order.would_reduce_only(side, position_qty),
+        }
+    }
+
+    /// Sets the liquidity side for the order (used by venues when simulating fills).
+    pub fn set_liquidity_side(&mut self, liquidity_side: LiquiditySide) {
+        match self {
+            Self::Limit(order) => order.liquidity_side = Some(liquidity_side),
+            Self::LimitIfTouched(order) => order.liquidity_side = Some(liquidity_side),
+            Self::Market(order) => order.liquidity_side = Some(liquidity_side),
+            Self::MarketIfTouched(order) => order.liquidity_side = Some(liquidity_side),
+            Self::MarketToLimit(order) => order.liquidity_side = Some(liquidity_side),
+            Self::StopLimit(order) => order.liquidity_side = Some(liquidity_side),
+            Self::StopMarket(order) => order.liquidity_side = Some(liquidity_side),
+            Self::TrailingStopLimit(order) => order.liquidity_side = Some(liquidity_side),
+            Self::TrailingStopMarket(order) => order.liquidity_side = Some(liquidity_side),
+        }
+    }
+
+    /// Sets the quantity of the order to the given `base_qty` in units of the base asset,
+    /// clearing any quote quantity flag (used when converting orders on submission).
+    pub fn set_base_quantity(&mut self, base_qty: Quantity) {
+        let core: &mut OrderCore = match self {
+            Self::Limit(order) => order,
+            Self::LimitIfTouched(order) => order,
+            Self::Market(order) => order,
+            Self::MarketIfTouched(order) => order,
+            Self::MarketToLimit(order) => order,
+            Self::StopLimit(order) => order,
+            Self::StopMarket(order) => order,
+            Self::TrailingStopLimit(order) => order,
+            Self::TrailingStopMarket(order) => order,
+        };
+        core.quantity = base_qty;
+        core.leaves_qty = base_qty - core.filled_qty;
+        core.is_quote_quantity = false;
+    }
+
     #[must_use]
     pub fn is_open(&self) -> bool {
         match self {
@@ -484,6 +897,7 @@ impl From<OrderAny> for PassiveOrderAny {
     fn from(order: OrderAny) -> PassiveOrderAny {
         match order {
             OrderAny::Limit(_) => PassiveOrderAny::Limit(order.into()),
+            OrderAny::MarketToLimit(_) => PassiveOrderAny::Limit(order.into()),
             OrderAny::LimitIfTouched(_) => PassiveOrderAny::Stop(order.into()),
             OrderAny::MarketIfTouched(_) => PassiveOrderAny::Stop(order.into()),
             OrderAny::StopLimit(_) => PassiveOrderAny::Stop(order.into()),
@@ -102,12 +102,14 @@ impl OrderStatus {
             (Self::Initialized, OrderEventAny::Canceled(_)) => Self::Canceled,  // External orders
             (Self::Initialized, OrderEventAny::Expired(_)) => Self::Expired,  // External orders
             (Self::Initialized, OrderEventAny::Triggered(_)) => Self::Triggered, // External orders
+            (Self::Initialized, OrderEventAny::Updated(_)) => Self::Initialized,  // Execution algo
             (Self::Emulated, OrderEventAny::Canceled(_)) => Self::Canceled,  // Emulated orders
             (Self::Emulated, OrderEventAny::Expired(_)) => Self::Expired,  // Emulated orders
             (Self::Emulated, OrderEventAny::Released(_)) => Self::Released,  // Emulated orders
             (Self::Released, OrderEventAny::Submitted(_)) => Self::Submitted,  // Emulated orders
             (Self::Released, OrderEventAny::Denied(_)) => Self::Denied,  // Emulated orders
             (Self::Released, OrderEventAny::Canceled(_)) => Self::Canceled,  // Execution algo
+            (Self::Released, OrderEventAny::Updated(_)) => Self::Released,  // Execution algo
             (Self::Submitted, OrderEventAny::PendingUpdate(_)) => Self::PendingUpdate,
             (Self::Submitted, OrderEventAny::PendingCancel(_)) => Self::PendingCancel,
             (Self::Submitted, OrderEventAny::Rejected(_)) => Self::Rejected,
@@ -154,6 +156,21 @@ impl OrderStatus {
             (Self::PartiallyFilled, OrderEventAny::Expired(_)) => Self::Expired,
             (Self::PartiallyFilled, OrderEventAny::PartiallyFilled(_)) => Self::PartiallyFilled,
             (Self::PartiallyFilled, OrderEventAny::Filled(_)) => Self::Filled,
+            (Self::Emulated, OrderEventAny::Updated(_)) => Self::Emulated,  // Emulated orders
+            (Self::Submitted, OrderEventAny::Updated(_)) => Self::Submitted,
+            (Self::Accepted, OrderEventAny::Updated(_)) => Self::Accepted,
+            (Self::Triggered, OrderEventAny::Updated(_)) => Self::Triggered,
+            (Self::PartiallyFilled, OrderEventAny::Updated(_)) => Self::PartiallyFilled,
+            (Self::PendingUpdate, OrderEventAny::Updated(_)) => Self::PendingUpdate,  // Restored to previous status
+            (Self::PendingUpdate, OrderEventAny::ModifyRejected(_)) => Self::PendingUpdate,  // Restored to previous status
+            (Self::PendingCancel, OrderEventAny::ModifyRejected(_)) => Self::PendingCancel,
+            (Self::PendingCancel, OrderEventAny::CancelRejected(_)) => Self::PendingCancel,  // Restored to previous status
+            (Self::Accepted, OrderEventAny::ModifyRejected(_)) => Self::Accepted,
+            (Self::Accepted, OrderEventAny::CancelRejected(_)) => Self::Accepted,
+            (Self::Triggered, OrderEventAny::ModifyRejected(_)) => Self::Triggered,
+            (Self::Triggered, OrderEventAny::CancelRejected(_)) => Self::Triggered,
+            (Self::PartiallyFilled, OrderEventAny::ModifyRejected(_)) => Self::PartiallyFilled,
+            (Self::PartiallyFilled, OrderEventAny::CancelRejected(_)) => Self::PartiallyFilled,
             _ => return Err(OrderError::InvalidStateTransition),
         };
         Ok(new_state)
@@ -490,8 +507,10 @@ impl OrderCore {
         assert_eq!(self.strategy_id, event.strategy_id());
 
         let new_status = self.status.transition(&event)?;
-        self.previous_status = Some(self.status);
-        self.status = new_status;
+        if new_status != self.status {
+            self.previous_status = Some(self.status);
+            self.status = new_status;
+        }
 
         match &event {
             OrderEventAny::Initialized(_) => return Err(OrderError::AlreadyInitialized),
@@ -551,12 +570,18 @@ impl OrderCore {
     }
 
     fn modify_rejected(&mut self, _event: &OrderModifyRejected) {
-        self.status = self
-            .previous_status
-            .unwrap_or_else(|| panic!("{}", OrderError::NoPreviousState));
+        if self.status == OrderStatus::PendingUpdate {
+            self.restore_previous_status();
+        }
     }
 
     fn cancel_rejected(&mut self, _event: &OrderCancelRejected) {
+        if self.status == OrderStatus::PendingCancel {
+            self.restore_previous_status();
+        }
+    }
+
+    fn restore_previous_status(&mut self) {
         self.status = self
             .previous_status
             .unwrap_or_else(|| panic!("{}", OrderError::NoPreviousState));
@@ -569,6 +594,10 @@ impl OrderCore {
     fn expired(&mut self, _event: &OrderExpired) {}
 
     fn updated(&mut self, event: &OrderUpdated) {
+        if self.status == OrderStatus::PendingUpdate {
+            self.restore_previous_status();
+        }
+
         if let Some(venue_order_id) = &event.venue_order_id {
             if self.venue_order_id.is_none()
                 || venue_order_id != self.venue_order_id.as_ref().unwrap()
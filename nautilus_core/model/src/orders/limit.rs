@@ -145,6 +145,58 @@ impl LimitOrder {
             trigger_instrument_id,
         })
     }
+
+    /// Transforms the given `order` into a [`LimitOrder`], retaining its identifiers,
+    /// quantity and event history.
+    ///
+    /// This is used to release a locally emulated order to the venue, at the given `price`
+    /// if provided, otherwise at the orders own limit price.
+    pub fn transform(
+        order: &OrderAny,
+        price: Option<Price>,
+        ts_init: UnixNanos,
+    ) -> anyhow::Result<Self> {
+        let Some(price) = price.or(order.price()) else {
+            anyhow::bail!(
+                "No price to transform {} into a `LimitOrder`",
+                order.client_order_id()
+            )
+        };
+
+        let mut transformed = Self::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            order.order_side(),
+            order.quantity(),
+            price,
+            order.time_in_force(),
+            order.expire_time().filter(|expire_time| *expire_time != 0),
+            order.is_post_only(),
+            order.is_reduce_only(),
+            order.is_quote_quantity(),
+            order.display_qty(),
+            Some(TriggerType::NoTrigger),
+            None,
+            order.contingency_type(),
+            order.order_list_id(),
+            order.linked_order_ids().map(<[ClientOrderId]>::to_vec),
+            order.parent_order_id(),
+            order.exec_algorithm_id(),
+            order.exec_algorithm_params().cloned(),
+            order.exec_spawn_id(),
+            order.tags().map(<[Ustr]>::to_vec),
+            order.init_id(),
+            ts_init,
+        )?;
+
+        let mut events: Vec<OrderEventAny> = order.events().into_iter().cloned().collect();
+        events.append(&mut transformed.core.events);
+        transformed.core.events = events;
+
+        Ok(transformed)
+    }
 }
 
 impl Deref for LimitOrder {
@@ -483,12 +535,14 @@ impl From<OrderInitialized> for LimitOrder {
 ////////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
+    use nautilus_core::nanos::UnixNanos;
     use rstest::rstest;
 
+    use super::LimitOrder;
     use crate::{
-        enums::{OrderSide, TimeInForce},
+        enums::{OrderSide, OrderType, TimeInForce, TriggerType},
         instruments::{currency_pair::CurrencyPair, stubs::*},
-        orders::stubs::TestOrderStubs,
+        orders::{base::Order, stubs::TestOrderStubs},
         types::{price::Price, quantity::Quantity},
     };
 
@@ -536,4 +590,43 @@ mod tests {
             Some(TimeInForce::Gtd),
         );
     }
+
+    #[rstest]
+    fn test_transform_with_price(audusd_sim: CurrencyPair) {
+        let order = TestOrderStubs::stop_market_order(
+            audusd_sim.id,
+            OrderSide::Buy,
+            Price::from("1.01000"),
+            Quantity::from(100_000),
+            None,
+            None,
+            None,
+        );
+
+        let transformed =
+            LimitOrder::transform(&order, Some(Price::from("1.01010")), UnixNanos::from(1))
+                .unwrap();
+
+        assert_eq!(transformed.client_order_id, order.client_order_id());
+        assert_eq!(transformed.order_type(), OrderType::Limit);
+        assert_eq!(transformed.price, Price::from("1.01010"));
+        assert_eq!(
+            transformed.emulation_trigger(),
+            Some(TriggerType::NoTrigger)
+        );
+        assert_eq!(transformed.events().len(), 2);
+    }
+
+    #[rstest]
+    fn test_transform_without_price_fails(audusd_sim: CurrencyPair) {
+        let order = TestOrderStubs::market_order(
+            audusd_sim.id,
+            OrderSide::Buy,
+            Quantity::from(100_000),
+            None,
+            None,
+        );
+
+        assert!(LimitOrder::transform(&order, None, UnixNanos::from(1)).is_err());
+    }
 }
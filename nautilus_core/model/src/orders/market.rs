@@ -122,6 +122,46 @@ impl MarketOrder {
             core: OrderCore::new(init_order).unwrap(),
         })
     }
+
+    /// Transforms the given `order` into a [`MarketOrder`], retaining its identifiers,
+    /// quantity and event history.
+    ///
+    /// This is used to release a locally emulated order to the venue. A `GTD` time in force
+    /// is converted to `GTC` as it is not supported for market orders.
+    pub fn transform(order: &OrderAny, ts_init: UnixNanos) -> anyhow::Result<Self> {
+        let time_in_force = match order.time_in_force() {
+            TimeInForce::Gtd => TimeInForce::Gtc,
+            time_in_force => time_in_force,
+        };
+
+        let mut transformed = Self::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            order.order_side(),
+            order.quantity(),
+            time_in_force,
+            order.init_id(),
+            ts_init,
+            order.is_reduce_only(),
+            order.is_quote_quantity(),
+            order.contingency_type(),
+            order.order_list_id(),
+            order.linked_order_ids().map(<[ClientOrderId]>::to_vec),
+            order.parent_order_id(),
+            order.exec_algorithm_id(),
+            order.exec_algorithm_params().cloned(),
+            order.exec_spawn_id(),
+            order.tags().map(<[Ustr]>::to_vec),
+        )?;
+
+        let mut events: Vec<OrderEventAny> = order.events().into_iter().cloned().collect();
+        events.append(&mut transformed.core.events);
+        transformed.core.events = events;
+
+        Ok(transformed)
+    }
 }
 
 impl Deref for MarketOrder {
@@ -445,13 +485,15 @@ impl From<OrderInitialized> for MarketOrder {
 ////////////////////////////////////////////////////////////////////////////////
 #[cfg(test)]
 mod tests {
+    use nautilus_core::nanos::UnixNanos;
     use rstest::rstest;
 
+    use super::MarketOrder;
     use crate::{
-        enums::{OrderSide, TimeInForce},
+        enums::{OrderSide, OrderStatus, OrderType, TimeInForce},
         instruments::{currency_pair::CurrencyPair, stubs::*},
-        orders::stubs::*,
-        types::quantity::Quantity,
+        orders::{base::Order, stubs::*},
+        types::{price::Price, quantity::Quantity},
     };
 
     #[rstest]
@@ -477,4 +519,26 @@ mod tests {
             Some(TimeInForce::Gtd),
         );
     }
+
+    #[rstest]
+    fn test_transform_from_stop_market(audusd_sim: CurrencyPair) {
+        let order = TestOrderStubs::stop_market_order(
+            audusd_sim.id,
+            OrderSide::Sell,
+            Price::from("0.99000"),
+            Quantity::from(100_000),
+            None,
+            None,
+            Some(TimeInForce::Gtc),
+        );
+
+        let transformed = MarketOrder::transform(&order, UnixNanos::from(1)).unwrap();
+
+        assert_eq!(transformed.client_order_id, order.client_order_id());
+        assert_eq!(transformed.order_type(), OrderType::Market);
+        assert_eq!(transformed.quantity(), order.quantity());
+        assert_eq!(transformed.status(), OrderStatus::Initialized);
+        assert_eq!(transformed.events().len(), 2);
+        assert_eq!(transformed.ts_init(), UnixNanos::from(1));
+    }
 }
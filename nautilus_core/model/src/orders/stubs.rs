@@ -17,9 +17,12 @@ use std::str::FromStr;
 
 use nautilus_core::{nanos::UnixNanos, uuid::UUID4};
 
-use super::{any::OrderAny, limit::LimitOrder, stop_market::StopMarketOrder};
+use super::{
+    any::OrderAny, limit::LimitOrder, stop_market::StopMarketOrder,
+    trailing_stop_limit::TrailingStopLimitOrder, trailing_stop_market::TrailingStopMarketOrder,
+};
 use crate::{
-    enums::{LiquiditySide, OrderSide, TimeInForce, TriggerType},
+    enums::{LiquiditySide, OrderSide, TimeInForce, TrailingOffsetType, TriggerType},
     events::order::{OrderAccepted, OrderEventAny, OrderFilled, OrderSubmitted},
     identifiers::{
         AccountId, ClientOrderId, InstrumentId, PositionId, StrategyId, TradeId, TraderId,
@@ -242,6 +245,98 @@ impl TestOrderStubs {
         OrderAny::StopMarket(order)
     }
 
+    #[must_use]
+    pub fn trailing_stop_market_order(
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        trigger_price: Price,
+        quantity: Quantity,
+        trigger_type: TriggerType,
+        trailing_offset: Price,
+        trailing_offset_type: TrailingOffsetType,
+    ) -> OrderAny {
+        let order = TrailingStopMarketOrder::new(
+            TraderId::default(),
+            StrategyId::default(),
+            instrument_id,
+            ClientOrderId::default(),
+            order_side,
+            quantity,
+            trigger_price,
+            trigger_type,
+            trailing_offset,
+            trailing_offset_type,
+            TimeInForce::Gtc,
+            None,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
+        )
+        .unwrap();
+        OrderAny::TrailingStopMarket(order)
+    }
+
+    #[must_use]
+    #[allow(clippy::too_many_arguments)]
+    pub fn trailing_stop_limit_order(
+        instrument_id: InstrumentId,
+        order_side: OrderSide,
+        price: Price,
+        trigger_price: Price,
+        quantity: Quantity,
+        trigger_type: TriggerType,
+        limit_offset: Price,
+        trailing_offset: Price,
+        trailing_offset_type: TrailingOffsetType,
+    ) -> OrderAny {
+        let order = TrailingStopLimitOrder::new(
+            TraderId::default(),
+            StrategyId::default(),
+            instrument_id,
+            ClientOrderId::default(),
+            order_side,
+            quantity,
+            price,
+            trigger_price,
+            trigger_type,
+            limit_offset,
+            trailing_offset,
+            trailing_offset_type,
+            TimeInForce::Gtc,
+            None,
+            false,
+            false,
+            false,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
+        )
+        .unwrap();
+        OrderAny::TrailingStopLimit(order)
+    }
+
     pub fn make_accepted_order(order: &OrderAny) -> OrderAny {
         let mut new_order = order.clone();
         let submitted_event =
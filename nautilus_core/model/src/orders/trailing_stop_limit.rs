@@ -360,6 +360,10 @@ impl Order for TrailingStopLimitOrder {
         if let OrderEventAny::Updated(ref event) = event {
             self.update(event);
         };
+        if let OrderEventAny::Triggered(ref event) = event {
+            self.is_triggered = true;
+            self.ts_triggered = Some(event.ts_event);
+        };
         let is_order_filled = matches!(event, OrderEventAny::Filled(_));
 
         self.core.apply(event)?;
@@ -205,6 +205,19 @@ impl Position {
         self.ts_last = fill.ts_event;
     }
 
+    /// Applies the given funding `payment` to the realized PnL of the position,
+    /// where a positive payment is received and a negative payment is paid.
+    pub fn apply_funding(&mut self, payment: Money) {
+        assert_eq!(
+            payment.currency, self.settlement_currency,
+            "`payment.currency` was not the position settlement currency",
+        );
+
+        let realized_pnl = self.realized_pnl.map_or(0.0, |pnl| pnl.as_f64());
+        self.realized_pnl =
+            Some(Money::new(realized_pnl + payment.as_f64(), self.settlement_currency).unwrap());
+    }
+
     pub fn handle_buy_order_fill(&mut self, fill: &OrderFilled) {
         // Handle case where commission could be None or not settlement currency
         let mut realized_pnl = if let Some(commission) = fill.commission {
@@ -2106,6 +2119,22 @@ mod tests {
         assert_eq!(position.signed_qty, expected);
     }
 
+    #[rstest]
+    fn test_position_apply_funding(audusd_sim: CurrencyPair) {
+        let audusd_sim = InstrumentAny::CurrencyPair(audusd_sim);
+        let mut fill = OrderFilled::default();
+        fill.position_id = Some(PositionId::from("1"));
+        fill.commission = Some(Money::from_str("2 USD").unwrap());
+        let mut position = Position::new(&audusd_sim, fill).unwrap();
+
+        position.apply_funding(Money::from_str("-3.50 USD").unwrap());
+
+        assert_eq!(
+            position.realized_pnl,
+            Some(Money::from_str("-5.50 USD").unwrap())
+        );
+    }
+
     #[rstest]
     fn test_position_with_commission_none(audusd_sim: CurrencyPair) {
         let audusd_sim = InstrumentAny::CurrencyPair(audusd_sim);
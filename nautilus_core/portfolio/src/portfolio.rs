@@ -0,0 +1,754 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Provides a `Portfolio` which aggregates net positions, exposures, PnL and margins.
+
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    rc::Rc,
+};
+
+use log::{error, info};
+use nautilus_common::cache::Cache;
+use nautilus_model::{
+    accounts::{any::AccountAny, margin::MarginAccount},
+    data::{bar::Bar, quote::QuoteTick},
+    enums::{PositionSide, PriceType},
+    events::position::PositionEvent,
+    identifiers::{InstrumentId, Venue},
+    instruments::{any::InstrumentAny, Instrument},
+    position::Position,
+    types::{currency::Currency, money::Money, price::Price, quantity::Quantity},
+};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+
+/// Provides a portfolio which tracks net positions, exposures, unrealized and realized PnL,
+/// and initial and maintenance margins per instrument and per venue.
+///
+/// Values per instrument are maintained incrementally from quotes, bars and position
+/// events, and are denominated in the instrument's own currency. Venue aggregates are
+/// converted to the venue account's base currency (if it has one).
+pub struct Portfolio {
+    cache: Rc<RefCell<Cache>>,
+    bids: HashMap<InstrumentId, Price>,
+    asks: HashMap<InstrumentId, Price>,
+    net_positions: HashMap<InstrumentId, Decimal>,
+    net_exposures: HashMap<InstrumentId, Money>,
+    unrealized_pnls: HashMap<InstrumentId, Money>,
+    realized_pnls: HashMap<InstrumentId, Money>,
+    margins_init: HashMap<InstrumentId, Money>,
+    margins_maint: HashMap<InstrumentId, Money>,
+}
+
+impl Portfolio {
+    /// Creates a new [`Portfolio`] instance.
+    #[must_use]
+    pub fn new(cache: Rc<RefCell<Cache>>) -> Self {
+        Self {
+            cache,
+            bids: HashMap::new(),
+            asks: HashMap::new(),
+            net_positions: HashMap::new(),
+            net_exposures: HashMap::new(),
+            unrealized_pnls: HashMap::new(),
+            realized_pnls: HashMap::new(),
+            margins_init: HashMap::new(),
+            margins_maint: HashMap::new(),
+        }
+    }
+
+    /// Initializes the portfolio from the positions held in the cache.
+    pub fn initialize_positions(&mut self) {
+        let instrument_ids: HashSet<InstrumentId> = self
+            .cache
+            .borrow()
+            .positions(None, None, None, None)
+            .iter()
+            .map(|position| position.instrument_id)
+            .collect();
+
+        for instrument_id in &instrument_ids {
+            self.update_instrument(instrument_id);
+        }
+
+        info!(
+            "Initialized {} open position(s)",
+            self.cache
+                .borrow()
+                .positions_open_count(None, None, None, None)
+        );
+    }
+
+    pub fn reset(&mut self) {
+        self.bids.clear();
+        self.asks.clear();
+        self.net_positions.clear();
+        self.net_exposures.clear();
+        self.unrealized_pnls.clear();
+        self.realized_pnls.clear();
+        self.margins_init.clear();
+        self.margins_maint.clear();
+    }
+
+    // -- UPDATES ---------------------------------------------------------------------------------
+
+    /// Updates the portfolio with the given `quote`, re-marking any open positions.
+    pub fn update_quote(&mut self, quote: &QuoteTick) {
+        self.bids.insert(quote.instrument_id, quote.bid_price);
+        self.asks.insert(quote.instrument_id, quote.ask_price);
+
+        if self.net_positions.contains_key(&quote.instrument_id) {
+            self.update_marked(&quote.instrument_id);
+        }
+    }
+
+    /// Updates the portfolio with the given `bar`, re-marking any open positions at its close.
+    pub fn update_bar(&mut self, bar: &Bar) {
+        let instrument_id = bar.bar_type.instrument_id;
+        self.bids.insert(instrument_id, bar.close);
+        self.asks.insert(instrument_id, bar.close);
+
+        if self.net_positions.contains_key(&instrument_id) {
+            self.update_marked(&instrument_id);
+        }
+    }
+
+    /// Updates the portfolio for the position of the given `event`.
+    ///
+    /// The position is expected to have already been updated in the cache.
+    pub fn update_position(&mut self, event: &PositionEvent) {
+        let instrument_id = match event {
+            PositionEvent::PositionOpened(event) => event.instrument_id,
+            PositionEvent::PositionChanged(event) => event.instrument_id,
+            PositionEvent::PositionClosed(event) => event.instrument_id,
+        };
+        self.update_instrument(&instrument_id);
+    }
+
+    // -- QUERIES ---------------------------------------------------------------------------------
+
+    /// Returns the net position (signed quantity) for the given `instrument_id`.
+    #[must_use]
+    pub fn net_position(&self, instrument_id: &InstrumentId) -> Decimal {
+        self.net_positions
+            .get(instrument_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    #[must_use]
+    pub fn is_net_long(&self, instrument_id: &InstrumentId) -> bool {
+        self.net_position(instrument_id) > Decimal::ZERO
+    }
+
+    #[must_use]
+    pub fn is_net_short(&self, instrument_id: &InstrumentId) -> bool {
+        self.net_position(instrument_id) < Decimal::ZERO
+    }
+
+    #[must_use]
+    pub fn is_flat(&self, instrument_id: &InstrumentId) -> bool {
+        self.net_position(instrument_id).is_zero()
+    }
+
+    #[must_use]
+    pub fn is_completely_flat(&self) -> bool {
+        self.net_positions.values().all(Decimal::is_zero)
+    }
+
+    /// Returns the net exposure (notional value of open positions) for the given `instrument_id`.
+    #[must_use]
+    pub fn net_exposure(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        self.net_exposures.get(instrument_id).copied()
+    }
+
+    #[must_use]
+    pub fn unrealized_pnl(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        self.unrealized_pnls.get(instrument_id).copied()
+    }
+
+    #[must_use]
+    pub fn realized_pnl(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        self.realized_pnls.get(instrument_id).copied()
+    }
+
+    /// Returns the total (realized plus unrealized) PnL for the given `instrument_id`.
+    #[must_use]
+    pub fn total_pnl(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        match (
+            self.realized_pnl(instrument_id),
+            self.unrealized_pnl(instrument_id),
+        ) {
+            (Some(realized), Some(unrealized)) if realized.currency == unrealized.currency => {
+                Some(realized + unrealized)
+            }
+            (Some(realized), None) => Some(realized),
+            (None, Some(unrealized)) => Some(unrealized),
+            _ => None,
+        }
+    }
+
+    /// Returns the initial margin for the open positions of the given `instrument_id`.
+    #[must_use]
+    pub fn margin_init(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        self.margins_init.get(instrument_id).copied()
+    }
+
+    /// Returns the maintenance margin for the open positions of the given `instrument_id`.
+    #[must_use]
+    pub fn margin_maint(&self, instrument_id: &InstrumentId) -> Option<Money> {
+        self.margins_maint.get(instrument_id).copied()
+    }
+
+    /// Returns the net exposures for the given `venue`, per currency.
+    #[must_use]
+    pub fn net_exposures(&self, venue: &Venue) -> HashMap<Currency, Money> {
+        self.aggregate(venue, &self.net_exposures)
+    }
+
+    /// Returns the unrealized PnLs for the given `venue`, per currency.
+    #[must_use]
+    pub fn unrealized_pnls(&self, venue: &Venue) -> HashMap<Currency, Money> {
+        self.aggregate(venue, &self.unrealized_pnls)
+    }
+
+    /// Returns the realized PnLs for the given `venue`, per currency.
+    #[must_use]
+    pub fn realized_pnls(&self, venue: &Venue) -> HashMap<Currency, Money> {
+        self.aggregate(venue, &self.realized_pnls)
+    }
+
+    /// Returns the initial margins for the given `venue`, per currency.
+    #[must_use]
+    pub fn margins_init(&self, venue: &Venue) -> HashMap<Currency, Money> {
+        self.aggregate(venue, &self.margins_init)
+    }
+
+    /// Returns the maintenance margins for the given `venue`, per currency.
+    #[must_use]
+    pub fn margins_maint(&self, venue: &Venue) -> HashMap<Currency, Money> {
+        self.aggregate(venue, &self.margins_maint)
+    }
+
+    // -- INTERNAL --------------------------------------------------------------------------------
+
+    fn update_instrument(&mut self, instrument_id: &InstrumentId) {
+        self.update_net_position(instrument_id);
+        self.update_realized_pnl(instrument_id);
+        self.update_marked(instrument_id);
+    }
+
+    fn update_net_position(&mut self, instrument_id: &InstrumentId) {
+        let cache = self.cache.borrow();
+        let positions = cache.positions_open(None, Some(instrument_id), None, None);
+        if positions.is_empty() {
+            self.net_positions.remove(instrument_id);
+            return;
+        }
+
+        let net_position: Decimal = positions
+            .iter()
+            .map(|position| match position.side {
+                PositionSide::Short => -position.quantity.as_decimal(),
+                _ => position.quantity.as_decimal(),
+            })
+            .sum();
+        self.net_positions.insert(*instrument_id, net_position);
+    }
+
+    fn update_realized_pnl(&mut self, instrument_id: &InstrumentId) {
+        let cache = self.cache.borrow();
+        let realized_pnl = cache
+            .positions(None, Some(instrument_id), None, None)
+            .iter()
+            .filter_map(|position| position.realized_pnl)
+            .reduce(|total, pnl| total + pnl);
+
+        match realized_pnl {
+            Some(realized_pnl) => self.realized_pnls.insert(*instrument_id, realized_pnl),
+            None => self.realized_pnls.remove(instrument_id),
+        };
+    }
+
+    /// Updates the values marked to the current price for the open positions of the instrument.
+    fn update_marked(&mut self, instrument_id: &InstrumentId) {
+        let cache = self.cache.borrow();
+        let positions = cache.positions_open(None, Some(instrument_id), None, None);
+        if positions.is_empty() {
+            self.net_exposures.remove(instrument_id);
+            self.unrealized_pnls.remove(instrument_id);
+            self.margins_init.remove(instrument_id);
+            self.margins_maint.remove(instrument_id);
+            return;
+        }
+
+        let mut net_exposure: Option<Money> = None;
+        let mut unrealized_pnl: Option<Money> = None;
+        let mut marks = Vec::with_capacity(positions.len());
+        for position in positions {
+            let mark_px = self.mark_price(&cache, position);
+            let exposure = position.notional_value(mark_px);
+            let pnl = position.unrealized_pnl(mark_px);
+            net_exposure = Some(net_exposure.map_or(exposure, |total| total + exposure));
+            unrealized_pnl = Some(unrealized_pnl.map_or(pnl, |total| total + pnl));
+            marks.push((position.quantity, mark_px));
+        }
+        // SAFETY: At least one open position was marked
+        self.net_exposures
+            .insert(*instrument_id, net_exposure.unwrap());
+        self.unrealized_pnls
+            .insert(*instrument_id, unrealized_pnl.unwrap());
+
+        let instrument = cache.instrument(instrument_id).cloned();
+        drop(cache);
+        self.update_margins(instrument_id, instrument, &marks);
+    }
+
+    fn update_margins(
+        &mut self,
+        instrument_id: &InstrumentId,
+        instrument: Option<InstrumentAny>,
+        marks: &[(Quantity, Price)],
+    ) {
+        let mut cache = self.cache.borrow_mut();
+        let (Some(instrument), Some(AccountAny::Margin(account))) = (
+            instrument,
+            cache.account_for_venue_mut(&instrument_id.venue),
+        ) else {
+            // Margins only apply to instruments traded on margin accounts
+            self.margins_init.remove(instrument_id);
+            self.margins_maint.remove(instrument_id);
+            return;
+        };
+
+        let mut margin_init: Option<Money> = None;
+        let mut margin_maint: Option<Money> = None;
+        for (quantity, price) in marks {
+            let (init, maint) = calculate_margins(account, &instrument, *quantity, *price);
+            margin_init = Some(margin_init.map_or(init, |total| total + init));
+            margin_maint = Some(margin_maint.map_or(maint, |total| total + maint));
+        }
+
+        if let (Some(margin_init), Some(margin_maint)) = (margin_init, margin_maint) {
+            self.margins_init.insert(*instrument_id, margin_init);
+            self.margins_maint.insert(*instrument_id, margin_maint);
+        }
+    }
+
+    /// Returns the price to mark the `position` at: the bid for long positions and the ask
+    /// for short positions, falling back to the last trade and then the last fill price.
+    fn mark_price(&self, cache: &Cache, position: &Position) -> Price {
+        let (prices, price_type) = match position.side {
+            PositionSide::Short => (&self.asks, PriceType::Ask),
+            _ => (&self.bids, PriceType::Bid),
+        };
+        prices
+            .get(&position.instrument_id)
+            .copied()
+            .or_else(|| cache.price(&position.instrument_id, price_type))
+            .or_else(|| cache.price(&position.instrument_id, PriceType::Last))
+            .unwrap_or_else(|| position.last_event().last_px)
+    }
+
+    fn aggregate(
+        &self,
+        venue: &Venue,
+        values: &HashMap<InstrumentId, Money>,
+    ) -> HashMap<Currency, Money> {
+        let cache = self.cache.borrow();
+        let base_currency = cache
+            .account_for_venue(venue)
+            .and_then(AccountAny::base_currency);
+
+        let mut totals: HashMap<Currency, Decimal> = HashMap::new();
+        for (instrument_id, value) in values {
+            if &instrument_id.venue != venue {
+                continue;
+            }
+
+            let (currency, amount) = match base_currency {
+                Some(base_currency) if base_currency != value.currency => {
+                    let Some(xrate) =
+                        cache.get_xrate(venue, value.currency, base_currency, PriceType::Mid)
+                    else {
+                        error!(
+                            "Cannot convert {value} for {instrument_id} to {base_currency}: no exchange rate"
+                        );
+                        continue;
+                    };
+                    (base_currency, value.as_decimal() * xrate)
+                }
+                _ => (value.currency, value.as_decimal()),
+            };
+            *totals.entry(currency).or_default() += amount;
+        }
+
+        totals
+            .into_iter()
+            .filter_map(|(currency, amount)| {
+                Money::new(amount.to_f64().unwrap_or_default(), currency)
+                    .map_err(|e| error!("Error aggregating {currency} for {venue}: {e}"))
+                    .ok()
+                    .map(|money| (currency, money))
+            })
+            .collect()
+    }
+}
+
+fn calculate_margins(
+    account: &mut MarginAccount,
+    instrument: &InstrumentAny,
+    quantity: Quantity,
+    price: Price,
+) -> (Money, Money) {
+    match instrument.clone() {
+        InstrumentAny::CryptoFuture(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::CryptoPerpetual(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::CurrencyPair(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::Equity(inst) => calculate_instrument_margins(account, inst, quantity, price),
+        InstrumentAny::FuturesContract(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::FuturesSpread(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::OptionsContract(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+        InstrumentAny::OptionsSpread(inst) => {
+            calculate_instrument_margins(account, inst, quantity, price)
+        }
+    }
+}
+
+fn calculate_instrument_margins<T: Instrument + Clone>(
+    account: &mut MarginAccount,
+    instrument: T,
+    quantity: Quantity,
+    price: Price,
+) -> (Money, Money) {
+    (
+        account.calculate_initial_margin(instrument.clone(), quantity, price, None),
+        account.calculate_maintenance_margin(instrument, quantity, price, None),
+    )
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::cache::Cache;
+    use nautilus_core::uuid::UUID4;
+    use nautilus_model::{
+        accounts::any::AccountAny,
+        data::{
+            bar::{Bar, BarType},
+            quote::QuoteTick,
+        },
+        enums::{AccountType, OmsType, OrderSide},
+        events::{
+            account::{state::AccountState, stubs::margin_account_state},
+            order::OrderFilled,
+            position::{closed::PositionClosed, opened::PositionOpened, PositionEvent},
+        },
+        identifiers::{AccountId, ClientOrderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::stubs::{TestOrderEventStubs, TestOrderStubs},
+        position::Position,
+        types::{
+            balance::AccountBalance, currency::Currency, money::Money, price::Price,
+            quantity::Quantity,
+        },
+    };
+    use rstest::{fixture, rstest};
+    use rust_decimal_macros::dec;
+
+    use super::Portfolio;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_portfolio(instrument: &InstrumentAny) -> (Portfolio, Rc<RefCell<Cache>>) {
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+        (Portfolio::new(cache.clone()), cache)
+    }
+
+    fn fill(
+        instrument: &InstrumentAny,
+        order_side: OrderSide,
+        client_order_id: &str,
+        last_px: &str,
+    ) -> OrderFilled {
+        let order = TestOrderStubs::market_order(
+            instrument.id(),
+            order_side,
+            Quantity::from(100_000),
+            Some(ClientOrderId::from(client_order_id)),
+            None,
+        );
+        TestOrderEventStubs::order_filled(
+            &order,
+            instrument,
+            None,
+            None,
+            Some(Price::from(last_px)),
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .into()
+    }
+
+    fn open_position(
+        portfolio: &mut Portfolio,
+        cache: &Rc<RefCell<Cache>>,
+        instrument: &InstrumentAny,
+        order_side: OrderSide,
+    ) -> Position {
+        let fill = fill(instrument, order_side, "O-1", "1.00000");
+        let position = Position::new(instrument, fill).unwrap();
+        cache
+            .borrow_mut()
+            .add_position(position.clone(), OmsType::Netting)
+            .unwrap();
+        portfolio.update_position(&PositionEvent::PositionOpened(PositionOpened::create(
+            &position,
+            &fill,
+            0.into(),
+        )));
+        position
+    }
+
+    fn quote(instrument: &InstrumentAny, bid: &str, ask: &str) -> QuoteTick {
+        QuoteTick::new(
+            instrument.id(),
+            Price::from(bid),
+            Price::from(ask),
+            Quantity::from(1_000_000),
+            Quantity::from(1_000_000),
+            0.into(),
+            0.into(),
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    fn test_update_position_opened(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+
+        open_position(&mut portfolio, &cache, &instrument, OrderSide::Buy);
+
+        let instrument_id = instrument.id();
+        assert_eq!(portfolio.net_position(&instrument_id), dec!(100_000));
+        assert!(portfolio.is_net_long(&instrument_id));
+        assert!(!portfolio.is_completely_flat());
+        assert_eq!(
+            portfolio.net_exposure(&instrument_id),
+            Some(Money::from("100000 USD"))
+        );
+        assert_eq!(
+            portfolio.unrealized_pnl(&instrument_id),
+            Some(Money::from("0 USD"))
+        );
+        assert_eq!(
+            portfolio.realized_pnl(&instrument_id),
+            Some(Money::from("-2 USD"))
+        );
+        assert_eq!(
+            portfolio.total_pnl(&instrument_id),
+            Some(Money::from("-2 USD"))
+        );
+        // No margin account is cached for the venue
+        assert_eq!(portfolio.margin_init(&instrument_id), None);
+    }
+
+    #[rstest]
+    #[case(OrderSide::Buy, "100010 USD", "10 USD")]
+    #[case(OrderSide::Sell, "100020 USD", "-20 USD")]
+    fn test_update_quote_marks_open_positions(
+        instrument: InstrumentAny,
+        #[case] order_side: OrderSide,
+        #[case] expected_exposure: &str,
+        #[case] expected_pnl: &str,
+    ) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        open_position(&mut portfolio, &cache, &instrument, order_side);
+
+        portfolio.update_quote(&quote(&instrument, "1.00010", "1.00020"));
+
+        let instrument_id = instrument.id();
+        assert_eq!(
+            portfolio.net_exposure(&instrument_id),
+            Some(Money::from(expected_exposure))
+        );
+        assert_eq!(
+            portfolio.unrealized_pnl(&instrument_id),
+            Some(Money::from(expected_pnl))
+        );
+        assert_eq!(
+            portfolio.unrealized_pnls(&instrument_id.venue)[&Currency::USD()],
+            Money::from(expected_pnl)
+        );
+    }
+
+    #[rstest]
+    fn test_update_bar_marks_at_close(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        open_position(&mut portfolio, &cache, &instrument, OrderSide::Sell);
+        let bar = Bar::new(
+            BarType::from("AUD/USD.SIM-1-MINUTE-LAST-EXTERNAL"),
+            Price::from("1.00000"),
+            Price::from("1.00010"),
+            Price::from("0.99940"),
+            Price::from("0.99950"),
+            Quantity::from(100_000),
+            0.into(),
+            0.into(),
+        );
+
+        portfolio.update_bar(&bar);
+
+        assert_eq!(
+            portfolio.unrealized_pnl(&instrument.id()),
+            Some(Money::from("50 USD"))
+        );
+    }
+
+    #[rstest]
+    fn test_update_position_closed_realizes_pnl(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        let mut position = open_position(&mut portfolio, &cache, &instrument, OrderSide::Buy);
+        let fill = fill(&instrument, OrderSide::Sell, "O-2", "1.00100");
+        position.apply(&fill);
+        cache.borrow_mut().update_position(&position).unwrap();
+
+        portfolio.update_position(&PositionEvent::PositionClosed(PositionClosed::create(
+            &position,
+            &fill,
+            0.into(),
+        )));
+
+        let instrument_id = instrument.id();
+        assert!(portfolio.is_flat(&instrument_id));
+        assert!(portfolio.is_completely_flat());
+        assert_eq!(portfolio.net_exposure(&instrument_id), None);
+        assert_eq!(portfolio.unrealized_pnl(&instrument_id), None);
+        assert_eq!(
+            portfolio.realized_pnl(&instrument_id),
+            Some(Money::from("96 USD"))
+        );
+        assert_eq!(
+            portfolio.realized_pnls(&instrument_id.venue)[&Currency::USD()],
+            Money::from("96 USD")
+        );
+    }
+
+    #[rstest]
+    fn test_margins_for_margin_account(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        cache
+            .borrow_mut()
+            .add_account(AccountAny::from(margin_account_state()))
+            .unwrap();
+
+        open_position(&mut portfolio, &cache, &instrument, OrderSide::Buy);
+
+        let instrument_id = instrument.id();
+        assert_eq!(
+            portfolio.margin_init(&instrument_id),
+            Some(Money::from("3004 USD"))
+        );
+        assert_eq!(
+            portfolio.margin_maint(&instrument_id),
+            Some(Money::from("3002 USD"))
+        );
+        assert_eq!(
+            portfolio.margins_maint(&instrument_id.venue)[&Currency::USD()],
+            Money::from("3002 USD")
+        );
+    }
+
+    #[rstest]
+    fn test_venue_aggregates_converted_to_base_currency(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        let balance = AccountBalance::new(
+            Money::from("1000000 AUD"),
+            Money::from("0 AUD"),
+            Money::from("1000000 AUD"),
+        )
+        .unwrap();
+        let account_state = AccountState::new(
+            AccountId::from("SIM-001"),
+            AccountType::Margin,
+            vec![balance],
+            vec![],
+            true,
+            UUID4::new(),
+            0.into(),
+            0.into(),
+            Some(Currency::AUD()),
+        )
+        .unwrap();
+        let quote = quote(&instrument, "0.99990", "1.00010");
+        {
+            let mut cache = cache.borrow_mut();
+            cache.add_account(AccountAny::from(account_state)).unwrap();
+            cache.add_quote(quote).unwrap();
+        }
+        open_position(&mut portfolio, &cache, &instrument, OrderSide::Buy);
+
+        portfolio.update_quote(&quote);
+
+        let venue = instrument.id().venue;
+        assert_eq!(
+            portfolio.unrealized_pnl(&instrument.id()),
+            Some(Money::from("-10 USD"))
+        );
+        let unrealized_pnls = portfolio.unrealized_pnls(&venue);
+        assert_eq!(unrealized_pnls.len(), 1);
+        assert_eq!(unrealized_pnls[&Currency::AUD()], Money::from("-10 AUD"));
+    }
+
+    #[rstest]
+    fn test_reset(instrument: InstrumentAny) {
+        let (mut portfolio, cache) = get_portfolio(&instrument);
+        open_position(&mut portfolio, &cache, &instrument, OrderSide::Buy);
+
+        portfolio.reset();
+
+        assert!(portfolio.is_completely_flat());
+        assert_eq!(portfolio.realized_pnl(&instrument.id()), None);
+
+        portfolio.initialize_positions();
+
+        assert_eq!(portfolio.net_position(&instrument.id()), dec!(100_000));
+    }
+}
@@ -0,0 +1,1262 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+//! Provides a `RiskEngine` which performs pre-trade risk checks on trading commands.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    cmp::Ordering,
+    collections::{HashMap, VecDeque},
+    rc::{Rc, Weak},
+};
+
+use log::{debug, error, info, warn};
+use nautilus_common::{
+    cache::Cache,
+    logging::{CMD, RECV},
+    msgbus::{MessageBus, MessageHandler, ShareableMessageHandler},
+};
+use nautilus_core::{datetime::NANOSECONDS_IN_SECOND, time::AtomicTime, uuid::UUID4};
+use nautilus_execution::messages::{
+    modify::ModifyOrder, submit::SubmitOrder, submit_list::SubmitOrderList, TradingCommand,
+};
+use nautilus_model::{
+    enums::{
+        InstrumentClass, OrderSide, OrderStatus, OrderType, PositionSide, TimeInForce, TradingState,
+    },
+    events::{
+        order::{denied::OrderDenied, modify_rejected::OrderModifyRejected, OrderEventAny},
+        risk::state::TradingStateChanged,
+    },
+    identifiers::{InstrumentId, PositionId},
+    instruments::any::InstrumentAny,
+    orders::any::OrderAny,
+    types::{money::Money, price::Price, quantity::Quantity},
+};
+use rust_decimal::Decimal;
+use ustr::Ustr;
+
+use crate::throttler::{RateLimit, Throttler};
+
+/// Configuration for `RiskEngine` instances.
+pub struct RiskEngineConfig {
+    /// If all risk checks are bypassed (orders are still throttled).
+    pub bypass: bool,
+    /// The maximum rate of submitted orders (order lists count as one submission).
+    pub max_order_submit_rate: RateLimit,
+    /// The maximum rate of modified orders.
+    pub max_order_modify_rate: RateLimit,
+    /// The maximum notional value of a single order per instrument.
+    pub max_notional_per_order: HashMap<InstrumentId, Decimal>,
+    /// If debug logging is enabled.
+    pub debug: bool,
+}
+
+impl Default for RiskEngineConfig {
+    fn default() -> Self {
+        Self {
+            bypass: false,
+            max_order_submit_rate: RateLimit::new(100, NANOSECONDS_IN_SECOND),
+            max_order_modify_rate: RateLimit::new(100, NANOSECONDS_IN_SECOND),
+            max_notional_per_order: HashMap::new(),
+            debug: false,
+        }
+    }
+}
+
+/// Provides a high-performance risk engine which checks order commands before they are
+/// routed to the `ExecutionEngine`.
+///
+/// Orders failing a check are denied, and modifications failing a check are rejected, with
+/// the resulting events sent to the `ExecutionEngine` for processing.
+pub struct RiskEngine {
+    clock: &'static AtomicTime,
+    cache: Rc<RefCell<Cache>>,
+    msgbus: Rc<RefCell<MessageBus>>,
+    trading_state: TradingState,
+    max_notional_per_order: HashMap<InstrumentId, Decimal>,
+    submit_throttler: Throttler,
+    modify_throttler: Throttler,
+    config: RiskEngineConfig,
+}
+
+impl RiskEngine {
+    /// Creates a new [`RiskEngine`] instance.
+    #[must_use]
+    pub fn new(
+        clock: &'static AtomicTime,
+        cache: Rc<RefCell<Cache>>,
+        msgbus: Rc<RefCell<MessageBus>>,
+        config: RiskEngineConfig,
+    ) -> Self {
+        Self {
+            clock,
+            cache,
+            msgbus,
+            trading_state: TradingState::Active,
+            max_notional_per_order: config.max_notional_per_order.clone(),
+            submit_throttler: Throttler::new(config.max_order_submit_rate),
+            modify_throttler: Throttler::new(config.max_order_modify_rate),
+            config,
+        }
+    }
+
+    /// Registers the `engine` on the message bus at the `RiskEngine.execute` endpoint
+    /// for trading commands.
+    ///
+    /// Commands received while the engine is already executing a command (such as a command
+    /// sent in reaction to an order denied by the engine) are executed in the order
+    /// received once the engine has finished.
+    pub fn register(engine: &Rc<RefCell<Self>>) {
+        let handler = RiskEngineHandler {
+            engine: Rc::downgrade(engine),
+            deferred: RefCell::new(VecDeque::new()),
+        };
+        engine.borrow().msgbus.borrow_mut().register(
+            "RiskEngine.execute",
+            ShareableMessageHandler(Rc::new(handler)),
+        );
+    }
+
+    #[must_use]
+    pub const fn trading_state(&self) -> TradingState {
+        self.trading_state
+    }
+
+    #[must_use]
+    pub fn max_notional_per_order(&self, instrument_id: &InstrumentId) -> Option<Decimal> {
+        self.max_notional_per_order.get(instrument_id).copied()
+    }
+
+    /// Sets the trading state for the engine, publishing a [`TradingStateChanged`] event
+    /// on the `events.risk` topic.
+    pub fn set_trading_state(&mut self, state: TradingState) {
+        if state == self.trading_state {
+            warn!("No change to trading state: already set to {state}");
+            return;
+        }
+
+        self.trading_state = state;
+        let ts_now = self.clock.get_time_ns();
+        let msgbus = self.msgbus.borrow();
+        let event = TradingStateChanged::new(msgbus.trader_id, state, UUID4::new(), ts_now, ts_now);
+        msgbus.publish("events.risk", &event as &dyn Any);
+        info!("Trading state set to {state}");
+    }
+
+    /// Sets the maximum notional value per order for the given `instrument_id`,
+    /// or removes the limit if `max_notional` is `None`.
+    pub fn set_max_notional_per_order(
+        &mut self,
+        instrument_id: InstrumentId,
+        max_notional: Option<Decimal>,
+    ) {
+        match max_notional {
+            Some(max_notional) => {
+                self.max_notional_per_order
+                    .insert(instrument_id, max_notional);
+                info!("Set MAX_NOTIONAL_PER_ORDER: {instrument_id} {max_notional}");
+            }
+            None => {
+                self.max_notional_per_order.remove(&instrument_id);
+                info!("Removed MAX_NOTIONAL_PER_ORDER: {instrument_id}");
+            }
+        }
+    }
+
+    pub fn execute(&mut self, command: TradingCommand) {
+        if self.config.debug {
+            debug!("{RECV}{CMD} {command:?}");
+        }
+
+        match command {
+            TradingCommand::SubmitOrder(cmd) => self.handle_submit_order(cmd),
+            TradingCommand::SubmitOrderList(cmd) => self.handle_submit_order_list(cmd),
+            TradingCommand::ModifyOrder(cmd) => self.handle_modify_order(cmd),
+            // Canceling and querying orders never increases risk
+            _ => self.send_to_execution(&command),
+        }
+    }
+
+    // -- COMMAND HANDLERS ----------------------------------------------------
+
+    fn handle_submit_order(&mut self, command: SubmitOrder) {
+        let order = self.cache.borrow().order(&command.client_order_id).cloned();
+        let Some(order) = order else {
+            error!(
+                "Cannot handle submit order: {} not found in the cache",
+                command.client_order_id
+            );
+            return;
+        };
+
+        if self.config.bypass {
+            self.execution_gateway(TradingCommand::SubmitOrder(command), &[order]);
+            return;
+        }
+
+        if order.is_reduce_only() && !self.check_reduce_only(&order, command.position_id) {
+            self.deny_order(
+                &order,
+                &format!(
+                    "Reduce only order would increase position for {}",
+                    order.instrument_id()
+                ),
+            );
+            return;
+        }
+
+        let instrument = self
+            .cache
+            .borrow()
+            .instrument(&order.instrument_id())
+            .cloned();
+        let Some(instrument) = instrument else {
+            self.deny_order(
+                &order,
+                &format!("Instrument for {} not found", order.instrument_id()),
+            );
+            return;
+        };
+
+        if let Some(reason) = self
+            .check_order(&instrument, &order)
+            .or_else(|| self.check_order_notional(&instrument, &order))
+        {
+            self.deny_order(&order, &reason);
+            return;
+        }
+
+        self.execution_gateway(TradingCommand::SubmitOrder(command), &[order]);
+    }
+
+    fn handle_submit_order_list(&mut self, command: SubmitOrderList) {
+        let orders = command.order_list.orders.clone();
+        if self.config.bypass {
+            self.execution_gateway(TradingCommand::SubmitOrderList(command), &orders);
+            return;
+        }
+
+        for order in &orders {
+            if order.is_reduce_only() && !self.check_reduce_only(order, command.position_id) {
+                self.deny_orders(
+                    &orders,
+                    &format!(
+                        "OrderList {} DENIED: Reduce only order {} would increase position for {}",
+                        command.order_list.id,
+                        order.client_order_id(),
+                        order.instrument_id()
+                    ),
+                );
+                return;
+            }
+        }
+
+        let instrument = self
+            .cache
+            .borrow()
+            .instrument(&command.instrument_id)
+            .cloned();
+        let Some(instrument) = instrument else {
+            self.deny_orders(
+                &orders,
+                &format!("Instrument for {} not found", command.instrument_id),
+            );
+            return;
+        };
+
+        for order in &orders {
+            if let Some(reason) = self
+                .check_order(&instrument, order)
+                .or_else(|| self.check_order_notional(&instrument, order))
+            {
+                self.deny_orders(
+                    &orders,
+                    &format!("OrderList {} DENIED: {reason}", command.order_list.id),
+                );
+                return;
+            }
+        }
+
+        self.execution_gateway(TradingCommand::SubmitOrderList(command), &orders);
+    }
+
+    fn handle_modify_order(&mut self, command: ModifyOrder) {
+        let order = self.cache.borrow().order(&command.client_order_id).cloned();
+        let Some(order) = order else {
+            error!(
+                "ModifyOrder DENIED: Order with {} not found",
+                command.client_order_id
+            );
+            return;
+        };
+
+        if order.is_closed() {
+            self.reject_modify_order(&order, "Order already closed");
+            return;
+        }
+        if order.status() == OrderStatus::PendingCancel {
+            self.reject_modify_order(&order, "Order already pending cancel");
+            return;
+        }
+
+        if !self.config.bypass {
+            if let Some(reason) = self.check_modify_order(&order, &command) {
+                self.reject_modify_order(&order, &reason);
+                return;
+            }
+        }
+
+        if !self.modify_throttler.try_send(self.clock.get_time_ns()) {
+            self.reject_modify_order(&order, "Exceeded MAX_ORDER_MODIFY_RATE");
+            return;
+        }
+
+        self.send_to_execution(&TradingCommand::ModifyOrder(command));
+    }
+
+    // -- PRE-TRADE CHECKS ----------------------------------------------------
+
+    fn check_order(&self, instrument: &InstrumentAny, order: &OrderAny) -> Option<String> {
+        if order.time_in_force() == TimeInForce::Gtd {
+            if let Some(expire_time) = order.expire_time() {
+                if expire_time <= self.clock.get_time_ns() {
+                    return Some(format!("GTD {expire_time} already past"));
+                }
+            }
+        }
+
+        check_price(instrument, order.price())
+            .or_else(|| check_price(instrument, order.trigger_price()))
+            .or_else(|| {
+                check_quantity(
+                    instrument,
+                    Some(order.quantity()),
+                    order.is_quote_quantity(),
+                )
+            })
+    }
+
+    fn check_order_notional(&self, instrument: &InstrumentAny, order: &OrderAny) -> Option<String> {
+        let notional = if order.is_quote_quantity() {
+            Money::new(order.quantity().as_f64(), instrument.quote_currency()).ok()?
+        } else {
+            let Some(price) = self.price_for_notional(order) else {
+                warn!(
+                    "Cannot check {} order notional: no prices for {}",
+                    order.order_type(),
+                    order.instrument_id(),
+                );
+                return None;
+            };
+            instrument.calculate_notional_value(order.quantity(), price, Some(true))
+        };
+
+        if let Some(max_notional) = self.max_notional_per_order(&instrument.id()) {
+            if notional.as_decimal() > max_notional {
+                return Some(format!(
+                    "NOTIONAL_EXCEEDS_MAX_PER_ORDER: max_notional={max_notional}, notional={notional}"
+                ));
+            }
+        }
+
+        if let Some(min_notional) = instrument.min_notional() {
+            if min_notional.currency == notional.currency && notional < min_notional {
+                return Some(format!(
+                    "NOTIONAL_LESS_THAN_MIN_FOR_INSTRUMENT: min_notional={min_notional}, notional={notional}"
+                ));
+            }
+        }
+
+        if let Some(max_notional) = instrument.max_notional() {
+            if max_notional.currency == notional.currency && notional > max_notional {
+                return Some(format!(
+                    "NOTIONAL_GREATER_THAN_MAX_FOR_INSTRUMENT: max_notional={max_notional}, notional={notional}"
+                ));
+            }
+        }
+
+        None
+    }
+
+    fn check_modify_order(&self, order: &OrderAny, command: &ModifyOrder) -> Option<String> {
+        let instrument = self
+            .cache
+            .borrow()
+            .instrument(&order.instrument_id())
+            .cloned();
+        let Some(instrument) = instrument else {
+            return Some(format!(
+                "Instrument for {} not found",
+                order.instrument_id()
+            ));
+        };
+
+        if let Some(reason) = check_price(&instrument, command.price)
+            .or_else(|| check_price(&instrument, command.trigger_price))
+            .or_else(|| check_quantity(&instrument, command.quantity, order.is_quote_quantity()))
+        {
+            return Some(reason);
+        }
+
+        match self.trading_state {
+            TradingState::Halted => Some("TradingState::HALTED: Cannot modify order".to_string()),
+            TradingState::Reducing => {
+                let quantity = command.quantity?;
+                let (side, _) = self.net_position(&order.instrument_id());
+                let would_increase = match order.order_side() {
+                    OrderSide::Buy => side != PositionSide::Short,
+                    OrderSide::Sell => side != PositionSide::Long,
+                    _ => false,
+                };
+                if quantity > order.quantity() && would_increase {
+                    Some("TradingState::REDUCING and update will increase exposure".to_string())
+                } else {
+                    None
+                }
+            }
+            TradingState::Active => None,
+        }
+    }
+
+    fn check_reduce_only(&self, order: &OrderAny, position_id: Option<PositionId>) -> bool {
+        let (side, quantity) = match position_id {
+            Some(position_id) => match self.cache.borrow().position(&position_id) {
+                Some(position) => (position.side, position.quantity),
+                None => return false,
+            },
+            None => self.net_position(&order.instrument_id()),
+        };
+        order.would_reduce_only(side, quantity)
+    }
+
+    // -- EXECUTION GATEWAY ---------------------------------------------------
+
+    fn execution_gateway(&mut self, command: TradingCommand, orders: &[OrderAny]) {
+        match self.trading_state {
+            TradingState::Halted => {
+                self.deny_orders(orders, "TradingState::HALTED");
+                return;
+            }
+            TradingState::Reducing => {
+                for order in orders {
+                    let (side, quantity) = self.net_position(&order.instrument_id());
+                    if !order.would_reduce_only(side, quantity) {
+                        self.deny_orders(
+                            orders,
+                            &format!(
+                                "{} when TradingState::REDUCING would increase position for {}",
+                                order.order_side(),
+                                order.instrument_id(),
+                            ),
+                        );
+                        return;
+                    }
+                }
+            }
+            TradingState::Active => {}
+        }
+
+        if !self.submit_throttler.try_send(self.clock.get_time_ns()) {
+            self.deny_orders(orders, "Exceeded MAX_ORDER_SUBMIT_RATE");
+            return;
+        }
+
+        self.send_to_execution(&command);
+    }
+
+    fn send_to_execution(&self, command: &TradingCommand) {
+        let endpoint = if self.is_emulated_command(command) {
+            "OrderEmulator.execute"
+        } else {
+            "ExecEngine.execute"
+        };
+        self.msgbus.borrow().send(endpoint, command as &dyn Any);
+    }
+
+    /// Returns whether the command targets orders held by the `OrderEmulator`.
+    fn is_emulated_command(&self, command: &TradingCommand) -> bool {
+        let cache = self.cache.borrow();
+        match command {
+            TradingCommand::SubmitOrder(cmd) => cache.is_order_emulated(&cmd.client_order_id),
+            TradingCommand::SubmitOrderList(cmd) => cmd
+                .order_list
+                .orders
+                .iter()
+                .any(|order| cache.is_order_emulated(&order.client_order_id())),
+            TradingCommand::ModifyOrder(cmd) => cache.is_order_emulated(&cmd.client_order_id),
+            TradingCommand::CancelOrder(cmd) => cache.is_order_emulated(&cmd.client_order_id),
+            TradingCommand::CancelAllOrders(cmd) => {
+                cache.orders_emulated_count(
+                    None,
+                    Some(&cmd.instrument_id),
+                    Some(&cmd.strategy_id),
+                    None,
+                ) > 0
+            }
+            _ => false,
+        }
+    }
+
+    // -- INTERNAL ------------------------------------------------------------
+
+    /// Returns the net position side and quantity for the instrument across all open positions.
+    fn net_position(&self, instrument_id: &InstrumentId) -> (PositionSide, Quantity) {
+        let cache = self.cache.borrow();
+        let mut net_raw: i128 = 0;
+        let mut precision = 0;
+        for position in cache.positions_open(None, Some(instrument_id), None, None) {
+            precision = position.size_precision;
+            match position.side {
+                PositionSide::Long => net_raw += i128::from(position.quantity.raw),
+                PositionSide::Short => net_raw -= i128::from(position.quantity.raw),
+                _ => {}
+            }
+        }
+
+        let side = match net_raw.cmp(&0) {
+            Ordering::Greater => PositionSide::Long,
+            Ordering::Less => PositionSide::Short,
+            Ordering::Equal => PositionSide::Flat,
+        };
+        let raw = u64::try_from(net_raw.unsigned_abs()).unwrap_or(u64::MAX);
+        let quantity = Quantity::from_raw(raw, precision).unwrap_or(Quantity::zero(precision));
+        (side, quantity)
+    }
+
+    fn price_for_notional(&self, order: &OrderAny) -> Option<Price> {
+        match order.order_type() {
+            OrderType::Market | OrderType::MarketToLimit => self.last_px(order),
+            OrderType::StopMarket | OrderType::MarketIfTouched => order.trigger_price(),
+            OrderType::TrailingStopMarket | OrderType::TrailingStopLimit => order
+                .price()
+                .or(order.trigger_price())
+                .or_else(|| self.last_px(order)),
+            _ => order.price(),
+        }
+    }
+
+    fn last_px(&self, order: &OrderAny) -> Option<Price> {
+        let cache = self.cache.borrow();
+        let instrument_id = order.instrument_id();
+        if let Some(quote) = cache.quote_tick(&instrument_id) {
+            return match order.order_side() {
+                OrderSide::Buy => Some(quote.ask_price),
+                _ => Some(quote.bid_price),
+            };
+        }
+        cache.trade_tick(&instrument_id).map(|trade| trade.price)
+    }
+
+    fn deny_orders(&self, orders: &[OrderAny], reason: &str) {
+        for order in orders {
+            self.deny_order(order, reason);
+        }
+    }
+
+    fn deny_order(&self, order: &OrderAny, reason: &str) {
+        warn!(
+            "SubmitOrder for {} DENIED: {reason}",
+            order.client_order_id()
+        );
+
+        if !self.cache.borrow().order_exists(&order.client_order_id()) {
+            if let Err(e) = self
+                .cache
+                .borrow_mut()
+                .add_order(order.clone(), None, None, false)
+            {
+                error!(
+                    "Error adding order {} to cache: {e}",
+                    order.client_order_id()
+                );
+                return;
+            }
+        }
+
+        if order.is_closed() {
+            return; // Already denied or duplicate (nothing further to do)
+        }
+
+        let ts_now = self.clock.get_time_ns();
+        let denied = match OrderDenied::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            reason.into(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+        ) {
+            Ok(denied) => denied,
+            Err(e) => {
+                error!("Error creating order denied event: {e}");
+                return;
+            }
+        };
+
+        let event = OrderEventAny::Denied(denied);
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+    }
+
+    fn reject_modify_order(&self, order: &OrderAny, reason: &str) {
+        warn!(
+            "ModifyOrder for {} REJECTED: {reason}",
+            order.client_order_id()
+        );
+
+        let ts_now = self.clock.get_time_ns();
+        let rejected = match OrderModifyRejected::new(
+            order.trader_id(),
+            order.strategy_id(),
+            order.instrument_id(),
+            order.client_order_id(),
+            reason.into(),
+            UUID4::new(),
+            ts_now,
+            ts_now,
+            false,
+            order.venue_order_id(),
+            order.account_id(),
+        ) {
+            Ok(rejected) => rejected,
+            Err(e) => {
+                error!("Error creating order modify rejected event: {e}");
+                return;
+            }
+        };
+
+        let event = OrderEventAny::ModifyRejected(rejected);
+        self.msgbus
+            .borrow()
+            .send("ExecEngine.process", &event as &dyn Any);
+    }
+}
+
+/// Handles the commands for the `RiskEngine.execute` endpoint, see [`RiskEngine::register`].
+struct RiskEngineHandler {
+    engine: Weak<RefCell<RiskEngine>>,
+    deferred: RefCell<VecDeque<TradingCommand>>,
+}
+
+impl MessageHandler for RiskEngineHandler {
+    fn id(&self) -> Ustr {
+        Ustr::from("RiskEngine.execute")
+    }
+
+    fn handle(&self, message: &dyn Any) {
+        let Some(command) = message.downcast_ref::<TradingCommand>() else {
+            error!("Cannot handle message for RiskEngine.execute: unrecognized type");
+            return;
+        };
+        let Some(engine) = self.engine.upgrade() else {
+            return;
+        };
+
+        self.deferred.borrow_mut().push_back(command.clone());
+        let Ok(mut engine) = engine.try_borrow_mut() else {
+            return; // Executed once the engine has finished the current command
+        };
+        loop {
+            let command = self.deferred.borrow_mut().pop_front();
+            match command {
+                Some(command) => engine.execute(command),
+                None => break,
+            }
+        }
+    }
+}
+
+fn check_price(instrument: &InstrumentAny, price: Option<Price>) -> Option<String> {
+    let price = price?;
+    if price.precision > instrument.price_precision() {
+        return Some(format!(
+            "price {price} invalid (precision {} > {})",
+            price.precision,
+            instrument.price_precision()
+        ));
+    }
+
+    if !matches!(
+        instrument.instrument_class(),
+        InstrumentClass::Option | InstrumentClass::OptionSpread
+    ) && price.raw <= 0
+    {
+        return Some(format!("price {price} invalid (<= 0)"));
+    }
+
+    None
+}
+
+fn check_quantity(
+    instrument: &InstrumentAny,
+    quantity: Option<Quantity>,
+    is_quote_quantity: bool,
+) -> Option<String> {
+    let quantity = quantity?;
+    if quantity.precision > instrument.size_precision() {
+        return Some(format!(
+            "quantity {quantity} invalid (precision {} > {})",
+            quantity.precision,
+            instrument.size_precision()
+        ));
+    }
+
+    // Quote quantities are not comparable with the instruments trade size limits
+    if is_quote_quantity {
+        return None;
+    }
+
+    if let Some(max_quantity) = instrument.max_quantity() {
+        if quantity > max_quantity {
+            return Some(format!(
+                "quantity {quantity} invalid (> maximum trade size of {max_quantity})"
+            ));
+        }
+    }
+
+    if let Some(min_quantity) = instrument.min_quantity() {
+        if quantity < min_quantity {
+            return Some(format!(
+                "quantity {quantity} invalid (< minimum trade size of {min_quantity})"
+            ));
+        }
+    }
+
+    None
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use nautilus_common::{
+        cache::Cache,
+        msgbus::{stubs::get_message_saving_handler, MessageBus},
+    };
+    use nautilus_core::{nanos::UnixNanos, time::AtomicTime, uuid::UUID4};
+    use nautilus_execution::messages::{
+        cancel::CancelOrder, modify::ModifyOrder, submit::SubmitOrder,
+        submit_list::SubmitOrderList, TradingCommand,
+    };
+    use nautilus_model::{
+        enums::{OmsType, OrderSide, TradingState, TriggerType},
+        events::{order::OrderEventAny, risk::state::TradingStateChanged},
+        identifiers::{ClientId, ClientOrderId, OrderListId, TraderId, VenueOrderId},
+        instruments::{any::InstrumentAny, stubs::audusd_sim},
+        orders::{
+            any::OrderAny,
+            list::OrderList,
+            stubs::{TestOrderEventStubs, TestOrderStubs},
+        },
+        position::Position,
+        types::{price::Price, quantity::Quantity},
+    };
+    use rstest::{fixture, rstest};
+    use rust_decimal_macros::dec;
+
+    use super::{RiskEngine, RiskEngineConfig};
+    use crate::throttler::RateLimit;
+
+    type SavedCommands = Rc<RefCell<Vec<TradingCommand>>>;
+    type SavedEvents = Rc<RefCell<Vec<OrderEventAny>>>;
+
+    #[fixture]
+    fn instrument() -> InstrumentAny {
+        InstrumentAny::CurrencyPair(audusd_sim())
+    }
+
+    fn get_risk_engine(
+        instrument: &InstrumentAny,
+        config: RiskEngineConfig,
+    ) -> (RiskEngine, Rc<RefCell<Cache>>, SavedCommands, SavedEvents) {
+        let clock = Box::leak(Box::new(AtomicTime::new(false, UnixNanos::default())));
+        let mut msgbus = MessageBus::new(TraderId::from("TRADER-001"), UUID4::new(), None, None);
+        let (handler, commands) = get_message_saving_handler::<TradingCommand>(None);
+        msgbus.register("ExecEngine.execute", handler);
+        let (handler, events) = get_message_saving_handler::<OrderEventAny>(None);
+        msgbus.register("ExecEngine.process", handler);
+
+        let mut cache = Cache::default();
+        cache.add_instrument(instrument.clone()).unwrap();
+        let cache = Rc::new(RefCell::new(cache));
+
+        let engine = RiskEngine::new(clock, cache.clone(), Rc::new(RefCell::new(msgbus)), config);
+        (engine, cache, commands, events)
+    }
+
+    fn limit_order(
+        instrument: &InstrumentAny,
+        cache: &Rc<RefCell<Cache>>,
+        client_order_id: &str,
+        order_side: OrderSide,
+        price: &str,
+        quantity: &str,
+    ) -> OrderAny {
+        let order = TestOrderStubs::limit_order(
+            instrument.id(),
+            order_side,
+            Price::from(price),
+            Quantity::from(quantity),
+            Some(ClientOrderId::from(client_order_id)),
+            None,
+        );
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+        order
+    }
+
+    fn submit_order(order: &OrderAny) -> TradingCommand {
+        TradingCommand::SubmitOrder(SubmitOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        })
+    }
+
+    fn modify_order(
+        order: &OrderAny,
+        price: Option<&str>,
+        quantity: Option<&str>,
+    ) -> TradingCommand {
+        TradingCommand::ModifyOrder(ModifyOrder {
+            trader_id: order.trader_id(),
+            strategy_id: order.strategy_id(),
+            instrument_id: order.instrument_id(),
+            client_order_id: order.client_order_id(),
+            price: price.map(Price::from),
+            quantity: quantity.map(Quantity::from),
+            ..Default::default()
+        })
+    }
+
+    fn denied_reason(events: &SavedEvents) -> String {
+        match events.borrow().last() {
+            Some(OrderEventAny::Denied(denied)) => denied.reason.to_string(),
+            event => panic!("Expected `OrderDenied`, was {event:?}"),
+        }
+    }
+
+    #[rstest]
+    fn test_submit_valid_order_is_sent_to_execution(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+
+        engine.execute(submit_order(&order));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert!(events.borrow().is_empty());
+    }
+
+    #[rstest]
+    #[case("1.000001", "1000", "price 1.000001 invalid (precision 6 > 5)")]
+    #[case("1.00000", "1000.5", "quantity 1000.5 invalid (precision 1 > 0)")]
+    #[case(
+        "1.00000",
+        "2000000",
+        "quantity 2000000 invalid (> maximum trade size of 1000000)"
+    )]
+    #[case("1.00000", "10", "quantity 10 invalid (< minimum trade size of 100)")]
+    fn test_submit_order_with_invalid_precision_or_size_is_denied(
+        instrument: InstrumentAny,
+        #[case] price: &str,
+        #[case] quantity: &str,
+        #[case] expected: &str,
+    ) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let order = limit_order(&instrument, &cache, "O-1", OrderSide::Buy, price, quantity);
+
+        engine.execute(submit_order(&order));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(denied_reason(&events), expected);
+    }
+
+    #[rstest]
+    fn test_submit_order_exceeding_max_notional_is_denied(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        engine.set_max_notional_per_order(instrument.id(), Some(dec!(100_000)));
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "200000",
+        );
+
+        engine.execute(submit_order(&order));
+
+        assert!(commands.borrow().is_empty());
+        assert!(denied_reason(&events).starts_with("NOTIONAL_EXCEEDS_MAX_PER_ORDER"));
+    }
+
+    #[rstest]
+    fn test_submit_order_when_halted_is_denied(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        engine.set_trading_state(TradingState::Halted);
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+
+        engine.execute(submit_order(&order));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(denied_reason(&events), "TradingState::HALTED");
+    }
+
+    #[rstest]
+    fn test_submit_order_when_reducing(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Quantity::from("1000"),
+            Some(ClientOrderId::from("O-0")),
+            None,
+        );
+        let fill = TestOrderEventStubs::order_filled(
+            &order,
+            &instrument,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        let position = Position::new(&instrument, fill.into()).unwrap();
+        cache
+            .borrow_mut()
+            .add_position(position, OmsType::Netting)
+            .unwrap();
+        engine.set_trading_state(TradingState::Reducing);
+        let buy = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+        let sell = limit_order(
+            &instrument,
+            &cache,
+            "O-2",
+            OrderSide::Sell,
+            "1.00000",
+            "1000",
+        );
+
+        engine.execute(submit_order(&buy));
+        engine.execute(submit_order(&sell));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert!(denied_reason(&events).starts_with("BUY when TradingState::REDUCING"));
+    }
+
+    #[rstest]
+    fn test_submit_reduce_only_order_without_position_is_denied(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let mut order = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Quantity::from("1000"),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        if let OrderAny::Market(ref mut market) = order {
+            market.is_reduce_only = true;
+        }
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+
+        engine.execute(submit_order(&order));
+
+        assert!(commands.borrow().is_empty());
+        assert!(denied_reason(&events).starts_with("Reduce only order would increase position"));
+    }
+
+    #[rstest]
+    fn test_submit_order_list_with_reduce_only_order_without_position_is_denied(
+        instrument: InstrumentAny,
+    ) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let entry = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+        let mut exit = TestOrderStubs::market_order(
+            instrument.id(),
+            OrderSide::Sell,
+            Quantity::from("1000"),
+            Some(ClientOrderId::from("O-2")),
+            None,
+        );
+        if let OrderAny::Market(ref mut market) = exit {
+            market.is_reduce_only = true;
+        }
+        cache
+            .borrow_mut()
+            .add_order(exit.clone(), None, None, false)
+            .unwrap();
+        let order_list = OrderList::new(
+            OrderListId::from("OL-1"),
+            instrument.id(),
+            entry.strategy_id(),
+            vec![entry.clone(), exit],
+            UnixNanos::default(),
+        )
+        .unwrap();
+        let command = SubmitOrderList::new(
+            entry.trader_id(),
+            ClientId::from("SIM"),
+            entry.strategy_id(),
+            instrument.id(),
+            entry.client_order_id(),
+            VenueOrderId::from("1"),
+            order_list,
+            None,
+            None,
+            UUID4::new(),
+            UnixNanos::default(),
+        )
+        .unwrap();
+
+        engine.execute(TradingCommand::SubmitOrderList(command));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(events.borrow().len(), 2);
+        assert!(denied_reason(&events)
+            .starts_with("OrderList OL-1 DENIED: Reduce only order O-2 would increase position"));
+    }
+
+    #[rstest]
+    fn test_submit_order_rate_is_throttled(instrument: InstrumentAny) {
+        let config = RiskEngineConfig {
+            max_order_submit_rate: RateLimit::new(1, 1_000_000_000),
+            ..Default::default()
+        };
+        let (mut engine, cache, commands, events) = get_risk_engine(&instrument, config);
+        let order1 = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+        let order2 = limit_order(
+            &instrument,
+            &cache,
+            "O-2",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+
+        engine.execute(submit_order(&order1));
+        engine.execute(submit_order(&order2));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert_eq!(denied_reason(&events), "Exceeded MAX_ORDER_SUBMIT_RATE");
+    }
+
+    #[rstest]
+    fn test_submit_emulated_order_is_sent_to_emulator(instrument: InstrumentAny) {
+        let (mut engine, cache, commands, _) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let (handler, emulator_commands) = get_message_saving_handler::<TradingCommand>(None);
+        engine
+            .msgbus
+            .borrow_mut()
+            .register("OrderEmulator.execute", handler);
+        let mut order = TestOrderStubs::limit_order(
+            instrument.id(),
+            OrderSide::Buy,
+            Price::from("1.00000"),
+            Quantity::from("1000"),
+            Some(ClientOrderId::from("O-1")),
+            None,
+        );
+        if let OrderAny::Limit(ref mut limit) = order {
+            limit.emulation_trigger = Some(TriggerType::BidAsk);
+        }
+        cache
+            .borrow_mut()
+            .add_order(order.clone(), None, None, false)
+            .unwrap();
+
+        engine.execute(submit_order(&order));
+        engine.execute(TradingCommand::CancelOrder(CancelOrder {
+            client_order_id: order.client_order_id(),
+            ..Default::default()
+        }));
+
+        assert!(commands.borrow().is_empty());
+        assert_eq!(emulator_commands.borrow().len(), 2);
+    }
+
+    #[rstest]
+    fn test_bypass_skips_risk_checks(instrument: InstrumentAny) {
+        let config = RiskEngineConfig {
+            bypass: true,
+            ..Default::default()
+        };
+        let (mut engine, cache, commands, events) = get_risk_engine(&instrument, config);
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.000001",
+            "1000",
+        );
+
+        engine.execute(submit_order(&order));
+
+        assert_eq!(commands.borrow().len(), 1);
+        assert!(events.borrow().is_empty());
+    }
+
+    #[rstest]
+    #[case(Some("1.000001"), None, TradingState::Active, false)]
+    #[case(None, Some("10"), TradingState::Active, false)]
+    #[case(Some("1.00001"), None, TradingState::Halted, false)]
+    #[case(None, Some("2000"), TradingState::Reducing, false)]
+    #[case(Some("1.00001"), None, TradingState::Active, true)]
+    fn test_modify_order(
+        instrument: InstrumentAny,
+        #[case] price: Option<&str>,
+        #[case] quantity: Option<&str>,
+        #[case] trading_state: TradingState,
+        #[case] expected_sent: bool,
+    ) {
+        let (mut engine, cache, commands, events) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        if trading_state != TradingState::Active {
+            engine.set_trading_state(trading_state);
+        }
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+
+        engine.execute(modify_order(&order, price, quantity));
+
+        assert_eq!(commands.borrow().len(), usize::from(expected_sent));
+        assert_eq!(
+            matches!(
+                events.borrow().last(),
+                Some(OrderEventAny::ModifyRejected(_))
+            ),
+            !expected_sent
+        );
+    }
+
+    #[rstest]
+    fn test_cancel_order_is_sent_when_halted(instrument: InstrumentAny) {
+        let (mut engine, _, commands, _) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        engine.set_trading_state(TradingState::Halted);
+
+        engine.execute(TradingCommand::CancelOrder(CancelOrder::default()));
+
+        assert_eq!(commands.borrow().len(), 1);
+    }
+
+    #[rstest]
+    fn test_set_trading_state_publishes_event(instrument: InstrumentAny) {
+        let (mut engine, _, _, _) = get_risk_engine(&instrument, RiskEngineConfig::default());
+        let (handler, events) = get_message_saving_handler::<TradingStateChanged>(None);
+        engine
+            .msgbus
+            .borrow_mut()
+            .subscribe("events.risk", handler, None);
+
+        engine.set_trading_state(TradingState::Halted);
+        engine.set_trading_state(TradingState::Halted);
+
+        let events = events.borrow();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].trader_id, TraderId::from("TRADER-001"));
+        assert_eq!(events[0].state, TradingState::Halted);
+    }
+
+    #[rstest]
+    fn test_register_executes_commands_sent_to_endpoint(instrument: InstrumentAny) {
+        let (engine, cache, commands, _) =
+            get_risk_engine(&instrument, RiskEngineConfig::default());
+        let msgbus = engine.msgbus.clone();
+        let engine = Rc::new(RefCell::new(engine));
+        RiskEngine::register(&engine);
+        let order = limit_order(
+            &instrument,
+            &cache,
+            "O-1",
+            OrderSide::Buy,
+            "1.00000",
+            "1000",
+        );
+
+        msgbus
+            .borrow()
+            .send("RiskEngine.execute", &submit_order(&order));
+
+        assert_eq!(commands.borrow().len(), 1);
+    }
+}
@@ -0,0 +1,141 @@
+// -------------------------------------------------------------------------------------------------
+//  Copyright (C) 2015-2024 Nautech Systems Pty Ltd. All rights reserved.
+//  https://nautechsystems.io
+//
+//  Licensed under the GNU Lesser General Public License Version 3.0 (the "License");
+//  You may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at https://www.gnu.org/licenses/lgpl-3.0.en.html
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+// -------------------------------------------------------------------------------------------------
+
+use std::collections::VecDeque;
+
+use nautilus_core::nanos::UnixNanos;
+
+/// Represents a limit of messages per time interval.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    /// The maximum number of messages within the interval.
+    pub limit: usize,
+    /// The interval (nanoseconds).
+    pub interval_ns: u64,
+}
+
+impl RateLimit {
+    /// Creates a new [`RateLimit`] instance.
+    #[must_use]
+    pub const fn new(limit: usize, interval_ns: u64) -> Self {
+        Self { limit, interval_ns }
+    }
+}
+
+/// Provides a sliding window throttler which drops messages exceeding the rate limit.
+#[derive(Clone, Debug)]
+pub struct Throttler {
+    pub rate_limit: RateLimit,
+    timestamps: VecDeque<UnixNanos>,
+}
+
+impl Throttler {
+    /// Creates a new [`Throttler`] instance.
+    #[must_use]
+    pub const fn new(rate_limit: RateLimit) -> Self {
+        Self {
+            rate_limit,
+            timestamps: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of messages sent within the interval ending at `ts_now`.
+    #[must_use]
+    pub fn sent_count(&self, ts_now: UnixNanos) -> usize {
+        self.timestamps
+            .iter()
+            .filter(|ts| !self.is_expired(**ts, ts_now))
+            .count()
+    }
+
+    /// Attempts to send a message at `ts_now`, returning whether it was within the rate limit.
+    ///
+    /// Messages are only counted against the limit when they are sent.
+    pub fn try_send(&mut self, ts_now: UnixNanos) -> bool {
+        while let Some(ts) = self.timestamps.front() {
+            if !self.is_expired(*ts, ts_now) {
+                break;
+            }
+            self.timestamps.pop_front();
+        }
+
+        if self.timestamps.len() >= self.rate_limit.limit {
+            return false;
+        }
+
+        self.timestamps.push_back(ts_now);
+        true
+    }
+
+    pub fn reset(&mut self) {
+        self.timestamps.clear();
+    }
+
+    fn is_expired(&self, ts: UnixNanos, ts_now: UnixNanos) -> bool {
+        ts.as_u64() + self.rate_limit.interval_ns <= ts_now.as_u64()
+    }
+}
+
+////////////////////////////////////////////////////////////////////////////////
+// Tests
+////////////////////////////////////////////////////////////////////////////////
+#[cfg(test)]
+mod tests {
+    use nautilus_core::nanos::UnixNanos;
+    use rstest::rstest;
+
+    use super::{RateLimit, Throttler};
+
+    #[rstest]
+    fn test_try_send_within_limit() {
+        let mut throttler = Throttler::new(RateLimit::new(2, 1_000));
+
+        assert!(throttler.try_send(UnixNanos::from(0)));
+        assert!(throttler.try_send(UnixNanos::from(100)));
+        assert_eq!(throttler.sent_count(UnixNanos::from(100)), 2);
+    }
+
+    #[rstest]
+    fn test_try_send_drops_when_limit_exceeded() {
+        let mut throttler = Throttler::new(RateLimit::new(2, 1_000));
+        throttler.try_send(UnixNanos::from(0));
+        throttler.try_send(UnixNanos::from(100));
+
+        assert!(!throttler.try_send(UnixNanos::from(200)));
+        assert_eq!(throttler.sent_count(UnixNanos::from(200)), 2);
+    }
+
+    #[rstest]
+    fn test_try_send_after_interval_elapsed() {
+        let mut throttler = Throttler::new(RateLimit::new(2, 1_000));
+        throttler.try_send(UnixNanos::from(0));
+        throttler.try_send(UnixNanos::from(100));
+
+        assert!(throttler.try_send(UnixNanos::from(1_000)));
+        assert!(!throttler.try_send(UnixNanos::from(1_050)));
+        assert!(throttler.try_send(UnixNanos::from(1_100)));
+    }
+
+    #[rstest]
+    fn test_reset() {
+        let mut throttler = Throttler::new(RateLimit::new(1, 1_000));
+        throttler.try_send(UnixNanos::from(0));
+
+        throttler.reset();
+
+        assert_eq!(throttler.sent_count(UnixNanos::from(0)), 0);
+        assert!(throttler.try_send(UnixNanos::from(0)));
+    }
+}
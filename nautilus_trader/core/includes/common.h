@@ -197,6 +197,9 @@ typedef enum LogLevel {
  * A real-time clock which uses system time.
  *
  * Timestamps are guaranteed to be unique and monotonically increasing.
+ *
+ * Timer events are handled on the runtime, so only Python callbacks are supported (setting a
+ * timer with a native Rust callback is an error, as these are `!Send`).
  */
 typedef struct LiveClock LiveClock;
 
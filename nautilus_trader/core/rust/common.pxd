@@ -104,6 +104,9 @@ cdef extern from "../includes/common.h":
     # A real-time clock which uses system time.
     #
     # Timestamps are guaranteed to be unique and monotonically increasing.
+    #
+    # Timer events are handled on the runtime, so only Python callbacks are supported (setting a
+    # timer with a native Rust callback is an error, as these are `!Send`).
     cdef struct LiveClock:
         pass
 